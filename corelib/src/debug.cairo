@@ -130,3 +130,14 @@ pub fn print_byte_array_as_string(self: @ByteArray) {
     print(serialized)
 }
 
+/// The magic prefixing the prints into a specific channel, followed by the name of the channel.
+pub(crate) const PRINT_CHANNEL_MAGIC: felt252 =
+    0x384ed676c13b01c1fec9fdc7c6ed3ead669cf71a0e8ac41816dfcb2c5a1f155;
+
+/// Prints `message` into the output channel named `channel`, e.g. `'debug'` or `'trace'`. Runners
+/// capturing the printed output return the output of each channel separately.
+pub fn print_to_channel(channel: felt252, message: Array<felt252>) {
+    let mut data = array![PRINT_CHANNEL_MAGIC, channel];
+    data.append_span(message.span());
+    print(data)
+}
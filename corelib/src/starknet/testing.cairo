@@ -90,3 +90,54 @@ pub fn pop_l2_to_l1_message(address: ContractAddress) -> Option<(felt252, Span<f
         (Serde::deserialize(ref l2_to_l1_message)?, Serde::deserialize(ref l2_to_l1_message)?,)
     )
 }
+
+// Returns the public key of a Stark curve private key.
+// Requires the signing cheatcodes to be enabled in the runner.
+pub fn stark_public_key(private_key: felt252) -> felt252 {
+    *cheatcode::<'stark_public_key'>(array![private_key].span()).at(0)
+}
+
+// Signs a message hash with a Stark curve private key, returning the `(r, s)` of the signature.
+// Requires the signing cheatcodes to be enabled in the runner.
+pub fn stark_sign(private_key: felt252, message_hash: felt252) -> (felt252, felt252) {
+    let signature = cheatcode::<'stark_sign'>(array![private_key, message_hash].span());
+    (*signature.at(0), *signature.at(1))
+}
+
+// Returns the `(x, y)` coordinates of the public key of a secp256k1 private key.
+// Requires the signing cheatcodes to be enabled in the runner.
+pub fn secp256k1_public_key(private_key: u256) -> (u256, u256) {
+    let mut input = array![];
+    private_key.serialize(ref input);
+    let mut public_key = cheatcode::<'secp256k1_public_key'>(input.span());
+    Serde::deserialize(ref public_key).unwrap()
+}
+
+// Signs a message hash with a secp256k1 private key.
+// Requires the signing cheatcodes to be enabled in the runner.
+pub fn secp256k1_sign(private_key: u256, message_hash: u256) -> starknet::secp256_trait::Signature {
+    let mut input = array![];
+    private_key.serialize(ref input);
+    message_hash.serialize(ref input);
+    let mut signature = cheatcode::<'secp256k1_sign'>(input.span());
+    Serde::deserialize(ref signature).unwrap()
+}
+
+// Returns the `(x, y)` coordinates of the public key of a secp256r1 private key.
+// Requires the signing cheatcodes to be enabled in the runner.
+pub fn secp256r1_public_key(private_key: u256) -> (u256, u256) {
+    let mut input = array![];
+    private_key.serialize(ref input);
+    let mut public_key = cheatcode::<'secp256r1_public_key'>(input.span());
+    Serde::deserialize(ref public_key).unwrap()
+}
+
+// Signs a message hash with a secp256r1 private key.
+// Requires the signing cheatcodes to be enabled in the runner.
+pub fn secp256r1_sign(private_key: u256, message_hash: u256) -> starknet::secp256_trait::Signature {
+    let mut input = array![];
+    private_key.serialize(ref input);
+    message_hash.serialize(ref input);
+    let mut signature = cheatcode::<'secp256r1_sign'>(input.span());
+    Serde::deserialize(ref signature).unwrap()
+}
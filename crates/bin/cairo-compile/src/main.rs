@@ -2,10 +2,39 @@ use std::fs;
 use std::path::PathBuf;
 
 use anyhow::Context;
-use cairo_lang_compiler::project::check_compiler_path;
-use cairo_lang_compiler::{compile_cairo_project_at_path, CompilerConfig};
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::determinism::{verify_deterministic_with, verify_identical_output};
+use cairo_lang_compiler::project::{check_compiler_path, setup_project};
+use cairo_lang_compiler::{
+    compile_cairo_project_at_path, compile_prepared_db_partially,
+    compile_prepared_db_with_source_map, CompilerConfig,
+};
+use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
+use cairo_lang_lowering::optimizations::config::InliningStrategy;
+use cairo_lang_sierra::program::Program;
+use cairo_lang_sierra_generator::replace_ids::DebugNaming;
+use cairo_lang_sierra_to_casm::metadata::calc_metadata;
 use cairo_lang_utils::logging::init_logging;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use itertools::Itertools;
+
+/// The clap-arg equivalent of [DebugNaming].
+#[derive(ValueEnum, Clone, Copy, Default, Debug, PartialEq, Eq)]
+enum DebugNamingArg {
+    #[default]
+    Full,
+    Short,
+    Mangled,
+}
+impl From<DebugNamingArg> for DebugNaming {
+    fn from(val: DebugNamingArg) -> Self {
+        match val {
+            DebugNamingArg::Full => DebugNaming::Full,
+            DebugNamingArg::Short => DebugNaming::Short,
+            DebugNamingArg::Mangled => DebugNaming::Mangled,
+        }
+    }
+}
 
 /// Compiles a Cairo project to Sierra.
 /// Exits with 0/1 if the compilation succeeds/fails.
@@ -22,6 +51,44 @@ struct Args {
     /// Replaces sierra ids with human-readable ones.
     #[arg(short, long, default_value_t = false)]
     replace_ids: bool,
+    /// The verbosity of the names of the replaced ids: full paths, names without module paths
+    /// (which may be ambiguous), or names without generic arguments followed by the numeric id.
+    #[arg(long, value_enum, default_value_t, requires = "replace_ids")]
+    debug_naming: DebugNamingArg,
+    /// Compiles the project twice, and fails if the outputs, or the CASM bytecode compiled from
+    /// them, are not identical.
+    #[arg(long, default_value_t = false)]
+    verify_deterministic: bool,
+    /// A previously compiled Sierra file, to verify the output is identical to.
+    #[arg(long)]
+    verify_against: Option<PathBuf>,
+    /// Compiles the functions that compile successfully even if others fail, omitting the failing
+    /// ones from the output.
+    #[arg(long, default_value_t = false)]
+    allow_partial: bool,
+    /// A file to write the source map of the program to, as JSON - mapping each Sierra statement
+    /// to the span of source code it was generated from.
+    #[arg(long, conflicts_with = "allow_partial")]
+    source_map_output: Option<PathBuf>,
+    /// A `#[cfg(...)]` option to compile with, as `name` or `key=value`. May be repeated.
+    #[arg(long = "cfg")]
+    cfg: Vec<Cfg>,
+    /// The strategy for inlining functions not annotated with `#[inline(always)]` or
+    /// `#[inline(never)]`: `default`, `avoid` (inline only `#[inline(always)]` functions) or
+    /// `aggressive` (inline every function that can be inlined).
+    #[arg(long, default_value = "default")]
+    inlining_strategy: InliningStrategy,
+}
+
+/// Compiles the given Sierra program to CASM, and returns the assembled bytecode, a word per line.
+fn compile_to_casm_bytecode(program: &Program) -> anyhow::Result<String> {
+    let metadata = calc_metadata(program, Default::default())
+        .context("Failed calculating Sierra variables.")?;
+    let gas_usage_check = true;
+    let cairo_program =
+        cairo_lang_sierra_to_casm::compiler::compile(program, &metadata, gas_usage_check)
+            .context("Compilation to CASM failed.")?;
+    Ok(cairo_program.assemble().bytecode.iter().join("\n"))
 }
 
 fn main() -> anyhow::Result<()> {
@@ -33,10 +100,61 @@ fn main() -> anyhow::Result<()> {
     // Check if args.path is a file or a directory.
     check_compiler_path(args.single_file, &args.path)?;
 
-    let sierra_program = compile_cairo_project_at_path(
-        &args.path,
-        CompilerConfig { replace_ids: args.replace_ids, ..CompilerConfig::default() },
-    )?;
+    let cfg_set = CfgSet::from_iter(args.cfg.iter().cloned());
+    let compiler_config = || CompilerConfig {
+        replace_ids: args.replace_ids,
+        debug_naming: args.debug_naming.into(),
+        cfg_set: cfg_set.clone(),
+        inlining_strategy: args.inlining_strategy,
+        ..CompilerConfig::default()
+    };
+    let mut source_map = None;
+    let mut compile = || {
+        if args.source_map_output.is_some() {
+            let mut db = RootDatabase::builder()
+                .detect_corelib()
+                .with_cfg(cfg_set.clone())
+                .with_inlining_strategy(args.inlining_strategy)
+                .build()?;
+            let main_crate_ids = setup_project(&mut db, &args.path)?;
+            let (sierra_program, program_source_map) =
+                compile_prepared_db_with_source_map(&mut db, main_crate_ids, compiler_config())?;
+            source_map = Some(program_source_map);
+            return Ok(sierra_program);
+        }
+        if !args.allow_partial {
+            return compile_cairo_project_at_path(&args.path, compiler_config());
+        }
+        let mut db = RootDatabase::builder()
+            .detect_corelib()
+            .with_cfg(cfg_set.clone())
+            .with_inlining_strategy(args.inlining_strategy)
+            .build()?;
+        let main_crate_ids = setup_project(&mut db, &args.path)?;
+        let (sierra_program, failed_functions) =
+            compile_prepared_db_partially(&mut db, main_crate_ids, compiler_config())?;
+        for function in failed_functions {
+            eprintln!("warning: `{function}` failed to compile, and was omitted from the output.");
+        }
+        Ok(sierra_program)
+    };
+    let sierra_program = if args.verify_deterministic {
+        verify_deterministic_with(compile, compile_to_casm_bytecode, "The CASM bytecode")?
+    } else {
+        compile()?
+    };
+    if let Some(previous_artifact) = &args.verify_against {
+        let expected = fs::read_to_string(previous_artifact)
+            .with_context(|| format!("Failed to read {}.", previous_artifact.display()))?;
+        // Ignore trailing line breaks, as printing to stdout adds one.
+        verify_identical_output(&sierra_program.to_string().trim_end(), expected.trim_end())?;
+    }
+
+    if let (Some(path), Some(source_map)) = (&args.source_map_output, source_map) {
+        let source_map = serde_json::to_string_pretty(&source_map)
+            .context("Failed to serialize the source map.")?;
+        fs::write(path, source_map).context("Failed to write the source map.")?;
+    }
 
     match args.output {
         Some(path) => {
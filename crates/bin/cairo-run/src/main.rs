@@ -3,19 +3,37 @@
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Ok};
+use cairo_felt::Felt252;
 use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
 use cairo_lang_compiler::project::{check_compiler_path, setup_project};
+use cairo_lang_debug::DebugWithDb;
 use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_lowering::optimizations::config::InliningStrategy;
+use cairo_lang_runner::args_file::parse_args_file;
+use cairo_lang_runner::available_gas::{function_available_gas, runner_plugin_suite, AvailableGas};
+use cairo_lang_runner::branch_coverage::format_untaken_branches;
+use cairo_lang_runner::gas_checkpoints::{
+    find_out_of_gas_checkpoint, format_checkpoint_location, format_gas_checkpoints,
+};
+use cairo_lang_runner::max_steps::function_max_steps;
 use cairo_lang_runner::profiling::ProfilingInfoProcessor;
 use cairo_lang_runner::short_string::as_cairo_short_string;
-use cairo_lang_runner::{SierraCasmRunner, StarknetState};
+use cairo_lang_runner::state_diff::StateDiff;
+use cairo_lang_runner::value_format::{function_param_names, function_return_type, ValueFormatter};
+use cairo_lang_runner::{
+    felt_utils, BlockContext, InnerPanicPolicy, ResourceBounds, SierraCasmRunner, StarknetState,
+    TxContext,
+};
 use cairo_lang_sierra_generator::db::SierraGenGroup;
 use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
 use cairo_lang_sierra_generator::replace_ids::{DebugReplacer, SierraIdReplacer};
 use cairo_lang_starknet::contract::get_contracts_info;
 use cairo_lang_utils::arc_unwrap_or_clone;
 use clap::Parser;
+use num_traits::ToPrimitive;
 
 /// Compiles a Cairo project and runs the function `main`.
 /// Exits with 1 if the compilation or run fails, otherwise 0.
@@ -27,18 +45,165 @@ struct Args {
     /// Whether path is a single file.
     #[arg(short, long)]
     single_file: bool,
+    /// A path to a JSON file with the arguments to pass to `main`, as an array of the values of
+    /// its parameters, e.g. `[1, "0x2", [[3], [4, 5]]]` for `felt252`, `u8` and
+    /// `Array<Span<felt252>>` parameters.
+    #[arg(long)]
+    args_file: Option<PathBuf>,
     /// Allows the compilation to succeed with warnings.
     #[arg(long)]
     allow_warnings: bool,
-    /// In cases where gas is available, the amount of provided gas.
+    /// A `#[cfg(...)]` option to compile with, as `name` or `key=value`. May be repeated.
+    #[arg(long = "cfg")]
+    cfg: Vec<Cfg>,
+    /// The strategy for inlining functions not annotated with `#[inline(always)]` or
+    /// `#[inline(never)]`: `default`, `avoid` (inline only `#[inline(always)]` functions) or
+    /// `aggressive` (inline every function that can be inlined).
+    #[arg(long, default_value = "default")]
+    inlining_strategy: InliningStrategy,
+    /// In cases where gas is available, the amount of provided gas. Overrides the
+    /// `#[available_gas(...)]` attribute of `main`.
     #[arg(long)]
     available_gas: Option<usize>,
+    /// If the run runs out of gas, retries it with doubled gas up to this amount, and reports the
+    /// minimal sufficient amount of gas.
+    #[arg(long, requires = "available_gas")]
+    max_available_gas: Option<usize>,
+    /// Whether to exclude the costs of the builtins from the gas accounting, so that the used gas
+    /// is the pure Sierra gas of the run.
+    #[arg(long, default_value_t = false)]
+    no_builtin_costs: bool,
     /// Whether to print the memory.
     #[arg(long, default_value_t = false)]
     print_full_memory: bool,
     /// Whether to run the profiler.
     #[arg(long, default_value_t = false)]
     run_profiler: bool,
+    /// A path to write the number of executions of each Sierra statement into, as a JSON array
+    /// indexed by the statement index.
+    #[arg(long, requires = "run_profiler")]
+    statement_heat_output: Option<PathBuf>,
+    /// Whether to print the gas checkpoints (withdrawals and redeposits of gas) reached in the
+    /// run.
+    #[arg(long, default_value_t = false)]
+    print_gas_checkpoints: bool,
+    /// Whether to print the branch coverage of the run, with the branches of user code that were
+    /// never taken.
+    #[arg(long, default_value_t = false)]
+    print_branch_coverage: bool,
+    /// Whether to print static statistics of the compiled program before running it.
+    #[arg(long, default_value_t = false)]
+    print_program_stats: bool,
+    /// Whether to print the monomorphized instances of the generic functions of the program, with
+    /// the size of their code, before running it.
+    #[arg(long, default_value_t = false)]
+    print_generic_instances: bool,
+    /// Whether to print the functions, libfuncs and types of the program unreachable from `main`,
+    /// before running it.
+    #[arg(long, default_value_t = false)]
+    print_dead_code: bool,
+    /// Whether to print the statically computed gas requirements of the functions of the program
+    /// before running it.
+    #[arg(long, default_value_t = false)]
+    print_gas_report: bool,
+    /// The maximal depth of the call stack of user functions. Runs calling deeper are aborted with
+    /// the offending call chain.
+    #[arg(long)]
+    max_call_depth: Option<usize>,
+    /// The maximal number of VM steps of the run, aborting it once exceeded. Defaults to the
+    /// `#[max_steps(N)]` attribute of `main`, if it has one.
+    #[arg(long)]
+    max_steps: Option<usize>,
+    /// Whether to print the state of the VM at the point the run panicked, if it panicked.
+    #[arg(long, default_value_t = false)]
+    print_panic_snapshot: bool,
+    /// Whether to enable the cheatcodes computing public keys and signatures of test keys, e.g.
+    /// `starknet::testing::stark_sign`.
+    #[arg(long, default_value_t = false)]
+    signing_cheatcodes: bool,
+    /// Whether to abort the run when a contract called during the run panics, locating the
+    /// panicking call, instead of returning the panic to the caller as on Starknet.
+    #[arg(long, default_value_t = false)]
+    abort_on_inner_panic: bool,
+    /// The block number returned by `get_execution_info`.
+    #[arg(long, default_value_t = 0)]
+    block_number: u64,
+    /// The block timestamp returned by `get_execution_info`.
+    #[arg(long, default_value_t = 0)]
+    block_timestamp: u64,
+    /// The sequencer address returned by `get_execution_info`, in decimal or `0x` prefixed hex.
+    #[arg(long, value_parser = parse_felt252)]
+    sequencer_address: Option<Felt252>,
+    /// The chain id returned by `get_execution_info`, in decimal or `0x` prefixed hex.
+    #[arg(long, value_parser = parse_felt252)]
+    chain_id: Option<Felt252>,
+    /// The transaction version returned by `get_execution_info`.
+    #[arg(long, value_parser = parse_felt252)]
+    tx_version: Option<Felt252>,
+    /// The transaction hash returned by `get_execution_info`.
+    #[arg(long, value_parser = parse_felt252)]
+    tx_hash: Option<Felt252>,
+    /// The transaction signature returned by `get_execution_info`, as comma separated felts.
+    #[arg(long, value_parser = parse_felt252, value_delimiter = ',')]
+    tx_signature: Vec<Felt252>,
+    /// The account contract address of the transaction returned by `get_execution_info`.
+    #[arg(long, value_parser = parse_felt252)]
+    account_contract_address: Option<Felt252>,
+    /// The transaction nonce returned by `get_execution_info`.
+    #[arg(long, value_parser = parse_felt252)]
+    nonce: Option<Felt252>,
+    /// The transaction max fee returned by `get_execution_info`.
+    #[arg(long, value_parser = parse_felt252)]
+    max_fee: Option<Felt252>,
+    /// The resource bounds of the V3 transaction returned by `get_execution_info`, as comma
+    /// separated `RESOURCE:MAX_AMOUNT:MAX_PRICE_PER_UNIT` triplets, e.g. `L1_GAS:1000:10`.
+    #[arg(long, value_parser = parse_resource_bounds, value_delimiter = ',')]
+    resource_bounds: Vec<ResourceBounds>,
+    /// The tip of the V3 transaction returned by `get_execution_info`.
+    #[arg(long, value_parser = parse_felt252)]
+    tip: Option<Felt252>,
+    /// The paymaster data of the V3 transaction returned by `get_execution_info`, as comma
+    /// separated felts.
+    #[arg(long, value_parser = parse_felt252, value_delimiter = ',')]
+    paymaster_data: Vec<Felt252>,
+    /// The nonce data availability mode of the V3 transaction returned by `get_execution_info`.
+    #[arg(long, value_parser = parse_felt252)]
+    nonce_data_availability_mode: Option<Felt252>,
+    /// The fee data availability mode of the V3 transaction returned by `get_execution_info`.
+    #[arg(long, value_parser = parse_felt252)]
+    fee_data_availability_mode: Option<Felt252>,
+    /// The account deployment data of the V3 transaction returned by `get_execution_info`, as
+    /// comma separated felts.
+    #[arg(long, value_parser = parse_felt252, value_delimiter = ',')]
+    account_deployment_data: Vec<Felt252>,
+    /// A path to write the changes of the run to the starknet state into, as a JSON Starknet
+    /// state diff.
+    #[arg(long)]
+    state_diff_output: Option<PathBuf>,
+}
+
+/// Parses a felt252 from its decimal or `0x` prefixed hex representation.
+fn parse_felt252(value: &str) -> Result<Felt252, String> {
+    felt_utils::parse_felt252(value).ok_or_else(|| format!("Invalid felt: {value}"))
+}
+
+/// Parses the bounds of a resource from a `RESOURCE:MAX_AMOUNT:MAX_PRICE_PER_UNIT` triplet, where
+/// the resource is a short string, e.g. `L1_GAS`.
+fn parse_resource_bounds(value: &str) -> Result<ResourceBounds, String> {
+    let [resource, max_amount, max_price_per_unit] = value.split(':').collect::<Vec<_>>()[..]
+    else {
+        return Err(format!(
+            "Invalid resource bounds: {value}, expected `RESOURCE:MAX_AMOUNT:MAX_PRICE_PER_UNIT`."
+        ));
+    };
+    if resource.is_empty() || resource.len() > 31 || !resource.is_ascii() {
+        return Err(format!("Invalid resource name: {resource}"));
+    }
+    std::result::Result::Ok(ResourceBounds {
+        resource: Felt252::from_bytes_be(resource.as_bytes()),
+        max_amount: parse_felt252(max_amount)?,
+        max_price_per_unit: parse_felt252(max_price_per_unit)?,
+    })
 }
 
 fn main() -> anyhow::Result<()> {
@@ -47,7 +212,12 @@ fn main() -> anyhow::Result<()> {
     // Check if args.path is a file or a directory.
     check_compiler_path(args.single_file, &args.path)?;
 
-    let db = &mut RootDatabase::builder().detect_corelib().build()?;
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_plugin_suite(runner_plugin_suite())
+        .with_cfg(CfgSet::from_iter(args.cfg.iter().cloned()))
+        .with_inlining_strategy(args.inlining_strategy)
+        .build()?;
 
     let main_crate_ids = setup_project(db, Path::new(&args.path))?;
 
@@ -64,62 +234,266 @@ fn main() -> anyhow::Result<()> {
             .to_option()
             .with_context(|| "Compilation failed without any diagnostics.")?,
     );
-    let replacer = DebugReplacer { db };
-    if args.available_gas.is_none() && sierra_program.requires_gas_counter() {
+    let replacer = DebugReplacer::new(db);
+    let main_func = sierra_program
+        .funcs
+        .iter()
+        .find(|func| replacer.replace_function_id(&func.id).to_string().ends_with("::main"));
+    let attribute_gas = if args.available_gas.is_none() {
+        main_func.and_then(|main| function_available_gas(db, &main.id))
+    } else {
+        None
+    };
+    let max_steps =
+        args.max_steps.or_else(|| main_func.and_then(|main| function_max_steps(db, &main.id)));
+    let gas_enabled = args.available_gas.is_some() || attribute_gas.is_some();
+    if !gas_enabled && sierra_program.requires_gas_counter() {
         anyhow::bail!("Program requires gas counter, please provide `--available-gas` argument.");
     }
 
     let contracts_info = get_contracts_info(db, main_crate_ids, &replacer)?;
-    let sierra_program = replacer.apply(&sierra_program);
+    let original_sierra_program = sierra_program;
+    let sierra_program = replacer.apply(&original_sierra_program);
 
     let runner = SierraCasmRunner::new(
         sierra_program.clone(),
-        if args.available_gas.is_some() { Some(Default::default()) } else { None },
+        if gas_enabled { Some(Default::default()) } else { None },
         contracts_info,
         args.run_profiler,
     )
-    .with_context(|| "Failed setting up runner.")?;
-    let result = runner
-        .run_function_with_starknet_context(
-            runner.find_function("::main")?,
-            &[],
-            args.available_gas,
-            StarknetState::default(),
-        )
-        .with_context(|| "Failed to run the function.")?;
+    .with_context(|| "Failed setting up runner.")?
+    // Gas checkpoints are also used to locate where the run ran out of gas, if it did.
+    .with_gas_checkpoints_log(args.print_gas_checkpoints || gas_enabled)
+    .with_branch_coverage(args.print_branch_coverage)
+    .with_max_call_depth(args.max_call_depth)
+    .with_max_steps(max_steps)
+    .with_panic_snapshot(args.print_panic_snapshot)
+    .with_signing_cheatcodes(args.signing_cheatcodes)
+    .with_builtin_costs(!args.no_builtin_costs)
+    .with_inner_panic_policy(if args.abort_on_inner_panic {
+        InnerPanicPolicy::Abort
+    } else {
+        InnerPanicPolicy::Recover
+    });
+    if args.print_program_stats {
+        println!("Program stats:\n{}", runner.program_stats());
+    }
+    if args.print_generic_instances {
+        println!("Generic instances:");
+        for instances in runner.generic_instances(db) {
+            print!("{instances}");
+        }
+    }
+    if args.print_gas_report {
+        match runner.gas_report() {
+            Some(gas_report) => println!("Gas report:\n{gas_report}"),
+            None => println!("Gas report: the program does not use gas."),
+        }
+    }
+    let func = runner.find_function("::main")?;
+    let available_gas = args.available_gas.or(match attribute_gas {
+        Some(AvailableGas::Amount(gas)) => Some(gas),
+        Some(AvailableGas::Static) => runner.initial_required_gas(func),
+        None => None,
+    });
+    if let Some(warning) = runner.gas_usage_warning(available_gas) {
+        eprintln!("Warning: {warning}");
+    }
+    if args.print_dead_code {
+        println!("Dead code:\n{}", runner.dead_code_report(&[func.id.clone()]));
+    }
+    let original_func = original_sierra_program
+        .funcs
+        .iter()
+        .find(|original_func| replacer.replace_function_id(&original_func.id) == func.id);
+    let return_type =
+        original_func.and_then(|original_func| function_return_type(db, &original_func.id));
+    let run_args = match &args.args_file {
+        Some(path) => {
+            let json = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed reading `{}`.", path.display()))?;
+            let param_names =
+                original_func.and_then(|original_func| function_param_names(db, &original_func.id));
+            parse_args_file(&json, &runner, func, param_names.as_deref()).with_context(|| {
+                format!("Failed parsing the arguments file `{}`.", path.display())
+            })?
+        }
+        None => vec![],
+    };
+    let starknet_state = StarknetState::default()
+        .with_block_context(BlockContext {
+            block_number: args.block_number.into(),
+            block_timestamp: args.block_timestamp.into(),
+            sequencer_address: args.sequencer_address.unwrap_or_default(),
+            chain_id: args.chain_id.unwrap_or_default(),
+        })
+        .with_tx_context(TxContext {
+            version: args.tx_version.unwrap_or_default(),
+            account_contract_address: args.account_contract_address.unwrap_or_default(),
+            max_fee: args.max_fee.unwrap_or_default(),
+            signature: args.tx_signature,
+            transaction_hash: args.tx_hash.unwrap_or_default(),
+            nonce: args.nonce.unwrap_or_default(),
+            resource_bounds: args.resource_bounds,
+            tip: args.tip.unwrap_or_default(),
+            paymaster_data: args.paymaster_data,
+            nonce_data_availability_mode: args.nonce_data_availability_mode.unwrap_or_default(),
+            fee_data_availability_mode: args.fee_data_availability_mode.unwrap_or_default(),
+            account_deployment_data: args.account_deployment_data,
+        });
+    let initial_starknet_state = starknet_state.clone();
+    let result = match (available_gas, args.max_available_gas) {
+        (Some(available_gas), Some(max_available_gas)) => {
+            let (minimal_gas, result) = runner
+                .run_function_with_gas_retries(
+                    func,
+                    &run_args,
+                    available_gas,
+                    max_available_gas,
+                    starknet_state,
+                )
+                .with_context(|| "Failed to run the function.")?;
+            // The amount is minimal only if the run was retried.
+            if minimal_gas != available_gas && !result.value.is_out_of_gas() {
+                println!("Minimal sufficient gas: {minimal_gas}");
+            }
+            result
+        }
+        _ => runner
+            .run_function_with_starknet_context(func, &run_args, available_gas, starknet_state)
+            .with_context(|| "Failed to run the function.")?,
+    };
 
-    if args.run_profiler {
-        let profiling_info_processor = ProfilingInfoProcessor::new(
-            Some(db),
-            sierra_program,
-            debug_info.statements_locations.get_statements_functions_map(db),
+    let statements_functions = debug_info.statements_locations.get_statements_functions_map(db);
+    if let (true, Some(gas_checkpoints)) = (args.print_gas_checkpoints, &result.gas_checkpoints) {
+        println!(
+            "Gas checkpoints:\n{}",
+            format_gas_checkpoints(gas_checkpoints, db, &debug_info.statements_locations)
         );
+    }
+    if let Some(branch_coverage) = &result.branch_coverage {
+        println!(
+            "Branch coverage: {}/{} branches taken.\n{}",
+            branch_coverage.n_taken_branches(),
+            branch_coverage.n_branches(),
+            format_untaken_branches(
+                branch_coverage,
+                &sierra_program,
+                db,
+                &debug_info.statements_locations
+            )
+        );
+    }
+    if args.run_profiler {
+        let profiling_info_processor =
+            ProfilingInfoProcessor::new(Some(db), sierra_program, statements_functions.clone());
         match result.profiling_info {
             Some(raw_profiling_info) => {
                 let profiling_info = profiling_info_processor.process(&raw_profiling_info);
                 println!("Profiling info:\n{}", profiling_info);
+                if let Some(path) = &args.statement_heat_output {
+                    let counts = &raw_profiling_info.sierra_statement_execution_counts;
+                    let counts: Vec<_> = counts.iter().map(usize::to_string).collect();
+                    std::fs::write(path, format!("[{}]", counts.join(",")))
+                        .with_context(|| format!("Failed writing to `{}`.", path.display()))?;
+                }
             }
             None => println!("Warning: Profiling info not found."),
         }
     }
 
-    match result.value {
+    if let Some(path) = &args.state_diff_output {
+        let state_diff = StateDiff::new(&initial_starknet_state, &result.starknet_state);
+        std::fs::write(path, state_diff.to_json())
+            .with_context(|| format!("Failed writing to `{}`.", path.display()))?;
+    }
+
+    match &result.value {
         cairo_lang_runner::RunResultValue::Success(values) => {
-            println!("Run completed successfully, returning {values:?}")
+            match return_type
+                .and_then(|ty| ValueFormatter::new(db, &result.memory).format(ty, values))
+            {
+                Some(value) => println!("Run completed successfully, returning {value}"),
+                None => println!("Run completed successfully, returning {values:?}"),
+            }
         }
         cairo_lang_runner::RunResultValue::Panic(values) => {
             print!("Run panicked with [");
-            for value in &values {
+            for value in values {
                 match as_cairo_short_string(value) {
                     Some(as_string) => print!("{value} ('{as_string}'), "),
                     None => print!("{value}, "),
                 }
             }
-            println!("].")
+            println!("].");
+            println!("Panic category: {}", result.value.panic_category().unwrap());
+            // Running out of gas is located using the gas checkpoints instead.
+            if let (false, Some(backtrace)) =
+                (result.value.is_out_of_gas(), result.panic_backtrace.as_deref())
+            {
+                if let Some((_, location)) =
+                    debug_info.statements_locations.first_user_code_location(db, backtrace)
+                {
+                    let location = location.diagnostic_location(db);
+                    println!("Panic source location: {:?}", location.debug(db as &dyn FilesGroup));
+                }
+            }
+            if let Some(snapshot) = &result.panic_snapshot {
+                let format_cells = |cells: &[Option<_>]| {
+                    let cells: Vec<String> = cells
+                        .iter()
+                        .map(|cell| match cell {
+                            None => "_".to_string(),
+                            Some(value) => format!("{value}"),
+                        })
+                        .collect();
+                    cells.join(", ")
+                };
+                println!(
+                    "Panic snapshot: statement {} ({}), pc: {}, ap: {}, fp: {}.",
+                    snapshot.statement_idx,
+                    snapshot.function,
+                    snapshot.pc,
+                    snapshot.ap,
+                    snapshot.fp
+                );
+                println!("Panicking frame: [{}]", format_cells(&snapshot.frame));
+                if args.print_full_memory {
+                    println!("Memory at panic: [{}]", format_cells(&snapshot.memory));
+                }
+            }
+        }
+    }
+    if let Some(checkpoint) = result
+        .gas_checkpoints
+        .as_deref()
+        .and_then(|checkpoints| find_out_of_gas_checkpoint(checkpoints, &result.value))
+    {
+        print!(
+            "Ran out of gas at statement {} ({})",
+            checkpoint.statement_idx,
+            format_checkpoint_location(
+                db,
+                &debug_info.statements_locations,
+                checkpoint.statement_idx
+            )
+        );
+        if let (Some(available_gas), Some(gas)) = (available_gas, &result.gas_counter) {
+            print!(", after consuming {} gas", available_gas - gas.to_usize().unwrap());
+        }
+        println!(".");
+        if let Some((_, location)) =
+            debug_info.statements_locations.nearest_statement_location(checkpoint.statement_idx)
+        {
+            let location = location.diagnostic_location(db);
+            println!("Nearest source location: {:?}", location.debug(db as &dyn FilesGroup));
         }
     }
     if let Some(gas) = result.gas_counter {
         println!("Remaining gas: {gas}");
+        if let Some(available_gas) = result.metadata.available_gas {
+            println!("Available gas: {available_gas}");
+        }
     }
     if args.print_full_memory {
         print!("Full memory: [");
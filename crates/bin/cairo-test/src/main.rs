@@ -4,7 +4,8 @@ use std::path::PathBuf;
 
 use anyhow::Ok;
 use cairo_lang_compiler::project::check_compiler_path;
-use cairo_lang_test_runner::{RunProfilerConfig, TestRunConfig, TestRunner};
+use cairo_lang_test_runner::report::{ReportFormat, TestReportConfig};
+use cairo_lang_test_runner::{RunProfilerConfig, TestRunConfig, TestRunner, TestShard};
 use clap::{Parser, ValueEnum};
 use serde::Serialize;
 
@@ -27,6 +28,22 @@ impl From<RunProfilerConfigArg> for RunProfilerConfig {
     }
 }
 
+/// The clap-arg equivalent of [ReportFormat].
+#[derive(ValueEnum, Clone, Debug, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+enum ReportFormatArg {
+    Junit,
+    Json,
+}
+impl From<ReportFormatArg> for ReportFormat {
+    fn from(val: ReportFormatArg) -> Self {
+        match val {
+            ReportFormatArg::Junit => ReportFormat::JUnit,
+            ReportFormatArg::Json => ReportFormat::Json,
+        }
+    }
+}
+
 /// Compiles a Cairo project and runs all the functions marked as `#[test]`.
 /// Exits with 1 if the compilation or run fails, otherwise 0.
 #[derive(Parser, Debug)]
@@ -56,6 +73,25 @@ struct Args {
     /// [cairo_lang_test_runner::RunProfilerConfig]
     #[clap(short, long, default_value_t, value_enum)]
     run_profiler: RunProfilerConfigArg,
+    /// Runs only a shard of the tests, given as `index/count`, e.g. `2/4` for the second of 4
+    /// shards. The tests are split between the shards after filtering.
+    #[arg(long)]
+    shard: Option<TestShard>,
+    /// Splits the tests into the given number of shards and runs the shards in parallel threads,
+    /// each compiling the tests in a database of its own.
+    #[arg(long, conflicts_with = "shard")]
+    parallel_shards: Option<usize>,
+    /// The format of the report of the test results to write to `--report-path`.
+    #[arg(long, value_enum, requires = "report_path")]
+    report_format: Option<ReportFormatArg>,
+    /// The path to write the report of the test results to, in the format of `--report-format`.
+    #[arg(long, requires = "report_format")]
+    report_path: Option<PathBuf>,
+    /// Instead of running the tests once, runs them against small mutations of the tested code,
+    /// e.g. flipped conditions and off-by-one literals, and reports the mutations no test
+    /// detected. Experimental.
+    #[arg(long, default_value_t = false)]
+    mutation_testing: bool,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -69,10 +105,20 @@ fn main() -> anyhow::Result<()> {
         ignored: args.ignored,
         include_ignored: args.include_ignored,
         run_profiler: args.run_profiler.into(),
+        shard: args.shard,
+        parallel_shards: args.parallel_shards,
+        report: args
+            .report_format
+            .zip(args.report_path)
+            .map(|(format, path)| TestReportConfig { format: format.into(), path }),
     };
 
-    let runner = TestRunner::new(&args.path, args.starknet, args.allow_warnings, config)?;
-    runner.run()?;
+    let mut runner = TestRunner::new(&args.path, args.starknet, args.allow_warnings, config)?;
+    if args.mutation_testing {
+        print!("{}", runner.run_mutation_testing()?);
+    } else {
+        runner.run()?;
+    }
 
     Ok(())
 }
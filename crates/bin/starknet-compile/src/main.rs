@@ -5,7 +5,9 @@ use anyhow::Context;
 use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
 use cairo_lang_compiler::project::check_compiler_path;
 use cairo_lang_compiler::CompilerConfig;
-use cairo_lang_starknet::compile::starknet_compile;
+use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
+use cairo_lang_lowering::optimizations::config::InliningStrategy;
+use cairo_lang_starknet::compile::{compile_path_artifacts, starknet_compile};
 use cairo_lang_starknet_classes::allowed_libfuncs::ListSelector;
 use clap::Parser;
 
@@ -36,6 +38,25 @@ struct Args {
     /// A file of the allowed libfuncs list to use.
     #[arg(long)]
     allowed_libfuncs_list_file: Option<String>,
+    /// Outputs all the artifacts of the compilation - the contract class, the Sierra text, the
+    /// CASM and the source map - as a single JSON, instead of the contract class alone.
+    #[arg(long, default_value_t = false)]
+    artifacts: bool,
+    /// A `#[cfg(...)]` option to compile with, as `name` or `key=value`. May be repeated.
+    #[arg(long = "cfg")]
+    cfg: Vec<Cfg>,
+    /// The strategy for inlining functions not annotated with `#[inline(always)]` or
+    /// `#[inline(never)]`: `default`, `avoid` (inline only `#[inline(always)]` functions) or
+    /// `aggressive` (inline every function that can be inlined).
+    #[arg(long, default_value = "default")]
+    inlining_strategy: InliningStrategy,
+    /// Applies the Sierra optimization pipeline to the contract before emitting the class.
+    #[arg(long, default_value_t = false)]
+    optimize_sierra: bool,
+    /// Adds a report of the storage writes and emitted events influenced by the calldata of the
+    /// entry points to the artifacts. Experimental.
+    #[arg(long, default_value_t = false, requires = "artifacts")]
+    calldata_taint_report: bool,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -51,16 +72,23 @@ fn main() -> anyhow::Result<()> {
     if args.allow_warnings {
         diagnostics_reporter = diagnostics_reporter.allow_warnings();
     }
-    let res = starknet_compile(
-        args.path,
-        args.contract_path,
-        Some(CompilerConfig {
-            replace_ids: args.replace_ids,
-            diagnostics_reporter,
-            ..CompilerConfig::default()
-        }),
-        Some(list_selector),
-    )?;
+    let compiler_config = CompilerConfig {
+        replace_ids: args.replace_ids,
+        diagnostics_reporter,
+        cfg_set: CfgSet::from_iter(args.cfg),
+        inlining_strategy: args.inlining_strategy,
+        optimize_sierra: args.optimize_sierra,
+        calldata_taint_report: args.calldata_taint_report,
+        ..CompilerConfig::default()
+    };
+    let res = if args.artifacts {
+        let artifacts =
+            compile_path_artifacts(&args.path, args.contract_path.as_deref(), compiler_config)?;
+        artifacts.contract_class.validate_version_compatible(list_selector)?;
+        serde_json::to_string_pretty(&artifacts).with_context(|| "Serialization failed.")?
+    } else {
+        starknet_compile(args.path, args.contract_path, Some(compiler_config), Some(list_selector))?
+    };
     match args.output {
         Some(path) => fs::write(path, res).with_context(|| "Failed to write output.")?,
         None => println!("{res}"),
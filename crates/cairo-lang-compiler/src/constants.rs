@@ -0,0 +1,89 @@
+//! Evaluation of the constants of a crate and of constant expressions, without compiling or
+//! running the code using them.
+use anyhow::{bail, Context, Result};
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::TopLevelLanguageElementId;
+use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::ids::CrateId;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::inline_macros::compute_constant_expr;
+use cairo_lang_semantic::Expr;
+use cairo_lang_syntax::node::helpers::WrappedArgListHelper;
+use cairo_lang_syntax::node::{ast, TypedSyntaxNode};
+use itertools::Itertools;
+use num_bigint::BigInt;
+
+use crate::db::RootDatabase;
+use crate::instance::parse_virtual_expr;
+
+#[cfg(test)]
+#[path = "constants_test.rs"]
+mod test;
+
+/// The value of a `const` item.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConstantValue {
+    /// The full path of the constant, e.g. `my_crate::utils::MAX`.
+    pub path: String,
+    /// The type of the constant, e.g. `core::integer::u32`.
+    pub ty: String,
+    /// The value of the constant.
+    pub value: BigInt,
+}
+
+/// Evaluates all the `const` items of the given crate, including the ones defined using
+/// `consteval_int!`, in module order.
+pub fn evaluate_crate_constants(
+    db: &RootDatabase,
+    crate_id: CrateId,
+) -> Result<Vec<ConstantValue>> {
+    let mut constants = vec![];
+    for module_id in db.crate_modules(crate_id).iter() {
+        let constant_ids = db.module_constants_ids(*module_id).to_option().with_context(|| {
+            format!("Failed finding the constants of `{}`.", module_id.full_path(db))
+        })?;
+        for constant_id in constant_ids.iter() {
+            let path = constant_id.full_path(db);
+            let diagnostics = db.constant_semantic_diagnostics(*constant_id);
+            if diagnostics.check_error_free().is_err() {
+                bail!("Failed evaluating `{path}`:\n{}", diagnostics.format(db));
+            }
+            let constant = db
+                .constant_semantic_data(*constant_id)
+                .to_option()
+                .with_context(|| format!("Failed evaluating `{path}`."))?;
+            let Expr::Literal(literal) = constant.value else {
+                bail!("`{path}` is not a literal constant.");
+            };
+            constants.push(ConstantValue { path, ty: literal.ty.format(db), value: literal.value });
+        }
+    }
+    Ok(constants)
+}
+
+/// Evaluates a constant integer expression the way `consteval_int!` does, e.g. `2 * (3 + 4)`. The
+/// expression may also be a `consteval_int!` call itself.
+pub fn evaluate_constant_expr(db: &RootDatabase, expr: &str) -> Result<BigInt> {
+    let (_, mut value) = parse_virtual_expr(db, "constant_expr", expr)
+        .with_context(|| format!("Failed parsing the expression `{expr}`."))?;
+    if let ast::Expr::InlineMacro(inline_macro) = &value {
+        if inline_macro.path(db).as_syntax_node().get_text_without_trivia(db) == "consteval_int" {
+            let args = inline_macro.arguments(db).arg_list(db).map(|args| args.elements(db));
+            let Some([arg]) = args.as_deref() else {
+                bail!("`consteval_int!` expects a single argument.");
+            };
+            let ast::ArgClause::Unnamed(arg) = arg.arg_clause(db) else {
+                bail!("`consteval_int!` expects an unnamed argument.");
+            };
+            value = arg.value(db);
+        }
+    }
+    let mut diagnostics = vec![];
+    match compute_constant_expr(db, &value, &mut diagnostics) {
+        Some(result) if diagnostics.is_empty() => Ok(result),
+        _ => bail!(
+            "Failed evaluating `{expr}`: {}",
+            diagnostics.into_iter().map(|diagnostic| diagnostic.message).join(", ")
+        ),
+    }
+}
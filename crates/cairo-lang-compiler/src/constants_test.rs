@@ -0,0 +1,61 @@
+use cairo_lang_semantic::test_utils::setup_test_crate;
+use indoc::indoc;
+use num_bigint::BigInt;
+use test_case::test_case;
+
+use super::{evaluate_constant_expr, evaluate_crate_constants, ConstantValue};
+use crate::db::RootDatabase;
+
+#[test]
+fn test_evaluate_crate_constants() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let crate_id = setup_test_crate(
+        &db,
+        indoc! {"
+            const SMALL: u8 = 7;
+            const SHIFTED: felt252 = consteval_int!(1 + (1 * 2 + 3) * 4);
+            mod utils {
+                const MAX: u128 = 0xffffffffffffffffffffffffffffffff;
+            }
+        "},
+    );
+    let constant = |path: &str, ty: &str, value: BigInt| ConstantValue {
+        path: path.into(),
+        ty: ty.into(),
+        value,
+    };
+    assert_eq!(
+        evaluate_crate_constants(&db, crate_id).unwrap(),
+        [
+            constant("test::SMALL", "core::integer::u8", 7.into()),
+            constant("test::SHIFTED", "core::felt252", 21.into()),
+            constant("test::utils::MAX", "core::integer::u128", u128::MAX.into()),
+        ]
+    );
+}
+
+#[test]
+fn test_evaluate_invalid_crate_constants() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let crate_id = setup_test_crate(&db, "const BIG: u8 = 256;");
+    let error = evaluate_crate_constants(&db, crate_id).unwrap_err();
+    assert!(format!("{error:?}").contains("Failed evaluating `test::BIG`"), "{error:?}");
+}
+
+#[test_case("2 * (3 + 4)", 14; "arithmetic")]
+#[test_case("-5 % 3", -2; "negative")]
+#[test_case("0xff & (1 | 6) ^ 2", 5; "bitwise")]
+#[test_case("consteval_int!(10 / 3)", 3; "consteval_int call")]
+fn test_evaluate_constant_expr(expr: &str, expected: i64) {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    assert_eq!(evaluate_constant_expr(&db, expr).unwrap(), BigInt::from(expected));
+}
+
+#[test_case("a + 1", "Unsupported expression"; "not constant")]
+#[test_case("2 < 3", "Unsupported binary operator"; "unsupported operator")]
+#[test_case("2 +", "Failed parsing the expression"; "syntax error")]
+fn test_evaluate_invalid_constant_expr(expr: &str, expected_error: &str) {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let error = evaluate_constant_expr(&db, expr).unwrap_err();
+    assert!(format!("{error:?}").contains(expected_error), "Unexpected error: {error:?}");
+}
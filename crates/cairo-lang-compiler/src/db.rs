@@ -10,6 +10,7 @@ use cairo_lang_filesystem::db::{
 };
 use cairo_lang_filesystem::detect::detect_corelib;
 use cairo_lang_lowering::db::{init_lowering_group, LoweringDatabase, LoweringGroup};
+use cairo_lang_lowering::optimizations::config::InliningStrategy;
 use cairo_lang_parser::db::ParserDatabase;
 use cairo_lang_project::ProjectConfig;
 use cairo_lang_semantic::db::{SemanticDatabase, SemanticGroup};
@@ -81,6 +82,7 @@ pub struct RootDatabaseBuilder {
     detect_corelib: bool,
     project_config: Option<Box<ProjectConfig>>,
     cfg_set: Option<CfgSet>,
+    inlining_strategy: InliningStrategy,
 }
 
 impl RootDatabaseBuilder {
@@ -90,6 +92,7 @@ impl RootDatabaseBuilder {
             detect_corelib: false,
             project_config: None,
             cfg_set: None,
+            inlining_strategy: InliningStrategy::default(),
         }
     }
 
@@ -118,6 +121,11 @@ impl RootDatabaseBuilder {
         self
     }
 
+    pub fn with_inlining_strategy(&mut self, inlining_strategy: InliningStrategy) -> &mut Self {
+        self.inlining_strategy = inlining_strategy;
+        self
+    }
+
     pub fn build(&mut self) -> Result<RootDatabase> {
         // NOTE: Order of operations matters here!
         //   Errors if something is not OK are very subtle, mostly this results in missing
@@ -133,6 +141,11 @@ impl RootDatabaseBuilder {
             db.use_cfg(cfg_set);
         }
 
+        let optimization_config = db.optimization_config().as_ref().clone();
+        db.set_optimization_config(Arc::new(
+            optimization_config.with_inlining_strategy(self.inlining_strategy),
+        ));
+
         if self.detect_corelib {
             let path =
                 detect_corelib().ok_or_else(|| anyhow!("Failed to find development corelib."))?;
@@ -0,0 +1,108 @@
+//! Verification of the determinism of compilation outputs, for reproducible-build audits.
+
+use std::fmt::Display;
+
+use anyhow::{bail, Result};
+
+#[cfg(test)]
+#[path = "determinism_test.rs"]
+mod test;
+
+/// The first difference between two textual compilation outputs (e.g. Sierra or CASM code).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputMismatch {
+    /// The (1-based) line number of the first differing line.
+    pub line: usize,
+    /// The line in the first output, or `None` if the first output ended before it.
+    pub first: Option<String>,
+    /// The line in the second output, or `None` if the second output ended before it.
+    pub second: Option<String>,
+}
+impl Display for OutputMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format_line = |line: &Option<String>| match line {
+            Some(line) => format!("`{line}`"),
+            None => "<end of output>".to_string(),
+        };
+        write!(
+            f,
+            "Outputs differ at line {}: {} vs {}.",
+            self.line,
+            format_line(&self.first),
+            format_line(&self.second)
+        )
+    }
+}
+
+/// Returns the first difference between the two given outputs, if they differ.
+pub fn find_output_mismatch(first: &str, second: &str) -> Option<OutputMismatch> {
+    let mut first_lines = first.lines();
+    let mut second_lines = second.lines();
+    for line in 1.. {
+        match (first_lines.next(), second_lines.next()) {
+            (None, None) => break,
+            (first, second) if first == second => continue,
+            (first, second) => {
+                return Some(OutputMismatch {
+                    line,
+                    first: first.map(str::to_string),
+                    second: second.map(str::to_string),
+                });
+            }
+        }
+    }
+    // The outputs may still differ in trailing line breaks.
+    (first != second).then(|| OutputMismatch {
+        line: first.lines().count() + 1,
+        first: None,
+        second: None,
+    })
+}
+
+/// Runs `compile` twice, and verifies the textual representations of the outputs are identical.
+/// Returns the output of the first run on success.
+///
+/// `compile` should build the compilation state from scratch on each call (e.g. by creating a new
+/// database) so that the runs are independent.
+pub fn verify_deterministic<T: Display>(compile: impl FnMut() -> Result<T>) -> Result<T> {
+    Ok(compile_twice_deterministically(compile)?.0)
+}
+
+/// Like [verify_deterministic], but additionally applies `lower` to the outputs of both runs (e.g.
+/// compiles the Sierra programs to CASM bytecode), and verifies the textual representations of
+/// the lowered outputs are identical as well. `lowered_name` names the lowered output in the error.
+pub fn verify_deterministic_with<T: Display, U: Display>(
+    compile: impl FnMut() -> Result<T>,
+    mut lower: impl FnMut(&T) -> Result<U>,
+    lowered_name: &str,
+) -> Result<T> {
+    let (output, other_output) = compile_twice_deterministically(compile)?;
+    if let Some(mismatch) =
+        find_output_mismatch(&lower(&output)?.to_string(), &lower(&other_output)?.to_string())
+    {
+        bail!("{lowered_name} is not deterministic. {mismatch}");
+    }
+    Ok(output)
+}
+
+/// Runs `compile` twice, verifies the textual representations of the outputs are identical, and
+/// returns both outputs.
+fn compile_twice_deterministically<T: Display>(
+    mut compile: impl FnMut() -> Result<T>,
+) -> Result<(T, T)> {
+    let output = compile()?;
+    let other_output = compile()?;
+    if let Some(mismatch) = find_output_mismatch(&output.to_string(), &other_output.to_string()) {
+        bail!("Compilation is not deterministic. {mismatch}");
+    }
+    Ok((output, other_output))
+}
+
+/// Verifies that the textual representation of `output` is identical to `expected`, e.g. the
+/// content of a previously compiled artifact.
+pub fn verify_identical_output(output: &impl Display, expected: &str) -> Result<()> {
+    if let Some(mismatch) = find_output_mismatch(&output.to_string(), expected) {
+        bail!("Compilation output differs from the expected output. {mismatch}");
+    }
+    Ok(())
+}
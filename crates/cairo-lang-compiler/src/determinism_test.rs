@@ -0,0 +1,62 @@
+use test_case::test_case;
+
+use super::{
+    find_output_mismatch, verify_deterministic, verify_deterministic_with, OutputMismatch,
+};
+
+#[test_case("a\nb\n", "a\nb\n", None; "identical")]
+#[test_case(
+    "a\nb\n",
+    "a\nc\n",
+    Some(OutputMismatch { line: 2, first: Some("b".into()), second: Some("c".into()) });
+    "different line"
+)]
+#[test_case(
+    "a\nb\n",
+    "a\n",
+    Some(OutputMismatch { line: 2, first: Some("b".into()), second: None });
+    "missing line"
+)]
+#[test_case(
+    "a\n",
+    "a",
+    Some(OutputMismatch { line: 2, first: None, second: None });
+    "trailing line break"
+)]
+fn test_find_output_mismatch(first: &str, second: &str, expected: Option<OutputMismatch>) {
+    assert_eq!(find_output_mismatch(first, second), expected);
+}
+
+#[test]
+fn test_verify_deterministic() {
+    assert_eq!(verify_deterministic(|| Ok("a\nb")).unwrap(), "a\nb");
+
+    let mut outputs = ["a\nb", "a\nc"].into_iter();
+    assert_eq!(
+        verify_deterministic(|| Ok(outputs.next().unwrap())).unwrap_err().to_string(),
+        "Compilation is not deterministic. Outputs differ at line 2: `b` vs `c`."
+    );
+}
+
+#[test]
+fn test_verify_deterministic_with() {
+    let lower = |output: &&str| Ok(output.replace('a', "x"));
+    assert_eq!(verify_deterministic_with(|| Ok("a\nb"), lower, "Bytecode").unwrap(), "a\nb");
+
+    // Identical outputs may still be lowered differently.
+    let mut lowered = ["x", "y"].into_iter();
+    assert_eq!(
+        verify_deterministic_with(|| Ok("a"), |_| Ok(lowered.next().unwrap()), "Bytecode")
+            .unwrap_err()
+            .to_string(),
+        "Bytecode is not deterministic. Outputs differ at line 1: `x` vs `y`."
+    );
+
+    let mut outputs = ["a", "b"].into_iter();
+    assert_eq!(
+        verify_deterministic_with(|| Ok(outputs.next().unwrap()), lower, "Bytecode")
+            .unwrap_err()
+            .to_string(),
+        "Compilation is not deterministic. Outputs differ at line 1: `a` vs `b`."
+    );
+}
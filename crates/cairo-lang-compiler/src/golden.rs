@@ -0,0 +1,57 @@
+//! Golden-file testing of the code generated for Cairo projects: the Sierra and CASM of a
+//! compiled project are compared against stored golden files, guarding against unintended codegen
+//! changes. The golden files are updated instead when the `CAIRO_FIX_TESTS` environment variable
+//! is set to `1`.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use cairo_lang_sierra_to_casm::compiler::compile;
+use cairo_lang_sierra_to_casm::metadata::calc_metadata;
+use cairo_lang_test_utils::compare_contents_or_fix_with_path;
+
+use crate::diagnostics::DiagnosticsReporter;
+use crate::{compile_cairo_project_at_path, CompilerConfig};
+
+#[cfg(test)]
+#[path = "golden_test.rs"]
+mod test;
+
+/// The generated code of a compiled project, as stored in golden files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoldenOutputs {
+    /// The Sierra program, with human-readable ids.
+    pub sierra: String,
+    /// The CASM program compiled from the Sierra program.
+    pub casm: String,
+}
+
+/// Compiles the Cairo project at the given path to Sierra, and to CASM with gas usage checks.
+pub fn compile_golden_outputs(path: &Path) -> Result<GoldenOutputs> {
+    let program = compile_cairo_project_at_path(
+        path,
+        CompilerConfig {
+            diagnostics_reporter: DiagnosticsReporter::stderr(),
+            replace_ids: true,
+            ..CompilerConfig::default()
+        },
+    )?;
+    let metadata = calc_metadata(&program, Default::default())
+        .with_context(|| "Failed calculating the metadata of the program.")?;
+    let casm = compile(&program, &metadata, true)
+        .with_context(|| "Failed compiling the program to CASM.")?;
+    Ok(GoldenOutputs { sierra: program.to_string(), casm: casm.to_string() })
+}
+
+/// Compiles the Cairo project at `path`, and compares its Sierra and CASM to the golden files
+/// `<name>.sierra` and `<name>.casm` in `golden_dir`, where `<name>` is the file stem of `path`,
+/// or writes them if `CAIRO_FIX_TESTS=1`.
+///
+/// Panics on compilation failures and on mismatches, for use in tests.
+pub fn compare_golden_files(path: &Path, golden_dir: &Path) {
+    let outputs = compile_golden_outputs(path)
+        .unwrap_or_else(|err| panic!("Failed compiling `{}`: {err:?}", path.display()));
+    let name = path.file_stem().expect("The project path has no file name.").to_string_lossy();
+    compare_contents_or_fix_with_path(&golden_dir.join(format!("{name}.sierra")), outputs.sierra);
+    compare_contents_or_fix_with_path(&golden_dir.join(format!("{name}.casm")), outputs.casm);
+}
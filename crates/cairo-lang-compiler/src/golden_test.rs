@@ -0,0 +1,10 @@
+use std::path::PathBuf;
+
+use super::compare_golden_files;
+
+#[test]
+fn test_compare_golden_files() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let test_data = dir.join("test_data");
+    compare_golden_files(&test_data.join("fib.cairo"), &test_data.join("golden"));
+}
@@ -0,0 +1,120 @@
+//! Compilation of instances of generic functions, given the concrete generic arguments.
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use cairo_lang_defs::ids::{FileIndex, ModuleFileId, ModuleId};
+use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::ids::{CrateId, FileId, FileKind, FileLongId, VirtualFile};
+use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_semantic::diagnostic::{NotFoundItemType, SemanticDiagnostics};
+use cairo_lang_semantic::expr::inference::InferenceId;
+use cairo_lang_semantic::resolve::{ResolvedConcreteItem, Resolver};
+use cairo_lang_semantic::substitution::SemanticRewriter;
+use cairo_lang_sierra::program::Program;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
+use cairo_lang_sierra_generator::replace_ids::{DebugReplacer, SierraIdReplacer};
+use cairo_lang_syntax::node::{ast, TypedSyntaxNode};
+use cairo_lang_utils::arc_unwrap_or_clone;
+
+use crate::db::RootDatabase;
+use crate::CompilerConfig;
+
+#[cfg(test)]
+#[path = "instance_test.rs"]
+mod test;
+
+/// Resolves an instance of a generic function, given its path relative to the root of `crate_id`
+/// along with its generic arguments, e.g. `utils::max::<u32>`. The generic arguments of impl
+/// parameters (e.g. `+PartialOrd<T>`) may be omitted, in which case they are inferred.
+pub fn resolve_function_instance(
+    db: &RootDatabase,
+    crate_id: CrateId,
+    path: &str,
+) -> Result<ConcreteFunctionWithBodyId> {
+    let (file_id, expr) = parse_virtual_expr(db, "function_instance", path)
+        .with_context(|| format!("Failed parsing the function path `{path}`."))?;
+    let ast::Expr::Path(expr_path) = expr else {
+        bail!("`{path}` is not a path to a function.");
+    };
+
+    let module_file_id = ModuleFileId(ModuleId::CrateRoot(crate_id), FileIndex(0));
+    let mut resolver = Resolver::new(db, module_file_id, InferenceId::NoContext);
+    let mut diagnostics = SemanticDiagnostics::new(file_id);
+    let resolved =
+        resolver.resolve_concrete_path(&mut diagnostics, &expr_path, NotFoundItemType::Function);
+    if let Some((stable_ptr, inference_err)) = resolver.inference().finalize() {
+        inference_err
+            .report(&mut diagnostics, stable_ptr.unwrap_or(expr_path.stable_ptr().untyped()));
+    }
+    let diagnostics = diagnostics.build();
+    if diagnostics.check_error_free().is_err() {
+        bail!("Invalid function instance `{path}`:\n{}", diagnostics.format(db));
+    }
+    let Ok(ResolvedConcreteItem::Function(function_id)) = resolved else {
+        bail!("`{path}` is not a function.");
+    };
+    let function_id = resolver
+        .inference()
+        .rewrite(function_id)
+        .ok()
+        .with_context(|| format!("Failed inferring the generic arguments of `{path}`."))?;
+    let function_with_body = function_id
+        .get_concrete(db)
+        .body(db)
+        .to_option()
+        .flatten()
+        .with_context(|| format!("`{path}` is not a function with a body."))?;
+    Ok(ConcreteFunctionWithBodyId::from_semantic(db, function_with_body))
+}
+
+/// Parses `content` as a standalone expression, in a virtual file named `name`.
+pub(crate) fn parse_virtual_expr(
+    db: &RootDatabase,
+    name: &str,
+    content: &str,
+) -> Result<(FileId, ast::Expr)> {
+    let file_id = db.intern_file(FileLongId::Virtual(VirtualFile {
+        parent: None,
+        name: name.into(),
+        content: Arc::new(content.into()),
+        code_mappings: Default::default(),
+        kind: FileKind::Expr,
+    }));
+    let syntax_diagnostics = db.file_syntax_diagnostics(file_id);
+    if syntax_diagnostics.check_error_free().is_err() {
+        bail!("{}", syntax_diagnostics.format(db));
+    }
+    Ok((file_id, db.file_expr_syntax(file_id).to_option().unwrap()))
+}
+
+/// Compiles just the instance of a generic function given by `path`, along with the functions it
+/// calls. See [resolve_function_instance] for the format of `path`.
+///
+/// # Returns
+/// * `Ok(Program)` - The compiled program.
+/// * `Err(anyhow::Error)` - Compilation failed, or the instance is invalid.
+pub fn compile_function_instance(
+    db: &mut RootDatabase,
+    crate_id: CrateId,
+    path: &str,
+    mut compiler_config: CompilerConfig<'_>,
+) -> Result<Program> {
+    compiler_config.diagnostics_reporter.ensure(db)?;
+    let function = resolve_function_instance(db, crate_id, path)?;
+
+    let SierraProgramWithDebug { program: mut sierra_program, .. } = arc_unwrap_or_clone(
+        db.get_sierra_program_for_functions(vec![function])
+            .to_option()
+            .with_context(|| format!("Failed compiling `{path}`."))?,
+    );
+
+    if compiler_config.replace_ids {
+        sierra_program =
+            DebugReplacer::new(db).with_naming(compiler_config.debug_naming).apply(&sierra_program);
+    }
+
+    Ok(sierra_program)
+}
@@ -0,0 +1,51 @@
+use cairo_lang_semantic::test_utils::setup_test_crate;
+use indoc::indoc;
+use test_case::test_case;
+
+use super::compile_function_instance;
+use crate::db::RootDatabase;
+use crate::CompilerConfig;
+
+const CODE: &str = indoc! {"
+    mod utils {
+        fn max<T, +PartialOrd<T>, +Drop<T>, +Copy<T>>(a: T, b: T) -> T {
+            if a > b { a } else { b }
+        }
+    }
+    extern fn extern_generic<T>(a: T) -> T nopanic;
+"};
+
+#[test_case("utils::max::<u32>", "test::utils::max::<core::integer::u32, "; "u32")]
+#[test_case("utils::max::<u64>", "test::utils::max::<core::integer::u64, "; "u64")]
+fn test_compile_function_instance(path: &str, expected_function_prefix: &str) {
+    let mut db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let crate_id = setup_test_crate(&db, CODE);
+    let program = compile_function_instance(
+        &mut db,
+        crate_id,
+        path,
+        CompilerConfig { replace_ids: true, ..CompilerConfig::default() },
+    )
+    .unwrap();
+    assert!(
+        program.funcs.iter().any(|func| func.id.to_string().starts_with(expected_function_prefix)),
+        "`{expected_function_prefix}` not found in:\n{program}"
+    );
+}
+
+#[test_case(
+    "utils::max::<felt252>",
+    "Trait has no implementation in context: core::traits::PartialOrd::<core::felt252>";
+    "unsatisfied bounds"
+)]
+#[test_case("utils::max::<Missing>", "Type not found"; "missing type")]
+#[test_case("utils::min::<u32>", "Function not found"; "missing function")]
+#[test_case("extern_generic::<u32>", "is not a function with a body"; "extern function")]
+#[test_case("utils::max::<", "Failed parsing the function path"; "syntax error")]
+fn test_invalid_function_instance(path: &str, expected_error: &str) {
+    let mut db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let crate_id = setup_test_crate(&db, CODE);
+    let error =
+        compile_function_instance(&mut db, crate_id, path, CompilerConfig::default()).unwrap_err();
+    assert!(format!("{error:?}").contains(expected_error), "Unexpected error: {error:?}");
+}
@@ -6,19 +6,31 @@ use std::path::Path;
 
 use ::cairo_lang_diagnostics::ToOption;
 use anyhow::{Context, Result};
+use cairo_lang_filesystem::cfg::CfgSet;
 use cairo_lang_filesystem::ids::CrateId;
+use cairo_lang_lowering::optimizations::config::InliningStrategy;
+use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_sierra::optimization::OptimizationPipeline;
 use cairo_lang_sierra::program::Program;
 use cairo_lang_sierra_generator::db::SierraGenGroup;
-use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
-use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
-use cairo_lang_utils::arc_unwrap_or_clone;
+use cairo_lang_sierra_generator::program_generator::{
+    get_partial_sierra_program, PartialSierraProgram, SierraProgramWithDebug,
+};
+use cairo_lang_sierra_generator::replace_ids::{DebugNaming, DebugReplacer, SierraIdReplacer};
+use cairo_lang_sierra_generator::statements_locations::SourceMap;
+use cairo_lang_utils::{arc_unwrap_or_clone, Upcast};
 
 use crate::db::RootDatabase;
 use crate::diagnostics::DiagnosticsReporter;
 use crate::project::{get_main_crate_ids_from_project, setup_project, ProjectConfig};
 
+pub mod constants;
 pub mod db;
+pub mod determinism;
 pub mod diagnostics;
+#[cfg(any(feature = "testing", test))]
+pub mod golden;
+pub mod instance;
 pub mod project;
 
 /// Configuration for the compiler.
@@ -28,9 +40,33 @@ pub struct CompilerConfig<'c> {
     /// Replaces sierra ids with human-readable ones.
     pub replace_ids: bool,
 
+    /// The verbosity of the human-readable ids, when replacing the ids.
+    pub debug_naming: DebugNaming,
+
     /// The name of the allowed libfuncs list to use in compilation.
     /// If None the default list of audited libfuncs will be used.
     pub allowed_libfuncs_list_name: Option<String>,
+
+    /// The `#[cfg(...)]` options to compile with. Only applied by the functions building the
+    /// database themselves, e.g. [compile_cairo_project_at_path] - the options of prepared
+    /// databases are set by
+    /// [RootDatabaseBuilder::with_cfg](crate::db::RootDatabaseBuilder::with_cfg).
+    pub cfg_set: CfgSet,
+
+    /// The strategy for inlining functions. Only applied by the functions building the database
+    /// themselves, as `cfg_set` - the strategy of prepared databases is set by
+    /// [RootDatabaseBuilder::with_inlining_strategy](crate::db::RootDatabaseBuilder).
+    pub inlining_strategy: InliningStrategy,
+
+    /// Applies the Sierra optimization pipeline to the compiled program, keeping the functions of
+    /// the compiled crates (or the entry points, for contracts). The statements locations of the
+    /// debug info are dropped, as they don't match the optimized statements.
+    pub optimize_sierra: bool,
+
+    /// Reports the storage writes and emitted events of the entry points of compiled contracts
+    /// that are influenced by their calldata, in the artifacts of the contracts. Experimental
+    /// - only explicit information flows are tracked.
+    pub calldata_taint_report: bool,
 }
 
 /// The default compiler configuration.
@@ -39,7 +75,12 @@ impl Default for CompilerConfig<'static> {
         CompilerConfig {
             diagnostics_reporter: DiagnosticsReporter::default(),
             replace_ids: false,
+            debug_naming: DebugNaming::default(),
             allowed_libfuncs_list_name: None,
+            cfg_set: CfgSet::new(),
+            inlining_strategy: InliningStrategy::default(),
+            optimize_sierra: false,
+            calldata_taint_report: false,
         }
     }
 }
@@ -58,7 +99,11 @@ pub fn compile_cairo_project_at_path(
     path: &Path,
     compiler_config: CompilerConfig<'_>,
 ) -> Result<Program> {
-    let mut db = RootDatabase::builder().detect_corelib().build()?;
+    let mut db = RootDatabase::builder()
+        .detect_corelib()
+        .with_cfg(compiler_config.cfg_set.clone())
+        .with_inlining_strategy(compiler_config.inlining_strategy)
+        .build()?;
     let main_crate_ids = setup_project(&mut db, path)?;
     compile_prepared_db(&mut db, main_crate_ids, compiler_config)
 }
@@ -76,7 +121,11 @@ pub fn compile(
     project_config: ProjectConfig,
     compiler_config: CompilerConfig<'_>,
 ) -> Result<Program> {
-    let mut db = RootDatabase::builder().with_project_config(project_config.clone()).build()?;
+    let mut db = RootDatabase::builder()
+        .with_project_config(project_config.clone())
+        .with_cfg(compiler_config.cfg_set.clone())
+        .with_inlining_strategy(compiler_config.inlining_strategy)
+        .build()?;
     let main_crate_ids = get_main_crate_ids_from_project(&mut db, &project_config);
 
     compile_prepared_db(&mut db, main_crate_ids, compiler_config)
@@ -96,19 +145,89 @@ pub fn compile(
 pub fn compile_prepared_db(
     db: &mut RootDatabase,
     main_crate_ids: Vec<CrateId>,
-    mut compiler_config: CompilerConfig<'_>,
+    compiler_config: CompilerConfig<'_>,
 ) -> Result<Program> {
+    Ok(compile_prepared_db_with_debug(db, main_crate_ids, compiler_config)?.program)
+}
+
+/// Same as [`compile_prepared_db`], additionally returning the [`SourceMap`] of the program.
+pub fn compile_prepared_db_with_source_map(
+    db: &mut RootDatabase,
+    main_crate_ids: Vec<CrateId>,
+    compiler_config: CompilerConfig<'_>,
+) -> Result<(Program, SourceMap)> {
+    let SierraProgramWithDebug { program, debug_info } =
+        compile_prepared_db_with_debug(db, main_crate_ids, compiler_config)?;
+    let source_map = debug_info.statements_locations.source_map(db, program.statements.len());
+    Ok((program, source_map))
+}
+
+/// Same as [`compile_prepared_db`], returning the program with its debug info.
+fn compile_prepared_db_with_debug(
+    db: &mut RootDatabase,
+    main_crate_ids: Vec<CrateId>,
+    mut compiler_config: CompilerConfig<'_>,
+) -> Result<SierraProgramWithDebug> {
     compiler_config.diagnostics_reporter.ensure(db)?;
 
-    let SierraProgramWithDebug { program: mut sierra_program, .. } = arc_unwrap_or_clone(
+    let mut sierra_program = arc_unwrap_or_clone(
         db.get_sierra_program(main_crate_ids)
             .to_option()
             .context("Compilation failed without any diagnostics")?,
     );
 
+    if compiler_config.optimize_sierra {
+        let roots = sierra_program.program.funcs.iter().map(|func| func.id.clone()).collect();
+        optimize_sierra_program(&mut sierra_program, roots);
+    }
     if compiler_config.replace_ids {
-        sierra_program = replace_sierra_ids_in_program(db, &sierra_program);
+        sierra_program.program = DebugReplacer::new(db)
+            .with_naming(compiler_config.debug_naming)
+            .apply(&sierra_program.program);
     }
 
     Ok(sierra_program)
 }
+
+/// Applies the Sierra optimization pipeline to the program, keeping the functions reachable from
+/// `roots`. The statements locations of the debug info are dropped, as they don't match the
+/// optimized statements.
+pub fn optimize_sierra_program(
+    sierra_program: &mut SierraProgramWithDebug,
+    roots: Vec<FunctionId>,
+) {
+    OptimizationPipeline::all(roots).apply(&mut sierra_program.program);
+    sierra_program.debug_info.statements_locations = Default::default();
+}
+
+/// Same as [`compile_prepared_db`], but instead of failing on errors, compiles the functions that
+/// compile successfully. The diagnostics are still reported.
+///
+/// # Returns
+/// * `Ok((Program, Vec<String>))` - The compiled program of the successfully compiled functions,
+///   along with the full paths of the functions that failed to compile.
+/// * `Err(anyhow::Error)` - No program could be generated.
+pub fn compile_prepared_db_partially(
+    db: &mut RootDatabase,
+    main_crate_ids: Vec<CrateId>,
+    mut compiler_config: CompilerConfig<'_>,
+) -> Result<(Program, Vec<String>)> {
+    compiler_config.diagnostics_reporter.check(db);
+
+    let PartialSierraProgram { program, failed_functions } =
+        get_partial_sierra_program(db, main_crate_ids)
+            .to_option()
+            .context("Compilation failed without any diagnostics")?;
+    let mut sierra_program = arc_unwrap_or_clone(program).program;
+
+    if compiler_config.replace_ids {
+        sierra_program =
+            DebugReplacer::new(db).with_naming(compiler_config.debug_naming).apply(&sierra_program);
+    }
+
+    let failed_functions = failed_functions
+        .into_iter()
+        .map(|function_id| function_id.base_semantic_function(db).full_path((*db).upcast()))
+        .collect();
+    Ok((sierra_program, failed_functions))
+}
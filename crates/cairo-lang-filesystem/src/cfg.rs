@@ -1,9 +1,14 @@
 use std::collections::BTreeSet;
 use std::fmt;
+use std::str::FromStr;
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use smol_str::SmolStr;
 
+#[cfg(test)]
+#[path = "cfg_test.rs"]
+mod test;
+
 /// Option for the `#[cfg(...)]` language attribute.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Cfg {
@@ -35,6 +40,22 @@ impl fmt::Display for Cfg {
     }
 }
 
+/// Parses a `cfg` option from the command line form `name` or `key=value`.
+impl FromStr for Cfg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_valid = |part: &str| {
+            !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        };
+        match s.split_once('=') {
+            Some((key, value)) if is_valid(key) && !value.is_empty() => Ok(Cfg::kv(key, value)),
+            None if is_valid(s) => Ok(Cfg::name(s)),
+            _ => Err(format!("Invalid cfg option `{s}`, expected `name` or `key=value`.")),
+        }
+    }
+}
+
 impl fmt::Debug for Cfg {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("Cfg").field(&DebugAsDisplay(&self)).finish()
@@ -0,0 +1,11 @@
+use super::Cfg;
+
+#[test]
+fn test_cfg_from_str() {
+    assert_eq!("test".parse(), Ok(Cfg::name("test")));
+    assert_eq!("feature=debug_logs".parse(), Ok(Cfg::kv("feature", "debug_logs")));
+    assert_eq!("target=a=b".parse(), Ok(Cfg::kv("target", "a=b")));
+    for invalid in ["", "=value", "key=", "two words"] {
+        assert!(invalid.parse::<Cfg>().is_err(), "`{invalid}` should be invalid.");
+    }
+}
@@ -20,9 +20,10 @@ use crate::graph_algorithms::feedback_set::flag_add_withdraw_gas;
 use crate::implicits::lower_implicits;
 use crate::inline::{apply_inlining, PrivInlineData};
 use crate::lower::{lower_semantic_function, MultiLowering};
+use crate::mutation::{apply_mutation, Mutation};
 use crate::optimizations::branch_inversion::branch_inversion;
 use crate::optimizations::cancel_ops::cancel_ops;
-use crate::optimizations::config::OptimizationConfig;
+use crate::optimizations::config::{InliningStrategy, OptimizationConfig};
 use crate::optimizations::const_folding::const_folding;
 use crate::optimizations::match_optimizer::optimize_matches;
 use crate::optimizations::remappings::optimize_remappings;
@@ -282,6 +283,11 @@ pub trait LoweringGroup: SemanticGroup + Upcast<dyn SemanticGroup> {
     /// Returns the configuration struct that controls the behavior of the optimization passes.
     #[salsa::input]
     fn optimization_config(&self) -> Arc<OptimizationConfig>;
+
+    /// Returns the mutation applied to the lowering of the program, for mutation testing, if any.
+    /// See [crate::mutation].
+    #[salsa::input]
+    fn lowering_mutation(&self) -> Option<Mutation>;
 }
 
 pub fn init_lowering_group(db: &mut (dyn LoweringGroup + 'static)) {
@@ -295,7 +301,11 @@ pub fn init_lowering_group(db: &mut (dyn LoweringGroup + 'static)) {
         moveable_functions.push(format!("integer::{}_wide_mul", ty));
     }
 
-    db.set_optimization_config(Arc::new(OptimizationConfig { moveable_functions }));
+    db.set_optimization_config(Arc::new(OptimizationConfig {
+        moveable_functions,
+        inlining_strategy: InliningStrategy::default(),
+    }));
+    db.set_lowering_mutation(None);
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
@@ -349,6 +359,10 @@ fn priv_concrete_function_with_body_lowered_flat(
     let mut lowered =
         (*db.function_with_body_lowering(function.function_with_body_id(db))?).clone();
     concretize_lowered(db, &mut lowered, &function.substitution(semantic_db)?)?;
+    if let Some(mutation) = db.lowering_mutation().filter(|mutation| mutation.function == function)
+    {
+        apply_mutation(&mut lowered, &mutation)?;
+    }
     Ok(Arc::new(lowered))
 }
 
@@ -15,6 +15,7 @@ use crate::db::LoweringGroup;
 use crate::diagnostic::{LoweringDiagnostic, LoweringDiagnosticKind, LoweringDiagnostics};
 use crate::ids::{ConcreteFunctionWithBodyId, FunctionWithBodyId};
 use crate::lower::context::{VarRequest, VariableAllocator};
+use crate::optimizations::config::InliningStrategy;
 use crate::utils::{Rebuilder, RebuilderEx};
 use crate::{
     BlockId, FlatBlock, FlatBlockEnd, FlatLowered, Statement, VarRemapping, VarUsage, VariableId,
@@ -48,15 +49,28 @@ pub fn priv_inline_data(
     );
     let config = db.function_declaration_inline_config(semantic_function_id)?;
 
-    let info = match config {
-        InlineConfiguration::Never(_) => InlineInfo { is_inlinable: false, should_inline: false },
-        InlineConfiguration::Should(_) => InlineInfo { is_inlinable: true, should_inline: true },
-        InlineConfiguration::Always(_) => {
+    let inlining_strategy = db.optimization_config().inlining_strategy;
+    let info = match (&config, inlining_strategy) {
+        (InlineConfiguration::Never(_), _)
+        | (InlineConfiguration::Should(_) | InlineConfiguration::None, InliningStrategy::Avoid) => {
+            InlineInfo { is_inlinable: false, should_inline: false }
+        }
+        (InlineConfiguration::Should(_), InliningStrategy::Default) => {
+            InlineInfo { is_inlinable: true, should_inline: true }
+        }
+        (InlineConfiguration::Always(_), _) => {
             gather_inlining_info(db, &mut diagnostics, true, function_id)?
         }
-        InlineConfiguration::None => {
+        (InlineConfiguration::None, InliningStrategy::Default) => {
             gather_inlining_info(db, &mut diagnostics, false, function_id)?
         }
+        (
+            InlineConfiguration::Should(_) | InlineConfiguration::None,
+            InliningStrategy::Aggressive,
+        ) => {
+            let info = gather_inlining_info(db, &mut diagnostics, false, function_id)?;
+            InlineInfo { should_inline: info.is_inlinable, ..info }
+        }
     };
     Ok(Arc::new(PrivInlineData { diagnostics: diagnostics.build(), config, info }))
 }
@@ -1,4 +1,5 @@
 use std::ops::Deref;
+use std::sync::Arc;
 
 use cairo_lang_debug::DebugWithDb;
 use cairo_lang_semantic::test_utils::setup_test_function;
@@ -9,6 +10,7 @@ use crate::db::LoweringGroup;
 use crate::fmt::LoweredFormatter;
 use crate::ids::ConcreteFunctionWithBodyId;
 use crate::inline::apply_inlining;
+use crate::optimizations::config::InliningStrategy;
 use crate::test_utils::LoweringDatabaseForTesting;
 
 cairo_lang_test_utils::test_file_test!(
@@ -17,15 +19,28 @@ cairo_lang_test_utils::test_file_test!(
     {
         inline :"inline",
         inline_diagnostics :"inline_diagnostics",
+        inlining_strategy :"inlining_strategy",
     },
     test_function_inlining
 );
 
 fn test_function_inlining(
     inputs: &OrderedHashMap<String, String>,
-    _args: &OrderedHashMap<String, String>,
+    args: &OrderedHashMap<String, String>,
 ) -> TestRunnerResult {
-    let db = &mut LoweringDatabaseForTesting::default();
+    let inlining_strategy: InliningStrategy =
+        args.get("inlining_strategy").map_or(Ok(Default::default()), |arg| arg.parse()).unwrap();
+    let db = &mut if inlining_strategy == InliningStrategy::Default {
+        LoweringDatabaseForTesting::default()
+    } else {
+        // The inputs of the shared db cannot be changed, so use a fresh one.
+        let mut db = LoweringDatabaseForTesting::new();
+        let optimization_config = db.optimization_config().as_ref().clone();
+        db.set_optimization_config(Arc::new(
+            optimization_config.with_inlining_strategy(inlining_strategy),
+        ));
+        db
+    };
     let (test_function, semantic_diagnostics) = setup_test_function(
         db,
         inputs["function"].as_str(),
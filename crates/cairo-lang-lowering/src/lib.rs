@@ -13,6 +13,8 @@ pub mod ids;
 pub mod implicits;
 pub mod inline;
 pub mod lower;
+pub mod metrics;
+pub mod mutation;
 pub mod objects;
 pub mod optimizations;
 pub mod panic;
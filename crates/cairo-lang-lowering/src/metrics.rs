@@ -0,0 +1,116 @@
+//! Metrics of the lowered functions of a program, e.g. for code quality dashboards.
+
+use cairo_lang_defs as defs;
+use cairo_lang_defs::ids::{ModuleId, ModuleItemId};
+use cairo_lang_diagnostics::Maybe;
+
+use crate::db::LoweringGroup;
+use crate::ids::{FunctionId, FunctionLongId};
+use crate::{FlatBlockEnd, FlatLowered, MatchInfo, Statement};
+
+#[cfg(test)]
+#[path = "metrics_test.rs"]
+mod test;
+
+/// Metrics of a lowered function, including the loops within it.
+///
+/// The metrics are computed on the lowering of the function before concretization and
+/// optimizations, so they reflect the function as written rather than the generated code.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FunctionMetrics {
+    /// The number of blocks.
+    pub n_blocks: usize,
+    /// The number of statements.
+    pub n_statements: usize,
+    /// The number of blocks ending with a match, i.e. the points where the flow branches.
+    pub n_branches: usize,
+    /// The number of loops.
+    pub n_loops: usize,
+    /// The cyclomatic complexity - the number of linearly independent paths through the function,
+    /// which is one more than the number of additional arms the matches add.
+    pub cyclomatic_complexity: usize,
+    /// The distinct functions called, by order of first call. The loops of the function are not
+    /// included.
+    pub callees: Vec<FunctionId>,
+}
+impl FunctionMetrics {
+    /// Returns the number of distinct functions called.
+    pub fn fan_out(&self) -> usize {
+        self.callees.len()
+    }
+
+    /// Adds the metrics of the given lowered body, being the function or one of its loops.
+    fn add_lowered(&mut self, db: &dyn LoweringGroup, lowered: &FlatLowered) {
+        for (_, block) in &lowered.blocks {
+            self.n_blocks += 1;
+            self.n_statements += block.statements.len();
+            for statement in &block.statements {
+                if let Statement::Call(statement_call) = statement {
+                    self.add_callee(db, statement_call.function);
+                }
+            }
+            if let FlatBlockEnd::Match { info } = &block.end {
+                self.n_branches += 1;
+                self.cyclomatic_complexity += info.arms().len().saturating_sub(1);
+                if let MatchInfo::Extern(s) = info {
+                    self.add_callee(db, s.function);
+                }
+            }
+        }
+    }
+
+    fn add_callee(&mut self, db: &dyn LoweringGroup, function: FunctionId) {
+        if matches!(function.lookup(db), FunctionLongId::Generated(_)) {
+            return;
+        }
+        if !self.callees.contains(&function) {
+            self.callees.push(function);
+        }
+    }
+}
+
+/// Computes the metrics of the given function.
+pub fn function_metrics(
+    db: &dyn LoweringGroup,
+    function_id: defs::ids::FunctionWithBodyId,
+) -> Maybe<FunctionMetrics> {
+    let multi_lowering = db.priv_function_with_body_multi_lowering(function_id)?;
+    let mut metrics = FunctionMetrics {
+        n_loops: multi_lowering.generated_lowerings.len(),
+        cyclomatic_complexity: 1,
+        ..Default::default()
+    };
+    metrics.add_lowered(db, &multi_lowering.main_lowering);
+    for lowered in multi_lowering.generated_lowerings.values() {
+        metrics.add_lowered(db, lowered);
+    }
+    Ok(metrics)
+}
+
+/// Computes the metrics of the free functions and impl functions of the given module, by order of
+/// declaration.
+pub fn module_function_metrics(
+    db: &dyn LoweringGroup,
+    module_id: ModuleId,
+) -> Maybe<Vec<(defs::ids::FunctionWithBodyId, FunctionMetrics)>> {
+    let mut function_ids = vec![];
+    for item in db.module_items(module_id)?.iter() {
+        match item {
+            ModuleItemId::FreeFunction(free_function) => {
+                function_ids.push(defs::ids::FunctionWithBodyId::Free(*free_function));
+            }
+            ModuleItemId::Impl(impl_def_id) => {
+                function_ids.extend(
+                    db.impl_functions(*impl_def_id)?
+                        .values()
+                        .map(|impl_function| defs::ids::FunctionWithBodyId::Impl(*impl_function)),
+                );
+            }
+            _ => {}
+        }
+    }
+    function_ids
+        .into_iter()
+        .map(|function_id| Ok((function_id, function_metrics(db, function_id)?)))
+        .collect()
+}
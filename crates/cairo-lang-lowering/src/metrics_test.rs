@@ -0,0 +1,57 @@
+use std::fmt::Write;
+
+use cairo_lang_defs::ids::TopLevelLanguageElementId;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+
+use super::module_function_metrics;
+use crate::test_utils::LoweringDatabaseForTesting;
+
+cairo_lang_test_utils::test_file_test!(
+    metrics,
+    "src/test_data",
+    {
+        metrics: "metrics",
+    },
+    test_function_metrics
+);
+
+fn test_function_metrics(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let db = &LoweringDatabaseForTesting::default();
+    let (test_module, semantic_diagnostics) =
+        setup_test_module(db, inputs["module_code"].as_str()).split();
+
+    let mut metrics = String::new();
+    for (function_id, function_metrics) in
+        module_function_metrics(db, test_module.module_id).unwrap()
+    {
+        writeln!(metrics, "{}:", function_id.full_path(db)).unwrap();
+        let callees = function_metrics
+            .callees
+            .iter()
+            .map(|callee| callee.semantic_full_path(db))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(
+            metrics,
+            "  blocks: {}, statements: {}, branches: {}, loops: {}, complexity: {}, fan out: {}",
+            function_metrics.n_blocks,
+            function_metrics.n_statements,
+            function_metrics.n_branches,
+            function_metrics.n_loops,
+            function_metrics.cyclomatic_complexity,
+            function_metrics.fan_out(),
+        )
+        .unwrap();
+        writeln!(metrics, "  callees: [{callees}]").unwrap();
+    }
+
+    TestRunnerResult::success(OrderedHashMap::from([
+        ("semantic_diagnostics".into(), semantic_diagnostics),
+        ("metrics".into(), metrics),
+    ]))
+}
@@ -0,0 +1,179 @@
+//! Mutations of lowered functions, for mutation testing - checking whether the tests of a program
+//! detect small changes to its code.
+//!
+//! A mutation is applied by setting it as the `lowering_mutation` input of the db, which changes
+//! the flat lowering of the mutated function, before inlining, so that the inlined copies of the
+//! function are mutated as well. The rest of the lowering of the program is reused from the
+//! cache of the db.
+
+use cairo_lang_defs::diagnostic_utils::StableLocation;
+use cairo_lang_diagnostics::{skip_diagnostic, Maybe};
+use cairo_lang_semantic as semantic;
+use cairo_lang_semantic::corelib::{core_bool_enum, validate_literal};
+use num_bigint::BigInt;
+
+use crate::db::LoweringGroup;
+use crate::ids::{ConcreteFunctionWithBodyId, GeneratedFunction};
+use crate::{
+    BlockId, FlatBlock, FlatBlockEnd, FlatLowered, MatchInfo, Statement, StatementLiteral,
+};
+
+#[cfg(test)]
+#[path = "mutation_test.rs"]
+mod test;
+
+/// The kind of a mutation.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MutationKind {
+    /// Swaps the arms of the match on a boolean ending the block, negating the condition, e.g.
+    /// turning `<` into `>=`.
+    FlipCondition,
+    /// Adds one to the literal of the given statement of the block.
+    IncrementLiteral { statement_idx: usize },
+    /// Subtracts one from the literal of the given statement of the block.
+    DecrementLiteral { statement_idx: usize },
+}
+
+/// A mutation of a block of a lowered function.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Mutation {
+    pub function: ConcreteFunctionWithBodyId,
+    pub block_id: BlockId,
+    pub kind: MutationKind,
+}
+impl Mutation {
+    /// Returns a short description of the mutation, e.g. for reporting a surviving mutant. Must be
+    /// called while the mutation is not applied.
+    pub fn describe(&self, db: &dyn LoweringGroup) -> Maybe<String> {
+        let lowered = db.priv_concrete_function_with_body_lowered_flat(self.function)?;
+        Ok(match self.kind {
+            MutationKind::FlipCondition => "flipped condition".into(),
+            MutationKind::IncrementLiteral { statement_idx }
+            | MutationKind::DecrementLiteral { statement_idx } => {
+                let literal = mutated_literal(&lowered.blocks[self.block_id], statement_idx)?;
+                let mutated =
+                    self.kind.mutated_value(&literal.value).ok_or_else(skip_diagnostic)?;
+                format!("replaced literal {} with {mutated}", literal.value)
+            }
+        })
+    }
+
+    /// Returns the location of the mutated code. Must be called while the mutation is not applied.
+    pub fn location(&self, db: &dyn LoweringGroup) -> Maybe<StableLocation> {
+        let lowered = db.priv_concrete_function_with_body_lowered_flat(self.function)?;
+        let block = &lowered.blocks[self.block_id];
+        let location = match self.kind {
+            MutationKind::FlipCondition => {
+                let FlatBlockEnd::Match { info } = &block.end else {
+                    return Err(skip_diagnostic());
+                };
+                *info.location()
+            }
+            MutationKind::IncrementLiteral { statement_idx }
+            | MutationKind::DecrementLiteral { statement_idx } => {
+                lowered.variables[mutated_literal(block, statement_idx)?.output].location
+            }
+        };
+        Ok(location.get(db).stable_location)
+    }
+}
+impl MutationKind {
+    /// Returns the value a literal mutation replaces the given literal value with, or `None` if
+    /// this is not a literal mutation.
+    fn mutated_value(&self, value: &BigInt) -> Option<BigInt> {
+        match self {
+            MutationKind::FlipCondition => None,
+            MutationKind::IncrementLiteral { .. } => Some(value + 1),
+            MutationKind::DecrementLiteral { .. } => Some(value - 1),
+        }
+    }
+}
+
+/// Returns the literal statement of the given index in the block, or an error if the statement is
+/// not a literal.
+fn mutated_literal(block: &FlatBlock, statement_idx: usize) -> Maybe<&StatementLiteral> {
+    match block.statements.get(statement_idx) {
+        Some(Statement::Literal(literal)) => Ok(literal),
+        _ => Err(skip_diagnostic()),
+    }
+}
+
+/// Returns the possible mutations of the given function and of the loops within it, by order of
+/// appearance.
+///
+/// Must be called while no mutation is applied to the db.
+pub fn function_mutations(
+    db: &dyn LoweringGroup,
+    function_id: semantic::ConcreteFunctionWithBodyId,
+) -> Maybe<Vec<Mutation>> {
+    let multi_lowering =
+        db.priv_function_with_body_multi_lowering(function_id.function_with_body_id(db.upcast()))?;
+    let mut functions = vec![ConcreteFunctionWithBodyId::from_semantic(db, function_id)];
+    functions.extend(
+        multi_lowering
+            .generated_lowerings
+            .keys()
+            .map(|element| GeneratedFunction { parent: function_id, element: *element }.body(db)),
+    );
+
+    let bool_enum = core_bool_enum(db.upcast());
+    let mut mutations = vec![];
+    for function in functions {
+        let lowered = db.priv_concrete_function_with_body_lowered_flat(function)?;
+        for (block_id, block) in lowered.blocks.iter() {
+            for (statement_idx, statement) in block.statements.iter().enumerate() {
+                let Statement::Literal(literal) = statement else { continue };
+                let ty = lowered.variables[literal.output].ty;
+                for kind in [
+                    MutationKind::IncrementLiteral { statement_idx },
+                    MutationKind::DecrementLiteral { statement_idx },
+                ] {
+                    // Mutations leaving the range of the type of the literal are skipped.
+                    let Some(mutated) = kind.mutated_value(&literal.value) else { continue };
+                    if validate_literal(db.upcast(), ty, mutated).is_ok() {
+                        mutations.push(Mutation { function, block_id, kind });
+                    }
+                }
+            }
+            if let FlatBlockEnd::Match { info: MatchInfo::Enum(info) } = &block.end {
+                if info.concrete_enum_id == bool_enum {
+                    mutations.push(Mutation {
+                        function,
+                        block_id,
+                        kind: MutationKind::FlipCondition,
+                    });
+                }
+            }
+        }
+    }
+    Ok(mutations)
+}
+
+/// Applies the given mutation to the lowering of its function. Fails if the mutation does not
+/// match the lowering.
+pub(crate) fn apply_mutation(lowered: &mut FlatLowered, mutation: &Mutation) -> Maybe<()> {
+    let block = &mut lowered.blocks[mutation.block_id];
+    match mutation.kind {
+        MutationKind::FlipCondition => {
+            let FlatBlockEnd::Match { info: MatchInfo::Enum(info) } = &mut block.end else {
+                return Err(skip_diagnostic());
+            };
+            // Both arms introduce a single unit variable, so swapping the blocks along with the
+            // variables keeps the lowering valid.
+            let [false_arm, true_arm] = &mut info.arms[..] else {
+                return Err(skip_diagnostic());
+            };
+            std::mem::swap(&mut false_arm.block_id, &mut true_arm.block_id);
+            std::mem::swap(&mut false_arm.var_ids, &mut true_arm.var_ids);
+        }
+        MutationKind::IncrementLiteral { statement_idx }
+        | MutationKind::DecrementLiteral { statement_idx } => {
+            let Some(Statement::Literal(literal)) = block.statements.get_mut(statement_idx) else {
+                return Err(skip_diagnostic());
+            };
+            literal.value =
+                mutation.kind.mutated_value(&literal.value).ok_or_else(skip_diagnostic)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use std::fmt::Write;
+
+use cairo_lang_debug::DebugWithDb;
+use cairo_lang_semantic::test_utils::setup_test_function;
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+
+use super::function_mutations;
+use crate::db::LoweringGroup;
+use crate::fmt::LoweredFormatter;
+use crate::ids::ConcreteFunctionWithBodyId;
+use crate::test_utils::LoweringDatabaseForTesting;
+
+cairo_lang_test_utils::test_file_test!(
+    mutation,
+    "src/test_data",
+    {
+        mutation: "mutation",
+    },
+    test_function_mutations
+);
+
+/// Lists the mutations of the function, and shows its final lowering with the mutation of index
+/// `applied_mutation` applied.
+fn test_function_mutations(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let db = &mut LoweringDatabaseForTesting::new();
+    let (test_function, semantic_diagnostics) = setup_test_function(
+        db,
+        inputs["function"].as_str(),
+        inputs["function_name"].as_str(),
+        inputs["module_code"].as_str(),
+    )
+    .split();
+
+    let mutations = function_mutations(db, test_function.concrete_function_id).unwrap();
+    let mut mutations_format = String::new();
+    for (idx, mutation) in mutations.iter().enumerate() {
+        let location = mutation.location(db).unwrap().diagnostic_location(db);
+        writeln!(
+            mutations_format,
+            "{idx}: {}, at {:?}",
+            mutation.describe(db).unwrap(),
+            location.debug(db)
+        )
+        .unwrap();
+    }
+
+    let applied_mutation = mutations[inputs["applied_mutation"].parse::<usize>().unwrap()];
+    db.set_lowering_mutation(Some(applied_mutation));
+    let function_id =
+        ConcreteFunctionWithBodyId::from_semantic(db, test_function.concrete_function_id);
+    let lowered = db.concrete_function_with_body_lowered(function_id).unwrap();
+    let lowered_formatter = LoweredFormatter::new(db, &lowered.variables);
+
+    TestRunnerResult::success(OrderedHashMap::from([
+        ("semantic_diagnostics".into(), semantic_diagnostics),
+        ("mutations".into(), mutations_format),
+        ("mutated_lowering".into(), format!("{:?}", lowered.debug(&lowered_formatter))),
+    ]))
+}
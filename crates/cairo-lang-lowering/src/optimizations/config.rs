@@ -1,3 +1,4 @@
+use std::str::FromStr;
 use std::sync::Arc;
 
 use cairo_lang_semantic::corelib;
@@ -7,18 +8,54 @@ use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
 use crate::db::LoweringGroup;
 use crate::ids::{FunctionId, FunctionLongId};
 
+/// The strategy for inlining functions that are not annotated with `#[inline(always)]` or
+/// `#[inline(never)]`, which are always respected.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+pub enum InliningStrategy {
+    /// Inline functions annotated with `#[inline]`, and small functions by a heuristic.
+    #[default]
+    Default,
+    /// Inline only functions annotated with `#[inline(always)]`.
+    Avoid,
+    /// Inline every function that can be inlined.
+    Aggressive,
+}
+
+impl FromStr for InliningStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "default" => Ok(InliningStrategy::Default),
+            "avoid" => Ok(InliningStrategy::Avoid),
+            "aggressive" => Ok(InliningStrategy::Aggressive),
+            _ => Err(format!(
+                "Invalid inlining strategy `{s}`, expected `default`, `avoid` or `aggressive`."
+            )),
+        }
+    }
+}
+
 /// A configuration struct that controls the behavior of the optimization passes.
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct OptimizationConfig {
     /// A list of functions that can be moved during the reorder_statements optimization.
     pub moveable_functions: Vec<String>,
+    /// The strategy for inlining functions.
+    pub inlining_strategy: InliningStrategy,
 }
 
 impl OptimizationConfig {
     /// A configuration where the list of movable functions is empty.
     /// Used to make tests easier to write.
     pub fn no_movable_functions() -> Self {
-        Self { moveable_functions: vec![] }
+        Self { moveable_functions: vec![], inlining_strategy: InliningStrategy::default() }
+    }
+
+    /// Sets the strategy for inlining functions.
+    pub fn with_inlining_strategy(mut self, inlining_strategy: InliningStrategy) -> Self {
+        self.inlining_strategy = inlining_strategy;
+        self
     }
 }
 
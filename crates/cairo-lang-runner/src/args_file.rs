@@ -0,0 +1,293 @@
+//! Parsing of the arguments of a run from a JSON arguments file, in the layout of the
+//! `--args-file` of `cairo1-run`, serialized according to the signature of the run function.
+use cairo_felt::Felt252;
+use cairo_lang_sierra::extensions::core::CoreTypeConcrete;
+use cairo_lang_sierra::extensions::starknet::StarkNetTypeConcrete;
+use cairo_lang_sierra::ids::ConcreteTypeId;
+use cairo_lang_sierra::program::Function;
+use num_bigint::{BigInt, BigUint};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::felt_utils::parse_felt252;
+use crate::{is_implicit_type, Arg, SierraCasmRunner};
+
+#[cfg(test)]
+#[path = "args_file_test.rs"]
+mod test;
+
+#[derive(Debug, Error)]
+pub enum ArgsFileError {
+    #[error("Invalid JSON: {0}.")]
+    InvalidJson(#[from] serde_json::Error),
+    #[error("Expected a JSON array of arguments, got `{0}`.")]
+    NotAnArray(String),
+    #[error("Expected {expected} arguments, got {actual}.")]
+    ArgumentsCountMismatch { expected: usize, actual: usize },
+    #[error(
+        "Invalid value `{value}` at {location}, expected an integer, or a decimal or `0x` \
+         prefixed hex string."
+    )]
+    InvalidValue { location: String, value: String },
+    #[error(
+        "Integer `{value}` at {location} is too large for a JSON number, pass it as a string."
+    )]
+    ImpreciseNumber { location: String, value: String },
+    #[error("Value `{value}` at {location} is out of the range of `{ty}`.")]
+    ValueOutOfRange { location: String, value: String, ty: String },
+    #[error("Type mismatch at {location}: expected {expected} for `{ty}`, got `{value}`.")]
+    TypeMismatch { location: String, ty: String, expected: String, value: String },
+    #[error(
+        "Invalid variant `{value}` at {location} for `{ty}`, which has {n_variants} variants."
+    )]
+    InvalidVariant { location: String, ty: String, value: String, n_variants: usize },
+    #[error("Unsupported argument type `{ty}` at {location}.")]
+    UnsupportedType { location: String, ty: String },
+}
+
+/// Parses the arguments of a run of `func` from a JSON array, where each element is the value of
+/// a parameter of the function, excluding its builtins, gas and system parameters.
+///
+/// Values are given according to their types:
+/// - Felts, integers and other single felt types as JSON integers, which may be negative, or as
+///   decimal or `0x` prefixed hex strings, for values too large for JSON integers.
+/// - Arrays, and their snapshots, as JSON arrays of their elements.
+/// - Structs with a single member, such as `Span`, as the value of their member.
+/// - Other structs and tuples as JSON arrays of their members.
+/// - Enums as `{"variant": <index>, "value": <value>}`, where `value` may be omitted for variants
+///   of the unit type. Enums of two unit variants, such as `bool`, may also be given as `false` and
+///   `true`.
+///
+/// For example, `[1, "0x2", [[3], []], {"variant": 0, "value": [4, 5]}]` for a function taking a
+/// `felt252`, a `u8`, an `Array<Span<felt252>>` and an `Option<u256>`.
+///
+/// `param_names` are the names of the parameters of the function, used for locating errors.
+pub fn parse_args_file(
+    json: &str,
+    runner: &SierraCasmRunner,
+    func: &Function,
+    param_names: Option<&[String]>,
+) -> Result<Vec<Arg>, ArgsFileError> {
+    let document: Value = serde_json::from_str(json)?;
+    let Value::Array(values) = document else {
+        return Err(ArgsFileError::NotAnArray(document.to_string()));
+    };
+    let param_types = func
+        .signature
+        .param_types
+        .iter()
+        .filter(|ty| !is_implicit_type(&runner.get_info(ty).long_id.generic_id))
+        .collect::<Vec<_>>();
+    if param_types.len() != values.len() {
+        return Err(ArgsFileError::ArgumentsCountMismatch {
+            expected: param_types.len(),
+            actual: values.len(),
+        });
+    }
+    let serializer = ArgsSerializer { runner };
+    let mut args = vec![];
+    for (param_index, (ty, value)) in param_types.into_iter().zip(values.iter()).enumerate() {
+        let location = match param_names.and_then(|names| names.get(param_index)) {
+            Some(name) => format!("parameter `{name}`"),
+            None => format!("argument {param_index}"),
+        };
+        serializer.serialize(ty, value, &location, &mut args)?;
+    }
+    Ok(args)
+}
+
+/// Serializes JSON values into the memory layout of their Sierra types.
+struct ArgsSerializer<'a> {
+    runner: &'a SierraCasmRunner,
+}
+impl ArgsSerializer<'_> {
+    /// Serializes `value` as a value of type `ty`, found at `location` in the arguments file, and
+    /// appends it to `args`.
+    fn serialize(
+        &self,
+        ty: &ConcreteTypeId,
+        value: &Value,
+        location: &str,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), ArgsFileError> {
+        let mismatch = |expected: &str| ArgsFileError::TypeMismatch {
+            location: location.into(),
+            ty: ty.to_string(),
+            expected: expected.into(),
+            value: value.to_string(),
+        };
+        match self.runner.sierra_program_registry.get_type(ty).unwrap() {
+            CoreTypeConcrete::Felt252(_)
+            | CoreTypeConcrete::Bytes31(_)
+            | CoreTypeConcrete::StarkNet(
+                StarkNetTypeConcrete::ClassHash(_)
+                | StarkNetTypeConcrete::ContractAddress(_)
+                | StarkNetTypeConcrete::StorageBaseAddress(_)
+                | StarkNetTypeConcrete::StorageAddress(_),
+            ) => args.push(Arg::Value(parse_json_felt252(value, location)?)),
+            CoreTypeConcrete::Uint8(_) => args.push(parse_unsigned(ty, value, location, 8)?),
+            CoreTypeConcrete::Uint16(_) => args.push(parse_unsigned(ty, value, location, 16)?),
+            CoreTypeConcrete::Uint32(_) => args.push(parse_unsigned(ty, value, location, 32)?),
+            CoreTypeConcrete::Uint64(_) => args.push(parse_unsigned(ty, value, location, 64)?),
+            CoreTypeConcrete::Uint128(_) => args.push(parse_unsigned(ty, value, location, 128)?),
+            CoreTypeConcrete::Sint8(_) => args.push(parse_signed(ty, value, location, 8)?),
+            CoreTypeConcrete::Sint16(_) => args.push(parse_signed(ty, value, location, 16)?),
+            CoreTypeConcrete::Sint32(_) => args.push(parse_signed(ty, value, location, 32)?),
+            CoreTypeConcrete::Sint64(_) => args.push(parse_signed(ty, value, location, 64)?),
+            CoreTypeConcrete::Sint128(_) => args.push(parse_signed(ty, value, location, 128)?),
+            CoreTypeConcrete::NonZero(wrapped) | CoreTypeConcrete::Snapshot(wrapped) => {
+                self.serialize(&wrapped.ty, value, location, args)?
+            }
+            CoreTypeConcrete::Array(array) => {
+                let Value::Array(elements) = value else {
+                    return Err(mismatch("an array"));
+                };
+                let mut element_args = vec![];
+                for (i, element) in elements.iter().enumerate() {
+                    let location = format!("{location}, element {i}");
+                    self.serialize(&array.ty, element, &location, &mut element_args)?;
+                }
+                args.push(Arg::Array(element_args));
+            }
+            CoreTypeConcrete::Struct(structure) => match &structure.members[..] {
+                [member] => self.serialize(member, value, location, args)?,
+                members => {
+                    let Value::Array(values) = value else {
+                        return Err(mismatch(&format!("an array of {} members", members.len())));
+                    };
+                    if values.len() != members.len() {
+                        return Err(mismatch(&format!("an array of {} members", members.len())));
+                    }
+                    for (i, (member, value)) in members.iter().zip(values).enumerate() {
+                        let location = format!("{location}, member {i}");
+                        self.serialize(member, value, &location, args)?;
+                    }
+                }
+            },
+            CoreTypeConcrete::Enum(enm) => {
+                let n_variants = enm.variants.len();
+                let (index, variant_value) = match value {
+                    // Enums of two unit variants, such as `bool`.
+                    Value::Bool(flag)
+                        if n_variants == 2
+                            && enm.variants.iter().all(|variant| self.size(variant) == 0) =>
+                    {
+                        (usize::from(*flag), None)
+                    }
+                    Value::Object(object) => {
+                        let index = object.get("variant").ok_or_else(|| {
+                            mismatch("an object of the `variant` index and its `value`")
+                        })?;
+                        let variant_index = index
+                            .as_u64()
+                            .and_then(|index| usize::try_from(index).ok())
+                            .filter(|index| *index < n_variants)
+                            .ok_or_else(|| ArgsFileError::InvalidVariant {
+                                location: location.into(),
+                                ty: ty.to_string(),
+                                value: index.to_string(),
+                                n_variants,
+                            })?;
+                        (variant_index, object.get("value"))
+                    }
+                    _ => return Err(mismatch("an object of the `variant` index and its `value`")),
+                };
+                // The selector is the index of the variant for enums of at most two variants, and
+                // otherwise the jump offset of the variant in the `enum_match` jump table.
+                let selector = if n_variants <= 2 { index } else { 2 * (n_variants - index) - 1 };
+                args.push(Arg::Value(selector.into()));
+                let variant = &enm.variants[index];
+                // The variant value is padded from the left to the size of the largest variant.
+                let padding = self.size(ty) - 1 - self.size(variant);
+                args.extend((0..padding).map(|_| Arg::Value(0.into())));
+                let location = format!("{location}, variant {index}");
+                match variant_value {
+                    Some(variant_value) => {
+                        self.serialize(variant, variant_value, &location, args)?
+                    }
+                    None if self.size(variant) == 0 => {}
+                    None => {
+                        return Err(mismatch("an object of the `variant` index and its `value`"));
+                    }
+                }
+            }
+            _ => {
+                return Err(ArgsFileError::UnsupportedType {
+                    location: location.into(),
+                    ty: ty.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the size of the given type in memory.
+    fn size(&self, ty: &ConcreteTypeId) -> usize {
+        self.runner.type_sizes[ty] as usize
+    }
+}
+
+/// Parses an unsigned integer of `bits` bits.
+fn parse_unsigned(
+    ty: &ConcreteTypeId,
+    value: &Value,
+    location: &str,
+    bits: u32,
+) -> Result<Arg, ArgsFileError> {
+    let felt = parse_json_felt252(value, location)?;
+    if felt.to_biguint() >= BigUint::from(1u32) << bits {
+        return Err(out_of_range(ty, value, location));
+    }
+    Ok(Arg::Value(felt))
+}
+
+/// Parses a signed integer of `bits` bits.
+fn parse_signed(
+    ty: &ConcreteTypeId,
+    value: &Value,
+    location: &str,
+    bits: u32,
+) -> Result<Arg, ArgsFileError> {
+    let felt = parse_json_felt252(value, location)?;
+    let bound = BigInt::from(1u32) << (bits - 1);
+    let signed = felt.to_signed_felt();
+    if signed >= bound || signed < -bound {
+        return Err(out_of_range(ty, value, location));
+    }
+    Ok(Arg::Value(felt))
+}
+
+/// Returns the error for a value found at `location` out of the range of `ty`.
+fn out_of_range(ty: &ConcreteTypeId, value: &Value, location: &str) -> ArgsFileError {
+    ArgsFileError::ValueOutOfRange {
+        location: location.into(),
+        value: value.to_string(),
+        ty: ty.to_string(),
+    }
+}
+
+/// Parses a felt from a JSON integer or string, found at `location` in the arguments file.
+fn parse_json_felt252(value: &Value, location: &str) -> Result<Felt252, ArgsFileError> {
+    let invalid =
+        || ArgsFileError::InvalidValue { location: location.into(), value: value.to_string() };
+    let value: BigInt = match value {
+        Value::Number(number) => {
+            if let Some(value) = number.as_i64() {
+                value.into()
+            } else if let Some(value) = number.as_u64() {
+                value.into()
+            } else if number.as_f64().is_some_and(|value| value.fract() == 0.0) {
+                // Integers out of the range of 64 bits are parsed as floats, losing precision.
+                return Err(ArgsFileError::ImpreciseNumber {
+                    location: location.into(),
+                    value: number.to_string(),
+                });
+            } else {
+                return Err(invalid());
+            }
+        }
+        Value::String(value) => return parse_felt252(value).ok_or_else(invalid),
+        _ => return Err(invalid()),
+    };
+    Ok(value.into())
+}
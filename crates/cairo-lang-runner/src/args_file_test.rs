@@ -0,0 +1,177 @@
+use cairo_felt::Felt252;
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use indoc::indoc;
+use test_case::test_case;
+
+use super::parse_args_file;
+use crate::value_format::function_param_names;
+use crate::{Arg, RunResultValue, SierraCasmRunner};
+
+/// Returns a runner of a program whose `run` function takes parameters of various types, and the
+/// names of the parameters.
+fn setup_runner() -> (SierraCasmRunner, Vec<String>) {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(
+        &db,
+        indoc! {"
+            fn run(
+                a: felt252, b: u8, c: Array<Span<felt252>>, d: Option<u256>, e: bool, f: i8,
+            ) -> felt252 {
+                let mut total = a + b.into() + f.into();
+                let mut c = c;
+                while let Option::Some(span) = c.pop_front() {
+                    let mut span = span;
+                    while let Option::Some(value) = span.pop_front() {
+                        total += *value;
+                    };
+                };
+                if let Option::Some(value) = d {
+                    total += value.low.into() * 1000 + value.high.into() * 100000;
+                }
+                if e {
+                    total += 1000000;
+                }
+                total
+            }
+        "},
+    )
+    .unwrap();
+    DiagnosticsReporter::stderr().with_crates(&[test_module.crate_id]).ensure(&db).unwrap();
+    let sierra_program = db.get_sierra_program(vec![test_module.crate_id]).unwrap().program.clone();
+    let replaced_sierra_program = replace_sierra_ids_in_program(&db, &sierra_program);
+    let (original_func, _) = sierra_program
+        .funcs
+        .iter()
+        .zip(&replaced_sierra_program.funcs)
+        .find(|(_, func)| func.id.to_string().ends_with("::run"))
+        .unwrap();
+    let param_names = function_param_names(&db, &original_func.id).unwrap();
+    let runner = SierraCasmRunner::new(
+        replaced_sierra_program,
+        Some(Default::default()),
+        Default::default(),
+        false,
+    )
+    .unwrap();
+    (runner, param_names)
+}
+
+#[test]
+fn test_parse_args_file() {
+    let (runner, param_names) = setup_runner();
+    let func = runner.find_function("::run").unwrap();
+    let value = |value: i64| Arg::Value(Felt252::from(value));
+    let args = parse_args_file(
+        r#"[1, "0x2", [[3], [], [4, 5]], {"variant": 0, "value": [6, "7"]}, true, -1]"#,
+        &runner,
+        func,
+        Some(&param_names),
+    )
+    .unwrap();
+    assert_eq!(
+        args,
+        vec![
+            value(1),
+            value(2),
+            Arg::Array(vec![
+                Arg::Array(vec![value(3)]),
+                Arg::Array(vec![]),
+                Arg::Array(vec![value(4), value(5)]),
+            ]),
+            // `Option::Some` selector, with no padding as `u256` is the largest variant.
+            value(0),
+            value(6),
+            value(7),
+            value(1),
+            value(-1),
+        ]
+    );
+    let result = runner
+        .run_function_with_starknet_context(func, &args, Some(usize::MAX), Default::default())
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![Felt252::from(1706014)]));
+
+    // `Option::None` selector, padded to the size of `u256`.
+    let args = parse_args_file(
+        r#"[0, 0, [], {"variant": 1}, false, 0]"#,
+        &runner,
+        func,
+        Some(&param_names),
+    )
+    .unwrap();
+    assert_eq!(args[3..], [value(1), value(0), value(0), value(0), value(0)]);
+}
+
+#[test_case("[1,", "Invalid JSON: EOF while parsing a value at line 1 column 3."; "invalid json")]
+#[test_case(r#"{"a": 1}"#, r#"Expected a JSON array of arguments, got `{"a":1}`."#; "not an array")]
+#[test_case("[1, 2]", "Expected 6 arguments, got 2."; "arguments count")]
+#[test_case(
+    r#"[true, 0, [], {"variant": 1}, false, 0]"#,
+    "Invalid value `true` at parameter `a`, expected an integer, or a decimal or `0x` prefixed \
+     hex string.";
+    "bool felt"
+)]
+#[test_case(
+    r#"["0xg", 0, [], {"variant": 1}, false, 0]"#,
+    "Invalid value `\"0xg\"` at parameter `a`, expected an integer, or a decimal or `0x` prefixed \
+     hex string.";
+    "invalid hex"
+)]
+#[test_case(
+    r#"[100000000000000000000, 0, [], {"variant": 1}, false, 0]"#,
+    "Integer `100000000000000000000` at parameter `a` is too large for a JSON number, pass it as \
+     a string.";
+    "large number"
+)]
+#[test_case(
+    r#"[0, 256, [], {"variant": 1}, false, 0]"#,
+    "Value `256` at parameter `b` is out of the range of `u8`.";
+    "u8 overflow"
+)]
+#[test_case(
+    r#"[0, 0, [], {"variant": 1}, false, -129]"#,
+    "Value `-129` at parameter `f` is out of the range of `i8`.";
+    "i8 underflow"
+)]
+#[test_case(
+    r#"[0, 0, [[1, [2]]], {"variant": 1}, false, 0]"#,
+    "Invalid value `[2]` at parameter `c`, element 0, element 1, expected an integer, or a \
+     decimal or `0x` prefixed hex string.";
+    "array too deep"
+)]
+#[test_case(
+    r#"[0, 0, [1], {"variant": 1}, false, 0]"#,
+    "Type mismatch at parameter `c`, element 0: expected an array for \
+     `Array<felt252>`, got `1`.";
+    "array too shallow"
+)]
+#[test_case(
+    r#"[0, 0, [], {"variant": 0, "value": [1]}, false, 0]"#,
+    "Type mismatch at parameter `d`, variant 0: expected an array of 2 members for \
+     `core::integer::u256`, got `[1]`.";
+    "struct members count"
+)]
+#[test_case(
+    r#"[0, 0, [], {"variant": 2}, false, 0]"#,
+    "Invalid variant `2` at parameter `d` for `core::option::Option::<core::integer::u256>`, \
+     which has 2 variants.";
+    "invalid variant"
+)]
+#[test_case(
+    r#"[0, 0, [], {"variant": 0}, false, 0]"#,
+    "Type mismatch at parameter `d`: expected an object of the `variant` index and its `value` \
+     for `core::option::Option::<core::integer::u256>`, got `{\"variant\":0}`.";
+    "missing variant value"
+)]
+fn test_parse_args_file_errors(json: &str, expected: &str) {
+    let (runner, param_names) = setup_runner();
+    let func = runner.find_function("::run").unwrap();
+    assert_eq!(
+        parse_args_file(json, &runner, func, Some(&param_names)).unwrap_err().to_string(),
+        expected
+    );
+}
@@ -0,0 +1,80 @@
+use cairo_lang_defs::plugin::{MacroPlugin, MacroPluginMetadata, PluginResult};
+use cairo_lang_lowering::db::LoweringGroup;
+use cairo_lang_semantic::items::attribute::SemanticQueryAttrs;
+use cairo_lang_semantic::plugin::PluginSuite;
+use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_syntax::attribute::structured::AttributeListStructurize;
+use cairo_lang_syntax::node::ast;
+use cairo_lang_syntax::node::db::SyntaxGroup;
+pub use cairo_lang_test_plugin::test_config::AvailableGas;
+use cairo_lang_test_plugin::test_config::{
+    malformed_available_gas_diagnostic, parse_available_gas,
+};
+pub use cairo_lang_test_plugin::AVAILABLE_GAS_ATTR;
+
+use crate::max_steps::MaxStepsPlugin;
+
+#[cfg(test)]
+#[path = "available_gas_test.rs"]
+mod test;
+
+/// Plugin allowing the `available_gas` attribute on functions to run, and creating diagnostics for
+/// malformed uses of it.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct AvailableGasPlugin;
+
+impl MacroPlugin for AvailableGasPlugin {
+    fn generate_code(
+        &self,
+        db: &dyn SyntaxGroup,
+        item_ast: ast::ModuleItem,
+        _metadata: &MacroPluginMetadata<'_>,
+    ) -> PluginResult {
+        let ast::ModuleItem::FreeFunction(free_func_ast) = item_ast else {
+            return PluginResult::default();
+        };
+        let attrs = free_func_ast.attributes(db).structurize(db);
+        PluginResult {
+            code: None,
+            diagnostics: attrs
+                .iter()
+                .filter(|attr| {
+                    attr.id.as_str() == AVAILABLE_GAS_ATTR
+                        && parse_available_gas(db, attr).is_none()
+                })
+                .map(malformed_available_gas_diagnostic)
+                .collect(),
+            remove_original_item: false,
+        }
+    }
+
+    fn declared_attributes(&self) -> Vec<String> {
+        vec![AVAILABLE_GAS_ATTR.to_string()]
+    }
+}
+
+/// Returns a plugin suite with the plugins required for running functions.
+pub fn runner_plugin_suite() -> PluginSuite {
+    let mut suite = PluginSuite::default();
+    suite.add_plugin::<AvailableGasPlugin>().add_plugin::<MaxStepsPlugin>();
+    suite
+}
+
+/// Returns the gas set by the `available_gas` attribute of the function generating the given
+/// Sierra function, if it has one. The id must be the one generated by `db`, i.e. before any
+/// replacement of the Sierra ids.
+pub fn function_available_gas(
+    db: &dyn SierraGenGroup,
+    function_id: &FunctionId,
+) -> Option<AvailableGas> {
+    let lowering_db: &dyn LoweringGroup = db.upcast();
+    let function = db.lookup_intern_sierra_function(function_id.clone());
+    let semantic_function = function.body(lowering_db).ok()??.base_semantic_function(lowering_db);
+    let attr = semantic_function
+        .function_with_body_id(db.upcast())
+        .find_attr(db.upcast(), AVAILABLE_GAS_ATTR)
+        .ok()??;
+    parse_available_gas(db.upcast(), &attr)
+}
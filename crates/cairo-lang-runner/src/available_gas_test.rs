@@ -0,0 +1,70 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_semantic::test_utils::{setup_test_crate, setup_test_module};
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use indoc::indoc;
+
+use super::{function_available_gas, runner_plugin_suite, AvailableGas};
+
+#[test]
+fn test_function_available_gas() {
+    let db = RootDatabase::builder()
+        .detect_corelib()
+        .with_plugin_suite(runner_plugin_suite())
+        .build()
+        .unwrap();
+    let test_module = setup_test_module(
+        &db,
+        indoc! {"
+            #[available_gas(1000)]
+            fn with_gas() -> felt252 {
+                with_static_gas()
+            }
+
+            #[available_gas(static)]
+            #[inline(never)]
+            fn with_static_gas() -> felt252 {
+                without_gas()
+            }
+
+            #[inline(never)]
+            fn without_gas() -> felt252 {
+                5
+            }
+        "},
+    )
+    .unwrap();
+    DiagnosticsReporter::stderr().with_crates(&[test_module.crate_id]).ensure(&db).unwrap();
+    let sierra_program = db.get_sierra_program(vec![test_module.crate_id]).unwrap();
+    let available_gas = sierra_program
+        .program
+        .funcs
+        .iter()
+        .map(|func| function_available_gas(&db, &func.id))
+        .collect::<Vec<_>>();
+    assert_eq!(available_gas, [Some(AvailableGas::Amount(1000)), Some(AvailableGas::Static), None]);
+}
+
+#[test]
+fn test_malformed_available_gas() {
+    let db = RootDatabase::builder()
+        .detect_corelib()
+        .with_plugin_suite(runner_plugin_suite())
+        .build()
+        .unwrap();
+    let crate_id = setup_test_crate(
+        &db,
+        indoc! {"
+            #[available_gas(-1)]
+            fn main() {}
+        "},
+    );
+    let mut diagnostics = String::new();
+    DiagnosticsReporter::write_to_string(&mut diagnostics).with_crates(&[crate_id]).check(&db);
+    assert!(
+        diagnostics.contains(
+            "Attribute should have a single non-negative literal in `i64` range or `static`."
+        ),
+        "Unexpected diagnostics: {diagnostics}"
+    );
+}
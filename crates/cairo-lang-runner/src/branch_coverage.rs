@@ -0,0 +1,202 @@
+//! Branch coverage of runs - which of the branches of the branching Sierra statements were taken,
+//! e.g. for finding the `if` and `match` arms a test suite never reaches.
+
+use std::fmt::Write;
+
+use cairo_lang_sierra::program::{GenStatement, Program, StatementIdx};
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::statements_locations::{SourceSpan, StatementsLocations};
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+
+#[cfg(test)]
+#[path = "branch_coverage_test.rs"]
+mod test;
+
+/// The branches of a Sierra statement branching to more than one target, and the number of times
+/// each of them was taken.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StatementBranches {
+    pub statement_idx: StatementIdx,
+    /// The statement each branch continues to.
+    pub targets: Vec<StatementIdx>,
+    /// The number of times each branch was taken.
+    pub hits: Vec<usize>,
+}
+
+/// A branch never taken in the covered runs.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UntakenBranch {
+    /// The branching statement.
+    pub statement_idx: StatementIdx,
+    /// The index of the branch within the branches of the statement.
+    pub branch_idx: usize,
+    /// The statement the branch continues to.
+    pub target: StatementIdx,
+}
+
+/// The branch coverage of runs of a program, covering all of its branching statements, including
+/// the ones never reached.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct BranchCoverage {
+    /// The branching statements of the program, by statement index.
+    pub statements: Vec<StatementBranches>,
+}
+impl BranchCoverage {
+    /// Creates the coverage of the given program, with no branch taken.
+    pub fn new(program: &Program) -> Self {
+        let statements = program
+            .statements
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, statement)| {
+                let GenStatement::Invocation(invocation) = statement else { return None };
+                if invocation.branches.len() < 2 {
+                    return None;
+                }
+                let statement_idx = StatementIdx(idx);
+                Some(StatementBranches {
+                    statement_idx,
+                    targets: invocation
+                        .branches
+                        .iter()
+                        .map(|branch| statement_idx.next(&branch.target))
+                        .collect(),
+                    hits: vec![0; invocation.branches.len()],
+                })
+            })
+            .collect();
+        Self { statements }
+    }
+
+    /// Creates the coverage of runs of the given program from the number of times each of its
+    /// statements was executed, where `code_offsets` are the offsets of the statements in the
+    /// compiled program, as in its debug info.
+    ///
+    /// As statements compiled into no instructions are counted with the statements following them,
+    /// the hits of a branch are the executions of its target only if no other branch or function
+    /// entry leads to the code of the target. Otherwise, a branch is given the executions of the
+    /// branching statement not attributed to its other branches, which is exact when it is the
+    /// only such branch of the statement, and bounded by the executions of its target.
+    pub fn from_execution_counts(
+        program: &Program,
+        code_offsets: &[usize],
+        execution_counts: &[usize],
+    ) -> Self {
+        // The number of ways into the code at each offset - the function entries, and the branches
+        // from the code at other offsets.
+        let mut entries = UnorderedHashMap::<usize, usize>::default();
+        for func in &program.funcs {
+            *entries.entry(code_offsets[func.entry_point.0]).or_default() += 1;
+        }
+        for (idx, statement) in program.statements.iter().enumerate() {
+            let GenStatement::Invocation(invocation) = statement else { continue };
+            for branch in &invocation.branches {
+                let target = StatementIdx(idx).next(&branch.target);
+                if code_offsets[target.0] != code_offsets[idx] {
+                    *entries.entry(code_offsets[target.0]).or_default() += 1;
+                }
+            }
+        }
+
+        let mut coverage = Self::new(program);
+        for statement in &mut coverage.statements {
+            let mut unattributed = execution_counts[statement.statement_idx.0];
+            let mut shared_branches = vec![];
+            for (branch_idx, target) in statement.targets.iter().enumerate() {
+                if entries.get(&code_offsets[target.0]) == Some(&1) {
+                    statement.hits[branch_idx] = execution_counts[target.0];
+                    unattributed = unattributed.saturating_sub(execution_counts[target.0]);
+                } else {
+                    shared_branches.push(branch_idx);
+                }
+            }
+            for branch_idx in shared_branches {
+                let target = statement.targets[branch_idx];
+                statement.hits[branch_idx] = unattributed.min(execution_counts[target.0]);
+            }
+        }
+        coverage
+    }
+
+    /// Adds the hits of another coverage of the same program, e.g. of another test.
+    pub fn merge(&mut self, other: &BranchCoverage) {
+        for (statement, other_statement) in self.statements.iter_mut().zip(&other.statements) {
+            assert_eq!(statement.statement_idx, other_statement.statement_idx);
+            for (hits, other_hits) in statement.hits.iter_mut().zip(&other_statement.hits) {
+                *hits += other_hits;
+            }
+        }
+    }
+
+    /// Returns the total number of branches.
+    pub fn n_branches(&self) -> usize {
+        self.statements.iter().map(|statement| statement.hits.len()).sum()
+    }
+
+    /// Returns the number of branches taken at least once.
+    pub fn n_taken_branches(&self) -> usize {
+        self.statements
+            .iter()
+            .map(|statement| statement.hits.iter().filter(|hits| **hits > 0).count())
+            .sum()
+    }
+
+    /// Returns the branches never taken, by statement index.
+    pub fn untaken_branches(&self) -> impl Iterator<Item = UntakenBranch> + '_ {
+        self.statements.iter().flat_map(|statement| {
+            statement.hits.iter().zip(&statement.targets).enumerate().filter_map(
+                move |(branch_idx, (hits, target))| {
+                    (*hits == 0).then_some(UntakenBranch {
+                        statement_idx: statement.statement_idx,
+                        branch_idx,
+                        target: *target,
+                    })
+                },
+            )
+        })
+    }
+}
+
+/// Formats the untaken branches of the given coverage whose branching statements are in user code
+/// - outside of the core library - with the source location the branch continues to, e.g. the
+/// location of the `if` or `match` arm. `program` is the covered program.
+pub fn format_untaken_branches(
+    coverage: &BranchCoverage,
+    program: &Program,
+    db: &dyn SierraGenGroup,
+    statements_locations: &StatementsLocations,
+) -> String {
+    let statements_functions = statements_locations.get_statements_functions_map(db);
+    let mut result = String::new();
+    for UntakenBranch { statement_idx, branch_idx, target } in coverage.untaken_branches() {
+        if statements_locations.first_user_code_location(db, &[statement_idx]).is_none() {
+            continue;
+        }
+        let function_identifier =
+            statements_functions.get(&statement_idx).map(String::as_str).unwrap_or("unknown");
+        // The targets of the branches are located at the branching construct itself, so the arm
+        // is located by the first following statement located elsewhere, up to the end of the
+        // function.
+        let branching_location = statements_locations.locations.get(&statement_idx);
+        let mut arm_statements = (target.0..program.statements.len()).take_while(|idx| {
+            idx == &target.0 || !matches!(program.statements[idx - 1], GenStatement::Return(_))
+        });
+        let location = arm_statements
+            .find_map(|idx| {
+                statements_locations
+                    .locations
+                    .get(&StatementIdx(idx))
+                    .filter(|location| Some(*location) != branching_location)
+            })
+            .and_then(|location| SourceSpan::from_location(db, *location))
+            .map(|span| format!("{}:{}:{}", span.file, span.start_line + 1, span.start_col + 1))
+            .unwrap_or_else(|| "unknown location".into());
+        writeln!(
+            result,
+            "  statement {statement_idx} ({function_identifier}): branch {branch_idx} to \
+             {location} never taken"
+        )
+        .unwrap();
+    }
+    result
+}
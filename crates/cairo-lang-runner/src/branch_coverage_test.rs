@@ -0,0 +1,59 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::arc_unwrap_or_clone;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+
+use super::{format_untaken_branches, BranchCoverage};
+use crate::{Arg, SierraCasmRunner};
+
+cairo_lang_test_utils::test_file_test!(
+    branch_coverage,
+    "src/branch_coverage_test_data/",
+    {
+        branch_coverage: "branch_coverage",
+    },
+    test_branch_coverage
+);
+
+/// Runs the function with each of the given arguments, separated by commas, and reports the
+/// merged branch coverage of the runs.
+pub fn test_branch_coverage(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(&db, &inputs["cairo_code"]).unwrap();
+    DiagnosticsReporter::stderr().with_crates(&[test_module.crate_id]).ensure(&db).unwrap();
+
+    // Compile to Sierra.
+    let SierraProgramWithDebug { program: sierra_program, debug_info } =
+        arc_unwrap_or_clone(db.get_sierra_program(vec![test_module.crate_id]).unwrap());
+    let sierra_program = replace_sierra_ids_in_program(&db, &sierra_program);
+    let runner = SierraCasmRunner::new(sierra_program, None, OrderedHashMap::default(), false)
+        .unwrap()
+        .with_branch_coverage(true);
+    let func = runner.find_function(&inputs["function_name"]).unwrap();
+    let mut coverage = BranchCoverage::new(&runner.sierra_program);
+    for arg in inputs["args"].split(',') {
+        let args = [Arg::Value(arg.trim().parse::<i64>().unwrap().into())];
+        let result = runner
+            .run_function_with_starknet_context(func, &args, None, Default::default())
+            .unwrap();
+        coverage.merge(&result.branch_coverage.unwrap());
+    }
+
+    TestRunnerResult::success(OrderedHashMap::from([(
+        "expected_untaken_branches".into(),
+        format_untaken_branches(
+            &coverage,
+            &runner.sierra_program,
+            &db,
+            &debug_info.statements_locations,
+        ),
+    )]))
+}
@@ -1,4 +1,5 @@
 use cairo_felt::Felt252;
+use cairo_lang_starknet::contract::storage_variable_base_address;
 use starknet_crypto::{pedersen_hash, FieldElement};
 
 /// Computes Pedersen hash using STARK curve on an array of elements, as defined
@@ -29,10 +30,15 @@ const CONTRACT_ADDRESS_PREFIX: FieldElement = FieldElement::from_mont([
 ]);
 
 /// Converts a Felt252 to the FieldElement type used in starknet-crypto.
-fn felt252_to_field_element(input: &Felt252) -> FieldElement {
+pub fn felt252_to_field_element(input: &Felt252) -> FieldElement {
     FieldElement::from_bytes_be(&input.to_be_bytes()).unwrap()
 }
 
+/// Converts a FieldElement of starknet-crypto to a Felt252.
+pub fn field_element_to_felt252(input: &FieldElement) -> Felt252 {
+    Felt252::from_bytes_be(&input.to_bytes_be())
+}
+
 /// Calculates the address of a starknet contract, as defined in
 /// <https://docs.starknet.io/documentation/architecture_and_concepts/Smart_Contracts/contract-address/>.
 pub fn calculate_contract_address(
@@ -53,5 +59,48 @@ pub fn calculate_contract_address(
     ]);
     address = address % ADDR_BOUND;
 
-    Felt252::from_bytes_be(&address.to_bytes_be())
+    field_element_to_felt252(&address)
+}
+
+/// Calculates the storage address of the entry of a `LegacyMap` storage variable, given the base
+/// address of the variable and the serialization of the key - as computed by `LegacyHash` followed
+/// by `storage_base_address_from_felt252`.
+pub fn calculate_storage_address(base_address: &Felt252, key: &[Felt252]) -> Felt252 {
+    let address = key.iter().fold(felt252_to_field_element(base_address), |current_hash, felt| {
+        pedersen_hash(&current_hash, &felt252_to_field_element(felt))
+    }) % ADDR_BOUND;
+
+    field_element_to_felt252(&address)
+}
+
+/// Calculates the storage address of the storage variable `name` of a contract, or of its entry
+/// for the given key if it is a `LegacyMap` - as computed by the code generated for the storage
+/// variable, starting from its base address `storage_variable_base_address(name)`.
+pub fn calculate_storage_variable_address(name: &str, key: &[Felt252]) -> Felt252 {
+    calculate_storage_address(&storage_variable_base_address(name), key)
+}
+
+/// Calculates the hash of a version 1 invoke transaction sent by `sender_address`, as defined in
+/// <https://docs.starknet.io/documentation/architecture_and_concepts/Network_Architecture/transactions/#v1_deprecated_hash_calculation>.
+pub fn calculate_invoke_transaction_hash(
+    sender_address: &Felt252,
+    calldata: &[Felt252],
+    max_fee: &Felt252,
+    chain_id: &Felt252,
+    nonce: &Felt252,
+) -> Felt252 {
+    let calldata_hash =
+        pedersen_hash_array(&calldata.iter().map(felt252_to_field_element).collect::<Vec<_>>());
+    let hash = pedersen_hash_array(&[
+        // The Cairo string "invoke".
+        FieldElement::from_byte_slice_be(b"invoke").unwrap(),
+        FieldElement::ONE,
+        felt252_to_field_element(sender_address),
+        FieldElement::ZERO,
+        calldata_hash,
+        felt252_to_field_element(max_fee),
+        felt252_to_field_element(chain_id),
+        felt252_to_field_element(nonce),
+    ]);
+    field_element_to_felt252(&hash)
 }
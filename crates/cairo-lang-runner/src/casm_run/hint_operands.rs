@@ -0,0 +1,163 @@
+use cairo_lang_casm::hints::{CoreHint, CoreHintBase, DeprecatedHint, Hint, StarknetHint};
+use cairo_lang_casm::operand::{CellRef, ResOperand};
+
+/// The operands of a hint, by their names in the hint.
+#[derive(Default)]
+pub struct HintOperands<'a> {
+    /// The operands read by the hint.
+    pub inputs: Vec<(&'static str, &'a ResOperand)>,
+    /// The cells written by the hint.
+    pub outputs: Vec<(&'static str, &'a CellRef)>,
+}
+
+/// Returns the operands of the given hint.
+pub fn hint_operands(hint: &Hint) -> HintOperands<'_> {
+    macro_rules! operands {
+        ([$($input:ident),*], [$($output:ident),*]) => {
+            HintOperands {
+                inputs: vec![$((stringify!($input), $input)),*],
+                outputs: vec![$((stringify!($output), $output)),*],
+            }
+        };
+    }
+    match hint {
+        Hint::Core(CoreHintBase::Core(hint)) => match hint {
+            CoreHint::AllocSegment { dst } => operands!([], [dst]),
+            CoreHint::TestLessThan { lhs, rhs, dst }
+            | CoreHint::TestLessThanOrEqual { lhs, rhs, dst } => operands!([lhs, rhs], [dst]),
+            CoreHint::WideMul128 { lhs, rhs, high, low } => operands!([lhs, rhs], [high, low]),
+            CoreHint::DivMod { lhs, rhs, quotient, remainder } => {
+                operands!([lhs, rhs], [quotient, remainder])
+            }
+            CoreHint::Uint256DivMod {
+                dividend0,
+                dividend1,
+                divisor0,
+                divisor1,
+                quotient0,
+                quotient1,
+                remainder0,
+                remainder1,
+            } => operands!(
+                [dividend0, dividend1, divisor0, divisor1],
+                [quotient0, quotient1, remainder0, remainder1]
+            ),
+            CoreHint::Uint512DivModByUint256 {
+                dividend0,
+                dividend1,
+                dividend2,
+                dividend3,
+                divisor0,
+                divisor1,
+                quotient0,
+                quotient1,
+                quotient2,
+                quotient3,
+                remainder0,
+                remainder1,
+            } => operands!(
+                [dividend0, dividend1, dividend2, dividend3, divisor0, divisor1],
+                [quotient0, quotient1, quotient2, quotient3, remainder0, remainder1]
+            ),
+            CoreHint::SquareRoot { value, dst } => operands!([value], [dst]),
+            CoreHint::Uint256SquareRoot {
+                value_low,
+                value_high,
+                sqrt0,
+                sqrt1,
+                remainder_low,
+                remainder_high,
+                sqrt_mul_2_minus_remainder_ge_u128,
+            } => operands!(
+                [value_low, value_high],
+                [sqrt0, sqrt1, remainder_low, remainder_high, sqrt_mul_2_minus_remainder_ge_u128]
+            ),
+            CoreHint::LinearSplit { value, scalar, max_x, x, y } => {
+                operands!([value, scalar, max_x], [x, y])
+            }
+            CoreHint::AllocFelt252Dict { segment_arena_ptr } => operands!([segment_arena_ptr], []),
+            CoreHint::Felt252DictEntryInit { dict_ptr, key } => operands!([dict_ptr, key], []),
+            CoreHint::Felt252DictEntryUpdate { dict_ptr, value } => {
+                operands!([dict_ptr, value], [])
+            }
+            CoreHint::GetSegmentArenaIndex { dict_end_ptr, dict_index } => {
+                operands!([dict_end_ptr], [dict_index])
+            }
+            CoreHint::InitSquashData {
+                dict_accesses,
+                ptr_diff,
+                n_accesses,
+                big_keys,
+                first_key,
+            } => {
+                operands!([dict_accesses, ptr_diff, n_accesses], [big_keys, first_key])
+            }
+            CoreHint::GetCurrentAccessIndex { range_check_ptr } => {
+                operands!([range_check_ptr], [])
+            }
+            CoreHint::ShouldSkipSquashLoop { should_skip_loop } => {
+                operands!([], [should_skip_loop])
+            }
+            CoreHint::GetCurrentAccessDelta { index_delta_minus1 } => {
+                operands!([], [index_delta_minus1])
+            }
+            CoreHint::ShouldContinueSquashLoop { should_continue } => {
+                operands!([], [should_continue])
+            }
+            CoreHint::GetNextDictKey { next_key } => operands!([], [next_key]),
+            CoreHint::AssertLeFindSmallArcs { range_check_ptr, a, b } => {
+                operands!([range_check_ptr, a, b], [])
+            }
+            CoreHint::AssertLeIsFirstArcExcluded { skip_exclude_a_flag } => {
+                operands!([], [skip_exclude_a_flag])
+            }
+            CoreHint::AssertLeIsSecondArcExcluded { skip_exclude_b_minus_a } => {
+                operands!([], [skip_exclude_b_minus_a])
+            }
+            CoreHint::RandomEcPoint { x, y } => operands!([], [x, y]),
+            CoreHint::FieldSqrt { val, sqrt } => operands!([val], [sqrt]),
+            CoreHint::DebugPrint { start, end } => operands!([start, end], []),
+            CoreHint::AllocConstantSize { size, dst } => operands!([size], [dst]),
+            CoreHint::U256InvModN {
+                b0,
+                b1,
+                n0,
+                n1,
+                g0_or_no_inv,
+                g1_option,
+                s_or_r0,
+                s_or_r1,
+                t_or_k0,
+                t_or_k1,
+            } => operands!(
+                [b0, b1, n0, n1],
+                [g0_or_no_inv, g1_option, s_or_r0, s_or_r1, t_or_k0, t_or_k1]
+            ),
+        },
+        Hint::Core(CoreHintBase::Deprecated(hint)) => match hint {
+            DeprecatedHint::AssertCurrentAccessIndicesIsEmpty
+            | DeprecatedHint::AssertAllKeysUsed
+            | DeprecatedHint::AssertLeAssertThirdArcExcluded => HintOperands::default(),
+            DeprecatedHint::AssertAllAccessesUsed { n_used_accesses } => {
+                operands!([], [n_used_accesses])
+            }
+            DeprecatedHint::AssertLtAssertValidInput { a, b } => operands!([a, b], []),
+            DeprecatedHint::Felt252DictRead { dict_ptr, key, value_dst } => {
+                operands!([dict_ptr, key], [value_dst])
+            }
+            DeprecatedHint::Felt252DictWrite { dict_ptr, key, value } => {
+                operands!([dict_ptr, key, value], [])
+            }
+        },
+        Hint::Starknet(hint) => match hint {
+            StarknetHint::SystemCall { system } => operands!([system], []),
+            StarknetHint::Cheatcode {
+                selector: _,
+                input_start,
+                input_end,
+                output_start,
+                output_end,
+            } => operands!([input_start, input_end], [output_start, output_end]),
+        },
+    }
+}
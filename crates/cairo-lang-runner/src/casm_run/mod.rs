@@ -1,6 +1,6 @@
 use std::any::Any;
 use std::borrow::Cow;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::ops::{Deref, Shl};
 use std::vec::IntoIter;
 
@@ -8,14 +8,17 @@ use ark_ff::fields::{Fp256, MontBackend, MontConfig};
 use ark_ff::{BigInteger, Field, PrimeField};
 use ark_std::UniformRand;
 use cairo_felt::{felt_str as felt252_str, Felt252};
-use cairo_lang_casm::hints::{CoreHint, DeprecatedHint, Hint, StarknetHint};
+use cairo_lang_casm::hints::{CoreHint, CoreHintBase, DeprecatedHint, Hint, StarknetHint};
 use cairo_lang_casm::operand::{
     BinOpOperand, CellRef, DerefOrImmediate, Operation, Register, ResOperand,
 };
 use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_starknet_classes::keccak::starknet_keccak;
 use cairo_lang_utils::bigint::BigIntAsHex;
 use cairo_lang_utils::byte_array::{BYTES_IN_WORD, BYTE_ARRAY_MAGIC};
 use cairo_lang_utils::extract_matches;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_vm::hint_processor::hint_processor_definition::{
     HintProcessor, HintProcessorLogic, HintReference,
 };
@@ -29,25 +32,36 @@ use cairo_vm::vm::errors::cairo_run_errors::CairoRunError;
 use cairo_vm::vm::errors::hint_errors::HintError;
 use cairo_vm::vm::errors::memory_errors::MemoryError;
 use cairo_vm::vm::errors::vm_errors::VirtualMachineError;
-use cairo_vm::vm::runners::cairo_runner::{CairoRunner, ResourceTracker, RunResources};
+use cairo_vm::vm::runners::cairo_runner::{
+    CairoRunner, ExecutionResources, ResourceTracker, RunResources,
+};
 use cairo_vm::vm::vm_core::VirtualMachine;
 use dict_manager::DictManagerExecScope;
-use itertools::Itertools;
+use itertools::{chain, Itertools};
 use num_bigint::{BigInt, BigUint};
 use num_integer::{ExtendedGcd, Integer};
 use num_traits::{FromPrimitive, Signed, ToPrimitive, Zero};
 use {ark_secp256k1 as secp256k1, ark_secp256r1 as secp256r1};
 
-use self::contract_address::calculate_contract_address;
+pub use self::contract_address::{
+    calculate_contract_address, calculate_invoke_transaction_hash, calculate_storage_address,
+    calculate_storage_variable_address,
+};
+pub(crate) use self::contract_address::{
+    felt252_to_field_element, field_element_to_felt252, pedersen_hash_array,
+};
 use self::dict_manager::DictSquashExecScope;
+use crate::event_decoding::{EmittedEvent, EventDecoder};
+use crate::observer::RunObservation;
 use crate::short_string::{as_cairo_short_string, as_cairo_short_string_ex};
-use crate::{Arg, RunResultValue, SierraCasmRunner};
+use crate::{Arg, RunLimits, RunResultValue, RunnerError, SierraCasmRunner};
 
 #[cfg(test)]
 mod test;
 
 mod contract_address;
 mod dict_manager;
+pub(crate) mod hint_operands;
 
 // TODO(orizi): This def is duplicated.
 /// Returns the Beta value of the Starkware elliptic curve.
@@ -100,6 +114,277 @@ pub struct CairoHintProcessor<'a> {
     pub starknet_state: StarknetState,
     // Maintains the resources of the run.
     pub run_resources: RunResources,
+    /// The log of the executed hints, if requested.
+    pub hint_executions_log: Option<HintExecutionsLog>,
+    /// The observation of the run, if the run is observed.
+    pub observation: Option<RunObservation<'a>>,
+    /// The behavior of the computationally heavy syscalls.
+    pub heavy_syscalls: HeavySyscallsConfig,
+    /// Additional handlers of hints, tried in order before the hint is executed by this
+    /// processor.
+    pub hint_handlers: Vec<Box<dyn HintHandler + 'a>>,
+    /// The output printed by the run, if captured. Otherwise, prints are written to the standard
+    /// output.
+    pub printed_output: Option<PrintedOutput>,
+    /// The calls to contracts made by the run, if traced.
+    pub call_traces: Option<Vec<CallTrace>>,
+    /// The behavior of the runner when a contract called by the run panics.
+    pub inner_panic_policy: InnerPanicPolicy,
+    /// The panic of a contract called by the run, if it aborted the run.
+    pub inner_call_panic: Option<InnerCallPanic>,
+    /// The error of the run of a contract called by the run, if it aborted the run.
+    pub inner_call_error: Option<RunnerError>,
+    /// The number of times each Sierra statement was executed by the calls to contracts made by
+    /// the run, if counted.
+    pub inner_statement_execution_counts: Option<Vec<usize>>,
+    /// The limits of the run, shared with the runs of the contracts it calls.
+    pub run_limits: RunLimits,
+    /// The decoder of the events emitted by the run, if the ABI of the contract it runs an entry
+    /// point of is known.
+    pub event_decoder: Option<EventDecoder<'a>>,
+    /// The events emitted by the run, including the events of the calls to contracts it made.
+    pub events: Vec<EmittedEvent>,
+}
+
+/// An additional handler of hints, layered on top of `CairoHintProcessor`. Allows adding
+/// cheatcodes, oracles and other custom behavior of hints without changing the processor.
+pub trait HintHandler {
+    /// Executes the given hint if it is handled by this handler. Returns `Ok(false)` if the hint
+    /// is not handled, in which case it is passed on to the next handler, and eventually to the
+    /// processor itself.
+    fn execute_hint(
+        &mut self,
+        vm: &mut VirtualMachine,
+        exec_scopes: &mut ExecutionScopes,
+        hint: &Hint,
+    ) -> Result<bool, HintError>;
+}
+
+/// The behavior of the runner when a computationally heavy syscall is called.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+pub enum SyscallBehavior {
+    /// The syscall is executed.
+    #[default]
+    Execute,
+    /// The syscall is not supported, and calling it stops the run with an error.
+    Unsupported,
+}
+
+/// The behavior of the runner when a contract called during a run, by a contract call, a library
+/// call or a deployment, panics.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+pub enum InnerPanicPolicy {
+    /// The panic data is returned to the caller as the error of the syscall, as on Starknet.
+    #[default]
+    Recover,
+    /// The whole run is aborted with [crate::RunnerError::InnerCallPanicked], locating the
+    /// panicking call.
+    Abort,
+}
+
+/// A panic of a contract call that aborted a run. See [InnerPanicPolicy::Abort].
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct InnerCallPanic {
+    /// The inner calls leading to the panic, outermost first, as pairs of the address of the
+    /// contract the call is executed in and the selector of the called entry point. The last call
+    /// is the panicking one.
+    pub call_chain: Vec<(Felt252, Felt252)>,
+    /// The panic data of the panicking call.
+    pub panic_data: Vec<Felt252>,
+    /// The backtrace of the panic within the panicking call. See
+    /// [crate::RunResultStarknet::panic_backtrace].
+    pub panic_backtrace: Option<Vec<StatementIdx>>,
+}
+impl std::fmt::Display for InnerCallPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hex = |felt: &Felt252| format!("{:#x}", felt.to_biguint());
+        write!(
+            f,
+            "Contract call panicked with [{}]. Call chain: {}.",
+            self.panic_data.iter().map(hex).join(", "),
+            self.call_chain
+                .iter()
+                .map(|(contract_address, selector)| format!(
+                    "{}::{}",
+                    hex(contract_address),
+                    hex(selector)
+                ))
+                .join(" -> ")
+        )
+    }
+}
+
+/// The behavior of the runner for each group of computationally heavy syscalls.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+pub struct HeavySyscallsConfig {
+    /// The behavior of the `keccak` syscall.
+    pub keccak: SyscallBehavior,
+    /// The behavior of the `secp256k1` syscalls.
+    pub secp256k1: SyscallBehavior,
+    /// The behavior of the `secp256r1` syscalls.
+    pub secp256r1: SyscallBehavior,
+}
+impl HeavySyscallsConfig {
+    /// A configuration where all the heavy syscalls are unsupported.
+    pub fn unsupported() -> Self {
+        Self {
+            keccak: SyscallBehavior::Unsupported,
+            secp256k1: SyscallBehavior::Unsupported,
+            secp256r1: SyscallBehavior::Unsupported,
+        }
+    }
+
+    /// Returns the behavior of the syscall with the given selector.
+    fn behavior(&self, selector: &str) -> SyscallBehavior {
+        if selector == "Keccak" {
+            self.keccak
+        } else if selector.starts_with("Secp256k1") {
+            self.secp256k1
+        } else if selector.starts_with("Secp256r1") {
+            self.secp256r1
+        } else {
+            SyscallBehavior::Execute
+        }
+    }
+}
+
+/// A single hint execution, as recorded in a `HintExecutionsLog`.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct HintExecution {
+    /// The index of the step at which the hint was executed. This is also the index of the
+    /// matching entry in the trace.
+    pub step: usize,
+    /// The offset of the instruction the hint is attached to.
+    pub pc: usize,
+    /// The executed hint.
+    pub hint: Hint,
+    /// The values of the operands read by the hint, resolved before its execution.
+    pub inputs: Vec<HintOperandValue>,
+    /// The values of the cells written by the hint, read after its execution.
+    pub outputs: Vec<HintOperandValue>,
+    /// The memory written by the hint besides its outputs, for syscalls and cheatcodes - whose
+    /// results depend on the state of the run rather than on the memory alone.
+    pub response: Option<HintResponse>,
+}
+
+/// The memory written by an executed syscall or cheatcode, besides the cells of its outputs.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct HintResponse {
+    /// The cells of the request in the syscall buffer. Empty for cheatcodes.
+    pub request: Vec<MaybeRelocatable>,
+    /// The cells of the response in the syscall buffer, following the request. Empty for
+    /// cheatcodes.
+    pub response: Vec<MaybeRelocatable>,
+    /// The segments created by the hint, as pairs of their index and their content, by order of
+    /// creation. These are the segments reachable from the response and the outputs.
+    pub segments: Vec<(isize, Vec<MaybeRelocatable>)>,
+}
+impl HintResponse {
+    /// Reads the response of a starknet hint after its execution. `request` is the start of the
+    /// syscall buffer and the cells of the request, read before the execution, for syscalls.
+    fn read(
+        vm: &VirtualMachine,
+        request: Option<(Relocatable, Vec<MaybeRelocatable>)>,
+        outputs: &[HintOperandValue],
+    ) -> Self {
+        let (request, response) = match request {
+            Some((ptr, request)) => {
+                let response = read_written_cells(vm, (ptr + request.len()).unwrap());
+                (request, response)
+            }
+            None => Default::default(),
+        };
+        let mut segments = BTreeMap::new();
+        let mut pending: Vec<isize> =
+            chain!(&response, outputs.iter().filter_map(|output| output.value.as_ref()))
+                .filter_map(pointed_segment)
+                .collect();
+        while let Some(segment_index) = pending.pop() {
+            if segments.contains_key(&segment_index) {
+                continue;
+            }
+            let content = read_written_cells(vm, Relocatable::from((segment_index, 0)));
+            pending.extend(content.iter().filter_map(pointed_segment));
+            segments.insert(segment_index, content);
+        }
+        Self { request, response, segments: segments.into_iter().collect() }
+    }
+}
+
+/// Returns the consecutive written cells starting at `ptr`.
+pub(crate) fn read_written_cells(vm: &VirtualMachine, ptr: Relocatable) -> Vec<MaybeRelocatable> {
+    (0..).map_while(|i: usize| vm.get_maybe(&(ptr + i).ok()?)).collect()
+}
+
+/// Returns the index of the segment the given value points to, if it is a pointer.
+fn pointed_segment(value: &MaybeRelocatable) -> Option<isize> {
+    match value {
+        MaybeRelocatable::RelocatableValue(ptr) => Some(ptr.segment_index),
+        MaybeRelocatable::Int(_) => None,
+    }
+}
+
+/// The value of a named operand of an executed hint.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct HintOperandValue {
+    /// The name of the operand in the hint (e.g. `lhs`).
+    pub name: &'static str,
+    /// The value of the operand - `None` if it could not be resolved, or the cell was not written.
+    pub value: Option<MaybeRelocatable>,
+}
+
+/// The kind of a call to a contract.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum CallKind {
+    /// A call of an external entry point of a deployed contract, by `call_contract_syscall`.
+    CallContract,
+    /// A call of an external entry point of a class, by `library_call_syscall`.
+    LibraryCall,
+    /// A call of the constructor of a contract deployed by `deploy_syscall`.
+    Constructor,
+}
+
+/// A call to a contract made during a run, with the calls it made in turn.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct CallTrace {
+    pub kind: CallKind,
+    /// The address of the calling contract.
+    pub caller_address: Felt252,
+    /// The address of the contract the call is executed in - the calling contract for library
+    /// calls.
+    pub contract_address: Felt252,
+    /// The hash of the class of the called entry point.
+    pub class_hash: Felt252,
+    /// The selector of the called entry point, which is the selector of `constructor` for
+    /// constructors.
+    pub selector: Felt252,
+    pub calldata: Vec<Felt252>,
+    /// The returned values on success, or the panic data.
+    pub result: RunResultValue,
+    /// The resources used by the call, excluding its inner calls.
+    pub used_resources: ExecutionResources,
+    /// The calls made by the call, in order.
+    pub inner_calls: Vec<CallTrace>,
+}
+
+/// A log of all the hints executed during a run, in execution order.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct HintExecutionsLog {
+    /// The number of steps executed so far.
+    n_steps: usize,
+    /// The executed hints.
+    pub executions: Vec<HintExecution>,
+    /// The executed syscalls.
+    pub syscalls: Vec<SyscallExecution>,
+}
+
+/// A single syscall execution, as recorded in a `HintExecutionsLog`.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct SyscallExecution {
+    /// The index of the step at which the syscall was executed.
+    pub step: usize,
+    /// The selector of the syscall (e.g. `StorageWrite`).
+    pub selector: String,
 }
 
 pub fn cell_ref_to_relocatable(cell_ref: &CellRef, vm: &VirtualMachine) -> Relocatable {
@@ -129,12 +414,16 @@ type L2ToL1Message = (Felt252, Vec<Felt252>);
 #[derive(Clone, Default)]
 pub struct StarknetState {
     /// The values of addresses in the simulated storage per contract.
-    storage: HashMap<Felt252, HashMap<Felt252, Felt252>>,
+    ///
+    /// The maps of the state are ordered, so that anything derived from iterating them, e.g. dumps
+    /// of the storage, is the same between runs.
+    pub(crate) storage: OrderedHashMap<Felt252, OrderedHashMap<Felt252, Felt252>>,
     /// A mapping from contract address to class hash.
-    #[allow(dead_code)]
-    deployed_contracts: HashMap<Felt252, Felt252>,
+    pub(crate) deployed_contracts: OrderedHashMap<Felt252, Felt252>,
     /// A mapping from contract address to logs.
-    logs: HashMap<Felt252, ContractLogs>,
+    logs: OrderedHashMap<Felt252, ContractLogs>,
+    /// The nonces of the accounts that executed transactions.
+    pub(crate) nonces: OrderedHashMap<Felt252, Felt252>,
     /// The simulated execution info.
     exec_info: ExecutionInfo,
     next_id: Felt252,
@@ -145,6 +434,117 @@ impl StarknetState {
         self.next_id.clone()
     }
 
+    /// Returns the class hash of the contract deployed at the given address, if there is one.
+    pub fn deployed_class_hash(&self, contract_address: &Felt252) -> Option<&Felt252> {
+        self.deployed_contracts.get(contract_address)
+    }
+
+    /// Returns the storage of the contract at the given address, as a mapping from storage address
+    /// to value.
+    pub fn contract_storage(
+        &self,
+        contract_address: &Felt252,
+    ) -> Option<&OrderedHashMap<Felt252, Felt252>> {
+        self.storage.get(contract_address)
+    }
+
+    /// Returns the events emitted by the contract at the given address, as `(keys, data)` pairs,
+    /// in the order of emission.
+    pub fn contract_events(&self, contract_address: &Felt252) -> impl Iterator<Item = &Log> {
+        self.logs.get(contract_address).into_iter().flat_map(|logs| logs.events.iter())
+    }
+
+    /// Returns the addresses of the contracts that emitted events or sent messages to L1.
+    pub(crate) fn logging_contracts(&self) -> impl Iterator<Item = &Felt252> {
+        self.logs.keys()
+    }
+
+    /// Sets the block context returned by `get_execution_info` in runs using this state.
+    pub fn with_block_context(mut self, block_context: BlockContext) -> Self {
+        let BlockContext { block_number, block_timestamp, sequencer_address, chain_id } =
+            block_context;
+        self.exec_info.block_info = BlockInfo { block_number, block_timestamp, sequencer_address };
+        self.exec_info.tx_info.chain_id = chain_id;
+        self
+    }
+
+    /// Sets the transaction context returned by `get_execution_info` in runs using this state.
+    pub fn with_tx_context(mut self, tx_context: TxContext) -> Self {
+        self.set_tx_context(tx_context);
+        self
+    }
+
+    /// Returns the transaction context returned by `get_execution_info` in runs using this state.
+    pub fn tx_context(&self) -> TxContext {
+        let tx_info = &self.exec_info.tx_info;
+        TxContext {
+            version: tx_info.version.clone(),
+            account_contract_address: tx_info.account_contract_address.clone(),
+            max_fee: tx_info.max_fee.clone(),
+            signature: tx_info.signature.clone(),
+            transaction_hash: tx_info.transaction_hash.clone(),
+            nonce: tx_info.nonce.clone(),
+            resource_bounds: tx_info.resource_bounds.clone(),
+            tip: tx_info.tip.clone(),
+            paymaster_data: tx_info.paymaster_data.clone(),
+            nonce_data_availability_mode: tx_info.nonce_data_availability_mode.clone(),
+            fee_data_availability_mode: tx_info.fee_data_availability_mode.clone(),
+            account_deployment_data: tx_info.account_deployment_data.clone(),
+        }
+    }
+
+    /// Replaces the transaction context returned by `get_execution_info` in runs using this state.
+    pub fn set_tx_context(&mut self, tx_context: TxContext) {
+        let TxContext {
+            version,
+            account_contract_address,
+            max_fee,
+            signature,
+            transaction_hash,
+            nonce,
+            resource_bounds,
+            tip,
+            paymaster_data,
+            nonce_data_availability_mode,
+            fee_data_availability_mode,
+            account_deployment_data,
+        } = tx_context;
+        let tx_info = &mut self.exec_info.tx_info;
+        tx_info.version = version;
+        tx_info.account_contract_address = account_contract_address;
+        tx_info.max_fee = max_fee;
+        tx_info.signature = signature;
+        tx_info.transaction_hash = transaction_hash;
+        tx_info.nonce = nonce;
+        tx_info.resource_bounds = resource_bounds;
+        tx_info.tip = tip;
+        tx_info.paymaster_data = paymaster_data;
+        tx_info.nonce_data_availability_mode = nonce_data_availability_mode;
+        tx_info.fee_data_availability_mode = fee_data_availability_mode;
+        tx_info.account_deployment_data = account_deployment_data;
+    }
+
+    /// Returns the id of the chain the runs using this state are executed in.
+    pub fn chain_id(&self) -> &Felt252 {
+        &self.exec_info.tx_info.chain_id
+    }
+
+    /// Returns the nonce of the account at the given address, i.e. the number of transactions it
+    /// executed.
+    pub fn nonce(&self, account_address: &Felt252) -> Felt252 {
+        self.nonces.get(account_address).cloned().unwrap_or_default()
+    }
+
+    /// Increments the nonce of the account at the given address.
+    pub fn increment_nonce(&mut self, account_address: &Felt252) {
+        *self.nonces.entry(account_address.clone()).or_default() += Felt252::from(1);
+    }
+
+    /// Returns the addresses in the context - the address of the contract and of its caller.
+    pub(crate) fn caller_context(&self) -> (Felt252, Felt252) {
+        (self.exec_info.contract_address.clone(), self.exec_info.caller_address.clone())
+    }
+
     /// Replaces the addresses in the context.
     pub fn open_caller_context(
         &mut self,
@@ -167,6 +567,39 @@ impl StarknetState {
     }
 }
 
+/// The context of the block a run is executed in, as returned by `get_execution_info`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BlockContext {
+    pub block_number: Felt252,
+    pub block_timestamp: Felt252,
+    pub sequencer_address: Felt252,
+    /// The id of the chain, part of the transaction info.
+    pub chain_id: Felt252,
+}
+
+/// The context of the transaction a run is executed in, as returned by `get_execution_info`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TxContext {
+    pub version: Felt252,
+    pub account_contract_address: Felt252,
+    pub max_fee: Felt252,
+    pub signature: Vec<Felt252>,
+    pub transaction_hash: Felt252,
+    pub nonce: Felt252,
+    /// The bounds on the resources of a V3 transaction, e.g. `L1_GAS`.
+    pub resource_bounds: Vec<ResourceBounds>,
+    /// The tip of a V3 transaction.
+    pub tip: Felt252,
+    /// The data of the paymaster of a V3 transaction.
+    pub paymaster_data: Vec<Felt252>,
+    /// The data availability mode of the nonce of a V3 transaction.
+    pub nonce_data_availability_mode: Felt252,
+    /// The data availability mode of the fee of a V3 transaction.
+    pub fee_data_availability_mode: Felt252,
+    /// The data used to deploy the account of a V3 `DEPLOY_ACCOUNT` transaction.
+    pub account_deployment_data: Vec<Felt252>,
+}
+
 /// Object storing logs for a contract.
 #[derive(Clone, Default)]
 struct ContractLogs {
@@ -213,11 +646,12 @@ struct TxInfo {
 }
 
 /// Copy of the cairo `ResourceBounds` struct.
-#[derive(Clone, Default)]
-struct ResourceBounds {
-    resource: Felt252,
-    max_amount: Felt252,
-    max_price_per_unit: Felt252,
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ResourceBounds {
+    /// The name of the resource, as a short string, e.g. `'L1_GAS'`.
+    pub resource: Felt252,
+    pub max_amount: Felt252,
+    pub max_price_per_unit: Felt252,
 }
 
 /// Execution scope for constant memory allocation.
@@ -414,33 +848,49 @@ impl HintProcessorLogic for CairoHintProcessor<'_> {
         _constants: &HashMap<String, Felt252>,
     ) -> Result<(), HintError> {
         let hint = hint_data.downcast_ref::<Hint>().unwrap();
-        let hint = match hint {
-            Hint::Core(core_hint_base) => {
-                return execute_core_hint_base(vm, exec_scopes, core_hint_base);
-            }
-            Hint::Starknet(hint) => hint,
+        let step = match (&self.hint_executions_log, &self.observation) {
+            (Some(log), _) => log.n_steps,
+            (None, Some(observation)) => observation.step(),
+            (None, None) => return self.execute_hint_inner(vm, exec_scopes, hint),
         };
-        match hint {
-            StarknetHint::SystemCall { system } => {
-                self.execute_syscall(system, vm, exec_scopes)?;
-            }
-            StarknetHint::Cheatcode {
-                selector,
-                input_start,
-                input_end,
-                output_start,
-                output_end,
-            } => {
-                self.execute_cheatcode(
-                    selector,
-                    [input_start, input_end],
-                    [output_start, output_end],
-                    vm,
-                    exec_scopes,
-                )?;
+        let operands = hint_operands::hint_operands(hint);
+        let inputs = operands
+            .inputs
+            .iter()
+            .map(|(name, operand)| HintOperandValue { name, value: get_maybe(vm, operand).ok() })
+            .collect();
+        let request = match hint {
+            Hint::Starknet(StarknetHint::SystemCall { system }) => {
+                extract_relocatable(vm, system).ok().map(|ptr| (ptr, read_written_cells(vm, ptr)))
             }
+            _ => None,
         };
-        Ok(())
+        let result = self.execute_hint_inner(vm, exec_scopes, hint);
+        let outputs: Vec<_> = operands
+            .outputs
+            .iter()
+            .map(|(name, cell)| HintOperandValue {
+                name,
+                value: vm.get_maybe(&cell_ref_to_relocatable(cell, vm)),
+            })
+            .collect();
+        let response =
+            matches!(hint, Hint::Starknet(_)).then(|| HintResponse::read(vm, request, &outputs));
+        let execution = HintExecution {
+            step,
+            pc: vm.get_pc().offset,
+            hint: hint.clone(),
+            inputs,
+            outputs,
+            response,
+        };
+        if let Some(observation) = &mut self.observation {
+            observation.on_hint(&execution);
+        }
+        if let Some(log) = &mut self.hint_executions_log {
+            log.executions.push(execution);
+        }
+        result
     }
 
     /// Trait function to store hint in the hint processor by string.
@@ -461,6 +911,12 @@ impl ResourceTracker for CairoHintProcessor<'_> {
     }
 
     fn consume_step(&mut self) {
+        if let Some(log) = &mut self.hint_executions_log {
+            log.n_steps += 1;
+        }
+        if let Some(observation) = &mut self.observation {
+            observation.end_step();
+        }
         self.run_resources.consume_step()
     }
 
@@ -619,6 +1075,54 @@ impl<'a> VMWrapper for MemBuffer<'a> {
 }
 
 impl<'a> CairoHintProcessor<'a> {
+    /// Executes a hint, without recording it in the hint executions log.
+    fn execute_hint_inner(
+        &mut self,
+        vm: &mut VirtualMachine,
+        exec_scopes: &mut ExecutionScopes,
+        hint: &Hint,
+    ) -> Result<(), HintError> {
+        for handler in &mut self.hint_handlers {
+            if handler.execute_hint(vm, exec_scopes, hint)? {
+                return Ok(());
+            }
+        }
+        let hint = match hint {
+            Hint::Core(CoreHintBase::Core(CoreHint::DebugPrint { start, end }))
+                if self.printed_output.is_some() =>
+            {
+                let felts = read_felts(vm, start, end)?;
+                self.printed_output.as_mut().unwrap().print(felts);
+                return Ok(());
+            }
+            Hint::Core(core_hint_base) => {
+                return execute_core_hint_base(vm, exec_scopes, core_hint_base);
+            }
+            Hint::Starknet(hint) => hint,
+        };
+        match hint {
+            StarknetHint::SystemCall { system } => {
+                self.execute_syscall(system, vm, exec_scopes)?;
+            }
+            StarknetHint::Cheatcode {
+                selector,
+                input_start,
+                input_end,
+                output_start,
+                output_end,
+            } => {
+                self.execute_cheatcode(
+                    selector,
+                    [input_start, input_end],
+                    [output_start, output_end],
+                    vm,
+                    exec_scopes,
+                )?;
+            }
+        };
+        Ok(())
+    }
+
     /// Executes a syscall.
     fn execute_syscall(
         &mut self,
@@ -651,7 +1155,19 @@ impl<'a> CairoHintProcessor<'a> {
                 }
                 Ok(())
             };
-        match std::str::from_utf8(&selector).unwrap() {
+        let selector = std::str::from_utf8(&selector).unwrap();
+        if let Some(log) = &mut self.hint_executions_log {
+            log.syscalls.push(SyscallExecution { step: log.n_steps, selector: selector.into() });
+        }
+        if let Some(observation) = &mut self.observation {
+            observation.on_syscall(selector);
+        }
+        if self.heavy_syscalls.behavior(selector) == SyscallBehavior::Unsupported {
+            return Err(HintError::CustomHint(Box::from(format!(
+                "Syscall `{selector}` is not supported in this run."
+            ))));
+        }
+        match selector {
             "StorageWrite" => execute_handle_helper(&mut |system_buffer, gas_counter| {
                 self.storage_write(
                     gas_counter,
@@ -911,6 +1427,13 @@ impl<'a> CairoHintProcessor<'a> {
     ) -> Result<SyscallResult, HintError> {
         deduct_gas!(gas_counter, EMIT_EVENT);
         let contract = self.starknet_state.exec_info.contract_address.clone();
+        let decoded = self.event_decoder.as_ref().and_then(|decoder| decoder.decode(&keys, &data));
+        self.events.push(EmittedEvent {
+            contract_address: contract.clone(),
+            keys: keys.clone(),
+            data: data.clone(),
+            decoded,
+        });
         self.starknet_state.logs.entry(contract).or_default().events.push_back((keys, data));
         Ok(SyscallResult::Success(vec![]))
     }
@@ -968,19 +1491,30 @@ impl<'a> CairoHintProcessor<'a> {
         // as the constructor could make an external call to this address.
         self.starknet_state
             .deployed_contracts
-            .insert(deployed_contract_address.clone(), class_hash);
+            .insert(deployed_contract_address.clone(), class_hash.clone());
 
         // Call constructor if it exists.
         let (res_data_start, res_data_end) = if let Some(constructor) = &contract_info.constructor {
             let old_addrs = self
                 .starknet_state
                 .open_caller_context((deployed_contract_address.clone(), deployer_address));
-            let res = self.call_entry_point(gas_counter, runner, constructor, calldata, vm);
+            let res = self.call_entry_point(
+                gas_counter,
+                runner,
+                (
+                    CallKind::Constructor,
+                    &class_hash,
+                    &Felt252::from(starknet_keccak(b"constructor")),
+                ),
+                constructor,
+                calldata,
+                vm,
+            );
             self.starknet_state.close_caller_context(old_addrs);
-            match res {
+            match res? {
                 Ok(value) => value,
                 Err(mut revert_reason) => {
-                    self.starknet_state.deployed_contracts.remove(&deployed_contract_address);
+                    self.starknet_state.deployed_contracts.shift_remove(&deployed_contract_address);
                     fail_syscall!(revert_reason, b"CONSTRUCTOR_FAILED");
                 }
             }
@@ -989,7 +1523,7 @@ impl<'a> CairoHintProcessor<'a> {
         } else {
             // Remove the contract from the deployed contracts,
             // since it failed to deploy.
-            self.starknet_state.deployed_contracts.remove(&deployed_contract_address);
+            self.starknet_state.deployed_contracts.shift_remove(&deployed_contract_address);
             fail_syscall!(b"INVALID_CALLDATA_LEN");
         };
 
@@ -1012,7 +1546,9 @@ impl<'a> CairoHintProcessor<'a> {
         deduct_gas!(gas_counter, CALL_CONTRACT);
 
         // Get the class hash of the contract.
-        let Some(class_hash) = self.starknet_state.deployed_contracts.get(&contract_address) else {
+        let Some(class_hash) =
+            self.starknet_state.deployed_contracts.get(&contract_address).cloned()
+        else {
             fail_syscall!(b"CONTRACT_NOT_DEPLOYED");
         };
 
@@ -1020,7 +1556,7 @@ impl<'a> CairoHintProcessor<'a> {
         let runner = self.runner.expect("Runner is needed for starknet.");
         let contract_info = runner
             .starknet_contracts_info
-            .get(class_hash)
+            .get(&class_hash)
             .expect("Deployed contract not found in registry.");
 
         // Call the function.
@@ -1032,10 +1568,17 @@ impl<'a> CairoHintProcessor<'a> {
             contract_address.clone(),
             self.starknet_state.exec_info.contract_address.clone(),
         ));
-        let res = self.call_entry_point(gas_counter, runner, entry_point, calldata, vm);
+        let res = self.call_entry_point(
+            gas_counter,
+            runner,
+            (CallKind::CallContract, &class_hash, &selector),
+            entry_point,
+            calldata,
+            vm,
+        );
         self.starknet_state.close_caller_context(old_addrs);
 
-        match res {
+        match res? {
             Ok((res_data_start, res_data_end)) => {
                 Ok(SyscallResult::Success(vec![res_data_start.into(), res_data_end.into()]))
             }
@@ -1065,7 +1608,8 @@ impl<'a> CairoHintProcessor<'a> {
         let Some(entry_point) = contract_info.externals.get(&selector) else {
             fail_syscall!(b"ENTRYPOINT_NOT_FOUND");
         };
-        match self.call_entry_point(gas_counter, runner, entry_point, calldata, vm) {
+        let call = (CallKind::LibraryCall, &class_hash, &selector);
+        match self.call_entry_point(gas_counter, runner, call, entry_point, calldata, vm)? {
             Ok((res_data_start, res_data_end)) => {
                 Ok(SyscallResult::Success(vec![res_data_start.into(), res_data_end.into()]))
             }
@@ -1096,37 +1640,101 @@ impl<'a> CairoHintProcessor<'a> {
         Ok(SyscallResult::Success(vec![]))
     }
 
-    /// Executes the entry point with the given calldata.
+    /// Executes the entry point with the given calldata, as the given call - its kind, the hash
+    /// of its class and its selector. Fails if the run should be aborted according to the inner
+    /// panic policy, or if the run of the entry point failed.
     fn call_entry_point(
         &mut self,
         gas_counter: &mut usize,
         runner: &SierraCasmRunner,
+        (kind, class_hash, selector): (CallKind, &Felt252, &Felt252),
         entry_point: &FunctionId,
         calldata: Vec<Felt252>,
         vm: &mut dyn VMWrapper,
-    ) -> Result<(Relocatable, Relocatable), Vec<Felt252>> {
+    ) -> Result<Result<(Relocatable, Relocatable), Vec<Felt252>>, HintError> {
         let function = runner
             .sierra_program_registry
             .get_function(entry_point)
             .expect("Entrypoint exists, but not found.");
-        let mut res = runner
-            .run_function_with_starknet_context(
-                function,
-                &[Arg::Array(calldata)],
-                Some(*gas_counter),
-                self.starknet_state.clone(),
-            )
-            .expect("Internal runner error.");
+        let contract_address = self.starknet_state.exec_info.contract_address.clone();
+        let res = runner.run_inner_call(
+            function,
+            &[Arg::Array(calldata.iter().cloned().map(Arg::Value).collect())],
+            Some(*gas_counter),
+            self.starknet_state.clone(),
+            self.run_limits.clone(),
+        );
+        let mut res = match res {
+            Ok(res) => res,
+            Err(RunnerError::InnerCallPanicked(mut inner_call_panic)) => {
+                inner_call_panic.call_chain.insert(0, (contract_address, selector.clone()));
+                return Err(self.abort_on_inner_call_panic(*inner_call_panic));
+            }
+            Err(err) => {
+                self.inner_call_error = Some(err);
+                return Err(HintError::CustomHint(Box::from("Contract call failed.".to_string())));
+            }
+        };
 
         *gas_counter = res.gas_counter.unwrap().to_usize().unwrap();
-        match res.value {
+        if let Some(counts) = res.statement_execution_counts.take() {
+            match &mut self.inner_statement_execution_counts {
+                Some(total_counts) => {
+                    for (total_count, count) in total_counts.iter_mut().zip(counts) {
+                        *total_count += count;
+                    }
+                }
+                None => self.inner_statement_execution_counts = Some(counts),
+            }
+        }
+        let result = match &res.value {
             RunResultValue::Success(value) => {
-                self.starknet_state = std::mem::take(&mut res.starknet_state);
-                Ok(segment_with_data(vm, read_array_result_as_vec(&res.memory, &value).into_iter())
-                    .expect("failed to allocate segment"))
+                RunResultValue::Success(read_array_result_as_vec(&res.memory, value))
             }
-            RunResultValue::Panic(panic_data) => Err(panic_data),
+            RunResultValue::Panic(panic_data) => RunResultValue::Panic(panic_data.clone()),
+        };
+        if let Some(call_traces) = &mut self.call_traces {
+            let exec_info = &self.starknet_state.exec_info;
+            // Library calls are executed in the context of the calling contract.
+            let caller_address = match kind {
+                CallKind::LibraryCall => &exec_info.contract_address,
+                CallKind::CallContract | CallKind::Constructor => &exec_info.caller_address,
+            };
+            call_traces.push(CallTrace {
+                kind,
+                caller_address: caller_address.clone(),
+                contract_address: exec_info.contract_address.clone(),
+                class_hash: class_hash.clone(),
+                selector: selector.clone(),
+                calldata,
+                result: result.clone(),
+                used_resources: res.used_resources.clone(),
+                inner_calls: res.call_traces.take().unwrap_or_default(),
+            });
         }
+        Ok(match result {
+            RunResultValue::Success(value) => {
+                self.starknet_state = std::mem::take(&mut res.starknet_state);
+                self.events.append(&mut res.events);
+                Ok(segment_with_data(vm, value.into_iter()).expect("failed to allocate segment"))
+            }
+            RunResultValue::Panic(panic_data) => {
+                if self.inner_panic_policy == InnerPanicPolicy::Abort {
+                    return Err(self.abort_on_inner_call_panic(InnerCallPanic {
+                        call_chain: vec![(contract_address, selector.clone())],
+                        panic_data,
+                        panic_backtrace: res.panic_backtrace,
+                    }));
+                }
+                Err(panic_data)
+            }
+        })
+    }
+
+    /// Records the panic of an inner call aborting the run, returning the error aborting it.
+    fn abort_on_inner_call_panic(&mut self, inner_call_panic: InnerCallPanic) -> HintError {
+        self.inner_call_panic = Some(inner_call_panic);
+        HintError::CustomHint(Box::from("Contract call panicked.".to_string()))
     }
 
     /// Executes a cheatcode.
@@ -1944,7 +2552,8 @@ pub fn execute_core_hint(
             )?;
         }
         CoreHint::DebugPrint { start, end } => {
-            print!("{}", format_for_debug(read_felts(vm, start, end)?.into_iter()));
+            let (_, felts) = split_print_channel(read_felts(vm, start, end)?);
+            print!("{}", format_for_debug(felts.into_iter()));
         }
         CoreHint::AllocConstantSize { size, dst } => {
             let object_size = get_val(vm, size)?.to_usize().expect("Object size too large.");
@@ -2042,7 +2651,10 @@ fn read_felts(
 }
 
 /// Reads the result of a function call that returns `Array<felt252>`.
-fn read_array_result_as_vec(memory: &[Option<Felt252>], value: &[Felt252]) -> Vec<Felt252> {
+pub(crate) fn read_array_result_as_vec(
+    memory: &[Option<Felt252>],
+    value: &[Felt252],
+) -> Vec<Felt252> {
     // TODO(spapini): Handle failures.
     let [res_start, res_end] = value else {
         panic!("Unexpected return value from contract call");
@@ -2085,7 +2697,8 @@ pub struct RunFunctionContext<'a> {
     pub data_len: usize,
 }
 
-type RunFunctionRes = (Vec<Option<Felt252>>, usize);
+/// The memory, the final ap value and the used resources of a run.
+type RunFunctionRes = (Vec<Option<Felt252>>, usize, ExecutionResources);
 
 /// Runs CairoRunner on layout with prime.
 /// Allows injecting custom CairoRunner.
@@ -2128,7 +2741,8 @@ pub fn build_cairo_runner(
     CairoRunner::new(&program, "all_cairo", false).map_err(CairoRunError::from).map_err(Box::new)
 }
 
-/// Runs `bytecode` on layout with prime, and returns the memory layout and ap value.
+/// Runs `bytecode` on layout with prime, and returns the memory layout, ap value and the used
+/// resources.
 /// Allows injecting custom HintProcessor.
 pub fn run_function<'a, 'b: 'a>(
     vm: &mut VirtualMachine,
@@ -2146,8 +2760,13 @@ pub fn run_function<'a, 'b: 'a>(
     let mut runner = build_cairo_runner(data, builtins, hints_dict)?;
 
     run_function_with_runner(vm, data_len, additional_initialization, hint_processor, &mut runner)?;
+    let used_resources = runner.get_execution_resources(vm).map_err(CairoRunError::from)?;
 
-    Ok((runner.relocated_memory, vm.get_relocated_trace().unwrap().last().unwrap().ap))
+    Ok((
+        runner.relocated_memory,
+        vm.get_relocated_trace().unwrap().last().unwrap().ap,
+        used_resources,
+    ))
 }
 
 /// Formats the given felts as a debug string.
@@ -2173,6 +2792,51 @@ fn format_for_debug(mut felts: IntoIter<Felt252>) -> String {
         .join("")
 }
 
+/// The magic prefixing the prints into a specific channel, followed by the short-string name of
+/// the channel. Matches `PRINT_CHANNEL_MAGIC` of the corelib.
+pub const PRINT_CHANNEL_MAGIC: &str =
+    "384ed676c13b01c1fec9fdc7c6ed3ead669cf71a0e8ac41816dfcb2c5a1f155";
+/// The channel of the prints not into a specific channel.
+pub const STDOUT_CHANNEL: &str = "stdout";
+
+/// The output printed by a run, split by the channels it was printed into.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PrintedOutput {
+    channels: OrderedHashMap<String, String>,
+}
+impl PrintedOutput {
+    /// Returns the output printed into the given channel.
+    pub fn channel(&self, name: &str) -> &str {
+        self.channels.get(name).map_or("", |output| output.as_str())
+    }
+
+    /// Returns the channels printed into along with their outputs, in the order of their first
+    /// prints.
+    pub fn channels(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.channels.iter().map(|(name, output)| (name.as_str(), output.as_str()))
+    }
+
+    /// Adds the felts printed by a `DebugPrint` hint to the output of their channel.
+    fn print(&mut self, felts: Vec<Felt252>) {
+        let (channel, felts) = split_print_channel(felts);
+        self.channels.entry(channel).or_default().push_str(&format_for_debug(felts.into_iter()));
+    }
+}
+
+/// Splits the felts printed by a `DebugPrint` hint into the name of the channel they are printed
+/// into and the printed felts. Prints into a specific channel (using
+/// `core::debug::print_to_channel`) start with `PRINT_CHANNEL_MAGIC` followed by the name of the
+/// channel, and all other prints are into `STDOUT_CHANNEL`.
+fn split_print_channel(felts: Vec<Felt252>) -> (String, Vec<Felt252>) {
+    match &felts[..] {
+        [magic, channel, ..] if *magic == felt252_str!(PRINT_CHANNEL_MAGIC, 16) => (
+            as_cairo_short_string(channel).unwrap_or_else(|| channel.to_string()),
+            felts[2..].to_vec(),
+        ),
+        _ => (STDOUT_CHANNEL.into(), felts),
+    }
+}
+
 /// A formatted string representation of anything formattable (e.g. ByteArray, felt, short-string).
 pub struct FormattedItem {
     /// The formatted string representing the item.
@@ -2185,6 +2849,10 @@ impl FormattedItem {
     pub fn get(self) -> String {
         self.item
     }
+    /// Returns whether the item is a string.
+    pub fn is_string(&self) -> bool {
+        self.is_string
+    }
     /// Wraps the formatted item with quote, if it's a string. Otherwise returns it as is.
     pub fn quote_if_string(self) -> String {
         if self.is_string { format!("\"{}\"", self.item) } else { self.item }
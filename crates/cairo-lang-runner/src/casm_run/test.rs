@@ -2,6 +2,7 @@ use cairo_felt::{felt_str, Felt252};
 use cairo_lang_casm::inline::CasmContext;
 use cairo_lang_casm::{casm, deref};
 use cairo_lang_utils::byte_array::BYTE_ARRAY_MAGIC;
+use cairo_vm::types::relocatable::{MaybeRelocatable, Relocatable};
 use cairo_vm::vm::runners::cairo_runner::RunResources;
 use cairo_vm::vm::vm_core::VirtualMachine;
 use indoc::indoc;
@@ -14,7 +15,10 @@ use super::format_for_debug;
 use crate::casm_run::contract_address::calculate_contract_address;
 use crate::casm_run::run_function;
 use crate::short_string::{as_cairo_short_string, as_cairo_short_string_ex};
-use crate::{build_hints_dict, CairoHintProcessor, StarknetState};
+use crate::{
+    build_hints_dict, CairoHintProcessor, HintExecution, HintExecutionsLog, HintOperandValue,
+    StarknetState,
+};
 
 #[test_case(
     casm! {
@@ -116,6 +120,19 @@ fn test_runner(function: CasmContext, n_returns: usize, expected: &[i128]) {
         string_to_hint,
         starknet_state: StarknetState::default(),
         run_resources: RunResources::default(),
+        hint_executions_log: None,
+        observation: None,
+        hint_handlers: vec![],
+        printed_output: None,
+        call_traces: None,
+        inner_panic_policy: Default::default(),
+        inner_call_panic: None,
+        inner_call_error: None,
+        inner_statement_execution_counts: None,
+        run_limits: Default::default(),
+        event_decoder: None,
+        events: vec![],
+        heavy_syscalls: Default::default(),
     };
     let bytecode: Vec<BigInt> = function
         .instructions
@@ -123,7 +140,7 @@ fn test_runner(function: CasmContext, n_returns: usize, expected: &[i128]) {
         .flat_map(|instruction| instruction.assemble().encode())
         .collect();
 
-    let (cells, ap) = run_function(
+    let (cells, ap, _) = run_function(
         &mut VirtualMachine::new(true),
         bytecode.iter(),
         vec![],
@@ -154,11 +171,24 @@ fn test_allocate_segment() {
         string_to_hint,
         starknet_state: StarknetState::default(),
         run_resources: RunResources::default(),
+        hint_executions_log: None,
+        observation: None,
+        hint_handlers: vec![],
+        printed_output: None,
+        call_traces: None,
+        inner_panic_policy: Default::default(),
+        inner_call_panic: None,
+        inner_call_error: None,
+        inner_statement_execution_counts: None,
+        run_limits: Default::default(),
+        event_decoder: None,
+        events: vec![],
+        heavy_syscalls: Default::default(),
     };
     let bytecode: Vec<BigInt> =
         casm.instructions.iter().flat_map(|instruction| instruction.assemble().encode()).collect();
 
-    let (memory, ap) = run_function(
+    let (memory, ap, _) = run_function(
         &mut VirtualMachine::new(true),
         bytecode.iter(),
         vec![],
@@ -175,6 +205,85 @@ fn test_allocate_segment() {
     assert_eq!(memory[ptr], Some(Felt252::from(1337)));
 }
 
+#[test]
+fn test_hint_executions_log() {
+    let casm = casm! {
+        [ap] = 39, ap++;
+        %{ memory[ap] = 13 < memory[ap - 1] %}
+        ap += 1;
+        %{ memory[ap] = segments.add() %}
+        ap += 1;
+        ret;
+    };
+
+    let (hints_dict, string_to_hint) = build_hints_dict(casm.instructions.iter());
+    let mut hint_processor = CairoHintProcessor {
+        runner: None,
+        string_to_hint,
+        starknet_state: StarknetState::default(),
+        run_resources: RunResources::default(),
+        hint_executions_log: Some(HintExecutionsLog::default()),
+        observation: None,
+        hint_handlers: vec![],
+        printed_output: None,
+        call_traces: None,
+        inner_panic_policy: Default::default(),
+        inner_call_panic: None,
+        inner_call_error: None,
+        inner_statement_execution_counts: None,
+        run_limits: Default::default(),
+        event_decoder: None,
+        events: vec![],
+        heavy_syscalls: Default::default(),
+    };
+    let bytecode: Vec<BigInt> =
+        casm.instructions.iter().flat_map(|instruction| instruction.assemble().encode()).collect();
+
+    run_function(
+        &mut VirtualMachine::new(true),
+        bytecode.iter(),
+        vec![],
+        |_| Ok(()),
+        &mut hint_processor,
+        hints_dict,
+    )
+    .expect("Running code failed.");
+    let executions = hint_processor.hint_executions_log.unwrap().executions;
+    let [less_than, alloc_segment] = &executions[..] else {
+        panic!("Expected exactly two hint executions, got: {executions:?}");
+    };
+    assert_eq!(
+        less_than,
+        &HintExecution {
+            step: 1,
+            pc: 2,
+            hint: casm.instructions[1].hints[0].clone(),
+            inputs: vec![
+                HintOperandValue { name: "lhs", value: Some(Felt252::from(13).into()) },
+                HintOperandValue { name: "rhs", value: Some(Felt252::from(39).into()) },
+            ],
+            outputs: vec![HintOperandValue { name: "dst", value: Some(Felt252::from(1).into()) }],
+            response: None,
+        }
+    );
+    assert_eq!(
+        (alloc_segment.step, alloc_segment.pc, &alloc_segment.hint),
+        (2, 4, &casm.instructions[2].hints[0])
+    );
+    assert!(alloc_segment.inputs.is_empty());
+    assert!(
+        matches!(
+            &alloc_segment.outputs[..],
+            [HintOperandValue {
+                name: "dst",
+                value: Some(MaybeRelocatable::RelocatableValue(Relocatable { offset: 0, .. })),
+            }]
+        ),
+        "Unexpected outputs: {:?}",
+        alloc_segment.outputs
+    );
+}
+
 #[test]
 fn test_as_cairo_short_string() {
     // Simple short strings.
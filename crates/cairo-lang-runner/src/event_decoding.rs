@@ -0,0 +1,262 @@
+use std::fmt::Display;
+
+use cairo_felt::Felt252;
+use cairo_lang_starknet_classes::abi::{
+    Contract, Enum, Event, EventField, EventFieldKind, EventKind, Item, Struct,
+};
+use cairo_lang_starknet_classes::keccak::starknet_keccak;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use itertools::Itertools;
+use num_traits::ToPrimitive;
+
+/// A value decoded according to a contract ABI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiValue {
+    /// A value serialized as a single felt252 (e.g. felt252, integers, contract addresses).
+    Felt(Felt252),
+    /// A struct, or a struct event, with its members by order.
+    Struct { name: String, members: Vec<(String, AbiValue)> },
+    /// An enum, or an enum event, with the value of its active variant.
+    Enum { name: String, variant: String, value: Box<AbiValue> },
+    /// An array or a span.
+    Array(Vec<AbiValue>),
+    /// A tuple.
+    Tuple(Vec<AbiValue>),
+}
+impl Display for AbiValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AbiValue::Felt(value) => write!(f, "{value}"),
+            AbiValue::Struct { name, members } if members.is_empty() => write!(f, "{name}"),
+            AbiValue::Struct { name, members } => write!(
+                f,
+                "{name} {{ {} }}",
+                members.iter().map(|(name, value)| format!("{name}: {value}")).join(", ")
+            ),
+            AbiValue::Enum { name, variant, value } => match value.as_ref() {
+                AbiValue::Tuple(elements) if elements.is_empty() => write!(f, "{name}::{variant}"),
+                value => write!(f, "{name}::{variant}({value})"),
+            },
+            AbiValue::Array(elements) => write!(f, "[{}]", elements.iter().join(", ")),
+            AbiValue::Tuple(elements) => match &elements[..] {
+                [element] => write!(f, "({element},)"),
+                _ => write!(f, "({})", elements.iter().join(", ")),
+            },
+        }
+    }
+}
+
+/// An event emitted by a run, or by the calls to contracts it made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmittedEvent {
+    /// The address of the contract that emitted the event.
+    pub contract_address: Felt252,
+    pub keys: Vec<Felt252>,
+    pub data: Vec<Felt252>,
+    /// The event decoded according to the ABI of the emitting contract, if the ABI is known and
+    /// the event matches it.
+    pub decoded: Option<AbiValue>,
+}
+
+/// Decodes events emitted by a contract into named fields and enum variants, according to the
+/// contract's ABI.
+pub struct EventDecoder<'a> {
+    structs: UnorderedHashMap<&'a str, &'a Struct>,
+    enums: UnorderedHashMap<&'a str, &'a Enum>,
+    events: UnorderedHashMap<&'a str, &'a Event>,
+    /// The top level event of the contract - the event not nested in any other event.
+    root_event: Option<&'a Event>,
+}
+impl<'a> EventDecoder<'a> {
+    pub fn new(abi: &'a Contract) -> Self {
+        let mut structs = UnorderedHashMap::default();
+        let mut enums = UnorderedHashMap::default();
+        let mut events = UnorderedHashMap::default();
+        for item in abi.items() {
+            match item {
+                Item::Struct(item) => {
+                    structs.insert(item.name.as_str(), item);
+                }
+                Item::Enum(item) => {
+                    enums.insert(item.name.as_str(), item);
+                }
+                Item::Event(item) => {
+                    events.insert(item.name.as_str(), item);
+                }
+                _ => {}
+            }
+        }
+        let root_event = abi.items().find_map(|item| {
+            let Item::Event(event) = item else { return None };
+            let is_nested = abi.items().any(|item| {
+                let Item::Event(other) = item else { return false };
+                event_fields(other).iter().any(|field| {
+                    matches!(field.kind, EventFieldKind::Nested | EventFieldKind::Flat)
+                        && field.ty == event.name
+                })
+            });
+            (!is_nested).then_some(event)
+        });
+        Self { structs, enums, events, root_event }
+    }
+
+    /// Decodes an event emitted by the contract, given its keys and data. Returns `None` if the
+    /// event does not match the ABI.
+    pub fn decode(&self, keys: &[Felt252], data: &[Felt252]) -> Option<AbiValue> {
+        let mut keys = keys;
+        let mut data = data;
+        let value = self.decode_event(self.root_event?, &mut keys, &mut data)?;
+        (keys.is_empty() && data.is_empty()).then_some(value)
+    }
+
+    /// Decodes an event, consuming its keys and data.
+    fn decode_event(
+        &self,
+        event: &Event,
+        keys: &mut &[Felt252],
+        data: &mut &[Felt252],
+    ) -> Option<AbiValue> {
+        match &event.kind {
+            EventKind::Struct { members } => {
+                let members = members
+                    .iter()
+                    .map(|member| {
+                        Some((member.name.clone(), self.decode_event_field(member, keys, data)?))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(AbiValue::Struct { name: event.name.clone(), members })
+            }
+            EventKind::Enum { variants } => {
+                for variant in variants {
+                    match variant.kind {
+                        EventFieldKind::Nested => {
+                            let (selector, rest) = keys.split_first()?;
+                            if *selector != Felt252::from(starknet_keccak(variant.name.as_bytes()))
+                            {
+                                continue;
+                            }
+                            *keys = rest;
+                        }
+                        EventFieldKind::Flat => {
+                            // A flat variant is identified by the selectors of its inner event, so
+                            // try decoding it without consuming the keys on failure.
+                            let (mut inner_keys, mut inner_data) = (*keys, *data);
+                            let Some(value) =
+                                self.decode_event_field(variant, &mut inner_keys, &mut inner_data)
+                            else {
+                                continue;
+                            };
+                            (*keys, *data) = (inner_keys, inner_data);
+                            return Some(AbiValue::Enum {
+                                name: event.name.clone(),
+                                variant: variant.name.clone(),
+                                value: Box::new(value),
+                            });
+                        }
+                        EventFieldKind::KeySerde | EventFieldKind::DataSerde => {}
+                    }
+                    let value = self.decode_event_field(variant, keys, data)?;
+                    return Some(AbiValue::Enum {
+                        name: event.name.clone(),
+                        variant: variant.name.clone(),
+                        value: Box::new(value),
+                    });
+                }
+                None
+            }
+        }
+    }
+
+    /// Decodes a field of an event, consuming its keys and data.
+    fn decode_event_field(
+        &self,
+        field: &EventField,
+        keys: &mut &[Felt252],
+        data: &mut &[Felt252],
+    ) -> Option<AbiValue> {
+        match field.kind {
+            EventFieldKind::KeySerde => self.deserialize(&field.ty, keys),
+            EventFieldKind::DataSerde => self.deserialize(&field.ty, data),
+            EventFieldKind::Nested | EventFieldKind::Flat => {
+                self.decode_event(self.events.get(field.ty.as_str())?, keys, data)
+            }
+        }
+    }
+
+    /// Deserializes a value of the type named `ty` in the ABI, as serialized by `Serde`.
+    fn deserialize(&self, ty: &str, values: &mut &[Felt252]) -> Option<AbiValue> {
+        let ty = ty.trim_start_matches('@');
+        if let Some(inner) = ty.strip_prefix('(').and_then(|ty| ty.strip_suffix(')')) {
+            return split_tuple_types(inner)
+                .into_iter()
+                .map(|ty| self.deserialize(ty, values))
+                .collect::<Option<Vec<_>>>()
+                .map(AbiValue::Tuple);
+        }
+        if let Some(inner) = ["core::array::Array::<", "core::array::Span::<"]
+            .into_iter()
+            .find_map(|prefix| ty.strip_prefix(prefix)?.strip_suffix('>'))
+        {
+            let len = pop_felt(values)?.to_usize()?;
+            return (0..len)
+                .map(|_| self.deserialize(inner, values))
+                .collect::<Option<Vec<_>>>()
+                .map(AbiValue::Array);
+        }
+        if let Some(item) = self.structs.get(ty) {
+            let members = item
+                .members
+                .iter()
+                .map(|member| Some((member.name.clone(), self.deserialize(&member.ty, values)?)))
+                .collect::<Option<Vec<_>>>()?;
+            return Some(AbiValue::Struct { name: item.name.clone(), members });
+        }
+        if let Some(item) = self.enums.get(ty) {
+            let variant = item.variants.get(pop_felt(values)?.to_usize()?)?;
+            return Some(AbiValue::Enum {
+                name: item.name.clone(),
+                variant: variant.name.clone(),
+                value: Box::new(self.deserialize(&variant.ty, values)?),
+            });
+        }
+        pop_felt(values).map(AbiValue::Felt)
+    }
+}
+
+/// Returns the fields of an event - the members of a struct event or the variants of an enum event.
+fn event_fields(event: &Event) -> &[EventField] {
+    match &event.kind {
+        EventKind::Struct { members } => members,
+        EventKind::Enum { variants } => variants,
+    }
+}
+
+/// Pops the first felt252 out of the given values.
+fn pop_felt(values: &mut &[Felt252]) -> Option<Felt252> {
+    let (value, rest) = values.split_first()?;
+    *values = rest;
+    Some(value.clone())
+}
+
+/// Splits the inner part of a tuple type name into the names of its element types.
+fn split_tuple_types(inner: &str) -> Vec<&str> {
+    let mut result = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' | '<' => depth += 1,
+            ')' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                result.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = inner[start..].trim();
+    if !last.is_empty() {
+        result.push(last);
+    }
+    result
+}
@@ -0,0 +1,50 @@
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
+
+#[cfg(test)]
+#[path = "fee_test.rs"]
+mod test;
+
+/// The pricing of execution resources, used for estimating the fee of a run.
+#[derive(Debug, Clone)]
+pub struct ResourcesPricing {
+    /// The gas cost of a single step.
+    pub step_weight: f64,
+    /// The gas cost of a single instance of each builtin, by the builtin name as reported in the
+    /// used resources (e.g. `range_check_builtin`). Builtins without a weight are free.
+    pub builtin_weights: OrderedHashMap<String, f64>,
+    /// The price of a single unit of gas.
+    pub gas_price: u128,
+}
+
+/// An estimation of the fee of a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeEstimate {
+    /// The estimated gas of the run.
+    pub gas: u128,
+    /// The estimated fee of the run - the gas multiplied by the gas price.
+    pub fee: u128,
+    /// The resource determining the gas - `n_steps` or the name of a builtin.
+    pub bottleneck: String,
+}
+
+/// Estimates the fee of a run using the given resources.
+///
+/// As in Starknet, the gas is determined by the most expensive resource - the maximum over the
+/// resources of their usage multiplied by their weight, rounded up.
+pub fn estimate_fee(resources: &ExecutionResources, pricing: &ResourcesPricing) -> FeeEstimate {
+    let mut bottleneck = "n_steps";
+    let mut max_gas = resources.n_steps as f64 * pricing.step_weight;
+    // Iterating over the weights and not the used builtins, for a deterministic choice of the
+    // bottleneck.
+    for (name, weight) in pricing.builtin_weights.iter() {
+        let used_instances = resources.builtin_instance_counter.get(name).copied().unwrap_or(0);
+        let gas = used_instances as f64 * weight;
+        if gas > max_gas {
+            bottleneck = name;
+            max_gas = gas;
+        }
+    }
+    let gas = max_gas.ceil() as u128;
+    FeeEstimate { gas, fee: gas * pricing.gas_price, bottleneck: bottleneck.to_string() }
+}
@@ -0,0 +1,47 @@
+use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
+use test_case::test_case;
+
+use super::{estimate_fee, FeeEstimate, ResourcesPricing};
+
+#[test_case(1000, 0, 0, 3, 9, "n_steps"; "steps bottleneck")]
+#[test_case(100, 20, 0, 2, 6, "range_check_builtin"; "range check bottleneck")]
+#[test_case(100, 20, 1, 5, 15, "pedersen_builtin"; "pedersen bottleneck")]
+#[test_case(0, 0, 0, 0, 0, "n_steps"; "empty run")]
+fn test_estimate_fee(
+    n_steps: usize,
+    range_checks: usize,
+    pedersens: usize,
+    expected_gas: u128,
+    expected_fee: u128,
+    expected_bottleneck: &str,
+) {
+    let resources = ExecutionResources {
+        n_steps,
+        n_memory_holes: 0,
+        builtin_instance_counter: [
+            ("range_check_builtin".to_string(), range_checks),
+            ("pedersen_builtin".to_string(), pedersens),
+            ("bitwise_builtin".to_string(), 0),
+        ]
+        .into_iter()
+        .collect(),
+    };
+    let pricing = ResourcesPricing {
+        step_weight: 0.0025,
+        builtin_weights: [
+            ("range_check_builtin".to_string(), 0.08),
+            ("pedersen_builtin".to_string(), 5.0),
+        ]
+        .into_iter()
+        .collect(),
+        gas_price: 3,
+    };
+    assert_eq!(
+        estimate_fee(&resources, &pricing),
+        FeeEstimate {
+            gas: expected_gas,
+            fee: expected_fee,
+            bottleneck: expected_bottleneck.to_string()
+        }
+    );
+}
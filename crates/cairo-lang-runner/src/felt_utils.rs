@@ -0,0 +1,45 @@
+//! Conversions of felts to and from their textual representations, and computation of entry point
+//! selectors, shared by the frontends of the runner.
+
+use cairo_felt::Felt252;
+
+use crate::hashing::starknet_keccak;
+
+#[cfg(test)]
+#[path = "felt_utils_test.rs"]
+mod test;
+
+/// Parses a felt252 from its decimal or `0x` prefixed hex representation, which may be negative,
+/// e.g. `-0x5` for `P - 5`.
+pub fn parse_felt252(value: &str) -> Option<Felt252> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+    let (digits, radix) = match digits.strip_prefix("0x") {
+        Some(digits) => (digits, 16),
+        None => (digits, 10),
+    };
+    // `parse_bytes` accepts an additional sign.
+    if digits.starts_with(['-', '+']) {
+        return None;
+    }
+    let value = Felt252::parse_bytes(digits.as_bytes(), radix)?;
+    Some(if negative { -value } else { value })
+}
+
+/// Formats a felt252 as a `0x` prefixed hex string, e.g. `0x2a`.
+pub fn format_felt252_hex(value: &Felt252) -> String {
+    format!("0x{}", value.to_str_radix(16))
+}
+
+/// Formats a felt252 as a decimal string, e.g. `42`.
+pub fn format_felt252_dec(value: &Felt252) -> String {
+    value.to_str_radix(10)
+}
+
+/// Returns the selector of the entry point with the given name, as computed by `selector!` and
+/// used in the calls of the entry point.
+pub fn entry_point_selector(name: &str) -> Felt252 {
+    Felt252::from(starknet_keccak(name.as_bytes()))
+}
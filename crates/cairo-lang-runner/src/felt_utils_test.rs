@@ -0,0 +1,38 @@
+use cairo_felt::Felt252;
+use test_case::test_case;
+
+use super::{entry_point_selector, format_felt252_dec, format_felt252_hex, parse_felt252};
+
+#[test_case("42", Some(Felt252::from(42)); "decimal")]
+#[test_case("0x2a", Some(Felt252::from(42)); "hex")]
+#[test_case("-1", Some(Felt252::from(-1)); "negative decimal")]
+#[test_case("-0x2a", Some(Felt252::from(-42)); "negative hex")]
+#[test_case("0xg", None; "invalid hex")]
+#[test_case("--1", None; "double sign")]
+#[test_case("", None; "empty")]
+fn test_parse_felt252(value: &str, expected: Option<Felt252>) {
+    assert_eq!(parse_felt252(value), expected);
+}
+
+#[test]
+fn test_format_felt252() {
+    let value = parse_felt252("-1").unwrap();
+    assert_eq!(
+        format_felt252_hex(&value),
+        "0x800000000000011000000000000000000000000000000000000000000000000"
+    );
+    assert_eq!(
+        format_felt252_dec(&value),
+        "3618502788666131213697322783095070105623107215331596699973092056135872020480"
+    );
+    assert_eq!(parse_felt252(&format_felt252_hex(&value)), Some(value));
+}
+
+#[test]
+fn test_entry_point_selector() {
+    // The selector of `transfer`, as found in ERC20 calls.
+    assert_eq!(
+        format_felt252_hex(&entry_point_selector("transfer")),
+        "0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e"
+    );
+}
@@ -0,0 +1,88 @@
+use std::fmt::{Display, Write};
+
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::statements_locations::StatementsLocations;
+
+use crate::RunResultValue;
+
+#[cfg(test)]
+#[path = "gas_checkpoints_test.rs"]
+mod test;
+
+/// The kind of a gas checkpoint.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum GasCheckpointKind {
+    /// A `withdraw_gas` call.
+    Withdraw,
+    /// A `withdraw_gas_all` call, withdrawing the gas including the builtin costs.
+    BuiltinWithdraw,
+    /// A `redeposit_gas` call.
+    Redeposit,
+}
+impl Display for GasCheckpointKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GasCheckpointKind::Withdraw => write!(f, "withdraw_gas"),
+            GasCheckpointKind::BuiltinWithdraw => write!(f, "withdraw_gas_all"),
+            GasCheckpointKind::Redeposit => write!(f, "redeposit_gas"),
+        }
+    }
+}
+
+/// A gas checkpoint reached during a run - a Sierra statement withdrawing or redepositing gas.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GasCheckpoint {
+    /// The index of the step at which the checkpoint was reached.
+    pub step: usize,
+    /// The Sierra statement of the checkpoint.
+    pub statement_idx: StatementIdx,
+    /// The kind of the checkpoint.
+    pub kind: GasCheckpointKind,
+    /// Whether the checkpoint succeeded. Withdrawals fail if there is not enough gas, redeposits
+    /// always succeed.
+    pub succeeded: bool,
+}
+
+/// Returns the gas checkpoint at which the run ran out of gas, if it ended with the out of gas
+/// panic. Failed withdrawals which the program recovered from are not reported.
+pub fn find_out_of_gas_checkpoint<'a>(
+    checkpoints: &'a [GasCheckpoint],
+    value: &RunResultValue,
+) -> Option<&'a GasCheckpoint> {
+    if !value.is_out_of_gas() {
+        return None;
+    }
+    checkpoints.iter().rev().find(|checkpoint| !checkpoint.succeeded)
+}
+
+/// Formats the given gas checkpoints as a timeline, with the Cairo function and the source
+/// location that generated each of them.
+pub fn format_gas_checkpoints(
+    checkpoints: &[GasCheckpoint],
+    db: &dyn SierraGenGroup,
+    statements_locations: &StatementsLocations,
+) -> String {
+    let mut result = String::new();
+    for GasCheckpoint { step, statement_idx, kind, succeeded } in checkpoints {
+        let location = format_checkpoint_location(db, statements_locations, *statement_idx);
+        let status = if *succeeded { "ok" } else { "out of gas" };
+        writeln!(
+            result,
+            "  step {step}: {kind} at statement {statement_idx} ({location}): {status}"
+        )
+        .unwrap();
+    }
+    result
+}
+
+/// Formats the Cairo function and the source location that generated the given checkpoint
+/// statement, e.g. `lib.cairo::sum, lib.cairo:6:3`. See
+/// [StatementsLocations::describe_statement_location].
+pub fn format_checkpoint_location(
+    db: &dyn SierraGenGroup,
+    statements_locations: &StatementsLocations,
+    statement_idx: StatementIdx,
+) -> String {
+    statements_locations.describe_statement_location(db, statement_idx)
+}
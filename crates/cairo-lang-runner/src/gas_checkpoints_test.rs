@@ -0,0 +1,116 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::arc_unwrap_or_clone;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+
+use super::{find_out_of_gas_checkpoint, format_checkpoint_location, format_gas_checkpoints};
+use crate::SierraCasmRunner;
+
+cairo_lang_test_utils::test_file_test!(
+    gas_checkpoints,
+    "src/gas_checkpoints_test_data/",
+    {
+        gas_checkpoints: "gas_checkpoints",
+    },
+    test_gas_checkpoints
+);
+
+cairo_lang_test_utils::test_file_test!(
+    out_of_gas_checkpoint,
+    "src/gas_checkpoints_test_data/",
+    {
+        out_of_gas_checkpoint: "out_of_gas_checkpoint",
+    },
+    test_out_of_gas_checkpoint
+);
+
+pub fn test_gas_checkpoints(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(&db, &inputs["cairo_code"]).unwrap();
+    DiagnosticsReporter::stderr().with_crates(&[test_module.crate_id]).ensure(&db).unwrap();
+
+    // Compile to Sierra.
+    let SierraProgramWithDebug { program: sierra_program, debug_info } =
+        arc_unwrap_or_clone(db.get_sierra_program(vec![test_module.crate_id]).unwrap());
+    let sierra_program = replace_sierra_ids_in_program(&db, &sierra_program);
+    let runner = SierraCasmRunner::new(
+        sierra_program,
+        Some(Default::default()),
+        OrderedHashMap::default(),
+        false,
+    )
+    .unwrap()
+    .with_gas_checkpoints_log(true);
+    let func = runner.find_function(&inputs["function_name"]).unwrap();
+    let available_gas = inputs["available_gas"].parse().unwrap();
+    let result = runner
+        .run_function_with_starknet_context(func, &[], Some(available_gas), Default::default())
+        .unwrap();
+
+    TestRunnerResult {
+        outputs: OrderedHashMap::from([(
+            "expected_gas_checkpoints".into(),
+            format_gas_checkpoints(
+                &result.gas_checkpoints.unwrap(),
+                &db,
+                &debug_info.statements_locations,
+            ),
+        )]),
+        error: None,
+    }
+}
+
+pub fn test_out_of_gas_checkpoint(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(&db, &inputs["cairo_code"]).unwrap();
+    DiagnosticsReporter::stderr().with_crates(&[test_module.crate_id]).ensure(&db).unwrap();
+
+    let SierraProgramWithDebug { program: sierra_program, debug_info } =
+        arc_unwrap_or_clone(db.get_sierra_program(vec![test_module.crate_id]).unwrap());
+    let sierra_program = replace_sierra_ids_in_program(&db, &sierra_program);
+    let runner = SierraCasmRunner::new(
+        sierra_program,
+        Some(Default::default()),
+        OrderedHashMap::default(),
+        false,
+    )
+    .unwrap()
+    .with_gas_checkpoints_log(true);
+    let func = runner.find_function(&inputs["function_name"]).unwrap();
+    let available_gas = inputs["available_gas"].parse().unwrap();
+    let result = runner
+        .run_function_with_starknet_context(func, &[], Some(available_gas), Default::default())
+        .unwrap();
+    let out_of_gas_checkpoint =
+        match find_out_of_gas_checkpoint(&result.gas_checkpoints.unwrap(), &result.value) {
+            Some(checkpoint) => format!(
+                "statement {} ({})",
+                checkpoint.statement_idx,
+                format_checkpoint_location(
+                    &db,
+                    &debug_info.statements_locations,
+                    checkpoint.statement_idx
+                )
+            ),
+            None => "None".into(),
+        };
+
+    TestRunnerResult {
+        outputs: OrderedHashMap::from([(
+            "expected_out_of_gas_checkpoint".into(),
+            out_of_gas_checkpoint,
+        )]),
+        error: None,
+    }
+}
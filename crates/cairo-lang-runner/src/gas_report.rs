@@ -0,0 +1,195 @@
+//! Reports of the statically computed gas requirements of the functions of a program, available
+//! without running it.
+
+use std::fmt::Display;
+
+use cairo_lang_sierra::extensions::core::CoreConcreteLibfunc;
+use cairo_lang_sierra::extensions::gas::{CostTokenType, GasConcreteLibfunc};
+use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_sierra::optimization::function_statements;
+use cairo_lang_sierra::program::{GenStatement, Program, StatementIdx};
+use cairo_lang_utils::casts::IntoOrPanic;
+use cairo_lang_utils::graph_algos::graph_node::GraphNode;
+use cairo_lang_utils::graph_algos::strongly_connected_components::compute_scc;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use itertools::Itertools;
+
+use crate::{token_gas_cost, SierraCasmRunner};
+
+/// A statement withdrawing gas, e.g. at the start of each iteration of a loop.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GasWithdrawal {
+    /// The Sierra statement withdrawing the gas.
+    pub statement_idx: StatementIdx,
+    /// The gas withdrawn, which is the gas required until the next withdrawal.
+    pub gas: usize,
+    /// Whether the statement is in a cycle - of branches within the function, or of recursive
+    /// calls, e.g. of a loop - so the gas is withdrawn on each iteration of the cycle.
+    pub in_cycle: bool,
+}
+
+/// The statically computed gas requirements of a user function.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FunctionGasInfo {
+    pub id: FunctionId,
+    /// The gas that must be available when calling the function - the worst case gas used until
+    /// the first withdrawal, or until the function returns if it never withdraws gas.
+    pub required_gas: usize,
+    /// The statements of the function withdrawing gas.
+    pub withdrawals: Vec<GasWithdrawal>,
+    /// Whether gas can be withdrawn by the function or any function it calls. If not, the function
+    /// uses at most `required_gas` gas. Otherwise, the gas used depends on the number of
+    /// withdrawals, e.g. the number of iterations of loops.
+    pub withdraws_gas: bool,
+}
+
+/// The statically computed gas requirements of the user functions of a program, by order of
+/// declaration.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GasReport {
+    pub functions: Vec<FunctionGasInfo>,
+}
+impl GasReport {
+    /// Creates the report of the program of the runner, or returns `None` if the program was
+    /// compiled without gas usage.
+    pub(crate) fn new(runner: &SierraCasmRunner) -> Option<Self> {
+        let program = &runner.sierra_program;
+        let gas_info = &runner.metadata.gas_info;
+        if gas_info.function_costs.is_empty() {
+            return None;
+        }
+        let function_indices: UnorderedHashMap<FunctionId, usize> =
+            program.funcs.iter().enumerate().map(|(i, func)| (func.id.clone(), i)).collect();
+        let mut functions = vec![];
+        let mut callees = vec![];
+        for func in &program.funcs {
+            let mut withdrawals = vec![];
+            let mut func_callees = vec![];
+            for idx in function_statements(program, func.entry_point) {
+                let GenStatement::Invocation(invocation) = &program.statements[idx.0] else {
+                    continue;
+                };
+                match runner.sierra_program_registry.get_libfunc(&invocation.libfunc_id) {
+                    Ok(CoreConcreteLibfunc::Gas(
+                        GasConcreteLibfunc::WithdrawGas(_)
+                        | GasConcreteLibfunc::BuiltinWithdrawGas(_),
+                    )) => withdrawals.push(GasWithdrawal {
+                        statement_idx: idx,
+                        gas: CostTokenType::iter()
+                            .filter(|token_type| {
+                                runner.builtin_costs || **token_type == CostTokenType::Const
+                            })
+                            .filter_map(|token_type| {
+                                let value = gas_info.variable_values.get(&(idx, *token_type))?;
+                                Some(value.into_or_panic::<usize>() * token_gas_cost(*token_type))
+                            })
+                            .sum(),
+                        in_cycle: is_in_branches_cycle(program, idx),
+                    }),
+                    Ok(CoreConcreteLibfunc::FunctionCall(libfunc)) => {
+                        func_callees.extend(function_indices.get(&libfunc.function.id).copied());
+                    }
+                    _ => {}
+                }
+            }
+            functions.push(FunctionGasInfo {
+                id: func.id.clone(),
+                required_gas: runner.initial_required_gas(func)?,
+                withdraws_gas: !withdrawals.is_empty(),
+                withdrawals,
+            });
+            callees.push(func_callees);
+        }
+
+        // Withdrawals of recursive functions are in cycles of calls.
+        for (index, func) in functions.iter_mut().enumerate() {
+            let scc = compute_scc(&CallGraphNode { index, callees: &callees });
+            if scc.len() > 1 || callees[index].contains(&index) {
+                for withdrawal in &mut func.withdrawals {
+                    withdrawal.in_cycle = true;
+                }
+            }
+        }
+
+        // Propagate the withdrawals to the callers, until a fixed point is reached.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            let withdrawing = functions.iter().map(|func| func.withdraws_gas).collect_vec();
+            for (func, func_callees) in functions.iter_mut().zip(&callees) {
+                if !func.withdraws_gas && func_callees.iter().any(|index| withdrawing[*index]) {
+                    func.withdraws_gas = true;
+                    changed = true;
+                }
+            }
+        }
+        Some(Self { functions })
+    }
+}
+impl Display for GasReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for FunctionGasInfo { id, required_gas, withdrawals, withdraws_gas } in &self.functions {
+            if *withdraws_gas {
+                writeln!(f, "{id}: {required_gas} gas until the first withdrawal.")?;
+            } else {
+                writeln!(f, "{id}: at most {required_gas} gas.")?;
+            }
+            for GasWithdrawal { statement_idx, gas, in_cycle } in withdrawals {
+                if *in_cycle {
+                    writeln!(
+                        f,
+                        "    Withdraws {gas} gas on each iteration of a cycle at statement \
+                         {statement_idx}."
+                    )?;
+                } else {
+                    writeln!(f, "    Withdraws {gas} gas at statement {statement_idx}.")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether the statement `idx` can be reached again from itself by branching.
+fn is_in_branches_cycle(program: &Program, idx: StatementIdx) -> bool {
+    let mut visited = UnorderedHashSet::<StatementIdx>::default();
+    let mut pending = vec![idx];
+    while let Some(current) = pending.pop() {
+        let Some(GenStatement::Invocation(invocation)) = program.statements.get(current.0) else {
+            continue;
+        };
+        for branch in &invocation.branches {
+            let next = current.next(&branch.target);
+            if next == idx {
+                return true;
+            }
+            if visited.insert(next) {
+                pending.push(next);
+            }
+        }
+    }
+    false
+}
+
+/// A node in the call graph of the user functions of a program, by their indices.
+#[derive(Clone)]
+struct CallGraphNode<'a> {
+    index: usize,
+    /// The indices of the functions called by each function.
+    callees: &'a [Vec<usize>],
+}
+impl GraphNode for CallGraphNode<'_> {
+    type NodeId = usize;
+
+    fn get_neighbors(&self) -> Vec<Self> {
+        self.callees[self.index]
+            .iter()
+            .map(|index| CallGraphNode { index: *index, callees: self.callees })
+            .collect()
+    }
+
+    fn get_id(&self) -> Self::NodeId {
+        self.index
+    }
+}
@@ -0,0 +1,78 @@
+//! Reporting of the monomorphized instances of generic functions in a compiled program.
+use std::fmt::Display;
+
+use cairo_lang_debug::DebugWithDb;
+use cairo_lang_lowering::db::LoweringGroup;
+use cairo_lang_lowering::ids::FunctionWithBodyId;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::replace_ids::strip_generic_args;
+use cairo_lang_sierra_to_casm::compiler::FunctionCodeInfo;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+
+#[cfg(test)]
+#[path = "generic_instances_test.rs"]
+mod test;
+
+/// The monomorphized instances of a generic function in a compiled program.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct GenericFunctionInstances {
+    /// The name of the generic function, without generic arguments, e.g.
+    /// `core::array::ArrayImpl::append`.
+    pub generic_name: String,
+    /// The instances of the function, sorted by their offsets.
+    pub instances: Vec<FunctionCodeInfo>,
+}
+impl GenericFunctionInstances {
+    /// Returns the number of bytecode words the code of all the instances occupies.
+    pub fn total_code_size(&self) -> usize {
+        self.instances.iter().map(|instance| instance.code_size).sum()
+    }
+}
+impl Display for GenericFunctionInstances {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{}: {} instances, {} words",
+            self.generic_name,
+            self.instances.len(),
+            self.total_code_size()
+        )?;
+        for instance in &self.instances {
+            writeln!(f, "    {}: {} words", instance.id, instance.code_size)?;
+        }
+        Ok(())
+    }
+}
+
+/// Groups the given user functions of a compiled program by the generic function they are an
+/// instance of, keeping the generic functions, and sorts the groups by the total size of their
+/// code, largest first. The ids of the functions must be the ones generated by `db`, up to their
+/// debug names.
+pub fn generic_instances(
+    db: &dyn SierraGenGroup,
+    function_code_info: Vec<FunctionCodeInfo>,
+) -> Vec<GenericFunctionInstances> {
+    let lowering_db: &dyn LoweringGroup = db.upcast();
+    let mut groups = OrderedHashMap::<FunctionWithBodyId, GenericFunctionInstances>::default();
+    for info in function_code_info {
+        let function = db.lookup_intern_sierra_function(info.id.clone());
+        let Ok(Some(body)) = function.body(lowering_db) else { continue };
+        let name = format!("{:?}", function.lookup(lowering_db).debug(lowering_db));
+        let generic_name = strip_generic_args(&name);
+        if generic_name == name {
+            continue;
+        }
+        groups
+            .entry(body.function_with_body_id(lowering_db))
+            .or_insert_with(|| GenericFunctionInstances { generic_name, instances: vec![] })
+            .instances
+            .push(info);
+    }
+    let mut instances = groups.into_iter().map(|(_, instances)| instances).collect::<Vec<_>>();
+    instances.sort_by(|a, b| {
+        b.total_code_size()
+            .cmp(&a.total_code_size())
+            .then_with(|| a.generic_name.cmp(&b.generic_name))
+    });
+    instances
+}
@@ -0,0 +1,50 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use indoc::indoc;
+
+use crate::SierraCasmRunner;
+
+#[test]
+fn test_generic_instances() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(
+        &db,
+        indoc! {"
+            #[inline(never)]
+            fn drop_value<T, +Drop<T>>(value: T) {}
+            fn main(a: felt252, b: u8) {
+                drop_value(a);
+                drop_value(b);
+            }
+        "},
+    )
+    .unwrap();
+    DiagnosticsReporter::stderr().with_crates(&[test_module.crate_id]).ensure(&db).unwrap();
+    let sierra_program = db.get_sierra_program(vec![test_module.crate_id]).unwrap().program.clone();
+    let runner = SierraCasmRunner::new(
+        replace_sierra_ids_in_program(&db, &sierra_program),
+        None,
+        Default::default(),
+        false,
+    )
+    .unwrap();
+    let instances = runner.generic_instances(&db);
+    let drop_value =
+        instances.iter().find(|instances| instances.generic_name == "test::drop_value").unwrap();
+    let sizes = drop_value.instances.iter().map(|info| info.code_size).collect::<Vec<_>>();
+    assert_eq!(
+        drop_value.to_string(),
+        format!(
+            "test::drop_value: 2 instances, {} words\n    test::drop_value::<core::felt252, \
+             core::felt252Drop>: {} words\n    test::drop_value::<core::integer::u8, \
+             core::integer::u8Drop>: {} words\n",
+            sizes[0] + sizes[1],
+            sizes[0],
+            sizes[1]
+        )
+    );
+    assert!(instances.iter().all(|instances| instances.generic_name != "test::main"));
+}
@@ -0,0 +1,53 @@
+//! The hash functions of Starknet over felts and bytes, as computed by the builtins and syscalls
+//! of the runner, for computing class hashes, storage keys and the like outside of runs.
+
+use cairo_felt::Felt252;
+pub use cairo_lang_starknet_classes::keccak::starknet_keccak;
+use num_bigint::BigUint;
+use sha3::{Digest, Keccak256};
+
+use crate::casm_run::{
+    felt252_to_field_element, field_element_to_felt252,
+    pedersen_hash_array as pedersen_hash_field_elements,
+};
+
+#[cfg(test)]
+#[path = "hashing_test.rs"]
+mod test;
+
+/// Computes the Pedersen hash of two felts, as computed by the `Pedersen` builtin.
+pub fn pedersen_hash(a: &Felt252, b: &Felt252) -> Felt252 {
+    field_element_to_felt252(&starknet_crypto::pedersen_hash(
+        &felt252_to_field_element(a),
+        &felt252_to_field_element(b),
+    ))
+}
+
+/// Computes the Pedersen hash of an array of felts, chaining the hashes of the elements and then
+/// of the length of the array, as defined in
+/// <https://docs.starknet.io/documentation/architecture_and_concepts/Hashing/hash-functions/#array_hashing>.
+pub fn pedersen_hash_array(values: &[Felt252]) -> Felt252 {
+    let values: Vec<_> = values.iter().map(felt252_to_field_element).collect();
+    field_element_to_felt252(&pedersen_hash_field_elements(&values))
+}
+
+/// Computes the Poseidon hash of two felts, as computed by `core::poseidon::hades_permutation(a, b,
+/// 2)`.
+pub fn poseidon_hash(a: &Felt252, b: &Felt252) -> Felt252 {
+    field_element_to_felt252(&starknet_crypto::poseidon_hash(
+        felt252_to_field_element(a),
+        felt252_to_field_element(b),
+    ))
+}
+
+/// Computes the Poseidon hash of a sequence of felts, as computed by
+/// `core::poseidon::poseidon_hash_span`.
+pub fn poseidon_hash_many(values: &[Felt252]) -> Felt252 {
+    let values: Vec<_> = values.iter().map(felt252_to_field_element).collect();
+    field_element_to_felt252(&starknet_crypto::poseidon_hash_many(&values))
+}
+
+/// Computes the Keccak-256 hash of the given bytes, as used in Ethereum.
+pub fn keccak256(data: &[u8]) -> BigUint {
+    BigUint::from_bytes_be(&Keccak256::digest(data))
+}
@@ -0,0 +1,26 @@
+use cairo_felt::Felt252;
+
+use super::{keccak256, pedersen_hash, pedersen_hash_array, starknet_keccak};
+use crate::felt_utils::format_felt252_hex;
+
+#[test]
+fn test_pedersen_hash_array() {
+    let felt = |value: u32| Felt252::from(value);
+    assert_eq!(
+        pedersen_hash_array(&[felt(1), felt(2)]),
+        pedersen_hash(&pedersen_hash(&pedersen_hash(&felt(0), &felt(1)), &felt(2)), &felt(2))
+    );
+}
+
+#[test]
+fn test_keccak() {
+    assert_eq!(
+        format!("{:x}", keccak256(b"")),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+    // `keccak256` with the 6 top bits removed.
+    assert_eq!(
+        format_felt252_hex(&Felt252::from(starknet_keccak(b""))),
+        "0x1d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+}
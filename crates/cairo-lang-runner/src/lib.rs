@@ -1,55 +1,117 @@
 //! Basic runner for running a Sierra program on the vm.
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use ark_std::iterable::Iterable;
+use branch_coverage::BranchCoverage;
 use cairo_felt::Felt252;
 use cairo_lang_casm::hints::Hint;
+use cairo_lang_casm::inline::CasmContext;
 use cairo_lang_casm::instructions::Instruction;
 use cairo_lang_casm::{casm, casm_extend};
 use cairo_lang_sierra::extensions::bitwise::BitwiseType;
+use cairo_lang_sierra::extensions::boxing::BoxType;
 use cairo_lang_sierra::extensions::core::{CoreConcreteLibfunc, CoreLibfunc, CoreType};
 use cairo_lang_sierra::extensions::ec::EcOpType;
 use cairo_lang_sierra::extensions::enm::EnumType;
-use cairo_lang_sierra::extensions::gas::{CostTokenType, GasBuiltinType};
+use cairo_lang_sierra::extensions::felt252_dict::{Felt252DictEntryType, Felt252DictType};
+use cairo_lang_sierra::extensions::gas::{CostTokenType, GasBuiltinType, GasConcreteLibfunc};
+use cairo_lang_sierra::extensions::nullable::NullableType;
 use cairo_lang_sierra::extensions::pedersen::PedersenType;
 use cairo_lang_sierra::extensions::poseidon::PoseidonType;
 use cairo_lang_sierra::extensions::range_check::RangeCheckType;
 use cairo_lang_sierra::extensions::segment_arena::SegmentArenaType;
+use cairo_lang_sierra::extensions::squashed_felt252_dict::SquashedFelt252DictType;
+use cairo_lang_sierra::extensions::starknet::secp256k1::Secp256k1PointType;
+use cairo_lang_sierra::extensions::starknet::secp256r1::Secp256r1PointType;
 use cairo_lang_sierra::extensions::starknet::syscalls::SystemType;
-use cairo_lang_sierra::extensions::{ConcreteType, NamedType};
-use cairo_lang_sierra::ids::{ConcreteTypeId, GenericTypeId};
+use cairo_lang_sierra::extensions::structure::StructConstructLibfunc;
+use cairo_lang_sierra::extensions::{ConcreteType, NamedLibfunc, NamedType};
+use cairo_lang_sierra::ids::{ConcreteLibfuncId, ConcreteTypeId, FunctionId, GenericTypeId};
+use cairo_lang_sierra::optimization::{DeadCodeReport, OptimizationPipeline, PassStatistics};
 use cairo_lang_sierra::program::{Function, GenStatement, GenericArg, StatementIdx};
 use cairo_lang_sierra::program_registry::{ProgramRegistry, ProgramRegistryError};
 use cairo_lang_sierra_ap_change::ApChangeError;
-use cairo_lang_sierra_to_casm::compiler::{CairoProgram, CompilationError};
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_to_casm::compiler::{CairoProgram, CompilationError, ProgramStats};
 use cairo_lang_sierra_to_casm::metadata::{
     calc_metadata, calc_metadata_ap_change_only, Metadata, MetadataComputationConfig, MetadataError,
 };
 use cairo_lang_sierra_type_size::{get_type_size_map, TypeSizeMap};
 use cairo_lang_starknet::contract::ContractInfo;
+use cairo_lang_starknet_classes::abi::Contract;
+use cairo_lang_starknet_classes::compiler_version::{
+    current_compiler_version_id, current_sierra_version_id, VersionId,
+};
+use cairo_lang_starknet_classes::contract_class::{
+    ContractClass, ContractEntryPoint, Felt252SerdeError,
+};
+use cairo_lang_starknet_classes::keccak::starknet_keccak;
 use cairo_lang_utils::casts::IntoOrPanic;
 use cairo_lang_utils::extract_matches;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
 use cairo_vm::hint_processor::hint_processor_definition::HintProcessor;
 use cairo_vm::serde::deserialize_program::{BuiltinName, HintParams};
 use cairo_vm::vm::errors::cairo_run_errors::CairoRunError;
-use cairo_vm::vm::runners::cairo_runner::RunResources;
+use cairo_vm::vm::errors::vm_errors::VirtualMachineError;
+use cairo_vm::vm::errors::vm_exception::VmException;
+use cairo_vm::vm::hooks::Hooks;
+use cairo_vm::vm::runners::cairo_runner::{ExecutionResources, RunResources};
 use cairo_vm::vm::trace::trace_entry::TraceEntry;
-use cairo_vm::vm::vm_core::VirtualMachine;
-use casm_run::hint_to_hint_params;
-pub use casm_run::{CairoHintProcessor, StarknetState};
-use itertools::chain;
+use cairo_vm::vm::vm_core::{VirtualMachine, VirtualMachineBuilder};
+pub use casm_run::{
+    calculate_contract_address, calculate_invoke_transaction_hash, calculate_storage_address,
+    calculate_storage_variable_address, BlockContext, CairoHintProcessor, CallKind, CallTrace,
+    HeavySyscallsConfig, HintExecution, HintExecutionsLog, HintHandler, HintOperandValue,
+    HintResponse, InnerCallPanic, InnerPanicPolicy, PrintedOutput, ResourceBounds, StarknetState,
+    SyscallBehavior, SyscallExecution, TxContext,
+};
+use casm_run::{format_next_item, hint_to_hint_params, read_array_result_as_vec};
+use gas_checkpoints::{GasCheckpoint, GasCheckpointKind};
+use gas_report::GasReport;
+use itertools::{chain, Itertools};
 use num_bigint::BigInt;
 use num_traits::ToPrimitive;
+use observer::{PreStepHook, RunObservation, RunObserver};
 use profiling::{user_function_idx_by_sierra_statement_idx, ProfilingInfo};
+use short_string::as_cairo_short_string;
 use thiserror::Error;
 
 use crate::casm_run::RunFunctionContext;
+use crate::event_decoding::{EmittedEvent, EventDecoder};
+use crate::generic_instances::GenericFunctionInstances;
+use crate::replay::ReplayMismatch;
+use crate::run_diff::read_value_contents;
+use crate::signing::SigningCheatcodes;
 
+pub mod args_file;
+pub mod available_gas;
+pub mod branch_coverage;
 pub mod casm_run;
+pub mod event_decoding;
+pub mod fee;
+pub mod felt_utils;
+pub mod gas_checkpoints;
+pub mod gas_report;
+pub mod generic_instances;
+pub mod hashing;
+pub mod max_steps;
+pub mod observer;
 pub mod profiling;
+pub mod replay;
+pub mod run_diff;
 pub mod short_string;
+pub mod sierra_version;
+pub mod signing;
+pub mod state_diff;
+pub mod value_format;
+
+#[cfg(test)]
+mod test;
 
 const MAX_STACK_TRACE_DEPTH_DEFAULT: usize = 100;
 
@@ -63,12 +125,55 @@ pub enum RunnerError {
         "Failed calculating gas usage, it is likely a call for `gas::withdraw_gas` is missing."
     )]
     FailedGasCalculation,
-    #[error("Function with suffix `{suffix}` to run not found.")]
-    MissingFunction { suffix: String },
+    #[error(
+        "Function with suffix `{suffix}` to run not found.{}",
+        format_function_suggestions(suggestions)
+    )]
+    MissingFunction { suffix: String, suggestions: Vec<String> },
+    #[error("Contract with class hash `{class_hash}` not found.")]
+    MissingContract { class_hash: Felt252 },
+    #[error("Entry point with selector `{selector}` not found in contract.")]
+    MissingEntryPoint { selector: Felt252 },
+    #[error("No contract is deployed at address `{contract_address}`.")]
+    ContractNotDeployed { contract_address: Felt252 },
+    #[error("A contract is already deployed at address `{contract_address}`.")]
+    ContractAlreadyDeployed { contract_address: Felt252 },
+    #[error("Contract has no constructor, but received constructor calldata.")]
+    UnexpectedConstructorCalldata,
     #[error("Function param {param_index} only partially contains argument {arg_index}.")]
     ArgumentUnaligned { param_index: usize, arg_index: usize },
+    #[error(
+        "Function param {param_index} of type `{ty}` can't be passed as an argument, as it \
+         contains `{inner_ty}` - {suggestion}."
+    )]
+    UnsupportedParamType {
+        param_index: usize,
+        ty: String,
+        inner_ty: String,
+        suggestion: &'static str,
+    },
     #[error("Function expects arguments of size {expected} and received {actual} instead.")]
     ArgumentsSizeMismatch { expected: usize, actual: usize },
+    #[error(
+        "Maximal call depth of {max_depth} exceeded. Call chain: {}.",
+        format_call_chain(call_chain)
+    )]
+    StackOverflow { max_depth: usize, call_chain: Vec<String> },
+    #[error("Maximal number of {max_steps} steps exceeded.")]
+    MaxStepsExceeded { max_steps: usize },
+    #[error("{0}")]
+    InnerCallPanicked(Box<InnerCallPanic>),
+    #[error(
+        "Sierra version {version} is not supported, the runner supports versions {oldest} to \
+         {newest}. Compile the program with a compiler of a supported Sierra version."
+    )]
+    UnsupportedSierraVersion { version: VersionId, oldest: VersionId, newest: VersionId },
+    #[error("Invalid Sierra program: {0}")]
+    InvalidSierraProgram(#[from] Felt252SerdeError),
+    #[error("Entry point {function_idx} of contract class refers to a missing function.")]
+    MissingEntryPointFunction { function_idx: usize },
+    #[error("Replay mismatch: {0}.")]
+    ReplayMismatch(ReplayMismatch),
     #[error(transparent)]
     ProgramRegistryError(#[from] Box<ProgramRegistryError>),
     #[error(transparent)]
@@ -78,15 +183,222 @@ pub enum RunnerError {
     #[error(transparent)]
     CairoRunError(#[from] Box<CairoRunError>),
 }
+impl RunnerError {
+    /// Returns the stage of the run pipeline the error occurred in, for hosts embedding the
+    /// runner to map it to a user-facing category.
+    pub fn kind(&self) -> RunnerErrorKind {
+        match self {
+            RunnerError::MissingFunction { .. }
+            | RunnerError::MissingContract { .. }
+            | RunnerError::MissingEntryPoint { .. }
+            | RunnerError::ContractNotDeployed { .. }
+            | RunnerError::ContractAlreadyDeployed { .. }
+            | RunnerError::UnexpectedConstructorCalldata
+            | RunnerError::ArgumentUnaligned { .. }
+            | RunnerError::ArgumentsSizeMismatch { .. }
+            | RunnerError::UnsupportedParamType { .. }
+            | RunnerError::UnsupportedSierraVersion { .. }
+            | RunnerError::InvalidSierraProgram(_)
+            | RunnerError::MissingEntryPointFunction { .. } => RunnerErrorKind::Setup,
+            RunnerError::ProgramRegistryError(_)
+            | RunnerError::SierraCompilationError(_)
+            | RunnerError::ApChangeError(_) => RunnerErrorKind::Compilation,
+            RunnerError::NotEnoughGasToCall
+            | RunnerError::GasBuiltinRequired
+            | RunnerError::FailedGasCalculation => RunnerErrorKind::Gas,
+            RunnerError::CairoRunError(error) => match error.as_ref() {
+                CairoRunError::VirtualMachine(VirtualMachineError::Hint(_))
+                | CairoRunError::VmException(VmException {
+                    inner_exc: VirtualMachineError::Hint(_),
+                    ..
+                }) => RunnerErrorKind::Hint,
+                _ => RunnerErrorKind::Vm,
+            },
+            RunnerError::StackOverflow { .. }
+            | RunnerError::InnerCallPanicked(_)
+            | RunnerError::ReplayMismatch(_) => RunnerErrorKind::Vm,
+            RunnerError::MaxStepsExceeded { .. } => RunnerErrorKind::Timeout,
+        }
+    }
+}
+
+/// The stage of the run pipeline a [RunnerError] occurred in.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RunnerErrorKind {
+    /// The run was set up wrongly, e.g. a missing function or mismatching arguments.
+    Setup,
+    /// The Sierra program failed compiling to CASM.
+    Compilation,
+    /// The gas usage of the program could not be computed, or the run can't be given enough gas.
+    Gas,
+    /// The VM failed executing the program, other than by a failing hint.
+    Vm,
+    /// A hint failed during the run, e.g. a failing syscall or cheatcode.
+    Hint,
+    /// The run was aborted for exceeding its maximal number of steps.
+    Timeout,
+}
+impl std::fmt::Display for RunnerErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunnerErrorKind::Setup => write!(f, "setup"),
+            RunnerErrorKind::Compilation => write!(f, "compilation"),
+            RunnerErrorKind::Gas => write!(f, "gas"),
+            RunnerErrorKind::Vm => write!(f, "vm"),
+            RunnerErrorKind::Hint => write!(f, "hint"),
+            RunnerErrorKind::Timeout => write!(f, "timeout"),
+        }
+    }
+}
+
+/// The result of executing a transaction of an account contract.
+pub struct AccountTransactionResult {
+    /// The hash of the transaction, which the account is expected to validate the signature of.
+    pub transaction_hash: Felt252,
+    /// The result of the `__validate__` call.
+    pub validate: RunResultStarknet,
+    /// The result of the `__execute__` call, if the transaction was validated.
+    pub execute: Option<RunResultStarknet>,
+}
+impl AccountTransactionResult {
+    /// Returns the starknet state resulting from the transaction.
+    pub fn into_starknet_state(self) -> StarknetState {
+        self.execute.unwrap_or(self.validate).starknet_state
+    }
+}
 
 /// The full result of a run with Starknet state.
 pub struct RunResultStarknet {
     pub gas_counter: Option<Felt252>,
     pub memory: Vec<Option<Felt252>>,
     pub value: RunResultValue,
+    /// The values of a successful run, with the contents of the arrays they point to in place of
+    /// their pointers, which depend on the memory layout of the run rather than on its behavior.
+    /// `None` if the run panicked, or if the values could not be followed in the memory.
+    pub value_contents: Option<Vec<Arg>>,
     pub starknet_state: StarknetState,
     /// The profiling info of the run, if requested.
     pub profiling_info: Option<ProfilingInfo>,
+    /// The gas checkpoints reached during the run, in execution order, if requested.
+    pub gas_checkpoints: Option<Vec<GasCheckpoint>>,
+    /// The branch coverage of the run, including the calls to contracts it made, if requested.
+    pub branch_coverage: Option<BranchCoverage>,
+    /// The number of times each Sierra statement was executed by the run, including the calls to
+    /// contracts it made, if branch coverage was requested.
+    pub statement_execution_counts: Option<Vec<usize>>,
+    /// The hints executed during the run, if requested.
+    pub hint_executions: Option<Vec<HintExecution>>,
+    /// The output printed by the run, by channel, if captured.
+    pub printed_output: Option<PrintedOutput>,
+    /// The calls to contracts made by the run, in order, if traced. For runs of contract entry
+    /// points, this is the entry call, with the calls it made as its inner calls.
+    pub call_traces: Option<Vec<CallTrace>>,
+    /// The events emitted by the run and by the calls to contracts it made, in order, decoded
+    /// where the ABIs of the emitting contracts are known. Events of reverted calls are excluded.
+    pub events: Vec<EmittedEvent>,
+    /// The backtrace of the panic of the run, if it panicked - the Sierra statements of the calls
+    /// leading to the panic, innermost first. The first of them in user code locates the source of
+    /// the panic.
+    pub panic_backtrace: Option<Vec<StatementIdx>>,
+    /// The state of the VM at the point the run panicked, if it panicked and it was requested.
+    pub panic_snapshot: Option<PanicSnapshot>,
+    /// The resources used by the run, excluding calls to other contracts.
+    pub used_resources: ExecutionResources,
+    /// The metadata of the run.
+    pub metadata: RunMetadata,
+}
+impl RunResultStarknet {
+    /// Returns the gas used by the run, if gas usage is checked.
+    pub fn gas_used(&self) -> Option<usize> {
+        let gas_counter = self.gas_counter.as_ref()?.to_usize()?;
+        Some(self.metadata.available_gas? - gas_counter)
+    }
+
+    /// Makes the given entry call of the run the root of its call traces, with the calls made by
+    /// the run as its inner calls.
+    fn trace_entry_call(
+        &mut self,
+        kind: CallKind,
+        (contract_address, caller_address): (Felt252, Felt252),
+        (class_hash, selector): (&Felt252, &Felt252),
+        calldata: Vec<Felt252>,
+    ) {
+        let inner_calls = self.call_traces.take().unwrap_or_default();
+        self.call_traces = Some(vec![CallTrace {
+            kind,
+            caller_address,
+            contract_address,
+            class_hash: class_hash.clone(),
+            selector: selector.clone(),
+            calldata,
+            result: self.value.clone(),
+            used_resources: self.used_resources.clone(),
+            inner_calls,
+        }]);
+    }
+}
+
+/// Metadata describing how a run was made, for making its results self-describing. The number of
+/// steps of the run is part of its used resources.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct RunMetadata {
+    /// The version of the compiler and runner.
+    pub compiler_version: VersionId,
+    /// The version of Sierra supported by the runner.
+    pub sierra_version: VersionId,
+    /// The gas available for the run, if gas usage is checked.
+    pub available_gas: Option<usize>,
+    /// Whether the profiler was run.
+    pub run_profiler: bool,
+    /// Whether the gas accounting included the costs of the builtins.
+    pub builtin_costs: bool,
+    /// The time it took to compile the Sierra program to CASM.
+    pub casm_compilation_time: Duration,
+    /// The time the run took.
+    pub run_time: Duration,
+    /// The peak memory used by the process at the end of the run, in bytes, if it can be measured
+    /// on the platform. The memory of the process is shared by the runs made in it.
+    pub peak_memory: Option<u64>,
+    /// The growth of the peak memory used by the process during the run, in bytes, if it can be
+    /// measured on the platform. Includes the memory used by runs made in parallel, if any.
+    pub peak_memory_growth: Option<u64>,
+}
+impl RunMetadata {
+    /// Creates the metadata of a run that took `run_time`, starting with the process at a peak
+    /// memory of `initial_peak_memory`.
+    fn new(
+        runner: &SierraCasmRunner,
+        available_gas: Option<usize>,
+        run_time: Duration,
+        initial_peak_memory: Option<u64>,
+    ) -> Self {
+        let peak_memory = peak_memory();
+        Self {
+            compiler_version: current_compiler_version_id(),
+            sierra_version: current_sierra_version_id(),
+            available_gas,
+            run_profiler: runner.run_profiler,
+            builtin_costs: runner.builtin_costs,
+            casm_compilation_time: runner.casm_compilation_time,
+            run_time,
+            peak_memory,
+            peak_memory_growth: peak_memory.zip(initial_peak_memory).map(
+                |(peak_memory, initial_peak_memory)| {
+                    peak_memory.saturating_sub(initial_peak_memory)
+                },
+            ),
+        }
+    }
+}
+
+/// Returns the peak resident set size of the process in bytes, as reported by the `VmHWM` entry of
+/// `/proc/self/status`, if available.
+fn peak_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kilobytes = status.lines().find_map(|line| {
+        line.strip_prefix("VmHWM:")?.trim().strip_suffix("kB")?.trim().parse::<u64>().ok()
+    })?;
+    Some(kilobytes * 1024)
 }
 
 /// The full result of a run.
@@ -97,6 +409,38 @@ pub struct RunResult {
     pub value: RunResultValue,
     /// The profiling info of the run, if requested.
     pub profiling_info: Option<ProfilingInfo>,
+    /// The gas checkpoints reached during the run, in execution order, if requested.
+    pub gas_checkpoints: Option<Vec<GasCheckpoint>>,
+    /// The number of times each Sierra statement was executed, if branch coverage was requested.
+    /// See `collect_statement_execution_counts`.
+    pub statement_execution_counts: Option<Vec<usize>>,
+    /// The backtrace of the panic of the run, if it panicked. See `collect_panic_backtrace`.
+    pub panic_backtrace: Option<Vec<StatementIdx>>,
+    /// The state of the VM at the point the run panicked. See `collect_panic_snapshot`.
+    pub panic_snapshot: Option<PanicSnapshot>,
+    /// The resources used by the run.
+    pub used_resources: ExecutionResources,
+}
+
+/// The state of the VM at the point a run panicked, for post-mortem debugging. The addresses are
+/// in the relocated memory of the run.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PanicSnapshot {
+    /// The Sierra statement at which the panic was raised.
+    pub statement_idx: StatementIdx,
+    /// The function in which the panic was raised.
+    pub function: FunctionId,
+    /// The value of the `pc` register at the panic point.
+    pub pc: usize,
+    /// The value of the `ap` register at the panic point.
+    pub ap: usize,
+    /// The value of the `fp` register at the panic point.
+    pub fp: usize,
+    /// The frame of the panicking function - the memory from `fp` to `ap`, holding its locals and
+    /// temporary values.
+    pub frame: Vec<Option<Felt252>>,
+    /// The memory up to `ap` - the program and the stack at the panic point.
+    pub memory: Vec<Option<Felt252>>,
 }
 
 /// The ran function return value.
@@ -107,6 +451,124 @@ pub enum RunResultValue {
     /// Run panicked, returning the carried error data.
     Panic(Vec<Felt252>),
 }
+impl RunResultValue {
+    /// Returns whether the run panicked due to running out of gas.
+    pub fn is_out_of_gas(&self) -> bool {
+        match self {
+            RunResultValue::Success(_) => false,
+            RunResultValue::Panic(values) => {
+                values.first() == Some(&Felt252::from_bytes_be(b"Out of gas"))
+            }
+        }
+    }
+
+    /// Returns the category of the panic of the run, if it panicked, according to the message the
+    /// panic data starts with.
+    pub fn panic_category(&self) -> Option<PanicCategory> {
+        let RunResultValue::Panic(values) = self else { return None };
+        let message = format_next_item(&mut values.iter().cloned())
+            .filter(|item| item.is_string())
+            .map(|item| item.get())
+            .or_else(|| values.first().and_then(as_cairo_short_string))
+            .unwrap_or_default();
+        Some(if message == "Out of gas" {
+            PanicCategory::OutOfGas
+        } else if message.ends_with(" Overflow") || message.ends_with(" Underflow") {
+            PanicCategory::Overflow
+        } else if message.ends_with("::unwrap failed.") || message.ends_with("::unwrap_err failed.")
+        {
+            PanicCategory::Unwrap
+        } else if message.starts_with("assertion ") || message == "assert(false)" {
+            PanicCategory::Assertion
+        } else {
+            PanicCategory::Explicit
+        })
+    }
+}
+
+/// The category of a panic of a run, for aggregating failures by their type.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PanicCategory {
+    /// A failed `assert!`, `assert_eq!` or similar assertion.
+    Assertion,
+    /// The run ran out of gas.
+    OutOfGas,
+    /// An arithmetic operation overflowed or underflowed.
+    Overflow,
+    /// `unwrap` was called on a `None` option or an `Err` result.
+    Unwrap,
+    /// Any other panic, e.g. by `panic!` or `panic_with_felt252`.
+    Explicit,
+}
+impl std::fmt::Display for PanicCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PanicCategory::Assertion => write!(f, "assertion"),
+            PanicCategory::OutOfGas => write!(f, "out-of-gas"),
+            PanicCategory::Overflow => write!(f, "overflow"),
+            PanicCategory::Unwrap => write!(f, "unwrap"),
+            PanicCategory::Explicit => write!(f, "explicit"),
+        }
+    }
+}
+
+/// A warning about the gas provided to a run not matching the gas usage of the program, found
+/// before running it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GasUsageWarning {
+    /// `available_gas` was provided, but the program does not use gas, so it has no effect.
+    UnusedAvailableGas,
+    /// The program withdraws gas, but no `available_gas` was provided, so the run starts with no
+    /// gas and any withdrawal fails.
+    MissingAvailableGas,
+}
+impl std::fmt::Display for GasUsageWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GasUsageWarning::UnusedAvailableGas => {
+                write!(f, "`available_gas` is provided, but the program does not use gas.")
+            }
+            GasUsageWarning::MissingAvailableGas => {
+                write!(f, "The program withdraws gas, but `available_gas` is not provided.")
+            }
+        }
+    }
+}
+
+/// Formats the suggestions of a `RunnerError::MissingFunction` error.
+fn format_function_suggestions(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        return String::new();
+    }
+    format!(" Did you mean {}?", suggestions.iter().map(|name| format!("`{name}`")).join(", "))
+}
+
+/// Formats a call chain of a stack overflow, collapsing consecutive calls of the same function
+/// (i.e. recursion).
+fn format_call_chain(call_chain: &[String]) -> String {
+    call_chain
+        .iter()
+        .dedup_with_count()
+        .map(|(count, name)| if count == 1 { name.clone() } else { format!("{name} (x{count})") })
+        .join(" -> ")
+}
+
+/// Returns the Levenshtein distance between the two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect_vec();
+    // The distances between the current prefix of `a` and each of the prefixes of `b`.
+    let mut distances = (0..=b.len()).collect_vec();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut prev_diagonal = distances[0];
+        distances[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = prev_diagonal + usize::from(a_char != *b_char);
+            prev_diagonal = distances[j + 1];
+            distances[j + 1] = substitution.min(distances[j] + 1).min(distances[j + 1] + 1);
+        }
+    }
+    distances[b.len()]
+}
 
 // Approximated costs token types.
 pub fn token_gas_cost(token_type: CostTokenType) -> usize {
@@ -119,11 +581,43 @@ pub fn token_gas_cost(token_type: CostTokenType) -> usize {
     }
 }
 
+/// Returns whether values of the given type are implicits of the run function, i.e. builtins, gas
+/// or system, which are filled by the runner rather than given as arguments or returned as values.
+fn is_implicit_type(ty: &GenericTypeId) -> bool {
+    [
+        PedersenType::ID,
+        RangeCheckType::ID,
+        BitwiseType::ID,
+        EcOpType::ID,
+        PoseidonType::ID,
+        SegmentArenaType::ID,
+        GasBuiltinType::ID,
+        SystemType::ID,
+    ]
+    .contains(ty)
+}
+
+/// Returns the total size of the given arguments in memory.
+fn args_size(args: &[Arg]) -> usize {
+    args.iter().map(Arg::size).sum()
+}
+
 /// An argument to a sierra function run,
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Arg {
     Value(Felt252),
-    Array(Vec<Felt252>),
+    /// An array, whose elements are laid out consecutively in a new segment. Nested arrays are
+    /// laid out as their start and end pointers.
+    Array(Vec<Arg>),
+}
+impl Arg {
+    /// Returns the size of the argument in memory.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Value(_) => 1,
+            Self::Array(_) => 2,
+        }
+    }
 }
 impl From<Felt252> for Arg {
     fn from(value: Felt252) -> Self {
@@ -131,6 +625,32 @@ impl From<Felt252> for Arg {
     }
 }
 
+/// A call of a function in a sequence of calls. See `run_functions_in_sequence`.
+#[derive(Debug)]
+pub struct SequentialCall {
+    /// The suffix of the name of the called function, as given to `find_function`.
+    pub function: String,
+    /// The arguments of the call.
+    pub args: Vec<Arg>,
+    /// The gas available to the call, if gas usage is checked.
+    pub available_gas: Option<usize>,
+}
+
+/// Creates a vm with a trace, calling the given hooks in order before each step.
+fn create_vm(pre_step_hooks: Vec<PreStepHook>) -> VirtualMachine {
+    if pre_step_hooks.is_empty() {
+        return VirtualMachine::new(true);
+    }
+    let pre_step_hook =
+        move |vm: &mut VirtualMachine, _: &mut dyn HintProcessor, _: &mut _, _: &[_], _: &_| {
+            pre_step_hooks.iter().try_for_each(|hook| hook(vm))
+        };
+    VirtualMachineBuilder::default()
+        .trace(Some(vec![]))
+        .hooks(Hooks::new(None, Some(Arc::new(pre_step_hook)), None))
+        .build()
+}
+
 /// Builds hints_dict required in cairo_vm::types::program::Program from instructions.
 pub fn build_hints_dict<'b>(
     instructions: impl Iterator<Item = &'b Instruction>,
@@ -155,6 +675,67 @@ pub fn build_hints_dict<'b>(
     (hints_dict, string_to_hint)
 }
 
+/// The call stack of user functions in a run with a limited call depth.
+#[derive(Default)]
+struct CallStack {
+    /// The names of the currently running functions, from the outermost.
+    call_chain: Vec<String>,
+    /// Whether the run was aborted for exceeding the maximal call depth.
+    overflowed: bool,
+}
+
+/// The error aborting the VM in a run exceeding the maximal call depth.
+#[derive(Debug, Error)]
+#[error("Maximal call depth of {max_depth} exceeded.")]
+struct CallDepthExceeded {
+    max_depth: usize,
+}
+
+/// The error aborting the VM in a run exceeding its maximal number of steps.
+#[derive(Debug, Error)]
+#[error("Maximal number of {max_steps} steps exceeded.")]
+struct StepsLimitExceeded {
+    max_steps: usize,
+}
+
+/// The step budget of a run with a limited number of steps, counting the steps of the run and of
+/// the runs of the contracts it calls.
+struct StepsBudget {
+    max_steps: usize,
+    /// The number of steps run so far.
+    n_steps: AtomicUsize,
+    /// Whether the run was aborted for exceeding the budget.
+    exceeded: AtomicBool,
+}
+impl StepsBudget {
+    fn new(max_steps: usize) -> Self {
+        Self { max_steps, n_steps: AtomicUsize::new(0), exceeded: AtomicBool::new(false) }
+    }
+}
+
+/// Creates a pre-step hook counting the steps of a run against the given budget, aborting the run
+/// once it is exceeded.
+fn max_steps_hook(steps_budget: Arc<StepsBudget>) -> PreStepHook {
+    Box::new(move |_: &mut VirtualMachine| {
+        if steps_budget.n_steps.fetch_add(1, Ordering::Relaxed) >= steps_budget.max_steps {
+            steps_budget.exceeded.store(true, Ordering::Relaxed);
+            let max_steps = steps_budget.max_steps;
+            return Err(VirtualMachineError::Other(StepsLimitExceeded { max_steps }.into()));
+        }
+        Ok(())
+    })
+}
+
+/// The limits of a run, shared with the runs of the contracts it calls, so that the steps and the
+/// call depth of the inner calls count towards the limits of the calling run.
+#[derive(Clone, Default)]
+pub struct RunLimits {
+    /// The call stack of user functions, if the call depth is limited.
+    call_stack: Option<Arc<Mutex<CallStack>>>,
+    /// The step budget, if the number of steps is limited.
+    steps_budget: Option<Arc<StepsBudget>>,
+}
+
 /// Runner enabling running a Sierra program on the vm.
 pub struct SierraCasmRunner {
     /// The sierra program.
@@ -172,6 +753,39 @@ pub struct SierraCasmRunner {
     starknet_contracts_info: OrderedHashMap<Felt252, ContractInfo>,
     /// Whether to run the profiler when running using this runner.
     run_profiler: bool,
+    /// Whether to log the hints executed when running using this runner.
+    log_hint_executions: bool,
+    /// Whether to log the gas checkpoints reached when running using this runner.
+    log_gas_checkpoints: bool,
+    /// Whether to collect the branch coverage of runs of this runner.
+    collect_branch_coverage: bool,
+    /// The behavior of the computationally heavy syscalls when running using this runner.
+    heavy_syscalls: HeavySyscallsConfig,
+    /// Whether to capture the output printed when running using this runner, instead of writing
+    /// it to the standard output.
+    capture_prints: bool,
+    /// Whether to capture the state of the VM at the panic point of panicking runs of this runner.
+    capture_panic_snapshot: bool,
+    /// Whether to enable the cheatcodes signing with test keys in runs of this runner.
+    signing_cheatcodes: bool,
+    /// Whether to trace the calls to contracts made in runs of this runner.
+    trace_calls: bool,
+    /// The behavior of runs of this runner when a contract called during the run panics.
+    inner_panic_policy: InnerPanicPolicy,
+    /// The maximal depth of the call stack of user functions when running using this runner, if
+    /// limited.
+    max_call_depth: Option<usize>,
+    /// The maximal number of steps of runs of this runner, if limited.
+    max_steps: Option<usize>,
+    /// Whether the gas accounting of runs includes the costs of the builtins.
+    builtin_costs: bool,
+    /// The time it took to compile the Sierra program to CASM.
+    casm_compilation_time: Duration,
+    /// The configuration the metadata of the program was computed with, if gas usage is checked.
+    metadata_config: Option<MetadataComputationConfig>,
+    /// The statistics of the Sierra optimization passes applied to the program, if it was
+    /// optimized.
+    optimization_statistics: Option<Vec<PassStatistics>>,
 }
 impl SierraCasmRunner {
     pub fn new(
@@ -181,15 +795,17 @@ impl SierraCasmRunner {
         run_profiler: bool,
     ) -> Result<Self, RunnerError> {
         let gas_usage_check = metadata_config.is_some();
-        let metadata = create_metadata(&sierra_program, metadata_config)?;
+        let metadata = create_metadata(&sierra_program, metadata_config.clone())?;
         let sierra_program_registry =
             ProgramRegistry::<CoreType, CoreLibfunc>::new(&sierra_program)?;
         let type_sizes = get_type_size_map(&sierra_program, &sierra_program_registry).unwrap();
+        let casm_compilation_start = Instant::now();
         let casm_program = cairo_lang_sierra_to_casm::compiler::compile(
             &sierra_program,
             &metadata,
             gas_usage_check,
         )?;
+        let casm_compilation_time = casm_compilation_start.elapsed();
 
         // Find all contracts.
         Ok(Self {
@@ -200,9 +816,213 @@ impl SierraCasmRunner {
             casm_program,
             starknet_contracts_info,
             run_profiler,
+            log_hint_executions: false,
+            log_gas_checkpoints: false,
+            collect_branch_coverage: false,
+            heavy_syscalls: HeavySyscallsConfig::default(),
+            capture_prints: false,
+            capture_panic_snapshot: false,
+            signing_cheatcodes: false,
+            trace_calls: false,
+            inner_panic_policy: InnerPanicPolicy::default(),
+            max_call_depth: None,
+            max_steps: None,
+            builtin_costs: true,
+            casm_compilation_time,
+            metadata_config,
+            optimization_statistics: None,
         })
     }
 
+    /// Creates a runner for the Sierra program of a contract class, after checking that the runner
+    /// can run programs of its Sierra version - see [sierra_version::check_sierra_version]. The
+    /// contract class is available to the runs under its class hash, e.g. for deploying it.
+    pub fn from_contract_class(
+        contract_class: &ContractClass,
+        metadata_config: Option<MetadataComputationConfig>,
+        run_profiler: bool,
+    ) -> Result<Self, RunnerError> {
+        let sierra_program = sierra_version::extract_runnable_program(contract_class)?;
+        let function_id = |entry_point: &ContractEntryPoint| -> Result<FunctionId, RunnerError> {
+            let func = sierra_program.funcs.get(entry_point.function_idx).ok_or(
+                RunnerError::MissingEntryPointFunction { function_idx: entry_point.function_idx },
+            )?;
+            Ok(func.id.clone())
+        };
+        let entry_points = |entry_points: &[ContractEntryPoint]| {
+            entry_points
+                .iter()
+                .map(|entry_point| {
+                    Ok((Felt252::from(entry_point.selector.clone()), function_id(entry_point)?))
+                })
+                .collect::<Result<OrderedHashMap<_, _>, RunnerError>>()
+        };
+        let entry_points_by_type = &contract_class.entry_points_by_type;
+        let contract_info = ContractInfo {
+            constructor: entry_points_by_type.constructor.first().map(function_id).transpose()?,
+            externals: entry_points(&entry_points_by_type.external)?,
+            l1_handlers: entry_points(&entry_points_by_type.l1_handler)?,
+            abi: contract_class.abi.clone(),
+        };
+        Self::new(
+            sierra_program,
+            metadata_config,
+            [(contract_class.class_hash(), contract_info)].into_iter().collect(),
+            run_profiler,
+        )
+    }
+
+    /// Applies the Sierra optimization pipeline to the program of this runner before compiling it
+    /// to CASM, keeping the functions reachable from `roots` and from the entry points of the
+    /// contracts. Other functions can no longer be run.
+    pub fn with_sierra_optimizations(self, roots: &[FunctionId]) -> Result<Self, RunnerError> {
+        let roots = self.with_contract_entry_points(roots);
+        let mut sierra_program = self.sierra_program.clone();
+        let optimization_statistics = OptimizationPipeline::all(roots).apply(&mut sierra_program);
+        // Costs can only be set for the functions kept in the program.
+        let metadata_config = self.metadata_config.clone().map(|mut config| {
+            config
+                .function_set_costs
+                .retain(|id, _| sierra_program.funcs.iter().any(|func| func.id == *id));
+            config
+        });
+        let optimized = Self::new(
+            sierra_program,
+            metadata_config,
+            self.starknet_contracts_info.clone(),
+            self.run_profiler,
+        )?;
+        Ok(Self {
+            sierra_program: optimized.sierra_program,
+            metadata: optimized.metadata,
+            sierra_program_registry: optimized.sierra_program_registry,
+            type_sizes: optimized.type_sizes,
+            casm_program: optimized.casm_program,
+            casm_compilation_time: optimized.casm_compilation_time,
+            metadata_config: optimized.metadata_config,
+            optimization_statistics: Some(optimization_statistics),
+            ..self
+        })
+    }
+
+    /// Returns the given functions along with the entry points of the contracts of the program.
+    fn with_contract_entry_points(&self, roots: &[FunctionId]) -> Vec<FunctionId> {
+        chain!(
+            roots.iter().cloned(),
+            self.starknet_contracts_info.values().flat_map(|contract_info| {
+                chain!(
+                    contract_info.constructor.iter().cloned(),
+                    contract_info.externals.values().cloned(),
+                    contract_info.l1_handlers.values().cloned()
+                )
+            })
+        )
+        .collect()
+    }
+
+    /// Returns the statistics of the Sierra optimization passes applied to the program of this
+    /// runner, if it was optimized by [Self::with_sierra_optimizations].
+    pub fn optimization_statistics(&self) -> Option<&[PassStatistics]> {
+        self.optimization_statistics.as_deref()
+    }
+
+    /// Sets whether runs of this runner should return a log of the executed hints.
+    pub fn with_hint_executions_log(mut self, log_hint_executions: bool) -> Self {
+        self.log_hint_executions = log_hint_executions;
+        self
+    }
+
+    /// Sets whether runs of this runner should return the gas checkpoints (withdrawals and
+    /// redeposits of gas) reached.
+    pub fn with_gas_checkpoints_log(mut self, log_gas_checkpoints: bool) -> Self {
+        self.log_gas_checkpoints = log_gas_checkpoints;
+        self
+    }
+
+    /// Sets whether runs of this runner should return their branch coverage - the number of times
+    /// each branch of each branching Sierra statement was taken.
+    pub fn with_branch_coverage(mut self, collect_branch_coverage: bool) -> Self {
+        self.collect_branch_coverage = collect_branch_coverage;
+        self
+    }
+
+    /// Sets whether runs of this runner should return the output they print, split by the channels
+    /// it is printed into, instead of writing it to the standard output.
+    pub fn with_print_capture(mut self, capture_prints: bool) -> Self {
+        self.capture_prints = capture_prints;
+        self
+    }
+
+    /// Sets whether panicking runs of this runner should return the state of the VM at the point
+    /// of the panic, in addition to the panic data.
+    pub fn with_panic_snapshot(mut self, capture_panic_snapshot: bool) -> Self {
+        self.capture_panic_snapshot = capture_panic_snapshot;
+        self
+    }
+
+    /// Sets whether runs of this runner can use the cheatcodes computing public keys and signatures
+    /// of test keys. See [SigningCheatcodes].
+    pub fn with_signing_cheatcodes(mut self, signing_cheatcodes: bool) -> Self {
+        self.signing_cheatcodes = signing_cheatcodes;
+        self
+    }
+
+    /// Sets whether runs of this runner should return the tree of the calls to contracts they
+    /// made, e.g. for inspecting reentrancy patterns and nested failures. The root of the tree of
+    /// a run of a contract entry point, e.g. by `call_contract` or `deploy_contract`, is the entry
+    /// call itself.
+    pub fn with_call_traces(mut self, trace_calls: bool) -> Self {
+        self.trace_calls = trace_calls;
+        self
+    }
+
+    /// Sets the behavior of runs of this runner when a contract called during the run panics.
+    pub fn with_inner_panic_policy(mut self, inner_panic_policy: InnerPanicPolicy) -> Self {
+        self.inner_panic_policy = inner_panic_policy;
+        self
+    }
+
+    /// Sets the behavior of the computationally heavy syscalls (keccak, secp256k1 and secp256r1)
+    /// in runs of this runner.
+    pub fn with_heavy_syscalls_config(mut self, heavy_syscalls: HeavySyscallsConfig) -> Self {
+        self.heavy_syscalls = heavy_syscalls;
+        self
+    }
+
+    /// Sets the maximal depth of the call stack of user functions in runs of this runner. Runs
+    /// exceeding it are aborted with [RunnerError::StackOverflow], instead of running until they
+    /// are out of gas or memory.
+    pub fn with_max_call_depth(mut self, max_call_depth: Option<usize>) -> Self {
+        self.max_call_depth = max_call_depth;
+        self
+    }
+
+    /// Sets the maximal number of steps of runs of this runner. Runs exceeding it are aborted with
+    /// [RunnerError::MaxStepsExceeded]. See [Self::run_function_with_max_steps] for limiting a
+    /// single run.
+    pub fn with_max_steps(mut self, max_steps: Option<usize>) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Sets whether the gas accounting of runs of this runner includes the costs of the builtins
+    /// (pedersen, poseidon, bitwise and EC op), as in the network cost model. Without them, the
+    /// gas used by runs is the pure Sierra gas of the executed code.
+    pub fn with_builtin_costs(mut self, builtin_costs: bool) -> Self {
+        self.builtin_costs = builtin_costs;
+        self
+    }
+
+    /// Checks whether the gas provided to a run of the program matches the gas usage of the
+    /// program, returning a warning if it does not.
+    pub fn gas_usage_warning(&self, available_gas: Option<usize>) -> Option<GasUsageWarning> {
+        match (available_gas, self.sierra_program.requires_gas_counter()) {
+            (Some(_), false) => Some(GasUsageWarning::UnusedAvailableGas),
+            (None, true) => Some(GasUsageWarning::MissingAvailableGas),
+            _ => None,
+        }
+    }
+
     /// Runs the vm starting from a function in the context of a given starknet state.
     pub fn run_function_with_starknet_context(
         &self,
@@ -211,6 +1031,122 @@ impl SierraCasmRunner {
         available_gas: Option<usize>,
         starknet_state: StarknetState,
     ) -> Result<RunResultStarknet, RunnerError> {
+        self.run_function_with_starknet_context_ex(
+            func,
+            args,
+            available_gas,
+            starknet_state,
+            None,
+            vec![],
+            Default::default(),
+        )
+    }
+
+    /// Same as `run_function_with_starknet_context`, aborting the run with
+    /// [RunnerError::MaxStepsExceeded] once it runs more than `max_steps` steps, if given, instead
+    /// of the maximal number of steps of the runner.
+    pub fn run_function_with_max_steps(
+        &self,
+        func: &Function,
+        args: &[Arg],
+        available_gas: Option<usize>,
+        starknet_state: StarknetState,
+        max_steps: Option<usize>,
+    ) -> Result<RunResultStarknet, RunnerError> {
+        self.run_function_with_starknet_context_ex(
+            func,
+            args,
+            available_gas,
+            starknet_state,
+            None,
+            vec![],
+            RunLimits {
+                steps_budget: max_steps.map(|max_steps| Arc::new(StepsBudget::new(max_steps))),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Runs a function of a contract called by another run, within the limits of the calling run.
+    pub(crate) fn run_inner_call(
+        &self,
+        func: &Function,
+        args: &[Arg],
+        available_gas: Option<usize>,
+        starknet_state: StarknetState,
+        limits: RunLimits,
+    ) -> Result<RunResultStarknet, RunnerError> {
+        self.run_function_with_starknet_context_ex(
+            func,
+            args,
+            available_gas,
+            starknet_state,
+            None,
+            vec![],
+            limits,
+        )
+    }
+
+    /// Same as `run_function_with_starknet_context`, with additional handlers of hints, tried in
+    /// order before the hints are executed by the runner's hint processor.
+    pub fn run_function_with_hint_handlers<'a>(
+        &'a self,
+        func: &Function,
+        args: &[Arg],
+        available_gas: Option<usize>,
+        starknet_state: StarknetState,
+        hint_handlers: Vec<Box<dyn HintHandler + 'a>>,
+    ) -> Result<RunResultStarknet, RunnerError> {
+        self.run_function_with_starknet_context_ex(
+            func,
+            args,
+            available_gas,
+            starknet_state,
+            None,
+            hint_handlers,
+            Default::default(),
+        )
+    }
+
+    /// Same as `run_function_with_starknet_context`, additionally reporting the events of the run
+    /// to the given observer.
+    pub fn run_function_with_observer(
+        &self,
+        func: &Function,
+        args: &[Arg],
+        available_gas: Option<usize>,
+        starknet_state: StarknetState,
+        observer: &mut dyn RunObserver,
+    ) -> Result<RunResultStarknet, RunnerError> {
+        self.run_function_with_starknet_context_ex(
+            func,
+            args,
+            available_gas,
+            starknet_state,
+            Some(observer),
+            vec![],
+            Default::default(),
+        )
+    }
+
+    /// Runs the vm starting from a function in the context of a given starknet state, reporting
+    /// the events of the run to `observer` if given, and executing hints with `hint_handlers`
+    /// before the runner's hint processor. The run is limited by `limits` where given, and
+    /// otherwise by the limits of the runner.
+    #[allow(clippy::too_many_arguments)]
+    fn run_function_with_starknet_context_ex<'a>(
+        &'a self,
+        func: &Function,
+        args: &[Arg],
+        available_gas: Option<usize>,
+        starknet_state: StarknetState,
+        observer: Option<&mut dyn RunObserver>,
+        mut hint_handlers: Vec<Box<dyn HintHandler + 'a>>,
+        limits: RunLimits,
+    ) -> Result<RunResultStarknet, RunnerError> {
+        if self.signing_cheatcodes {
+            hint_handlers.push(Box::new(SigningCheatcodes));
+        }
         let initial_gas = self.get_initial_available_gas(func, available_gas)?;
         let (entry_code, builtins) = self.create_entry_code(func, args, initial_gas)?;
         let footer = Self::create_code_footer();
@@ -218,28 +1154,516 @@ impl SierraCasmRunner {
             build_hints_dict(chain!(entry_code.iter(), self.casm_program.instructions.iter()));
         let assembled_program = self.casm_program.clone().assemble_ex(&entry_code, &footer);
 
+        let entry_code_size = entry_code.iter().map(|instr| instr.body.op_size()).sum();
+        let mut pre_step_hooks = vec![];
+        // Runs of called contracts continue the call stack of the calling run.
+        let call_stack = self.max_call_depth.map(|max_depth| {
+            let call_stack = limits.call_stack.unwrap_or_default();
+            pre_step_hooks.push(self.call_depth_limit_hook(
+                entry_code_size,
+                max_depth,
+                call_stack.clone(),
+            ));
+            call_stack
+        });
+        let steps_budget = limits
+            .steps_budget
+            .or_else(|| self.max_steps.map(|max_steps| Arc::new(StepsBudget::new(max_steps))));
+        if let Some(steps_budget) = &steps_budget {
+            pre_step_hooks.push(max_steps_hook(steps_budget.clone()));
+        }
+        let observation = observer.map(|observer| {
+            let (functions_by_pc, return_pcs) = self.function_entry_and_return_pcs(entry_code_size);
+            let (observation, hook) = RunObservation::new(observer, functions_by_pc, return_pcs);
+            pre_step_hooks.push(hook);
+            observation
+        });
+
         let mut hint_processor = CairoHintProcessor {
             runner: Some(self),
             starknet_state,
             string_to_hint,
             run_resources: RunResources::default(),
+            hint_executions_log: self.log_hint_executions.then(HintExecutionsLog::default),
+            observation,
+            heavy_syscalls: self.heavy_syscalls,
+            hint_handlers,
+            printed_output: self.capture_prints.then(PrintedOutput::default),
+            call_traces: self.trace_calls.then(Vec::new),
+            inner_panic_policy: self.inner_panic_policy,
+            inner_call_panic: None,
+            inner_call_error: None,
+            inner_statement_execution_counts: None,
+            run_limits: RunLimits {
+                call_stack: call_stack.clone(),
+                steps_budget: steps_budget.clone(),
+            },
+            event_decoder: self.entry_point_abi(&func.id).map(EventDecoder::new),
+            events: vec![],
         };
-        let RunResult { gas_counter, memory, value, profiling_info } = self.run_function(
+        let initial_peak_memory = peak_memory();
+        let run_start = Instant::now();
+        let mut vm = create_vm(pre_step_hooks);
+        let result = self.run_function_with_vm(
             func,
+            &mut vm,
             &mut hint_processor,
             hints_dict,
             assembled_program.bytecode.iter(),
             builtins,
-        )?;
+        );
+        if let Some(call_stack) = call_stack {
+            let call_stack = call_stack.lock().unwrap();
+            if call_stack.overflowed {
+                return Err(RunnerError::StackOverflow {
+                    max_depth: self.max_call_depth.unwrap(),
+                    call_chain: call_stack.call_chain.clone(),
+                });
+            }
+        }
+        if let Some(steps_budget) = steps_budget {
+            if steps_budget.exceeded.load(Ordering::Relaxed) {
+                return Err(RunnerError::MaxStepsExceeded { max_steps: steps_budget.max_steps });
+            }
+        }
+        if let Some(inner_call_panic) = hint_processor.inner_call_panic.take() {
+            return Err(RunnerError::InnerCallPanicked(Box::new(inner_call_panic)));
+        }
+        if let Some(inner_call_error) = hint_processor.inner_call_error.take() {
+            return Err(inner_call_error);
+        }
+        let RunResult {
+            gas_counter,
+            memory,
+            value,
+            profiling_info,
+            gas_checkpoints,
+            statement_execution_counts,
+            panic_backtrace,
+            panic_snapshot,
+            used_resources,
+        } = result?;
+        let run_time = run_start.elapsed();
+        let value_contents = match &value {
+            RunResultValue::Success(values) => {
+                read_value_contents(self, &self.returned_value_types(func), values, &memory)
+            }
+            RunResultValue::Panic(_) => None,
+        };
+        // The statements executed by the calls to contracts are covered as well.
+        let statement_execution_counts = statement_execution_counts.map(|mut counts| {
+            let inner_counts = hint_processor.inner_statement_execution_counts.unwrap_or_default();
+            for (count, inner_count) in counts.iter_mut().zip(inner_counts) {
+                *count += inner_count;
+            }
+            counts
+        });
+        let branch_coverage = statement_execution_counts.as_ref().map(|counts| {
+            let code_offsets = self
+                .casm_program
+                .debug_info
+                .sierra_statement_info
+                .iter()
+                .map(|info| info.code_offset)
+                .collect_vec();
+            BranchCoverage::from_execution_counts(&self.sierra_program, &code_offsets, counts)
+        });
         Ok(RunResultStarknet {
             gas_counter,
             memory,
             value,
+            value_contents,
             starknet_state: hint_processor.starknet_state,
             profiling_info,
+            gas_checkpoints,
+            branch_coverage,
+            statement_execution_counts,
+            hint_executions: hint_processor.hint_executions_log.map(|log| log.executions),
+            printed_output: hint_processor.printed_output,
+            call_traces: hint_processor.call_traces,
+            events: hint_processor.events,
+            panic_backtrace,
+            panic_snapshot,
+            used_resources,
+            metadata: RunMetadata::new(self, available_gas, run_time, initial_peak_memory),
         })
     }
 
+    /// Runs an external entry point of a contract in the context of a given starknet state. The
+    /// contract is identified by its class hash, and the entry point is run through its wrapper,
+    /// which deserializes `calldata` into the arguments of the entry point.
+    ///
+    /// On success, the value of the result is the serialization of the entry point return value.
+    pub fn run_contract_entry_point(
+        &self,
+        class_hash: &Felt252,
+        selector: &Felt252,
+        calldata: Vec<Felt252>,
+        available_gas: Option<usize>,
+        starknet_state: StarknetState,
+    ) -> Result<RunResultStarknet, RunnerError> {
+        let func = self.find_contract_entry_point(class_hash, selector)?;
+        let addresses = starknet_state.caller_context();
+        let traced_calldata = self.trace_calls.then(|| calldata.clone());
+        let mut result = self.run_function_with_starknet_context(
+            func,
+            &[Arg::Array(calldata.into_iter().map(Arg::Value).collect())],
+            available_gas,
+            starknet_state,
+        )?;
+        if let RunResultValue::Success(values) = &result.value {
+            let values = read_array_result_as_vec(&result.memory, values);
+            result.value_contents = Some(values.iter().cloned().map(Arg::Value).collect());
+            result.value = RunResultValue::Success(values);
+        }
+        if let Some(calldata) = traced_calldata {
+            result.trace_entry_call(
+                CallKind::CallContract,
+                addresses,
+                (class_hash, selector),
+                calldata,
+            );
+        }
+        Ok(result)
+    }
+
+    /// Runs an external entry point of a contract, given by its name, in the context of a given
+    /// starknet state. See `run_contract_entry_point` for more details.
+    pub fn run_contract_entry_point_by_name(
+        &self,
+        class_hash: &Felt252,
+        name: &str,
+        calldata: Vec<Felt252>,
+        available_gas: Option<usize>,
+        starknet_state: StarknetState,
+    ) -> Result<RunResultStarknet, RunnerError> {
+        let selector = Felt252::from(starknet_keccak(name.as_bytes()));
+        self.run_contract_entry_point(
+            class_hash,
+            &selector,
+            calldata,
+            available_gas,
+            starknet_state,
+        )
+    }
+
+    /// Calls an external entry point of a contract deployed in the given starknet state, as if
+    /// called by `caller_address`. Calls made by the entry point to other deployed contracts are
+    /// executed within the same run.
+    ///
+    /// On success, the value of the result is the serialization of the entry point return value.
+    /// If the entry point panics its changes are reverted, and the resulting starknet state is the
+    /// given one.
+    pub fn call_contract(
+        &self,
+        contract_address: &Felt252,
+        selector: &Felt252,
+        calldata: Vec<Felt252>,
+        caller_address: &Felt252,
+        available_gas: Option<usize>,
+        mut starknet_state: StarknetState,
+    ) -> Result<RunResultStarknet, RunnerError> {
+        let class_hash =
+            starknet_state.deployed_class_hash(contract_address).cloned().ok_or_else(|| {
+                RunnerError::ContractNotDeployed { contract_address: contract_address.clone() }
+            })?;
+        let original_starknet_state = starknet_state.clone();
+        let old_addresses =
+            starknet_state.open_caller_context((contract_address.clone(), caller_address.clone()));
+        let mut result = self.run_contract_entry_point(
+            &class_hash,
+            selector,
+            calldata,
+            available_gas,
+            starknet_state,
+        )?;
+        result.starknet_state.close_caller_context(old_addresses);
+        if let RunResultValue::Panic(_) = result.value {
+            result.starknet_state = original_starknet_state;
+        }
+        Ok(result)
+    }
+
+    /// Deploys a contract into the given starknet state, running its constructor with `calldata`
+    /// if it has one. The address of the deployed contract is computed from the salt, the class
+    /// hash, the calldata and the deployer address as on Starknet.
+    ///
+    /// Returns the address of the deployed contract along with the result of the constructor run.
+    /// If the constructor panics the deployment is reverted, and the resulting starknet state is
+    /// the given one. Fails if a contract is already deployed at the address.
+    pub fn deploy_contract(
+        &self,
+        class_hash: &Felt252,
+        salt: &Felt252,
+        calldata: Vec<Felt252>,
+        deployer_address: &Felt252,
+        available_gas: Option<usize>,
+        mut starknet_state: StarknetState,
+    ) -> Result<(Felt252, RunResultStarknet), RunnerError> {
+        let contract_info = self
+            .starknet_contracts_info
+            .get(class_hash)
+            .ok_or_else(|| RunnerError::MissingContract { class_hash: class_hash.clone() })?;
+        let contract_address =
+            calculate_contract_address(salt, class_hash, &calldata, deployer_address);
+        if starknet_state.deployed_contracts.contains_key(&contract_address) {
+            return Err(RunnerError::ContractAlreadyDeployed { contract_address });
+        }
+        let original_starknet_state = starknet_state.clone();
+        // The contract is deployed before running the constructor, as the constructor could make
+        // an external call to its own address.
+        starknet_state.deployed_contracts.insert(contract_address.clone(), class_hash.clone());
+        let constructor_selector = Felt252::from(starknet_keccak(b"constructor"));
+        let addresses = (contract_address.clone(), deployer_address.clone());
+        let Some(constructor) = &contract_info.constructor else {
+            if !calldata.is_empty() {
+                return Err(RunnerError::UnexpectedConstructorCalldata);
+            }
+            let mut result = RunResultStarknet {
+                gas_counter: available_gas.map(Felt252::from),
+                memory: vec![],
+                value: RunResultValue::Success(vec![]),
+                value_contents: Some(vec![]),
+                starknet_state,
+                profiling_info: None,
+                gas_checkpoints: None,
+                branch_coverage: None,
+                statement_execution_counts: None,
+                hint_executions: None,
+                printed_output: self.capture_prints.then(PrintedOutput::default),
+                call_traces: self.trace_calls.then(Vec::new),
+                events: vec![],
+                panic_backtrace: None,
+                panic_snapshot: None,
+                used_resources: ExecutionResources::default(),
+                metadata: RunMetadata::new(self, available_gas, Duration::ZERO, peak_memory()),
+            };
+            if self.trace_calls {
+                result.trace_entry_call(
+                    CallKind::Constructor,
+                    addresses,
+                    (class_hash, &constructor_selector),
+                    vec![],
+                );
+            }
+            return Ok((contract_address, result));
+        };
+        let func = self.sierra_program_registry.get_function(constructor)?;
+        let old_addresses = starknet_state.open_caller_context(addresses.clone());
+        let traced_calldata = self.trace_calls.then(|| calldata.clone());
+        let mut result = self.run_function_with_starknet_context(
+            func,
+            &[Arg::Array(calldata.into_iter().map(Arg::Value).collect())],
+            available_gas,
+            starknet_state,
+        )?;
+        result.starknet_state.close_caller_context(old_addresses);
+        match &result.value {
+            RunResultValue::Success(values) => {
+                let values = read_array_result_as_vec(&result.memory, values);
+                result.value_contents = Some(values.iter().cloned().map(Arg::Value).collect());
+                result.value = RunResultValue::Success(values);
+            }
+            RunResultValue::Panic(_) => result.starknet_state = original_starknet_state,
+        }
+        if let Some(calldata) = traced_calldata {
+            result.trace_entry_call(
+                CallKind::Constructor,
+                addresses,
+                (class_hash, &constructor_selector),
+                calldata,
+            );
+        }
+        Ok((contract_address, result))
+    }
+
+    /// Executes an invoke transaction of the account contract deployed at the given address, as on
+    /// Starknet: calls its `__validate__` entry point and, once the transaction is validated, its
+    /// `__execute__` entry point, both with `calldata` and with a zero caller address. Each of the
+    /// calls gets `available_gas`.
+    ///
+    /// The transaction context of the calls is that of a version 1 transaction of the account, with
+    /// the given signature, the current nonce of the account and its matching transaction hash -
+    /// see [calculate_invoke_transaction_hash]. The other fields of the context are kept, and the
+    /// original context is restored in the resulting starknet state.
+    ///
+    /// The transaction is validated if `__validate__` returns `'VALID'`, in which case the nonce
+    /// of the account is incremented, even if `__execute__` panics. Otherwise the transaction is
+    /// rejected, and the resulting starknet state is the given one.
+    pub fn execute_account_transaction(
+        &self,
+        account_address: &Felt252,
+        calldata: Vec<Felt252>,
+        signature: Vec<Felt252>,
+        available_gas: Option<usize>,
+        mut starknet_state: StarknetState,
+    ) -> Result<AccountTransactionResult, RunnerError> {
+        let original_starknet_state = starknet_state.clone();
+        let original_tx_context = starknet_state.tx_context();
+        let nonce = starknet_state.nonce(account_address);
+        let transaction_hash = calculate_invoke_transaction_hash(
+            account_address,
+            &calldata,
+            &original_tx_context.max_fee,
+            starknet_state.chain_id(),
+            &nonce,
+        );
+        starknet_state.set_tx_context(TxContext {
+            version: Felt252::from(1),
+            account_contract_address: account_address.clone(),
+            max_fee: original_tx_context.max_fee.clone(),
+            signature,
+            transaction_hash: transaction_hash.clone(),
+            nonce,
+            ..Default::default()
+        });
+        // The entry points of account transactions are called by the protocol.
+        let caller_address = Felt252::from(0);
+        let mut validate = self.call_contract(
+            account_address,
+            &Felt252::from(starknet_keccak(b"__validate__")),
+            calldata.clone(),
+            &caller_address,
+            available_gas,
+            starknet_state,
+        )?;
+        let validated = Felt252::from_bytes_be(b"VALID");
+        if validate.value != RunResultValue::Success(vec![validated]) {
+            validate.starknet_state = original_starknet_state;
+            return Ok(AccountTransactionResult { transaction_hash, validate, execute: None });
+        }
+        let mut starknet_state = validate.starknet_state.clone();
+        starknet_state.increment_nonce(account_address);
+        let mut execute = self.call_contract(
+            account_address,
+            &Felt252::from(starknet_keccak(b"__execute__")),
+            calldata,
+            &caller_address,
+            available_gas,
+            starknet_state,
+        )?;
+        execute.starknet_state.set_tx_context(original_tx_context);
+        Ok(AccountTransactionResult { transaction_hash, validate, execute: Some(execute) })
+    }
+
+    /// Finds the wrapper function of an external entry point of a contract.
+    pub fn find_contract_entry_point(
+        &self,
+        class_hash: &Felt252,
+        selector: &Felt252,
+    ) -> Result<&Function, RunnerError> {
+        let contract_info = self
+            .starknet_contracts_info
+            .get(class_hash)
+            .ok_or_else(|| RunnerError::MissingContract { class_hash: class_hash.clone() })?;
+        let function_id = contract_info
+            .externals
+            .get(selector)
+            .ok_or_else(|| RunnerError::MissingEntryPoint { selector: selector.clone() })?;
+        Ok(self.sierra_program_registry.get_function(function_id)?)
+    }
+
+    /// Runs the function in the context of a given starknet state, doubling the available gas
+    /// whenever the run runs out of gas, up to `max_available_gas`. Once a sufficient amount of gas
+    /// is found, searches for the minimal sufficient amount.
+    ///
+    /// Returns the amount of gas of the returned run along with its result. This is the minimal
+    /// sufficient amount if the run initially ran out of gas, `available_gas` if it did not, or
+    /// `max_available_gas` if even that was insufficient.
+    pub fn run_function_with_gas_retries(
+        &self,
+        func: &Function,
+        args: &[Arg],
+        available_gas: usize,
+        max_available_gas: usize,
+        starknet_state: StarknetState,
+    ) -> Result<(usize, RunResultStarknet), RunnerError> {
+        let run = |available_gas| {
+            self.run_function_with_starknet_context(
+                func,
+                args,
+                Some(available_gas),
+                starknet_state.clone(),
+            )
+        };
+        // Runs the function, returning whether the gas was sufficient along with the result of the
+        // run, which is kept to be returned if no larger amount of gas is allowed.
+        let try_run = |available_gas| match run(available_gas) {
+            Ok(result) => Ok((!result.value.is_out_of_gas(), Ok(result))),
+            Err(RunnerError::NotEnoughGasToCall) => {
+                Ok((false, Err(RunnerError::NotEnoughGasToCall)))
+            }
+            Err(err) => Err(err),
+        };
+        // The largest amount of gas known to be insufficient.
+        let mut insufficient_gas = None;
+        let mut available_gas = available_gas.min(max_available_gas);
+        let mut result = loop {
+            let (sufficient, result) = try_run(available_gas)?;
+            if sufficient {
+                break result?;
+            }
+            if available_gas >= max_available_gas {
+                return Ok((available_gas, result?));
+            }
+            insufficient_gas = Some(available_gas);
+            available_gas = available_gas.saturating_mul(2).max(1).min(max_available_gas);
+        };
+        // Binary search for the minimal sufficient amount of gas.
+        let Some(mut insufficient_gas) = insufficient_gas else {
+            return Ok((available_gas, result));
+        };
+        while available_gas - insufficient_gas > 1 {
+            let middle_gas = insufficient_gas + (available_gas - insufficient_gas) / 2;
+            match try_run(middle_gas)? {
+                (true, middle_result) => {
+                    available_gas = middle_gas;
+                    result = middle_result?;
+                }
+                (false, _) => insufficient_gas = middle_gas,
+            }
+        }
+        Ok((available_gas, result))
+    }
+
+    /// Runs the given calls in order, each starting from the starknet state the previous call
+    /// ended with, e.g. for flows of setup, action and teardown functions. Stops after the first
+    /// call that panics, as later calls would start from the state of an incomplete flow.
+    ///
+    /// The gas of each call is accounted for independently, and a call running out of gas is
+    /// sandboxed: its changes are reverted and the following calls are run. The gas used by each
+    /// call is given by [RunResultStarknet::gas_used].
+    ///
+    /// Returns the results of the calls that were run, in order.
+    pub fn run_functions_in_sequence(
+        &self,
+        calls: &[SequentialCall],
+        mut starknet_state: StarknetState,
+    ) -> Result<Vec<RunResultStarknet>, RunnerError> {
+        let mut results = vec![];
+        for call in calls {
+            let func = self.find_function(&call.function)?;
+            let result = self.run_function_with_starknet_context(
+                func,
+                &call.args,
+                call.available_gas,
+                starknet_state.clone(),
+            )?;
+            let panicked = match &result.value {
+                RunResultValue::Success(_) => {
+                    starknet_state = result.starknet_state.clone();
+                    false
+                }
+                RunResultValue::Panic(_) => !result.value.is_out_of_gas(),
+            };
+            results.push(result);
+            if panicked {
+                break;
+            }
+        }
+        Ok(results)
+    }
+
     /// Runs the vm starting from a function with custom hint processor. Function may have
     /// implicits, but no other ref params. The cost of the function is deducted from
     /// `available_gas` before the execution begins.
@@ -259,11 +1683,11 @@ impl SierraCasmRunner {
     {
         let return_types = self.generic_id_and_size_from_concrete(&func.signature.ret_types);
 
-        let (cells, ap) = casm_run::run_function(
+        let (cells, ap, used_resources) = casm_run::run_function(
             vm,
             bytecode,
             builtins,
-            initialize_vm,
+            if self.builtin_costs { initialize_vm } else { initialize_vm_without_builtin_costs },
             hint_processor,
             hints_dict,
         )?;
@@ -286,7 +1710,308 @@ impl SierraCasmRunner {
             None
         };
 
-        Ok(RunResult { gas_counter, memory: cells, value, profiling_info })
+        let gas_checkpoints = if self.log_gas_checkpoints {
+            Some(self.collect_gas_checkpoints(vm.get_relocated_trace().unwrap()))
+        } else {
+            None
+        };
+
+        let statement_execution_counts = if self.collect_branch_coverage {
+            Some(self.collect_statement_execution_counts(vm.get_relocated_trace().unwrap()))
+        } else {
+            None
+        };
+
+        let panic_backtrace = matches!(value, RunResultValue::Panic(_))
+            .then(|| self.collect_panic_backtrace(vm.get_relocated_trace().unwrap()));
+
+        let panic_snapshot =
+            if self.capture_panic_snapshot && matches!(value, RunResultValue::Panic(_)) {
+                self.collect_panic_snapshot(vm.get_relocated_trace().unwrap(), &cells)
+            } else {
+                None
+            };
+
+        Ok(RunResult {
+            gas_counter,
+            memory: cells,
+            value,
+            profiling_info,
+            gas_checkpoints,
+            statement_execution_counts,
+            panic_backtrace,
+            panic_snapshot,
+            used_resources,
+        })
+    }
+
+    /// Returns the user functions by the pc of their first instruction, and the pcs of the first
+    /// instructions of the `return` statements. `entry_code_size` is the size of the code
+    /// preceding the program.
+    fn function_entry_and_return_pcs(
+        &self,
+        entry_code_size: usize,
+    ) -> (UnorderedHashMap<usize, FunctionId>, UnorderedHashSet<usize>) {
+        let statements_info = &self.casm_program.debug_info.sierra_statement_info;
+        let functions_by_pc = self
+            .sierra_program
+            .funcs
+            .iter()
+            .map(|func| {
+                (entry_code_size + statements_info[func.entry_point.0].code_offset, func.id.clone())
+            })
+            .collect();
+        let return_pcs = self
+            .sierra_program
+            .statements
+            .iter()
+            .zip(statements_info)
+            .filter(|(statement, _)| matches!(statement, GenStatement::Return(_)))
+            .map(|(_, info)| entry_code_size + info.code_offset)
+            .collect();
+        (functions_by_pc, return_pcs)
+    }
+
+    /// Returns the ABI of the contract the given function is an entry point of, if it is known.
+    fn entry_point_abi(&self, function_id: &FunctionId) -> Option<&Contract> {
+        self.starknet_contracts_info
+            .values()
+            .find(|contract_info| {
+                chain!(
+                    &contract_info.constructor,
+                    contract_info.externals.values(),
+                    contract_info.l1_handlers.values()
+                )
+                .contains(function_id)
+            })?
+            .abi
+            .as_ref()
+    }
+
+    /// Creates a pre-step hook tracking the call stack of user functions in `call_stack`, aborting
+    /// the run once its depth exceeds `max_depth`. `entry_code_size` is the size of the code
+    /// preceding the program.
+    fn call_depth_limit_hook(
+        &self,
+        entry_code_size: usize,
+        max_depth: usize,
+        hook_call_stack: Arc<Mutex<CallStack>>,
+    ) -> PreStepHook {
+        let (functions_by_pc, return_pcs) = self.function_entry_and_return_pcs(entry_code_size);
+        Box::new(move |vm: &mut VirtualMachine| {
+            // The program is loaded at the start of the first segment.
+            let pc = vm.get_pc().offset;
+            let mut call_stack = hook_call_stack.lock().unwrap();
+            if let Some(function_id) = functions_by_pc.get(&pc) {
+                call_stack.call_chain.push(function_id.to_string());
+                if call_stack.call_chain.len() > max_depth {
+                    call_stack.overflowed = true;
+                    return Err(VirtualMachineError::Other(CallDepthExceeded { max_depth }.into()));
+                }
+            }
+            if return_pcs.contains(&pc) {
+                call_stack.call_chain.pop();
+            }
+            Ok(())
+        })
+    }
+
+    /// Collects the backtrace of the panic of the current run using the trace.
+    ///
+    /// Once a run panics, it only returns from its functions, so the panic originates from the last
+    /// function entered. The backtrace is the `function_call` statements of the call stack at that
+    /// point, innermost first.
+    fn collect_panic_backtrace(&self, trace: &[TraceEntry]) -> Vec<StatementIdx> {
+        let statements_info = &self.casm_program.debug_info.sierra_statement_info;
+        let bytecode_len = statements_info.last().unwrap().code_offset;
+        // See `collect_profiling_info` for details on the header and `real_pc_0`.
+        let real_pc_0 = trace.last().unwrap().pc + 1;
+        let function_offsets: UnorderedHashSet<usize> = self
+            .sierra_program
+            .funcs
+            .iter()
+            .map(|func| statements_info[func.entry_point.0].code_offset)
+            .collect();
+        // The real pcs of the trace entries which are the start of a Sierra statement, skipping
+        // the header and the footer.
+        let statement_pcs = trace.iter().filter_map(|entry| {
+            let real_pc =
+                entry.pc.checked_sub(real_pc_0).filter(|real_pc| *real_pc < bytecode_len)?;
+            let statement_idx = self.sierra_statement_index_by_pc(real_pc);
+            (statements_info[statement_idx.0].code_offset == real_pc)
+                .then_some((real_pc, statement_idx))
+        });
+        let Some(last_entry) = statement_pcs
+            .clone()
+            .enumerate()
+            .filter(|(_, (real_pc, _))| function_offsets.contains(real_pc))
+            .last()
+            .map(|(i, _)| i)
+        else {
+            return vec![];
+        };
+
+        // The `function_call` statements of the call stack.
+        let mut call_stack = vec![];
+        let mut prev_statement_idx = None;
+        for (real_pc, statement_idx) in statement_pcs.take(last_entry + 1) {
+            if function_offsets.contains(&real_pc) {
+                call_stack.extend(prev_statement_idx);
+            }
+            if matches!(self.sierra_program.statements[statement_idx.0], GenStatement::Return(_)) {
+                call_stack.pop();
+            }
+            prev_statement_idx = Some(statement_idx);
+        }
+        call_stack.reverse();
+        call_stack
+    }
+
+    /// Collects the state of the VM at the panic point of the current run using the trace and the
+    /// final memory.
+    ///
+    /// A panic is raised by constructing a `core::panics::Panic`, which happens exactly once in a
+    /// panicking run, so the panic point is the last time the start of such a statement was
+    /// reached. The memory is taken from the final memory of the run: the cells set at the panic
+    /// point keep their values, but cells below `ap` that were still unset there and were written
+    /// afterwards (e.g. the locals of an outer frame) appear with their final values.
+    fn collect_panic_snapshot(
+        &self,
+        trace: &[TraceEntry],
+        cells: &[Option<Felt252>],
+    ) -> Option<PanicSnapshot> {
+        let statements_info = &self.casm_program.debug_info.sierra_statement_info;
+        // See `collect_profiling_info` for details on the header and `real_pc_0`.
+        let real_pc_0 = trace.last().unwrap().pc + 1;
+        let panic_libfuncs: UnorderedHashSet<&ConcreteLibfuncId> = self
+            .sierra_program
+            .libfunc_declarations
+            .iter()
+            .filter(|declaration| {
+                declaration.long_id.generic_id.0 == StructConstructLibfunc::STR_ID
+                    && matches!(
+                        &declaration.long_id.generic_args[..],
+                        [GenericArg::Type(ty)] if matches!(
+                            &self.get_info(ty).long_id.generic_args[..],
+                            [GenericArg::UserType(ut), ..]
+                                if ut.debug_name.as_deref() == Some("core::panics::Panic")
+                        )
+                    )
+            })
+            .map(|declaration| &declaration.id)
+            .collect();
+        let panic_statements: UnorderedHashMap<usize, StatementIdx> = self
+            .sierra_program
+            .statements
+            .iter()
+            .enumerate()
+            .filter(|(_, statement)| {
+                matches!(statement, GenStatement::Invocation(invocation)
+                    if panic_libfuncs.contains(&invocation.libfunc_id))
+            })
+            .map(|(idx, _)| (statements_info[idx].code_offset, StatementIdx(idx)))
+            .collect();
+        let (entry, statement_idx) = trace.iter().rev().find_map(|entry| {
+            let real_pc = entry.pc.checked_sub(real_pc_0)?;
+            Some((entry, *panic_statements.get(&real_pc)?))
+        })?;
+        let function = self
+            .sierra_program
+            .funcs
+            .iter()
+            .filter(|func| func.entry_point <= statement_idx)
+            .max_by_key(|func| func.entry_point)?;
+        let memory = |range: std::ops::Range<usize>| {
+            range.map(|address| cells.get(address).cloned().flatten()).collect()
+        };
+        Some(PanicSnapshot {
+            statement_idx,
+            function: function.id.clone(),
+            pc: entry.pc,
+            ap: entry.ap,
+            fp: entry.fp,
+            frame: memory(entry.fp..entry.ap),
+            memory: memory(0..entry.ap),
+        })
+    }
+
+    /// Collects the gas checkpoints reached in the current run using the trace.
+    fn collect_gas_checkpoints(&self, trace: &[TraceEntry]) -> Vec<GasCheckpoint> {
+        let statements_info = &self.casm_program.debug_info.sierra_statement_info;
+        let bytecode_len = statements_info.last().unwrap().code_offset;
+        // See `collect_profiling_info` for details on the header and `real_pc_0`.
+        let real_pc_0 = trace.last().unwrap().pc + 1;
+
+        let mut checkpoints = vec![];
+        // The last reached checkpoint, if its outcome is not known yet, along with the code offset
+        // of its success branch.
+        let mut pending: Option<(GasCheckpoint, usize)> = None;
+        for (step, entry) in trace.iter().enumerate() {
+            // Skip the header and the footer.
+            let Some(real_pc) = entry.pc.checked_sub(real_pc_0) else { continue };
+            if real_pc >= bytecode_len {
+                continue;
+            }
+            // Only the first instruction of a statement is of interest.
+            let statement_idx = self.sierra_statement_index_by_pc(real_pc);
+            if statements_info[statement_idx.0].code_offset != real_pc {
+                continue;
+            }
+            // The statement following a checkpoint is the target of the branch it took.
+            if let Some((mut checkpoint, success_offset)) = pending.take() {
+                checkpoint.succeeded = real_pc == success_offset;
+                checkpoints.push(checkpoint);
+            }
+            let GenStatement::Invocation(invocation) =
+                &self.sierra_program.statements[statement_idx.0]
+            else {
+                continue;
+            };
+            let kind = match self.sierra_program_registry.get_libfunc(&invocation.libfunc_id) {
+                Ok(CoreConcreteLibfunc::Gas(GasConcreteLibfunc::WithdrawGas(_))) => {
+                    GasCheckpointKind::Withdraw
+                }
+                Ok(CoreConcreteLibfunc::Gas(GasConcreteLibfunc::BuiltinWithdrawGas(_))) => {
+                    GasCheckpointKind::BuiltinWithdraw
+                }
+                Ok(CoreConcreteLibfunc::Gas(GasConcreteLibfunc::RedepositGas(_))) => {
+                    GasCheckpointKind::Redeposit
+                }
+                _ => continue,
+            };
+            let success_target = statement_idx.next(&invocation.branches[0].target);
+            pending = Some((
+                GasCheckpoint { step, statement_idx, kind, succeeded: false },
+                statements_info[success_target.0].code_offset,
+            ));
+        }
+        checkpoints.extend(pending.map(|(checkpoint, _)| checkpoint));
+        checkpoints
+    }
+
+    /// Collects the number of times each Sierra statement was executed in the current run using
+    /// the trace, indexed by the statement index. A statement is executed each time its first
+    /// instruction is. Statements compiled into no instructions share the offset of the statement
+    /// following them, and are counted with it.
+    fn collect_statement_execution_counts(&self, trace: &[TraceEntry]) -> Vec<usize> {
+        let statements_info = &self.casm_program.debug_info.sierra_statement_info;
+        let bytecode_len = statements_info.last().unwrap().code_offset;
+        // See `collect_profiling_info` for details on the header and `real_pc_0`.
+        let real_pc_0 = trace.last().unwrap().pc + 1;
+
+        // The number of steps at each PC of the original CASM program.
+        let mut pc_step_counts = vec![0; bytecode_len];
+        for entry in trace {
+            // Skip the header and the footer.
+            let Some(real_pc) = entry.pc.checked_sub(real_pc_0) else { continue };
+            if real_pc < bytecode_len {
+                pc_step_counts[real_pc] += 1;
+            }
+        }
+        statements_info[..statements_info.len() - 1]
+            .iter()
+            .map(|info| pc_step_counts.get(info.code_offset).copied().unwrap_or_default())
+            .collect()
     }
 
     /// Collects profiling info of the current run using the trace.
@@ -398,7 +2123,13 @@ impl SierraCasmRunner {
         // Remove the footer.
         sierra_statement_weights.remove(&StatementIdx(sierra_len));
 
-        ProfilingInfo { sierra_statement_weights, stack_trace_weights }
+        let sierra_statement_execution_counts = self.collect_statement_execution_counts(trace);
+
+        ProfilingInfo {
+            sierra_statement_weights,
+            sierra_statement_execution_counts,
+            stack_trace_weights,
+        }
     }
 
     fn sierra_statement_index_by_pc(&self, pc: usize) -> StatementIdx {
@@ -414,25 +2145,48 @@ impl SierraCasmRunner {
         )
     }
 
+    /// Returns the types of the values of a successful run of `func`: the inner type of its panic
+    /// wrapper, if it has one, and otherwise its returned types, excluding its implicits.
+    fn returned_value_types(&self, func: &Function) -> Vec<ConcreteTypeId> {
+        let ret_types = func
+            .signature
+            .ret_types
+            .iter()
+            .filter(|ty| !is_implicit_type(&self.get_info(ty).long_id.generic_id))
+            .cloned()
+            .collect_vec();
+        match &ret_types[..] {
+            [ty] => vec![
+                self.inner_type_from_panic_wrapper(&self.get_info(ty).long_id.generic_id, func)
+                    .unwrap_or_else(|| ty.clone()),
+            ],
+            _ => ret_types,
+        }
+    }
+
     /// Extract inner type if `ty` is a panic wrapper
     fn inner_type_from_panic_wrapper(
         &self,
         ty: &GenericTypeId,
         func: &Function,
     ) -> Option<ConcreteTypeId> {
-        let info = func
+        let (ret_ty, info) = func
             .signature
             .ret_types
             .iter()
             .find_map(|rt| {
                 let info = self.get_info(rt);
-                (info.long_id.generic_id == *ty).then_some(info)
+                (info.long_id.generic_id == *ty).then_some((rt, info))
             })
             .unwrap();
 
+        // The names of user types are not kept in contract classes, unlike the names of the
+        // concrete types.
         if *ty == EnumType::ID
             && matches!(&info.long_id.generic_args[0], GenericArg::UserType(ut)
-                if ut.debug_name.as_ref().unwrap().starts_with("core::panics::PanicResult::"))
+            if ut.debug_name.as_ref().or(ret_ty.debug_name.as_ref()).is_some_and(
+                |name| name.starts_with("core::panics::PanicResult::")
+            ))
         {
             return Some(extract_matches!(&info.long_id.generic_args[1], GenericArg::Type).clone());
         }
@@ -533,7 +2287,33 @@ impl SierraCasmRunner {
             .find(|f| {
                 if let Some(name) = &f.id.debug_name { name.ends_with(name_suffix) } else { false }
             })
-            .ok_or_else(|| RunnerError::MissingFunction { suffix: name_suffix.to_owned() })
+            .ok_or_else(|| RunnerError::MissingFunction {
+                suffix: name_suffix.to_owned(),
+                suggestions: self.suggest_functions(name_suffix),
+            })
+    }
+
+    /// Returns the names of the functions whose name is the closest to ending with `name_suffix`,
+    /// for functions that are not a match but are within a few edits of it.
+    fn suggest_functions(&self, name_suffix: &str) -> Vec<String> {
+        const MAX_SUGGESTIONS: usize = 3;
+        let Some(query) = name_suffix.rsplit("::").next().filter(|query| !query.is_empty()) else {
+            return vec![];
+        };
+        let max_distance = query.len() / 3 + 1;
+        self.sierra_program
+            .funcs
+            .iter()
+            .filter_map(|f| {
+                let name = f.id.debug_name.as_ref()?;
+                let function_name = name.rsplit("::").next()?;
+                let distance = edit_distance(query, function_name);
+                (distance <= max_distance).then(|| (distance, name.to_string()))
+            })
+            .sorted()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, name)| name)
+            .collect()
     }
 
     /// Converts array of `ConcreteTypeId`s into corresponding `GenericTypeId`s and their sizes
@@ -552,6 +2332,52 @@ impl SierraCasmRunner {
             .collect()
     }
 
+    /// Checks that the parameters of `func` can be passed as arguments of a run, returning an error
+    /// explaining how to adapt the first parameter that can't.
+    pub fn check_function_signature(&self, func: &Function) -> Result<(), RunnerError> {
+        for (param_index, ty) in func.signature.param_types.iter().enumerate() {
+            if let Some((inner_ty, suggestion)) = self.find_unsupported_param_type(ty) {
+                return Err(RunnerError::UnsupportedParamType {
+                    param_index,
+                    ty: ty.to_string(),
+                    inner_ty: inner_ty.to_string(),
+                    suggestion,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the first type that can't be passed as an argument of a run contained in `ty`,
+    /// possibly `ty` itself, along with a suggestion on how to pass it instead.
+    fn find_unsupported_param_type<'a>(
+        &'a self,
+        ty: &'a ConcreteTypeId,
+    ) -> Option<(&'a ConcreteTypeId, &'static str)> {
+        let long_id = &self.get_info(ty).long_id;
+        let generic_id = &long_id.generic_id;
+        let suggestion = if *generic_id == BoxType::ID {
+            Some("take the boxed value instead, and box it in the function")
+        } else if *generic_id == NullableType::ID {
+            Some("take an `Option` of the value instead")
+        } else if [Felt252DictType::ID, Felt252DictEntryType::ID, SquashedFelt252DictType::ID]
+            .contains(generic_id)
+        {
+            Some("take an array of the entries instead, and build the dictionary in the function")
+        } else if [Secp256k1PointType::ID, Secp256r1PointType::ID].contains(generic_id) {
+            Some("take the coordinates of the point instead, and build the point in the function")
+        } else {
+            None
+        };
+        if let Some(suggestion) = suggestion {
+            return Some((ty, suggestion));
+        }
+        long_id.generic_args.iter().find_map(|arg| match arg {
+            GenericArg::Type(inner_ty) => self.find_unsupported_param_type(inner_ty),
+            _ => None,
+        })
+    }
+
     fn get_info(
         &self,
         ty: &cairo_lang_sierra::ids::ConcreteTypeId,
@@ -559,6 +2385,62 @@ impl SierraCasmRunner {
         self.sierra_program_registry.get_type(ty).unwrap().info()
     }
 
+    /// Adds the instructions loading the content of an array argument into a new segment, after
+    /// loading the content of the arrays nested in it into their own segments. Returns the offset
+    /// from the start of the arguments of the cell holding the start of the new segment.
+    fn load_array_arg(ctx: &mut CasmContext, values: &[Arg], ap_offset: &mut i16) -> i16 {
+        let mut nested_array_offsets = values
+            .iter()
+            .filter_map(|value| match value {
+                Arg::Value(_) => None,
+                Arg::Array(nested_values) => {
+                    Some((Self::load_array_arg(ctx, nested_values, ap_offset), nested_values))
+                }
+            })
+            .collect_vec()
+            .into_iter();
+        let array_offset = *ap_offset;
+        casm_extend! {ctx,
+            %{ memory[ap + 0] = segments.add() %}
+            ap += 1;
+        }
+        *ap_offset += 1;
+        // Writes the value at `[ap]` into the next cell of the array.
+        let mut cell_index: i16 = 0;
+        let mut push_cell = |ctx: &mut CasmContext, ap_offset: &mut i16| {
+            let arr_at = *ap_offset - array_offset;
+            casm_extend! {ctx,
+                [ap + 0] = [[ap - arr_at] + cell_index], ap++;
+            }
+            *ap_offset += 1;
+            cell_index += 1;
+        };
+        for value in values {
+            match value {
+                Arg::Value(value) => {
+                    casm_extend! {ctx,
+                        [ap + 0] = (value.to_bigint());
+                    }
+                    push_cell(ctx, ap_offset);
+                }
+                Arg::Array(_) => {
+                    let (nested_array_offset, nested_values) = nested_array_offsets.next().unwrap();
+                    let offset = nested_array_offset - *ap_offset;
+                    casm_extend! {ctx,
+                        [ap + 0] = [ap + (offset)];
+                    }
+                    push_cell(ctx, ap_offset);
+                    let offset = nested_array_offset - *ap_offset;
+                    casm_extend! {ctx,
+                        [ap + 0] = [ap + (offset)] + (args_size(nested_values));
+                    }
+                    push_cell(ctx, ap_offset);
+                }
+            }
+        }
+        array_offset
+    }
+
     pub fn create_entry_code_from_params(
         param_types: &[(GenericTypeId, i16)],
         args: &[Arg],
@@ -587,19 +2469,7 @@ impl SierraCasmRunner {
         let mut ap_offset: i16 = 0;
         for arg in args {
             let Arg::Array(values) = arg else { continue };
-            array_args_data.push(ap_offset);
-            casm_extend! {ctx,
-                %{ memory[ap + 0] = segments.add() %}
-                ap += 1;
-            }
-            for (i, v) in values.iter().enumerate() {
-                let arr_at: i16 = (i + 1).into_or_panic();
-                casm_extend! {ctx,
-                    [ap + 0] = (v.to_bigint());
-                    [ap + 0] = [[ap - arr_at] + i.into_or_panic()], ap++;
-                }
-            }
-            ap_offset += (1 + values.len()).into_or_panic::<i16>();
+            array_args_data.push(Self::load_array_arg(&mut ctx, values, &mut ap_offset));
         }
         let mut array_args_data_iter = array_args_data.iter();
         let after_arrays_data_offset = ap_offset;
@@ -664,7 +2534,7 @@ impl SierraCasmRunner {
                             let offset = -ap_offset + array_args_data_iter.next().unwrap();
                             casm_extend! {ctx,
                                 [ap + 0] = [ap + (offset)], ap++;
-                                [ap + 0] = [ap - 1] + (values.len()), ap++;
+                                [ap + 0] = [ap - 1] + (args_size(values)), ap++;
                             }
                             ap_offset += 2;
                             if ap_offset > param_ap_offset_end {
@@ -679,13 +2549,7 @@ impl SierraCasmRunner {
                 param_index += 1;
             };
         }
-        let actual_args_size = args
-            .iter()
-            .map(|arg| match arg {
-                Arg::Value(_) => 1,
-                Arg::Array(_) => 2,
-            })
-            .sum::<usize>();
+        let actual_args_size = args_size(args);
         if expected_arguments_size != actual_args_size {
             return Err(RunnerError::ArgumentsSizeMismatch {
                 expected: expected_arguments_size,
@@ -711,6 +2575,7 @@ impl SierraCasmRunner {
         args: &[Arg],
         initial_gas: usize,
     ) -> Result<(Vec<Instruction>, Vec<BuiltinName>), RunnerError> {
+        self.check_function_signature(func)?;
         let params = self.generic_id_and_size_from_concrete(&func.signature.param_types);
 
         let entry_point = func.entry_point.0;
@@ -748,6 +2613,9 @@ impl SierraCasmRunner {
         Some(
             self.metadata.gas_info.function_costs[&func.id]
                 .iter()
+                .filter(|(token_type, _)| {
+                    self.builtin_costs || **token_type == CostTokenType::Const
+                })
                 .map(|(token_type, val)| val.into_or_panic::<usize>() * token_gas_cost(*token_type))
                 .sum(),
         )
@@ -766,6 +2634,32 @@ impl SierraCasmRunner {
     pub fn get_casm_program(&self) -> &CairoProgram {
         &self.casm_program
     }
+
+    /// Returns static statistics of the program, available before running it.
+    pub fn program_stats(&self) -> ProgramStats {
+        self.casm_program.stats(&self.sierra_program)
+    }
+
+    /// Returns the statically computed gas requirements of the functions of the program, or `None`
+    /// if it does not use gas.
+    pub fn gas_report(&self) -> Option<GasReport> {
+        GasReport::new(self)
+    }
+
+    /// Returns the report of the items of the program unreachable from the given root functions and
+    /// from the entry points of the contracts.
+    pub fn dead_code_report(&self, roots: &[FunctionId]) -> DeadCodeReport {
+        DeadCodeReport::new(&self.sierra_program, &self.with_contract_entry_points(roots))
+    }
+
+    /// Returns the monomorphized instances of the generic functions of the program, largest first.
+    /// The program must have been generated by `db`. See [generic_instances::generic_instances].
+    pub fn generic_instances(&self, db: &dyn SierraGenGroup) -> Vec<GenericFunctionInstances> {
+        generic_instances::generic_instances(
+            db,
+            self.casm_program.function_code_info(&self.sierra_program),
+        )
+    }
 }
 
 // TODO(yuval): consider changing this setting to use flags.
@@ -786,13 +2680,31 @@ fn get_max_stack_trace_depth() -> usize {
 /// Initializes a vm by adding a new segment with builtins cost and a necessary pointer at the end
 /// of the program
 pub fn initialize_vm(context: RunFunctionContext<'_>) -> Result<(), Box<CairoRunError>> {
+    initialize_vm_ex(context, true)
+}
+
+/// Same as `initialize_vm`, with all the builtins costs set to zero, so that the gas accounting
+/// of the run excludes them.
+pub fn initialize_vm_without_builtin_costs(
+    context: RunFunctionContext<'_>,
+) -> Result<(), Box<CairoRunError>> {
+    initialize_vm_ex(context, false)
+}
+
+/// Initializes a vm as done by `initialize_vm`, with the builtins costs set to zero if
+/// `builtin_costs` is false.
+fn initialize_vm_ex(
+    context: RunFunctionContext<'_>,
+    builtin_costs: bool,
+) -> Result<(), Box<CairoRunError>> {
     let vm = context.vm;
     // Create the builtin cost segment, with dummy values.
     let builtin_cost_segment = vm.add_memory_segment();
     for token_type in CostTokenType::iter_precost() {
+        let cost = if builtin_costs { token_gas_cost(*token_type) } else { 0 };
         vm.insert_value(
             (builtin_cost_segment + (token_type.offset_in_builtin_costs() as usize)).unwrap(),
-            Felt252::from(token_gas_cost(*token_type)),
+            Felt252::from(cost),
         )
         .map_err(|e| Box::new(e.into()))?;
     }
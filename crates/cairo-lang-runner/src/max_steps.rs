@@ -0,0 +1,63 @@
+use cairo_lang_defs::plugin::{MacroPlugin, MacroPluginMetadata, PluginResult};
+use cairo_lang_lowering::db::LoweringGroup;
+use cairo_lang_semantic::items::attribute::SemanticQueryAttrs;
+use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_syntax::attribute::structured::AttributeListStructurize;
+use cairo_lang_syntax::node::ast;
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_test_plugin::test_config::{malformed_max_steps_diagnostic, parse_max_steps};
+pub use cairo_lang_test_plugin::MAX_STEPS_ATTR;
+
+#[cfg(test)]
+#[path = "max_steps_test.rs"]
+mod test;
+
+/// Plugin allowing the `max_steps` attribute on functions to run, and creating diagnostics for
+/// malformed uses of it.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct MaxStepsPlugin;
+
+impl MacroPlugin for MaxStepsPlugin {
+    fn generate_code(
+        &self,
+        db: &dyn SyntaxGroup,
+        item_ast: ast::ModuleItem,
+        _metadata: &MacroPluginMetadata<'_>,
+    ) -> PluginResult {
+        let ast::ModuleItem::FreeFunction(free_func_ast) = item_ast else {
+            return PluginResult::default();
+        };
+        let attrs = free_func_ast.attributes(db).structurize(db);
+        PluginResult {
+            code: None,
+            diagnostics: attrs
+                .iter()
+                .filter(|attr| {
+                    attr.id.as_str() == MAX_STEPS_ATTR && parse_max_steps(db, attr).is_none()
+                })
+                .map(malformed_max_steps_diagnostic)
+                .collect(),
+            remove_original_item: false,
+        }
+    }
+
+    fn declared_attributes(&self) -> Vec<String> {
+        vec![MAX_STEPS_ATTR.to_string()]
+    }
+}
+
+/// Returns the number of steps set by the `max_steps` attribute of the function generating the
+/// given Sierra function, if it has one. The id must be the one generated by `db`, i.e. before any
+/// replacement of the Sierra ids.
+pub fn function_max_steps(db: &dyn SierraGenGroup, function_id: &FunctionId) -> Option<usize> {
+    let lowering_db: &dyn LoweringGroup = db.upcast();
+    let function = db.lookup_intern_sierra_function(function_id.clone());
+    let semantic_function = function.body(lowering_db).ok()??.base_semantic_function(lowering_db);
+    let attr = semantic_function
+        .function_with_body_id(db.upcast())
+        .find_attr(db.upcast(), MAX_STEPS_ATTR)
+        .ok()??;
+    parse_max_steps(db.upcast(), &attr)
+}
@@ -0,0 +1,63 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_semantic::test_utils::{setup_test_crate, setup_test_module};
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use indoc::indoc;
+
+use super::function_max_steps;
+use crate::available_gas::runner_plugin_suite;
+
+#[test]
+fn test_function_max_steps() {
+    let db = RootDatabase::builder()
+        .detect_corelib()
+        .with_plugin_suite(runner_plugin_suite())
+        .build()
+        .unwrap();
+    let test_module = setup_test_module(
+        &db,
+        indoc! {"
+            #[max_steps(1000)]
+            fn with_max_steps() -> felt252 {
+                without_max_steps()
+            }
+
+            #[inline(never)]
+            fn without_max_steps() -> felt252 {
+                5
+            }
+        "},
+    )
+    .unwrap();
+    DiagnosticsReporter::stderr().with_crates(&[test_module.crate_id]).ensure(&db).unwrap();
+    let sierra_program = db.get_sierra_program(vec![test_module.crate_id]).unwrap();
+    let max_steps = sierra_program
+        .program
+        .funcs
+        .iter()
+        .map(|func| function_max_steps(&db, &func.id))
+        .collect::<Vec<_>>();
+    assert_eq!(max_steps, [Some(1000), None]);
+}
+
+#[test]
+fn test_malformed_max_steps() {
+    let db = RootDatabase::builder()
+        .detect_corelib()
+        .with_plugin_suite(runner_plugin_suite())
+        .build()
+        .unwrap();
+    let crate_id = setup_test_crate(
+        &db,
+        indoc! {"
+            #[max_steps(static)]
+            fn main() {}
+        "},
+    );
+    let mut diagnostics = String::new();
+    DiagnosticsReporter::write_to_string(&mut diagnostics).with_crates(&[crate_id]).check(&db);
+    assert!(
+        diagnostics.contains("Attribute should have a single non-negative literal in `i64` range."),
+        "Unexpected diagnostics: {diagnostics}"
+    );
+}
@@ -0,0 +1,131 @@
+use std::sync::{Arc, Mutex};
+
+use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use cairo_vm::vm::errors::vm_errors::VirtualMachineError;
+use cairo_vm::vm::vm_core::VirtualMachine;
+
+use crate::casm_run::HintExecution;
+
+/// Hooks into the events of a run, allowing tools such as profilers, tracers and visualizers to
+/// follow a run without changing the runner.
+///
+/// The events are reported live, in execution order, each with the index of the step at which it
+/// happened - which is also the index of the matching entry in the trace. All the callbacks do
+/// nothing by default.
+#[allow(unused_variables)]
+pub trait RunObserver {
+    /// Called when a function is entered, at the step of the first instruction of the function.
+    fn on_function_entry(&mut self, step: usize, function_id: &FunctionId) {}
+
+    /// Called when a function returns, at the step of its `return` statement.
+    fn on_function_exit(&mut self, step: usize, function_id: &FunctionId) {}
+
+    /// Called when a hint is executed.
+    fn on_hint(&mut self, execution: &HintExecution) {}
+
+    /// Called when a syscall is executed, with the selector of the syscall (e.g. `StorageWrite`).
+    fn on_syscall(&mut self, step: usize, selector: &str) {}
+
+    /// Called every `step_milestone_interval` steps, starting from the first step.
+    fn on_step_milestone(&mut self, step: usize) {}
+
+    /// The number of steps between calls to `on_step_milestone`. If `None`, `on_step_milestone`
+    /// is never called.
+    fn step_milestone_interval(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// A hook called with the vm before each step of a run.
+pub(crate) type PreStepHook =
+    Box<dyn Fn(&mut VirtualMachine) -> Result<(), VirtualMachineError> + Send + Sync>;
+
+/// The observation of a run, notifying a [RunObserver] of the events of the run as they happen.
+///
+/// The pc of each step is reported by a pre-step hook of the vm, and the hints, syscalls and ends
+/// of the steps by the hint processor. The function and milestone events of a step are dispatched
+/// before its first hint, or at its end if it has no hints.
+pub struct RunObservation<'a> {
+    observer: &'a mut dyn RunObserver,
+    /// The index of the current step.
+    step: usize,
+    /// The pc of the current step, until the events of its start are dispatched.
+    pending_pc: Arc<Mutex<Option<usize>>>,
+    /// The functions, by the pc of their first instruction.
+    functions_by_pc: UnorderedHashMap<usize, FunctionId>,
+    /// The pcs of the first instructions of the `return` statements.
+    return_pcs: UnorderedHashSet<usize>,
+    /// The stack of the currently running functions.
+    function_stack: Vec<FunctionId>,
+    milestone_interval: Option<usize>,
+}
+impl<'a> RunObservation<'a> {
+    /// Creates an observation of a run, and the pre-step hook reporting the pcs of its steps.
+    pub(crate) fn new(
+        observer: &'a mut dyn RunObserver,
+        functions_by_pc: UnorderedHashMap<usize, FunctionId>,
+        return_pcs: UnorderedHashSet<usize>,
+    ) -> (Self, PreStepHook) {
+        let pending_pc = Arc::new(Mutex::new(None));
+        let hook_pending_pc = pending_pc.clone();
+        let pre_step_hook: PreStepHook = Box::new(move |vm: &mut VirtualMachine| {
+            // The program is loaded at the start of the first segment.
+            *hook_pending_pc.lock().unwrap() = Some(vm.get_pc().offset);
+            Ok(())
+        });
+        let milestone_interval =
+            observer.step_milestone_interval().filter(|interval| *interval > 0);
+        let observation = Self {
+            observer,
+            step: 0,
+            pending_pc,
+            functions_by_pc,
+            return_pcs,
+            function_stack: vec![],
+            milestone_interval,
+        };
+        (observation, pre_step_hook)
+    }
+
+    /// Returns the index of the current step.
+    pub(crate) fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Notifies the observer of an executed hint of the current step.
+    pub(crate) fn on_hint(&mut self, execution: &HintExecution) {
+        self.dispatch_step_start();
+        self.observer.on_hint(execution);
+    }
+
+    /// Notifies the observer of an executed syscall of the current step.
+    pub(crate) fn on_syscall(&mut self, selector: &str) {
+        self.dispatch_step_start();
+        self.observer.on_syscall(self.step, selector);
+    }
+
+    /// Ends the current step.
+    pub(crate) fn end_step(&mut self) {
+        self.dispatch_step_start();
+        self.step += 1;
+    }
+
+    /// Dispatches the events of the start of the current step, if not dispatched yet.
+    fn dispatch_step_start(&mut self) {
+        let Some(pc) = self.pending_pc.lock().unwrap().take() else { return };
+        if self.milestone_interval.is_some_and(|interval| self.step % interval == 0) {
+            self.observer.on_step_milestone(self.step);
+        }
+        if let Some(function_id) = self.functions_by_pc.get(&pc) {
+            self.observer.on_function_entry(self.step, function_id);
+            self.function_stack.push(function_id.clone());
+        }
+        if self.return_pcs.contains(&pc) {
+            if let Some(function_id) = self.function_stack.pop() {
+                self.observer.on_function_exit(self.step, &function_id);
+            }
+        }
+    }
+}
@@ -19,6 +19,10 @@ pub struct ProfilingInfo {
     /// The number of steps in the trace that originated from each sierra statement.
     pub sierra_statement_weights: UnorderedHashMap<StatementIdx, usize>,
 
+    /// The number of times each sierra statement was executed, indexed by the statement index.
+    /// Unlike the weights, a statement compiled into several steps is counted once per execution.
+    pub sierra_statement_execution_counts: Vec<usize>,
+
     /// A map of weights of each stack trace.
     /// The key is a function stack trace of an executed function. The stack trace is represented
     /// as a vector of indices of the functions in the stack (indices of the functions according to
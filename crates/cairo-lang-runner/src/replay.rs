@@ -0,0 +1,391 @@
+//! Recording of runs as compact replay files, and re-execution of recorded runs with verification
+//! that they are reproduced.
+//!
+//! The responses of the syscalls and cheatcodes of a run are recorded, and fed back to the run
+//! when it is replayed instead of being executed, so a replay does not depend on the starknet state
+//! the run was recorded in. The other hints are re-executed, and their outputs are verified.
+use cairo_felt::Felt252;
+use cairo_lang_casm::hints::{Hint, StarknetHint};
+use cairo_lang_sierra::program::Function;
+use cairo_lang_starknet_classes::keccak::starknet_keccak;
+use cairo_lang_utils::bigint::BigUintAsHex;
+use cairo_vm::types::exec_scope::ExecutionScopes;
+use cairo_vm::types::relocatable::{MaybeRelocatable, Relocatable};
+use cairo_vm::vm::errors::hint_errors::HintError;
+use cairo_vm::vm::vm_core::VirtualMachine;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::casm_run::hint_operands::hint_operands;
+use crate::casm_run::{
+    cell_ref_to_relocatable, extract_relocatable, read_written_cells, HintExecution, HintResponse,
+    StarknetState,
+};
+use crate::observer::RunObserver;
+use crate::{Arg, HintHandler, RunResultStarknet, RunResultValue, RunnerError, SierraCasmRunner};
+
+/// A recorded run of a function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunReplay {
+    /// The hash of the Sierra program the run was recorded with.
+    pub program_hash: BigUintAsHex,
+    /// The name of the run function.
+    pub function: String,
+    /// The arguments the function was run with.
+    pub args: Vec<ReplayArg>,
+    /// The gas available to the run.
+    pub available_gas: Option<usize>,
+    /// The executed hints, in execution order.
+    pub hints: Vec<RecordedHint>,
+    /// The executed syscalls, as pairs of the step and the selector of the syscall.
+    pub syscalls: Vec<(usize, String)>,
+    /// The outputs of the run.
+    pub outputs: ReplayOutputs,
+}
+impl RunReplay {
+    /// Serializes the replay into a compact JSON string.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Deserializes a replay from a JSON string.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+/// An argument of a recorded run.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplayArg {
+    Value(BigUintAsHex),
+    Array(Vec<ReplayArg>),
+}
+impl From<&Arg> for ReplayArg {
+    fn from(arg: &Arg) -> Self {
+        match arg {
+            Arg::Value(value) => Self::Value(felt_to_hex(value)),
+            Arg::Array(values) => Self::Array(values.iter().map(Self::from).collect()),
+        }
+    }
+}
+impl From<&ReplayArg> for Arg {
+    fn from(arg: &ReplayArg) -> Self {
+        match arg {
+            ReplayArg::Value(value) => Self::Value(value.value.clone().into()),
+            ReplayArg::Array(values) => Self::Array(values.iter().map(Self::from).collect()),
+        }
+    }
+}
+
+/// A value of a memory cell of a recorded run.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplayValue {
+    Felt(BigUintAsHex),
+    Pointer { segment_index: isize, offset: usize },
+}
+impl From<&MaybeRelocatable> for ReplayValue {
+    fn from(value: &MaybeRelocatable) -> Self {
+        match value {
+            MaybeRelocatable::Int(value) => Self::Felt(felt_to_hex(value)),
+            MaybeRelocatable::RelocatableValue(ptr) => {
+                Self::Pointer { segment_index: ptr.segment_index, offset: ptr.offset }
+            }
+        }
+    }
+}
+impl From<&ReplayValue> for MaybeRelocatable {
+    fn from(value: &ReplayValue) -> Self {
+        match value {
+            ReplayValue::Felt(value) => Self::Int(value.value.clone().into()),
+            ReplayValue::Pointer { segment_index, offset } => {
+                Self::RelocatableValue(Relocatable::from((*segment_index, *offset)))
+            }
+        }
+    }
+}
+
+/// An executed hint of a recorded run.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedHint {
+    /// The step at which the hint was executed.
+    pub step: usize,
+    /// The offset of the instruction the hint is attached to.
+    pub pc: usize,
+    /// The values of the cells written by the hint - `None` for cells it did not write.
+    pub outputs: Vec<Option<ReplayValue>>,
+    /// The response of the hint, for syscalls and cheatcodes.
+    pub response: Option<RecordedResponse>,
+}
+impl From<&HintExecution> for RecordedHint {
+    fn from(execution: &HintExecution) -> Self {
+        Self {
+            step: execution.step,
+            pc: execution.pc,
+            outputs: execution
+                .outputs
+                .iter()
+                .map(|output| output.value.as_ref().map(ReplayValue::from))
+                .collect(),
+            response: execution.response.as_ref().map(RecordedResponse::from),
+        }
+    }
+}
+
+/// The response of a syscall or a cheatcode of a recorded run. See [HintResponse].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub request: Vec<ReplayValue>,
+    pub response: Vec<ReplayValue>,
+    pub segments: Vec<(isize, Vec<ReplayValue>)>,
+}
+impl From<&HintResponse> for RecordedResponse {
+    fn from(response: &HintResponse) -> Self {
+        let values = |values: &[MaybeRelocatable]| values.iter().map(ReplayValue::from).collect();
+        Self {
+            request: values(&response.request),
+            response: values(&response.response),
+            segments: response
+                .segments
+                .iter()
+                .map(|(segment_index, content)| (*segment_index, values(content)))
+                .collect(),
+        }
+    }
+}
+
+/// The outputs of a recorded run.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayOutputs {
+    /// Whether the run panicked.
+    pub panicked: bool,
+    /// The returned values, or the panic data if the run panicked.
+    pub values: Vec<BigUintAsHex>,
+    /// The gas left at the end of the run.
+    pub gas_counter: Option<BigUintAsHex>,
+}
+impl From<&RunResultStarknet> for ReplayOutputs {
+    fn from(result: &RunResultStarknet) -> Self {
+        let (panicked, values) = match &result.value {
+            RunResultValue::Success(values) => (false, values),
+            RunResultValue::Panic(values) => (true, values),
+        };
+        Self {
+            panicked,
+            values: values.iter().map(felt_to_hex).collect(),
+            gas_counter: result.gas_counter.as_ref().map(felt_to_hex),
+        }
+    }
+}
+
+/// An observer recording the hints and syscalls of a run.
+#[derive(Default)]
+struct ReplayRecorder {
+    hints: Vec<RecordedHint>,
+    syscalls: Vec<(usize, String)>,
+}
+impl RunObserver for ReplayRecorder {
+    fn on_hint(&mut self, execution: &HintExecution) {
+        self.hints.push(RecordedHint::from(execution));
+    }
+
+    fn on_syscall(&mut self, step: usize, selector: &str) {
+        self.syscalls.push((step, selector.to_string()));
+    }
+}
+
+/// A divergence of a replayed run from its recording.
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum ReplayMismatch {
+    #[error("the run was recorded with a different program")]
+    DifferentProgram,
+    #[error("the run executed an unrecorded syscall")]
+    UnrecordedSyscall,
+    #[error("the run executed fewer syscalls than recorded")]
+    MissingSyscalls,
+    #[error("the run diverged at the hint of step {step}")]
+    DivergedAtStep { step: usize },
+    #[error("the request of the hint of step {step} differs")]
+    RequestDiffers { step: usize },
+    #[error("the run diverged at hint #{index}")]
+    DivergedAtHint { index: usize },
+    #[error("the outputs of the run differ")]
+    OutputsDiffer,
+}
+
+/// A hint handler feeding the recorded responses of the syscalls and cheatcodes to a replayed
+/// run, instead of executing them.
+struct ResponseFeed<'a> {
+    /// The recorded syscalls and cheatcodes not fed yet.
+    hints: std::iter::Peekable<Box<dyn Iterator<Item = &'a RecordedHint> + 'a>>,
+    /// The divergence of the replayed run from the recording, if it diverged.
+    mismatch: Option<ReplayMismatch>,
+}
+impl<'a> ResponseFeed<'a> {
+    fn new(replay: &'a RunReplay) -> Self {
+        let hints: Box<dyn Iterator<Item = &'a RecordedHint>> =
+            Box::new(replay.hints.iter().filter(|hint| hint.response.is_some()));
+        Self { hints: hints.peekable(), mismatch: None }
+    }
+
+    /// Writes the recorded response of the next syscall or cheatcode to the memory.
+    fn feed(&mut self, vm: &mut VirtualMachine, hint: &Hint) -> Result<(), ReplayMismatch> {
+        let recorded = self.hints.next().ok_or(ReplayMismatch::UnrecordedSyscall)?;
+        if recorded.pc != vm.get_pc().offset {
+            return Err(ReplayMismatch::DivergedAtStep { step: recorded.step });
+        }
+        let response = recorded.response.as_ref().unwrap();
+        let mismatch = || ReplayMismatch::RequestDiffers { step: recorded.step };
+        for (segment_index, content) in &response.segments {
+            let mut ptr = vm.add_memory_segment();
+            while ptr.segment_index < *segment_index {
+                ptr = vm.add_memory_segment();
+            }
+            if ptr.segment_index != *segment_index {
+                return Err(mismatch());
+            }
+            write_values(vm, ptr, content).map_err(|_| mismatch())?;
+        }
+        if let Hint::Starknet(StarknetHint::SystemCall { system }) = hint {
+            let ptr = extract_relocatable(vm, system).map_err(|_| mismatch())?;
+            let request: Vec<_> =
+                read_written_cells(vm, ptr).iter().map(ReplayValue::from).collect();
+            if request != response.request {
+                return Err(mismatch());
+            }
+            let response_ptr = (ptr + request.len()).unwrap();
+            write_values(vm, response_ptr, &response.response).map_err(|_| mismatch())?;
+        }
+        let outputs = hint_operands(hint).outputs;
+        if outputs.len() != recorded.outputs.len() {
+            return Err(mismatch());
+        }
+        for ((_, cell), value) in outputs.into_iter().zip(&recorded.outputs) {
+            if let Some(value) = value {
+                vm.insert_value(cell_ref_to_relocatable(cell, vm), MaybeRelocatable::from(value))
+                    .map_err(|_| mismatch())?;
+            }
+        }
+        Ok(())
+    }
+}
+impl HintHandler for &mut ResponseFeed<'_> {
+    fn execute_hint(
+        &mut self,
+        vm: &mut VirtualMachine,
+        _exec_scopes: &mut ExecutionScopes,
+        hint: &Hint,
+    ) -> Result<bool, HintError> {
+        if !matches!(hint, Hint::Starknet(_)) {
+            return Ok(false);
+        }
+        match self.feed(vm, hint) {
+            Ok(()) => Ok(true),
+            Err(mismatch) => {
+                self.mismatch = Some(mismatch.clone());
+                Err(HintError::CustomHint(Box::from(format!("Replay mismatch: {mismatch}."))))
+            }
+        }
+    }
+}
+
+/// Writes the given values to the consecutive cells starting at `ptr`.
+fn write_values(
+    vm: &mut VirtualMachine,
+    ptr: Relocatable,
+    values: &[ReplayValue],
+) -> Result<(), HintError> {
+    for (i, value) in values.iter().enumerate() {
+        vm.insert_value((ptr + i)?, MaybeRelocatable::from(value))?;
+    }
+    Ok(())
+}
+
+impl SierraCasmRunner {
+    /// Runs a function in the context of the given starknet state, recording the run as a replay.
+    pub fn record_run(
+        &self,
+        func: &Function,
+        args: &[Arg],
+        available_gas: Option<usize>,
+        starknet_state: StarknetState,
+    ) -> Result<(RunResultStarknet, RunReplay), RunnerError> {
+        let mut recorder = ReplayRecorder::default();
+        let result = self.run_function_with_observer(
+            func,
+            args,
+            available_gas,
+            starknet_state,
+            &mut recorder,
+        )?;
+        let replay = RunReplay {
+            program_hash: self.program_hash(),
+            function: func.id.to_string(),
+            args: args.iter().map(ReplayArg::from).collect(),
+            available_gas,
+            hints: recorder.hints,
+            syscalls: recorder.syscalls,
+            outputs: ReplayOutputs::from(&result),
+        };
+        Ok((result, replay))
+    }
+
+    /// Re-executes a recorded run, feeding it the recorded responses of its syscalls and
+    /// cheatcodes, and verifying that it is reproduced - that the program is the one the run was
+    /// recorded with, that the same hints are executed with the same outputs and the same requests
+    /// to the syscalls, and that the same outputs are returned. Returns the result of the
+    /// re-executed run.
+    pub fn replay_run(&self, replay: &RunReplay) -> Result<RunResultStarknet, RunnerError> {
+        if self.program_hash() != replay.program_hash {
+            return Err(RunnerError::ReplayMismatch(ReplayMismatch::DifferentProgram));
+        }
+        let func = self
+            .sierra_program
+            .funcs
+            .iter()
+            .find(|func| func.id.to_string() == replay.function)
+            .ok_or_else(|| RunnerError::MissingFunction {
+                suffix: replay.function.clone(),
+                suggestions: vec![],
+            })?;
+        let args: Vec<Arg> = replay.args.iter().map(Arg::from).collect();
+        let mut recorder = ReplayRecorder::default();
+        let mut feed = ResponseFeed::new(replay);
+        let result = self.run_function_with_starknet_context_ex(
+            func,
+            &args,
+            replay.available_gas,
+            StarknetState::default(),
+            Some(&mut recorder),
+            vec![Box::new(&mut feed)],
+            Default::default(),
+        );
+        if let Some(mismatch) = feed.mismatch.take() {
+            return Err(RunnerError::ReplayMismatch(mismatch));
+        }
+        let result = result?;
+        if feed.hints.peek().is_some() {
+            return Err(RunnerError::ReplayMismatch(ReplayMismatch::MissingSyscalls));
+        }
+        if let Some(idx) = first_divergence(&replay.hints, &recorder.hints) {
+            return Err(RunnerError::ReplayMismatch(ReplayMismatch::DivergedAtHint { index: idx }));
+        }
+        if replay.outputs != ReplayOutputs::from(&result) {
+            return Err(RunnerError::ReplayMismatch(ReplayMismatch::OutputsDiffer));
+        }
+        Ok(result)
+    }
+
+    /// Returns the hash of the Sierra program, identifying the program of recorded runs.
+    fn program_hash(&self) -> BigUintAsHex {
+        BigUintAsHex { value: starknet_keccak(self.sierra_program.to_string().as_bytes()) }
+    }
+}
+
+/// Returns the index of the first difference between the recorded and the actual events, if any.
+fn first_divergence<T: PartialEq>(recorded: &[T], actual: &[T]) -> Option<usize> {
+    (0..recorded.len().max(actual.len())).find(|idx| recorded.get(*idx) != actual.get(*idx))
+}
+
+fn felt_to_hex(value: &Felt252) -> BigUintAsHex {
+    BigUintAsHex { value: value.to_biguint() }
+}
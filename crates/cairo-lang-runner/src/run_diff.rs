@@ -0,0 +1,239 @@
+//! Structured comparison of the results of two runs, for regression checks, e.g. whether a refactor
+//! changed the behavior or the gas usage of a function.
+
+use cairo_felt::Felt252;
+use cairo_lang_sierra::extensions::core::CoreTypeConcrete;
+use cairo_lang_sierra::ids::ConcreteTypeId;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+use num_traits::ToPrimitive;
+
+use crate::state_diff::sorted_keys;
+use crate::{Arg, RunResultStarknet, RunResultValue, SierraCasmRunner};
+
+/// An event, as its keys and data.
+pub type Event = (Vec<Felt252>, Vec<Felt252>);
+
+/// The differences between the results of two runs, `a` and `b`. Every field is `None` or empty
+/// if the runs agree on it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunResultsDiff {
+    /// The returned values of the runs, if they differ.
+    pub value: Option<(ReturnedValue, ReturnedValue)>,
+    /// The gas used by the runs, if it differs.
+    pub gas_used: Option<(Option<usize>, Option<usize>)>,
+    /// The number of steps of the runs, if it differs.
+    pub n_steps: Option<(usize, usize)>,
+    /// The contracts whose emitted events differ, sorted by address.
+    pub events: Vec<ContractEventsDiff>,
+    /// The storage values that differ, sorted by contract address and then by key.
+    pub storage: Vec<StorageValueDiff>,
+}
+impl RunResultsDiff {
+    /// Returns whether the runs have the same results.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Returns whether the runs behave differently - whether their return values, events or
+    /// storage differ, as opposed to only their costs.
+    pub fn behavior_changed(&self) -> bool {
+        self.value.is_some() || !self.events.is_empty() || !self.storage.is_empty()
+    }
+}
+
+/// The returned value of a run, as compared between runs: the contents of the arrays of a
+/// successful run are compared rather than their pointers, which depend on the memory layout of
+/// the run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReturnedValue {
+    /// The values of a successful run, with the contents of the arrays they point to.
+    Success(Vec<Arg>),
+    /// The values of a successful run whose arrays could not be followed in the memory.
+    Raw(Vec<Felt252>),
+    /// The panic data of a panicking run.
+    Panic(Vec<Felt252>),
+}
+impl ReturnedValue {
+    fn new(result: &RunResultStarknet) -> Self {
+        match (&result.value, &result.value_contents) {
+            (RunResultValue::Success(_), Some(contents)) => Self::Success(contents.clone()),
+            (RunResultValue::Success(values), None) => Self::Raw(values.clone()),
+            (RunResultValue::Panic(panic_data), _) => Self::Panic(panic_data.clone()),
+        }
+    }
+}
+
+/// The events emitted by a contract in two runs, in the order of emission, when they differ.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractEventsDiff {
+    pub contract_address: Felt252,
+    pub a: Vec<Event>,
+    pub b: Vec<Event>,
+}
+
+/// A storage value of a contract that differs between two runs. Unwritten values are zero.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageValueDiff {
+    pub contract_address: Felt252,
+    pub key: Felt252,
+    pub a: Felt252,
+    pub b: Felt252,
+}
+
+/// Compares the results of two runs, e.g. of two versions of the same function with the same
+/// arguments and starting state.
+pub fn diff_run_results(a: &RunResultStarknet, b: &RunResultStarknet) -> RunResultsDiff {
+    let (a_state, b_state) = (&a.starknet_state, &b.starknet_state);
+    let events = a_state
+        .logging_contracts()
+        .chain(b_state.logging_contracts())
+        .unique()
+        .sorted_by_key(|address| address.to_biguint())
+        .filter_map(|address| {
+            let a_events = a_state.contract_events(address).cloned().collect_vec();
+            let b_events = b_state.contract_events(address).cloned().collect_vec();
+            (a_events != b_events).then(|| ContractEventsDiff {
+                contract_address: address.clone(),
+                a: a_events,
+                b: b_events,
+            })
+        })
+        .collect();
+    let empty_storage = OrderedHashMap::default();
+    let storage = sorted_keys(&a_state.storage, &b_state.storage)
+        .into_iter()
+        .flat_map(|address| {
+            let a_storage = a_state.storage.get(address).unwrap_or(&empty_storage);
+            let b_storage = b_state.storage.get(address).unwrap_or(&empty_storage);
+            sorted_keys(a_storage, b_storage).into_iter().filter_map(move |key| {
+                let zero = Felt252::from(0);
+                let a_value = a_storage.get(key).unwrap_or(&zero);
+                let b_value = b_storage.get(key).unwrap_or(&zero);
+                (a_value != b_value).then(|| StorageValueDiff {
+                    contract_address: address.clone(),
+                    key: key.clone(),
+                    a: a_value.clone(),
+                    b: b_value.clone(),
+                })
+            })
+        })
+        .collect();
+    let (a_value, b_value) = (ReturnedValue::new(a), ReturnedValue::new(b));
+    let (a_gas, b_gas) = (a.gas_used(), b.gas_used());
+    let (a_steps, b_steps) = (a.used_resources.n_steps, b.used_resources.n_steps);
+    RunResultsDiff {
+        value: (a_value != b_value).then_some((a_value, b_value)),
+        gas_used: (a_gas != b_gas).then_some((a_gas, b_gas)),
+        n_steps: (a_steps != b_steps).then_some((a_steps, b_steps)),
+        events,
+        storage,
+    }
+}
+
+/// Reads the values of a successful run, of the given types, following their pointers to the
+/// contents of their arrays and boxes in the memory of the run. Returns `None` if the memory does
+/// not hold the pointed values.
+pub(crate) fn read_value_contents(
+    runner: &SierraCasmRunner,
+    types: &[ConcreteTypeId],
+    values: &[Felt252],
+    memory: &[Option<Felt252>],
+) -> Option<Vec<Arg>> {
+    let reader = ValueContentsReader { runner, memory };
+    let mut contents = vec![];
+    let mut values = values;
+    for ty in types {
+        let (value, rest) = split_prefix(values, reader.size(ty))?;
+        reader.read(ty, value, &mut contents)?;
+        values = rest;
+    }
+    values.is_empty().then_some(contents)
+}
+
+/// Reads values from the memory of a run, according to their Sierra types.
+struct ValueContentsReader<'a> {
+    runner: &'a SierraCasmRunner,
+    memory: &'a [Option<Felt252>],
+}
+impl ValueContentsReader<'_> {
+    /// Reads `value`, a value of type `ty`, into `contents`.
+    fn read(&self, ty: &ConcreteTypeId, value: &[Felt252], contents: &mut Vec<Arg>) -> Option<()> {
+        match self.runner.sierra_program_registry.get_type(ty).ok()? {
+            CoreTypeConcrete::Array(array) => {
+                let [start, end] = value else { return None };
+                let size = end.to_usize()?.checked_sub(start.to_usize()?)?;
+                let cells = self.read_memory(start, size)?;
+                let element_size = self.size(&array.ty);
+                let mut elements = vec![];
+                if element_size != 0 {
+                    for element in cells.chunks(element_size) {
+                        self.read(&array.ty, element, &mut elements)?;
+                    }
+                }
+                contents.push(Arg::Array(elements));
+            }
+            CoreTypeConcrete::Box(boxed) => {
+                let [ptr] = value else { return None };
+                let cells = self.read_memory(ptr, self.size(&boxed.ty))?;
+                self.read(&boxed.ty, &cells, contents)?;
+            }
+            CoreTypeConcrete::Nullable(nullable) => {
+                let [ptr] = value else { return None };
+                if ptr == &Felt252::from(0) {
+                    contents.push(Arg::Value(ptr.clone()));
+                } else {
+                    let cells = self.read_memory(ptr, self.size(&nullable.ty))?;
+                    let mut inner = vec![];
+                    self.read(&nullable.ty, &cells, &mut inner)?;
+                    contents.push(Arg::Array(inner));
+                }
+            }
+            CoreTypeConcrete::Snapshot(wrapped) | CoreTypeConcrete::NonZero(wrapped) => {
+                self.read(&wrapped.ty, value, contents)?
+            }
+            CoreTypeConcrete::Struct(structure) => {
+                let mut value = value;
+                for member in &structure.members {
+                    let (member_value, rest) = split_prefix(value, self.size(member))?;
+                    self.read(member, member_value, contents)?;
+                    value = rest;
+                }
+            }
+            CoreTypeConcrete::Enum(enm) => {
+                let (selector, payload) = value.split_first()?;
+                let n_variants = enm.variants.len();
+                let selector = selector.to_usize()?;
+                // See `build_enum_init` of the Sierra to CASM compiler for the variant selectors.
+                let index = if n_variants <= 2 {
+                    selector
+                } else {
+                    n_variants.checked_sub((selector + 1) / 2)?
+                };
+                let variant = enm.variants.get(index)?;
+                // The variant value is padded from the left to the size of the largest variant.
+                let padding = payload.len().checked_sub(self.size(variant))?;
+                contents.extend(value[..=padding].iter().cloned().map(Arg::Value));
+                self.read(variant, &payload[padding..], contents)?;
+            }
+            _ => contents.extend(value.iter().cloned().map(Arg::Value)),
+        }
+        Some(())
+    }
+
+    /// Returns the size of the given type in memory.
+    fn size(&self, ty: &ConcreteTypeId) -> usize {
+        self.runner.type_sizes[ty] as usize
+    }
+
+    /// Reads `size` cells of the memory, starting at `start`.
+    fn read_memory(&self, start: &Felt252, size: usize) -> Option<Vec<Felt252>> {
+        let start = start.to_usize()?;
+        self.memory.get(start..start.checked_add(size)?)?.iter().cloned().collect()
+    }
+}
+
+/// Splits the first `size` values from the given values, if there are enough of them.
+fn split_prefix(values: &[Felt252], size: usize) -> Option<(&[Felt252], &[Felt252])> {
+    (size <= values.len()).then(|| values.split_at(size))
+}
@@ -1,5 +1,33 @@
 use cairo_felt::Felt252;
 use num_traits::Zero;
+use thiserror::Error;
+
+#[cfg(test)]
+#[path = "short_string_test.rs"]
+mod test;
+
+/// The maximal length of a Cairo short-string, the number of bytes fitting in a felt252.
+pub const SHORT_STRING_MAX_LEN: usize = 31;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ShortStringError {
+    #[error("Short strings can have at most 31 characters, got {0}.")]
+    TooLong(usize),
+    #[error("Short strings can only contain ASCII characters, got `{0}`.")]
+    NonAscii(char),
+}
+
+/// Encodes a string as a Cairo short-string, i.e. a felt252 whose big-endian bytes are the ASCII
+/// characters of the string, as in the literal `'hello'`.
+pub fn encode_cairo_short_string(value: &str) -> Result<Felt252, ShortStringError> {
+    if let Some(c) = value.chars().find(|c| !c.is_ascii()) {
+        return Err(ShortStringError::NonAscii(c));
+    }
+    if value.len() > SHORT_STRING_MAX_LEN {
+        return Err(ShortStringError::TooLong(value.len()));
+    }
+    Ok(Felt252::from_bytes_be(value.as_bytes()))
+}
 
 /// Converts a bigint representing a felt252 to a Cairo short-string.
 pub fn as_cairo_short_string(value: &Felt252) -> Option<String> {
@@ -25,7 +53,7 @@ pub fn as_cairo_short_string_ex(value: &Felt252, length: usize) -> Option<String
     if length == 0 {
         return if value.is_zero() { Some("".to_string()) } else { None };
     }
-    if length > 31 {
+    if length > SHORT_STRING_MAX_LEN {
         // A short string can't be longer than 31 bytes.
         return None;
     }
@@ -0,0 +1,19 @@
+use cairo_felt::Felt252;
+use test_case::test_case;
+
+use super::{as_cairo_short_string, encode_cairo_short_string, ShortStringError};
+
+#[test_case("", Ok(Felt252::from(0)); "empty")]
+#[test_case("hello", Ok(Felt252::from(0x68656c6c6f_u64)); "hello")]
+#[test_case("a".repeat(31).as_str(), Ok(Felt252::from_bytes_be(&[b'a'; 31])); "longest")]
+#[test_case("a".repeat(32).as_str(), Err(ShortStringError::TooLong(32)); "too long")]
+#[test_case("héllo", Err(ShortStringError::NonAscii('é')); "non ascii")]
+fn test_encode_cairo_short_string(value: &str, expected: Result<Felt252, ShortStringError>) {
+    assert_eq!(encode_cairo_short_string(value), expected);
+}
+
+#[test]
+fn test_short_string_round_trip() {
+    let encoded = encode_cairo_short_string("Out of gas").unwrap();
+    assert_eq!(as_cairo_short_string(&encoded), Some("Out of gas".to_string()));
+}
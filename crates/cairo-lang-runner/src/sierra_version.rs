@@ -0,0 +1,38 @@
+//! Negotiation of the Sierra versions of the programs the runner can run.
+use cairo_lang_sierra::program::Program;
+use cairo_lang_starknet_classes::compiler_version::{current_sierra_version_id, VersionId};
+use cairo_lang_starknet_classes::contract_class::ContractClass;
+
+use crate::RunnerError;
+
+#[cfg(test)]
+#[path = "sierra_version_test.rs"]
+mod test;
+
+/// Returns the oldest and the newest Sierra versions of the programs the runner can run. Programs
+/// of another major version are incompatible, and programs of a newer version may use libfuncs
+/// unknown to the runner.
+pub fn supported_sierra_versions() -> (VersionId, VersionId) {
+    let newest = current_sierra_version_id();
+    (VersionId { major: newest.major, minor: 0, patch: 0 }, newest)
+}
+
+/// Checks that the runner can run Sierra programs of the given version.
+pub fn check_sierra_version(version: VersionId) -> Result<(), RunnerError> {
+    let (oldest, newest) = supported_sierra_versions();
+    let supported = version.major == newest.major
+        && (version.minor, version.patch) <= (newest.minor, newest.patch);
+    if supported {
+        Ok(())
+    } else {
+        Err(RunnerError::UnsupportedSierraVersion { version, oldest, newest })
+    }
+}
+
+/// Extracts the Sierra program of a contract class, after checking that the runner can run
+/// programs of its Sierra version.
+pub fn extract_runnable_program(contract_class: &ContractClass) -> Result<Program, RunnerError> {
+    let (sierra_version, _) = contract_class.versions()?;
+    check_sierra_version(sierra_version)?;
+    Ok(contract_class.extract_sierra_program()?)
+}
@@ -0,0 +1,43 @@
+use cairo_lang_sierra::program::Program;
+use cairo_lang_starknet_classes::compiler_version::{current_sierra_version_id, VersionId};
+use cairo_lang_starknet_classes::contract_class::ContractClass;
+
+use super::{check_sierra_version, extract_runnable_program, supported_sierra_versions};
+use crate::RunnerError;
+
+#[test]
+fn test_check_sierra_version() {
+    let newest = current_sierra_version_id();
+    let (oldest, _) = supported_sierra_versions();
+    for (version, supported) in [
+        (oldest, true),
+        (newest, true),
+        (VersionId { patch: newest.patch + 1, ..newest }, false),
+        (VersionId { minor: newest.minor + 1, patch: 0, ..newest }, false),
+        (VersionId { major: newest.major + 1, minor: 0, patch: 0 }, false),
+        (VersionId { major: newest.major - 1, ..newest }, false),
+    ] {
+        assert_eq!(check_sierra_version(version).is_ok(), supported, "Version {version}.");
+    }
+}
+
+#[test]
+fn test_extract_runnable_program() {
+    let program = Program {
+        type_declarations: vec![],
+        libfunc_declarations: vec![],
+        statements: vec![],
+        funcs: vec![],
+    };
+    let mut contract_class = ContractClass::new(&program, Default::default(), None).unwrap();
+    assert_eq!(extract_runnable_program(&contract_class).unwrap(), program);
+
+    // The Sierra version is the first felt252s of the program, as its major, minor and patch.
+    let newest = current_sierra_version_id();
+    contract_class.sierra_program[1].value += 1u32;
+    assert!(matches!(
+        extract_runnable_program(&contract_class),
+        Err(RunnerError::UnsupportedSierraVersion { version, .. })
+            if version == VersionId { minor: newest.minor + 1, ..newest }
+    ));
+}
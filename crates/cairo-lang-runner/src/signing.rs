@@ -0,0 +1,239 @@
+//! Cheatcodes computing public keys and signatures of test keys during runs, so that signature
+//! verification can be exercised without precomputing signatures offline.
+
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ff::{Field, PrimeField};
+use cairo_felt::Felt252;
+use cairo_lang_casm::hints::{Hint, StarknetHint};
+use cairo_vm::types::exec_scope::ExecutionScopes;
+use cairo_vm::vm::errors::hint_errors::HintError;
+use cairo_vm::vm::vm_core::VirtualMachine;
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use starknet_crypto::{get_public_key, rfc6979_generate_k, sign, FieldElement, SignError};
+use {ark_secp256k1 as secp256k1, ark_secp256r1 as secp256r1};
+
+use crate::casm_run::{
+    cell_ref_to_relocatable, extract_relocatable, felt252_to_field_element,
+    field_element_to_felt252, vm_get_range, HintHandler, MemBuffer,
+};
+
+#[cfg(test)]
+#[path = "signing_test.rs"]
+mod test;
+
+/// A [HintHandler] adding cheatcodes for signing with test keys, with the following selectors,
+/// where `u256` values are passed as their `low` and `high` parts:
+/// - `stark_public_key`: `[private_key]` -> `[public_key]`.
+/// - `stark_sign`: `[private_key, message_hash]` -> `[r, s]`.
+/// - `secp256k1_public_key`, `secp256r1_public_key`: `[private_key: u256]` -> `[x: u256, y: u256]`.
+/// - `secp256k1_sign`, `secp256r1_sign`: `[private_key: u256, message_hash: u256]` -> `[r: u256, s:
+///   u256, y_parity]`, with a low `s`.
+///
+/// The nonces of the signatures are derived deterministically from the key and the message, so
+/// the signatures must only be used for testing.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SigningCheatcodes;
+
+impl HintHandler for SigningCheatcodes {
+    fn execute_hint(
+        &mut self,
+        vm: &mut VirtualMachine,
+        _exec_scopes: &mut ExecutionScopes,
+        hint: &Hint,
+    ) -> Result<bool, HintError> {
+        let Hint::Starknet(StarknetHint::Cheatcode {
+            selector,
+            input_start,
+            input_end,
+            output_start,
+            output_end,
+        }) = hint
+        else {
+            return Ok(false);
+        };
+        let selector = selector.value.to_bytes_be().1;
+        let Ok(selector) = std::str::from_utf8(&selector) else {
+            return Ok(false);
+        };
+        let selector = selector.to_string();
+        let input_start = extract_relocatable(vm, input_start)?;
+        let input_end = extract_relocatable(vm, input_end)?;
+        let Some(outputs) = signing_cheatcode(&selector, vm_get_range(vm, input_start, input_end)?)
+        else {
+            return Ok(false);
+        };
+        let outputs = outputs.map_err(|error| {
+            HintError::CustomHint(Box::from(format!("`{selector}` cheatcode failed: {error}")))
+        })?;
+        let mut res_segment = MemBuffer::new_segment(vm);
+        let res_segment_start = res_segment.ptr;
+        res_segment.write_data(outputs.iter())?;
+        let res_segment_end = res_segment.ptr;
+        vm.insert_value(cell_ref_to_relocatable(output_start, vm), res_segment_start)?;
+        vm.insert_value(cell_ref_to_relocatable(output_end, vm), res_segment_end)?;
+        Ok(true)
+    }
+}
+
+/// Executes the signing cheatcode with the given selector, or returns `None` if it is not a
+/// signing cheatcode.
+fn signing_cheatcode(selector: &str, inputs: Vec<Felt252>) -> Option<Result<Vec<Felt252>, String>> {
+    Some(match selector {
+        "stark_public_key" => stark_public_key(inputs),
+        "stark_sign" => stark_sign(inputs),
+        "secp256k1_public_key" => secp_public_key::<secp256k1::Config>(inputs),
+        "secp256k1_sign" => secp_sign::<secp256k1::Config>(inputs),
+        "secp256r1_public_key" => secp_public_key::<secp256r1::Config>(inputs),
+        "secp256r1_sign" => secp_sign::<secp256r1::Config>(inputs),
+        _ => return None,
+    })
+}
+
+/// Computes the public key of a Stark curve private key.
+fn stark_public_key(inputs: Vec<Felt252>) -> Result<Vec<Felt252>, String> {
+    let [private_key] = <[Felt252; 1]>::try_from(inputs).map_err(wrong_input_count(1))?;
+    let private_key = felt252_to_field_element(&private_key);
+    Ok(vec![field_element_to_felt252(&get_public_key(&private_key))])
+}
+
+/// Signs a message hash with a Stark curve private key, with an RFC-6979 nonce.
+fn stark_sign(inputs: Vec<Felt252>) -> Result<Vec<Felt252>, String> {
+    let [private_key, message_hash] =
+        <[Felt252; 2]>::try_from(inputs).map_err(wrong_input_count(2))?;
+    let private_key = felt252_to_field_element(&private_key);
+    let message_hash = felt252_to_field_element(&message_hash);
+    let mut seed = None;
+    loop {
+        let k = rfc6979_generate_k(&message_hash, &private_key, seed.as_ref());
+        match sign(&private_key, &message_hash, &k) {
+            Ok(signature) => {
+                return Ok(vec![
+                    field_element_to_felt252(&signature.r),
+                    field_element_to_felt252(&signature.s),
+                ]);
+            }
+            // Retry with another nonce.
+            Err(SignError::InvalidK) => {
+                seed = Some(seed.unwrap_or(FieldElement::ZERO) + FieldElement::ONE);
+            }
+            Err(SignError::InvalidMessageHash) => {
+                return Err("The message hash must be smaller than 2**251.".into());
+            }
+        }
+    }
+}
+
+/// Computes the public key of a private key of a secp256 curve.
+fn secp_public_key<Curve: SWCurveConfig>(inputs: Vec<Felt252>) -> Result<Vec<Felt252>, String>
+where
+    Curve::BaseField: PrimeField,
+    Curve::ScalarField: PrimeField,
+{
+    let [low, high] = <[Felt252; 2]>::try_from(inputs).map_err(wrong_input_count(2))?;
+    let private_key = secp_private_key::<Curve>(u256_from_parts(low, high)?)?;
+    let public_key: Affine<Curve> = (Curve::GENERATOR * private_key).into();
+    Ok([u256_to_parts(public_key.x.into()), u256_to_parts(public_key.y.into())].concat())
+}
+
+/// Signs a message hash with a private key of a secp256 curve.
+fn secp_sign<Curve: SWCurveConfig>(inputs: Vec<Felt252>) -> Result<Vec<Felt252>, String>
+where
+    Curve::BaseField: PrimeField,
+    Curve::ScalarField: PrimeField,
+{
+    let [key_low, key_high, hash_low, hash_high] =
+        <[Felt252; 4]>::try_from(inputs).map_err(wrong_input_count(4))?;
+    let private_key = u256_from_parts(key_low, key_high)?;
+    let message_hash = u256_from_parts(hash_low, hash_high)?;
+    let d = secp_private_key::<Curve>(private_key.clone())?;
+    let z = Curve::ScalarField::from(message_hash.clone());
+    let order: BigUint = Curve::ScalarField::MODULUS.into();
+    for attempt in 0_u64.. {
+        let k = secp_nonce::<Curve>(&private_key, &message_hash, attempt);
+        let point: Affine<Curve> = (Curve::GENERATOR * k).into();
+        let x: BigUint = point.x.into();
+        // Points whose `x` is not a valid `r` can't be recovered from the signature.
+        if k.is_zero() || x.is_zero() || x >= order {
+            continue;
+        }
+        let r = Curve::ScalarField::from(x.clone());
+        let Some(k_inverse) = k.inverse() else { continue };
+        let mut s = k_inverse * (z + r * d);
+        if s.is_zero() {
+            continue;
+        }
+        let y: BigUint = point.y.into();
+        let mut y_parity = y.is_odd();
+        let s_value: BigUint = s.into();
+        if s_value > &order >> 1 {
+            s = -s;
+            y_parity = !y_parity;
+        }
+        return Ok([
+            u256_to_parts(x),
+            u256_to_parts(s.into()),
+            vec![Felt252::from(y_parity as u8)],
+        ]
+        .concat());
+    }
+    unreachable!("Exhausted all signing attempts.")
+}
+
+/// Validates a private key of a secp256 curve, returning it as a scalar.
+fn secp_private_key<Curve: SWCurveConfig>(
+    private_key: BigUint,
+) -> Result<Curve::ScalarField, String>
+where
+    Curve::ScalarField: PrimeField,
+{
+    if private_key.is_zero() || private_key >= Curve::ScalarField::MODULUS.into() {
+        return Err("The private key must be in the range [1, N).".into());
+    }
+    Ok(private_key.into())
+}
+
+/// Derives the nonce of the `attempt`-th attempt of signing `message_hash` with `private_key`, by
+/// hashing them with keccak.
+fn secp_nonce<Curve: SWCurveConfig>(
+    private_key: &BigUint,
+    message_hash: &BigUint,
+    attempt: u64,
+) -> Curve::ScalarField
+where
+    Curve::ScalarField: PrimeField,
+{
+    let mut state = [0u64; 25];
+    for (i, digit) in private_key.iter_u64_digits().enumerate() {
+        state[i] = digit;
+    }
+    for (i, digit) in message_hash.iter_u64_digits().enumerate() {
+        state[4 + i] = digit;
+    }
+    state[8] = attempt;
+    keccak::f1600(&mut state);
+    let bytes: Vec<u8> = state[..4].iter().flat_map(|word| word.to_le_bytes()).collect();
+    Curve::ScalarField::from_le_bytes_mod_order(&bytes)
+}
+
+/// Returns an error for receiving a wrong number of inputs.
+fn wrong_input_count(expected: usize) -> impl Fn(Vec<Felt252>) -> String {
+    move |inputs| format!("Expected {expected} inputs, got {}.", inputs.len())
+}
+
+/// Builds a u256 from its `low` and `high` parts.
+fn u256_from_parts(low: Felt252, high: Felt252) -> Result<BigUint, String> {
+    let bound = BigUint::one() << 128;
+    let (low, high) = (low.to_biguint(), high.to_biguint());
+    if low >= bound || high >= bound {
+        return Err("The parts of a u256 must be smaller than 2**128.".into());
+    }
+    Ok(low + (high << 128))
+}
+
+/// Splits a u256 into its `low` and `high` parts.
+fn u256_to_parts(value: BigUint) -> Vec<Felt252> {
+    let (high, low) = value.div_rem(&(BigUint::one() << 128));
+    vec![Felt252::from(low), Felt252::from(high)]
+}
@@ -0,0 +1,46 @@
+use cairo_felt::Felt252;
+use starknet_crypto::{verify, FieldElement};
+use test_case::test_case;
+
+use super::{felt252_to_field_element, signing_cheatcode};
+
+fn felts(values: &[u128]) -> Vec<Felt252> {
+    values.iter().map(|value| Felt252::from(*value)).collect()
+}
+
+#[test]
+fn test_stark_sign() {
+    let [public_key] = <[Felt252; 1]>::try_from(
+        signing_cheatcode("stark_public_key", felts(&[0x1234])).unwrap().unwrap(),
+    )
+    .unwrap();
+    let [r, s] = <[Felt252; 2]>::try_from(
+        signing_cheatcode("stark_sign", felts(&[0x1234, 0x5678])).unwrap().unwrap(),
+    )
+    .unwrap();
+    let [public_key, r, s] = [public_key, r, s].map(|felt| felt252_to_field_element(&felt));
+    assert!(verify(&public_key, &FieldElement::from(0x5678_u32), &r, &s).unwrap());
+}
+
+#[test_case("secp256k1_sign")]
+#[test_case("secp256r1_sign")]
+fn test_secp_sign_is_deterministic_and_low_s(selector: &str) {
+    let inputs = felts(&[7, 0, 0x1234, 0x5678]);
+    let signature = signing_cheatcode(selector, inputs.clone()).unwrap().unwrap();
+    assert_eq!(signature.len(), 5);
+    assert_eq!(signing_cheatcode(selector, inputs).unwrap().unwrap(), signature);
+    // The high part of a low `s` is at most `N / 2` of both curves.
+    assert!(signature[3] <= Felt252::from(u128::MAX >> 1));
+}
+
+#[test_case("stark_sign", felts(&[1]), "Expected 2 inputs, got 1."; "stark wrong inputs")]
+#[test_case("secp256k1_public_key", felts(&[0, 0]), "The private key must be in the range [1, N)."; "zero key")]
+#[test_case("secp256r1_sign", [felts(&[1]), vec![Felt252::from(u128::MAX) + 1u32], felts(&[0, 0])].concat(), "The parts of a u256 must be smaller than 2**128."; "invalid u256")]
+fn test_signing_errors(selector: &str, inputs: Vec<Felt252>, expected: &str) {
+    assert_eq!(signing_cheatcode(selector, inputs), Some(Err(expected.into())));
+}
+
+#[test]
+fn test_unknown_selector() {
+    assert_eq!(signing_cheatcode("oracle", vec![]), None);
+}
@@ -0,0 +1,152 @@
+//! The state diff between two starknet states, in the JSON format of the state diffs of Starknet
+//! state updates, so that it can be compared against the output of a sequencer.
+use cairo_felt::Felt252;
+use cairo_lang_utils::bigint::BigUintAsHex;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::StarknetState;
+
+#[cfg(test)]
+#[path = "state_diff_test.rs"]
+mod test;
+
+/// The changes of a starknet state relative to a base state. All the lists are sorted by address.
+///
+/// Runs do not declare classes, so the declared classes are left for the caller to fill.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDiff {
+    pub storage_diffs: Vec<ContractStorageDiff>,
+    pub deprecated_declared_classes: Vec<BigUintAsHex>,
+    pub declared_classes: Vec<DeclaredClass>,
+    pub deployed_contracts: Vec<DeployedContract>,
+    pub replaced_classes: Vec<ReplacedClass>,
+    pub nonces: Vec<ContractNonce>,
+}
+impl StateDiff {
+    /// Computes the changes of `state` relative to `base`. Storage values and nonces missing from
+    /// a state are considered zero.
+    pub fn new(base: &StarknetState, state: &StarknetState) -> Self {
+        let empty_storage = OrderedHashMap::default();
+        let storage_diffs = sorted_keys(&base.storage, &state.storage)
+            .into_iter()
+            .filter_map(|address| {
+                let base_storage = base.storage.get(address).unwrap_or(&empty_storage);
+                let storage = state.storage.get(address).unwrap_or(&empty_storage);
+                let storage_entries = changed_values(base_storage, storage)
+                    .map(|(key, value)| StorageEntry { key: to_hex(key), value: to_hex(&value) })
+                    .collect_vec();
+                (!storage_entries.is_empty())
+                    .then(|| ContractStorageDiff { address: to_hex(address), storage_entries })
+            })
+            .collect();
+        let (deployed_contracts, replaced_classes) = state
+            .deployed_contracts
+            .iter()
+            .filter(|(address, class_hash)| {
+                base.deployed_contracts.get(*address) != Some(class_hash)
+            })
+            .sorted_by_key(|(address, _)| address.to_biguint())
+            .partition_map(|(address, class_hash)| {
+                if base.deployed_contracts.contains_key(address) {
+                    itertools::Either::Right(ReplacedClass {
+                        contract_address: to_hex(address),
+                        class_hash: to_hex(class_hash),
+                    })
+                } else {
+                    itertools::Either::Left(DeployedContract {
+                        address: to_hex(address),
+                        class_hash: to_hex(class_hash),
+                    })
+                }
+            });
+        let nonces = changed_values(&base.nonces, &state.nonces)
+            .map(|(address, nonce)| ContractNonce {
+                contract_address: to_hex(address),
+                nonce: to_hex(&nonce),
+            })
+            .collect();
+        Self {
+            storage_diffs,
+            deprecated_declared_classes: vec![],
+            declared_classes: vec![],
+            deployed_contracts,
+            replaced_classes,
+            nonces,
+        }
+    }
+
+    /// Serializes the state diff into a JSON string.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// The changed storage entries of a contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractStorageDiff {
+    pub address: BigUintAsHex,
+    pub storage_entries: Vec<StorageEntry>,
+}
+
+/// A storage entry, with its new value.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageEntry {
+    pub key: BigUintAsHex,
+    pub value: BigUintAsHex,
+}
+
+/// A declared class, with the hash of its compiled class.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeclaredClass {
+    pub class_hash: BigUintAsHex,
+    pub compiled_class_hash: BigUintAsHex,
+}
+
+/// A contract, with the hash of its class.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeployedContract {
+    pub address: BigUintAsHex,
+    pub class_hash: BigUintAsHex,
+}
+
+/// A contract whose class was replaced, with the hash of its new class.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplacedClass {
+    pub contract_address: BigUintAsHex,
+    pub class_hash: BigUintAsHex,
+}
+
+/// The new nonce of a contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractNonce {
+    pub contract_address: BigUintAsHex,
+    pub nonce: BigUintAsHex,
+}
+
+/// Returns the keys of both maps, sorted.
+pub(crate) fn sorted_keys<'a, V>(
+    base: &'a OrderedHashMap<Felt252, V>,
+    map: &'a OrderedHashMap<Felt252, V>,
+) -> Vec<&'a Felt252> {
+    base.keys().chain(map.keys()).unique().sorted_by_key(|key| key.to_biguint()).collect()
+}
+
+/// Returns the entries of `map` whose values differ from the values in `base`, including the
+/// entries of `base` missing from `map` as zero, sorted by key.
+fn changed_values<'a>(
+    base: &'a OrderedHashMap<Felt252, Felt252>,
+    map: &'a OrderedHashMap<Felt252, Felt252>,
+) -> impl Iterator<Item = (&'a Felt252, Felt252)> + 'a {
+    let zero = Felt252::from(0);
+    sorted_keys(base, map).into_iter().filter_map(move |key| {
+        let value = map.get(key).unwrap_or(&zero);
+        (base.get(key).unwrap_or(&zero) != value).then(|| (key, value.clone()))
+    })
+}
+
+/// Converts a felt252 to its hex JSON representation.
+fn to_hex(value: &Felt252) -> BigUintAsHex {
+    BigUintAsHex { value: value.to_biguint() }
+}
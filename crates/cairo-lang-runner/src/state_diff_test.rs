@@ -0,0 +1,74 @@
+use cairo_felt::Felt252;
+use indoc::indoc;
+
+use super::StateDiff;
+use crate::StarknetState;
+
+#[test]
+fn test_state_diff() {
+    let felt = |value: u32| Felt252::from(value);
+    let mut base = StarknetState::default();
+    base.deployed_contracts.insert(felt(1), felt(10));
+    base.deployed_contracts.insert(felt(2), felt(20));
+    base.storage.entry(felt(1)).or_default().extend([(felt(5), felt(50)), (felt(6), felt(60))]);
+    base.increment_nonce(&felt(1));
+
+    let mut state = base.clone();
+    // A new contract, and a replaced class.
+    state.deployed_contracts.insert(felt(3), felt(30));
+    state.deployed_contracts.insert(felt(2), felt(21));
+    // A changed value, an unchanged value and a new value.
+    state.storage.entry(felt(1)).or_default().insert(felt(5), felt(51));
+    state.storage.entry(felt(3)).or_default().insert(felt(7), felt(70));
+    // A storage write not changing the value.
+    state.storage.entry(felt(2)).or_default().insert(felt(8), felt(0));
+    state.increment_nonce(&felt(1));
+
+    assert_eq!(
+        StateDiff::new(&base, &state).to_json(),
+        indoc! {r#"
+            {
+              "storage_diffs": [
+                {
+                  "address": "0x1",
+                  "storage_entries": [
+                    {
+                      "key": "0x5",
+                      "value": "0x33"
+                    }
+                  ]
+                },
+                {
+                  "address": "0x3",
+                  "storage_entries": [
+                    {
+                      "key": "0x7",
+                      "value": "0x46"
+                    }
+                  ]
+                }
+              ],
+              "deprecated_declared_classes": [],
+              "declared_classes": [],
+              "deployed_contracts": [
+                {
+                  "address": "0x3",
+                  "class_hash": "0x1e"
+                }
+              ],
+              "replaced_classes": [
+                {
+                  "contract_address": "0x2",
+                  "class_hash": "0x15"
+                }
+              ],
+              "nonces": [
+                {
+                  "contract_address": "0x1",
+                  "nonce": "0x2"
+                }
+              ]
+            }"#}
+    );
+    assert_eq!(StateDiff::new(&state, &state), StateDiff::default());
+}
@@ -0,0 +1,2772 @@
+use cairo_felt::Felt252;
+use cairo_lang_casm::hints::{Hint, StarknetHint};
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_compiler::CompilerConfig;
+use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_sierra::optimization::function_statements;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
+use cairo_lang_sierra_generator::replace_ids::{
+    replace_sierra_ids_in_program, DebugReplacer, SierraIdReplacer,
+};
+use cairo_lang_sierra_to_casm::metadata::MetadataComputationConfig;
+use cairo_lang_starknet::abi::AbiBuilder;
+use cairo_lang_starknet::compile::compile_contract_in_prepared_db;
+use cairo_lang_starknet::contract::{
+    find_contracts, get_contract_abi_functions, get_contracts_info,
+};
+use cairo_lang_starknet::plugin::consts::{CONSTRUCTOR_MODULE, EXTERNAL_MODULE, L1_HANDLER_MODULE};
+use cairo_lang_starknet::starknet_plugin_suite;
+use cairo_lang_starknet_classes::abi::Contract;
+use cairo_lang_starknet_classes::casm_contract_class::ENTRY_POINT_COST;
+use cairo_lang_starknet_classes::compiler_version::{
+    current_compiler_version_id, current_sierra_version_id,
+};
+use cairo_lang_starknet_classes::keccak::starknet_keccak;
+use cairo_lang_utils::arc_unwrap_or_clone;
+use cairo_lang_utils::bigint::BigUintAsHex;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use cairo_vm::types::exec_scope::ExecutionScopes;
+use cairo_vm::vm::errors::hint_errors::HintError;
+use cairo_vm::vm::vm_core::VirtualMachine;
+use indoc::indoc;
+use itertools::{chain, Itertools};
+
+use crate::branch_coverage::BranchCoverage;
+use crate::casm_run::{
+    cell_ref_to_relocatable, read_array_result_as_vec, HintExecution, HintHandler, MemBuffer,
+};
+use crate::event_decoding::{AbiValue, EventDecoder};
+use crate::observer::RunObserver;
+use crate::replay::{ReplayArg, ReplayMismatch, ReplayOutputs, RunReplay};
+use crate::run_diff::{diff_run_results, ContractEventsDiff, ReturnedValue, StorageValueDiff};
+use crate::{
+    calculate_contract_address, calculate_invoke_transaction_hash, calculate_storage_address,
+    calculate_storage_variable_address, hashing, token_gas_cost, Arg, BlockContext, CallKind,
+    GasUsageWarning, HeavySyscallsConfig, InnerPanicPolicy, PanicCategory, ResourceBounds,
+    RunResultValue, RunnerError, RunnerErrorKind, SequentialCall, SierraCasmRunner, StarknetState,
+    TxContext,
+};
+
+/// Compiles the given code, and returns a runner for it along with the class hashes of the
+/// contracts it contains, in declaration order.
+fn setup_contracts_runner(cairo_code: &str) -> (SierraCasmRunner, Vec<Felt252>) {
+    let (runner, class_hashes, _) = setup_contracts_runner_with_abis(cairo_code);
+    (runner, class_hashes)
+}
+
+/// Same as `setup_contracts_runner`, additionally returning the ABIs of the contracts.
+fn setup_contracts_runner_with_abis(
+    cairo_code: &str,
+) -> (SierraCasmRunner, Vec<Felt252>, Vec<Contract>) {
+    let db = RootDatabase::builder()
+        .with_plugin_suite(starknet_plugin_suite())
+        // The class hash of the contract is only generated in tests.
+        .with_cfg(CfgSet::from_iter([Cfg::name("test")]))
+        .detect_corelib()
+        .build()
+        .unwrap();
+    let test_module = setup_test_module(&db, cairo_code).unwrap();
+    DiagnosticsReporter::stderr().with_crates(&[test_module.crate_id]).ensure(&db).unwrap();
+    let replacer = DebugReplacer::new(&db);
+    let contracts_info = get_contracts_info(&db, vec![test_module.crate_id], &replacer).unwrap();
+    let class_hashes = contracts_info.keys().cloned().collect();
+    let abis = find_contracts(&db, &[test_module.crate_id])
+        .into_iter()
+        .map(|contract| {
+            AbiBuilder::from_submodule(&db, contract.submodule_id, Default::default())
+                .unwrap()
+                .finalize()
+                .unwrap()
+        })
+        .collect();
+    let sierra_program = db.get_sierra_program(vec![test_module.crate_id]).unwrap();
+    let runner = SierraCasmRunner::new(
+        replacer.apply(&sierra_program.program),
+        Some(Default::default()),
+        contracts_info,
+        false,
+    )
+    .unwrap();
+    (runner, class_hashes, abis)
+}
+
+/// Same as `setup_contracts_runner`, with the costs of the contract entry points set as done by the
+/// test runner, for contracts whose entry points' builtin costs can't be computed otherwise.
+fn setup_contracts_runner_with_entry_point_costs(
+    cairo_code: &str,
+) -> (SierraCasmRunner, Vec<Felt252>) {
+    let db = RootDatabase::builder()
+        .with_plugin_suite(starknet_plugin_suite())
+        // The class hash of the contract is only generated in tests.
+        .with_cfg(CfgSet::from_iter([Cfg::name("test")]))
+        .detect_corelib()
+        .build()
+        .unwrap();
+    let test_module = setup_test_module(&db, cairo_code).unwrap();
+    DiagnosticsReporter::stderr().with_crates(&[test_module.crate_id]).ensure(&db).unwrap();
+    let replacer = DebugReplacer::new(&db);
+    let contracts_info = get_contracts_info(&db, vec![test_module.crate_id], &replacer).unwrap();
+    let class_hashes = contracts_info.keys().cloned().collect();
+    let function_set_costs = find_contracts(&db, &[test_module.crate_id])
+        .iter()
+        .flat_map(|contract| {
+            chain!(
+                get_contract_abi_functions(&db, contract, EXTERNAL_MODULE).unwrap(),
+                get_contract_abi_functions(&db, contract, CONSTRUCTOR_MODULE).unwrap(),
+                get_contract_abi_functions(&db, contract, L1_HANDLER_MODULE).unwrap(),
+            )
+        })
+        .map(|function| {
+            let function = ConcreteFunctionWithBodyId::from_semantic(&db, function.value);
+            (
+                replacer.replace_function_id(&db.function_with_body_sierra(function).unwrap().id),
+                [(CostTokenType::Const, ENTRY_POINT_COST)].into_iter().collect(),
+            )
+        })
+        .collect();
+    let sierra_program = db.get_sierra_program(vec![test_module.crate_id]).unwrap();
+    let runner = SierraCasmRunner::new(
+        replacer.apply(&sierra_program.program),
+        Some(MetadataComputationConfig { function_set_costs, ..Default::default() }),
+        contracts_info,
+        false,
+    )
+    .unwrap();
+    (runner, class_hashes)
+}
+
+#[test]
+fn test_runner_from_contract_class() {
+    let db = RootDatabase::builder()
+        .with_plugin_suite(starknet_plugin_suite())
+        .detect_corelib()
+        .build()
+        .unwrap();
+    let test_module = setup_test_module(
+        &db,
+        indoc! {"
+            #[starknet::contract]
+            mod counter {
+                #[storage]
+                struct Storage {
+                    value: felt252,
+                }
+
+                #[external(v0)]
+                fn add(ref self: ContractState, amount: felt252) -> felt252 {
+                    let value = self.value.read() + amount;
+                    self.value.write(value);
+                    value
+                }
+            }
+        "},
+    )
+    .unwrap();
+    let mut contract_class = compile_contract_in_prepared_db(
+        &db,
+        None,
+        vec![test_module.crate_id],
+        CompilerConfig { replace_ids: true, ..Default::default() },
+    )
+    .unwrap();
+    let runner =
+        SierraCasmRunner::from_contract_class(&contract_class, Some(Default::default()), false)
+            .unwrap();
+    let result = runner
+        .run_contract_entry_point_by_name(
+            &contract_class.class_hash(),
+            "add",
+            vec![Felt252::from(3)],
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![Felt252::from(3)]));
+
+    // The Sierra version is the first felt252s of the program, as its major, minor and patch.
+    contract_class.sierra_program[0].value += 1u32;
+    assert!(matches!(
+        SierraCasmRunner::from_contract_class(&contract_class, Some(Default::default()), false),
+        Err(RunnerError::UnsupportedSierraVersion { version, .. })
+            if version.major == current_sierra_version_id().major + 1
+    ));
+}
+
+#[test]
+fn test_run_contract_entry_point() {
+    let (runner, class_hashes) = setup_contracts_runner(indoc! {"
+        #[starknet::contract]
+        mod counter {
+            #[storage]
+            struct Storage {
+                value: felt252,
+            }
+
+            #[external(v0)]
+            fn add(ref self: ContractState, amount: felt252) -> felt252 {
+                let value = self.value.read() + amount;
+                self.value.write(value);
+                value
+            }
+        }
+    "});
+    let class_hash = &class_hashes[0];
+
+    let result = runner
+        .run_contract_entry_point_by_name(
+            class_hash,
+            "add",
+            vec![Felt252::from(3)],
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![Felt252::from(3)]));
+    // Running again in the resulting state accumulates the stored value.
+    let result = runner
+        .run_contract_entry_point_by_name(
+            class_hash,
+            "add",
+            vec![Felt252::from(4)],
+            Some(u32::MAX as usize),
+            result.starknet_state,
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![Felt252::from(7)]));
+    // Calldata that does not match the entry point arguments fails deserialization.
+    let result = runner
+        .run_contract_entry_point_by_name(
+            class_hash,
+            "add",
+            vec![],
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(
+        result.value,
+        RunResultValue::Panic(vec![Felt252::from_bytes_be(b"Failed to deserialize param #1")])
+    );
+    assert!(matches!(
+        runner.run_contract_entry_point_by_name(
+            class_hash,
+            "sub",
+            vec![],
+            Some(u32::MAX as usize),
+            Default::default(),
+        ),
+        Err(RunnerError::MissingEntryPoint { .. })
+    ));
+}
+
+#[test]
+fn test_deploy_contract() {
+    let (runner, class_hashes) = setup_contracts_runner(indoc! {"
+        #[starknet::contract]
+        mod counter {
+            #[storage]
+            struct Storage {
+                value: felt252,
+            }
+
+            #[constructor]
+            fn constructor(ref self: ContractState, initial_value: felt252) {
+                assert(initial_value != 0, 'zero initial value');
+                self.value.write(initial_value);
+            }
+        }
+    "});
+    let class_hash = &class_hashes[0];
+    let salt = Felt252::from(7);
+    let deployer_address = Felt252::from(1000);
+    let (contract_address, result) = runner
+        .deploy_contract(
+            class_hash,
+            &salt,
+            vec![Felt252::from(5)],
+            &deployer_address,
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![]));
+    assert_eq!(
+        contract_address,
+        calculate_contract_address(&salt, class_hash, &[Felt252::from(5)], &deployer_address)
+    );
+    assert_eq!(result.starknet_state.deployed_class_hash(&contract_address), Some(class_hash));
+
+    // Deploying to the same address again fails.
+    let Err(err) = runner.deploy_contract(
+        class_hash,
+        &salt,
+        vec![Felt252::from(5)],
+        &deployer_address,
+        Some(u32::MAX as usize),
+        result.starknet_state,
+    ) else {
+        panic!("Expected the deployment to fail.");
+    };
+    assert!(matches!(
+        err,
+        RunnerError::ContractAlreadyDeployed { contract_address: address }
+            if address == contract_address
+    ));
+
+    // A failing constructor reverts the deployment.
+    let (contract_address, result) = runner
+        .deploy_contract(
+            class_hash,
+            &salt,
+            vec![Felt252::from(0)],
+            &deployer_address,
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(
+        result.value,
+        RunResultValue::Panic(vec![Felt252::from_bytes_be(b"zero initial value")])
+    );
+    assert_eq!(result.starknet_state.deployed_class_hash(&contract_address), None);
+}
+
+#[test]
+fn test_call_contract() {
+    let (runner, class_hashes) = setup_contracts_runner(indoc! {"
+        #[starknet::interface]
+        trait ICounter<T> {
+            fn add(ref self: T, amount: felt252) -> felt252;
+        }
+
+        #[starknet::contract]
+        mod counter {
+            #[storage]
+            struct Storage {
+                value: felt252,
+            }
+
+            #[external(v0)]
+            fn add(ref self: ContractState, amount: felt252) -> felt252 {
+                let value = self.value.read() + amount;
+                self.value.write(value);
+                value
+            }
+        }
+
+        #[starknet::contract]
+        mod proxy {
+            use starknet::ContractAddress;
+            use super::{ICounterDispatcher, ICounterDispatcherTrait};
+
+            #[storage]
+            struct Storage {}
+
+            #[external(v0)]
+            fn add_twice(
+                ref self: ContractState, counter: ContractAddress, amount: felt252
+            ) -> felt252 {
+                let counter = ICounterDispatcher { contract_address: counter };
+                counter.add(amount);
+                counter.add(amount)
+            }
+        }
+    "});
+    let deployer_address = Felt252::from(1000);
+    let deploy = |class_hash, starknet_state| {
+        runner
+            .deploy_contract(
+                class_hash,
+                &Felt252::from(0),
+                vec![],
+                &deployer_address,
+                Some(u32::MAX as usize),
+                starknet_state,
+            )
+            .unwrap()
+    };
+    let (counter_address, result) = deploy(&class_hashes[0], Default::default());
+    let (proxy_address, result) = deploy(&class_hashes[1], result.starknet_state);
+
+    let selector = |name: &str| Felt252::from(starknet_keccak(name.as_bytes()));
+    let result = runner
+        .call_contract(
+            &proxy_address,
+            &selector("add_twice"),
+            vec![counter_address.clone(), Felt252::from(3)],
+            &deployer_address,
+            Some(u32::MAX as usize),
+            result.starknet_state,
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![Felt252::from(6)]));
+    // The storage of the counter was updated by the calls from the proxy.
+    let result = runner
+        .call_contract(
+            &counter_address,
+            &selector("add"),
+            vec![Felt252::from(1)],
+            &deployer_address,
+            Some(u32::MAX as usize),
+            result.starknet_state,
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![Felt252::from(7)]));
+    assert!(matches!(
+        runner.call_contract(
+            &Felt252::from(1),
+            &selector("add"),
+            vec![Felt252::from(1)],
+            &deployer_address,
+            Some(u32::MAX as usize),
+            result.starknet_state,
+        ),
+        Err(RunnerError::ContractNotDeployed { .. })
+    ));
+}
+
+#[test]
+fn test_execute_account_transaction() {
+    let (runner, class_hashes) = setup_contracts_runner_with_entry_point_costs(indoc! {"
+        #[starknet::contract(account)]
+        mod account {
+            use starknet::account::Call;
+
+            #[storage]
+            struct Storage {
+                public_key: felt252,
+            }
+
+            #[constructor]
+            fn constructor(ref self: ContractState, public_key: felt252) {
+                self.public_key.write(public_key);
+            }
+
+            #[external(v0)]
+            fn __validate__(self: @ContractState, calls: Array<Call>) -> felt252 {
+                let tx_info = starknet::get_tx_info().unbox();
+                let signature = tx_info.signature;
+                assert(signature.len() == 2, 'invalid signature length');
+                assert(
+                    core::ecdsa::check_ecdsa_signature(
+                        tx_info.transaction_hash, self.public_key.read(), *signature[0], *signature[1]
+                    ),
+                    'invalid signature'
+                );
+                starknet::VALIDATED
+            }
+
+            #[external(v0)]
+            fn __execute__(ref self: ContractState, mut calls: Array<Call>) -> Array<Span<felt252>> {
+                let mut results = array![];
+                loop {
+                    match calls.pop_front() {
+                        Option::Some(call) => {
+                            let result = starknet::call_contract_syscall(
+                                call.to, call.selector, call.calldata
+                            );
+                            results.append(result.unwrap());
+                        },
+                        Option::None => { break; },
+                    };
+                };
+                results
+            }
+        }
+
+        #[starknet::contract]
+        mod counter {
+            #[storage]
+            struct Storage {
+                value: felt252,
+            }
+
+            #[external(v0)]
+            fn add(ref self: ContractState, amount: felt252) -> felt252 {
+                assert(amount != 0, 'zero amount');
+                let value = self.value.read() + amount;
+                self.value.write(value);
+                value
+            }
+        }
+    "});
+    let to_field_element =
+        |felt: &Felt252| starknet_crypto::FieldElement::from_bytes_be(&felt.to_be_bytes()).unwrap();
+    let to_felt =
+        |element: starknet_crypto::FieldElement| Felt252::from_bytes_be(&element.to_bytes_be());
+    let private_key = to_field_element(&Felt252::from(1234));
+    let sign = |transaction_hash: &Felt252| {
+        let message_hash = to_field_element(transaction_hash);
+        let k = starknet_crypto::rfc6979_generate_k(&message_hash, &private_key, None);
+        let signature = starknet_crypto::sign(&private_key, &message_hash, &k).unwrap();
+        vec![to_felt(signature.r), to_felt(signature.s)]
+    };
+    let deploy = |class_hash, calldata, starknet_state| {
+        runner
+            .deploy_contract(
+                class_hash,
+                &Felt252::from(0),
+                calldata,
+                &Felt252::from(1000),
+                Some(u32::MAX as usize),
+                starknet_state,
+            )
+            .unwrap()
+    };
+    let public_key = to_felt(starknet_crypto::get_public_key(&private_key));
+    let (account_address, result) = deploy(&class_hashes[0], vec![public_key], Default::default());
+    let (counter_address, result) = deploy(&class_hashes[1], vec![], result.starknet_state);
+    let calldata = |amount: u32| {
+        let selector = Felt252::from(starknet_keccak(b"add"));
+        vec![Felt252::from(1), counter_address.clone(), selector, Felt252::from(1), amount.into()]
+    };
+    let transaction_hash = |nonce: u32, calldata: &[Felt252], starknet_state: &StarknetState| {
+        calculate_invoke_transaction_hash(
+            &account_address,
+            calldata,
+            &Felt252::from(0),
+            starknet_state.chain_id(),
+            &nonce.into(),
+        )
+    };
+    let execute = |calldata: Vec<Felt252>, signature, starknet_state| {
+        runner
+            .execute_account_transaction(
+                &account_address,
+                calldata,
+                signature,
+                Some(u32::MAX as usize),
+                starknet_state,
+            )
+            .unwrap()
+    };
+
+    let starknet_state = result.starknet_state;
+    let signature = sign(&transaction_hash(0, &calldata(3), &starknet_state));
+    let result = execute(calldata(3), signature, starknet_state);
+    assert_eq!(
+        result.transaction_hash,
+        transaction_hash(0, &calldata(3), &result.validate.starknet_state)
+    );
+    let execute_result = result.execute.as_ref().unwrap();
+    assert_eq!(
+        execute_result.value,
+        RunResultValue::Success(vec![Felt252::from(1), Felt252::from(1), Felt252::from(3)])
+    );
+    let starknet_state = result.into_starknet_state();
+    assert_eq!(starknet_state.nonce(&account_address), Felt252::from(1));
+    assert_eq!(starknet_state.tx_context(), TxContext::default());
+
+    // A transaction signed with the previous nonce is rejected.
+    let signature = sign(&transaction_hash(0, &calldata(5), &starknet_state));
+    let result = execute(calldata(5), signature, starknet_state);
+    assert!(matches!(result.validate.value, RunResultValue::Panic(_)));
+    assert!(result.execute.is_none());
+    let starknet_state = result.into_starknet_state();
+    assert_eq!(starknet_state.nonce(&account_address), Felt252::from(1));
+
+    // A validated transaction increments the nonce even if its execution fails.
+    let signature = sign(&transaction_hash(1, &calldata(0), &starknet_state));
+    let result = execute(calldata(0), signature, starknet_state);
+    assert!(matches!(result.execute.as_ref().unwrap().value, RunResultValue::Panic(_)));
+    let starknet_state = result.into_starknet_state();
+    assert_eq!(starknet_state.nonce(&account_address), Felt252::from(2));
+    let storage = starknet_state.contract_storage(&counter_address).unwrap();
+    assert_eq!(storage.values().collect_vec(), [&Felt252::from(3)]);
+}
+
+#[test]
+fn test_call_traces() {
+    let (runner, class_hashes) = setup_contracts_runner(indoc! {"
+        #[starknet::contract]
+        mod counter {
+            #[storage]
+            struct Storage {
+                value: felt252,
+            }
+
+            #[external(v0)]
+            fn add(ref self: ContractState, amount: felt252) -> felt252 {
+                assert(amount != 0, 'zero amount');
+                let value = self.value.read() + amount;
+                self.value.write(value);
+                value
+            }
+        }
+
+        #[starknet::contract]
+        mod proxy {
+            use starknet::ContractAddress;
+
+            #[storage]
+            struct Storage {}
+
+            #[external(v0)]
+            fn add_all(
+                ref self: ContractState, counter: ContractAddress, amounts: Array<felt252>
+            ) -> u32 {
+                let mut failures = 0;
+                let mut amounts = amounts.span();
+                loop {
+                    match amounts.pop_front() {
+                        Option::Some(amount) => {
+                            let result = starknet::call_contract_syscall(
+                                counter, selector!(\"add\"), array![*amount].span()
+                            );
+                            if result.is_err() {
+                                failures += 1;
+                            }
+                        },
+                        Option::None => { break; },
+                    };
+                };
+                failures
+            }
+        }
+    "});
+    let runner = runner.with_call_traces(true);
+    let deployer_address = Felt252::from(1000);
+    let deploy = |class_hash, starknet_state| {
+        runner
+            .deploy_contract(
+                class_hash,
+                &Felt252::from(0),
+                vec![],
+                &deployer_address,
+                Some(u32::MAX as usize),
+                starknet_state,
+            )
+            .unwrap()
+    };
+    let (counter_address, result) = deploy(&class_hashes[0], Default::default());
+    let (proxy_address, result) = deploy(&class_hashes[1], result.starknet_state);
+    let selector = |name: &str| Felt252::from(starknet_keccak(name.as_bytes()));
+    let [constructor] = &result.call_traces.unwrap()[..] else { panic!("Expected a root call.") };
+    assert_eq!(
+        (
+            constructor.kind,
+            &constructor.caller_address,
+            &constructor.contract_address,
+            &constructor.class_hash,
+            &constructor.selector,
+            constructor.inner_calls.len()
+        ),
+        (
+            CallKind::Constructor,
+            &deployer_address,
+            &proxy_address,
+            &class_hashes[1],
+            &selector("constructor"),
+            0
+        )
+    );
+
+    let calldata =
+        vec![counter_address.clone(), Felt252::from(2), Felt252::from(3), Felt252::from(0)];
+    let result = runner
+        .call_contract(
+            &proxy_address,
+            &selector("add_all"),
+            calldata.clone(),
+            &deployer_address,
+            Some(u32::MAX as usize),
+            result.starknet_state,
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![Felt252::from(1)]));
+    let [root] = &result.call_traces.unwrap()[..] else { panic!("Expected a root call.") };
+    assert_eq!(
+        (
+            root.kind,
+            &root.caller_address,
+            &root.contract_address,
+            &root.class_hash,
+            &root.selector,
+            &root.calldata,
+            &root.result
+        ),
+        (
+            CallKind::CallContract,
+            &deployer_address,
+            &proxy_address,
+            &class_hashes[1],
+            &selector("add_all"),
+            &calldata,
+            &result.value
+        )
+    );
+    assert_eq!(root.used_resources, result.used_resources);
+    let call_traces = &root.inner_calls;
+    let summary = call_traces
+        .iter()
+        .map(|trace| {
+            (
+                trace.kind,
+                trace.caller_address.clone(),
+                trace.contract_address.clone(),
+                trace.calldata.clone(),
+                trace.result.clone(),
+                trace.inner_calls.len(),
+            )
+        })
+        .collect_vec();
+    assert_eq!(
+        summary,
+        [
+            (
+                CallKind::CallContract,
+                proxy_address.clone(),
+                counter_address.clone(),
+                vec![Felt252::from(3)],
+                RunResultValue::Success(vec![Felt252::from(3)]),
+                0
+            ),
+            (
+                CallKind::CallContract,
+                proxy_address,
+                counter_address,
+                vec![Felt252::from(0)],
+                RunResultValue::Panic(vec![Felt252::from_bytes_be(b"zero amount")]),
+                0
+            ),
+        ]
+    );
+    assert!(call_traces.iter().all(|trace| trace.class_hash == class_hashes[0]
+        && trace.selector == selector("add")
+        && trace.used_resources.n_steps > 0));
+}
+
+#[test]
+fn test_branch_coverage_of_inner_calls() {
+    let (runner, class_hashes) = setup_contracts_runner(indoc! {"
+        #[starknet::contract]
+        mod counter {
+            #[storage]
+            struct Storage {}
+
+            #[external(v0)]
+            fn add(ref self: ContractState, amount: felt252) {
+                assert(amount != 0, 'zero amount');
+            }
+        }
+
+        #[starknet::contract]
+        mod proxy {
+            use starknet::{ContractAddress, SyscallResultTrait};
+
+            #[storage]
+            struct Storage {}
+
+            #[external(v0)]
+            fn add_all(ref self: ContractState, counter: ContractAddress, amounts: Array<felt252>) {
+                let mut amounts = amounts.span();
+                while let Option::Some(amount) = amounts.pop_front() {
+                    starknet::call_contract_syscall(counter, selector!(\"add\"), array![*amount].span())
+                        .unwrap_syscall();
+                };
+            }
+        }
+    "});
+    let runner = runner.with_branch_coverage(true);
+    let deployer_address = Felt252::from(1000);
+    let deploy = |class_hash, starknet_state| {
+        runner
+            .deploy_contract(
+                class_hash,
+                &Felt252::from(0),
+                vec![],
+                &deployer_address,
+                Some(u32::MAX as usize),
+                starknet_state,
+            )
+            .unwrap()
+    };
+    let (counter_address, result) = deploy(&class_hashes[0], Default::default());
+    let (proxy_address, result) = deploy(&class_hashes[1], result.starknet_state);
+    let coverage = |amounts: &[i64]| {
+        let calldata = chain!(
+            [counter_address.clone(), Felt252::from(amounts.len())],
+            amounts.iter().map(|amount| Felt252::from(*amount))
+        )
+        .collect();
+        runner
+            .call_contract(
+                &proxy_address,
+                &Felt252::from(starknet_keccak(b"add_all")),
+                calldata,
+                &deployer_address,
+                Some(u32::MAX as usize),
+                result.starknet_state.clone(),
+            )
+            .unwrap()
+            .branch_coverage
+            .unwrap()
+    };
+    // The branches of the counter are only reached through the calls of the proxy.
+    let counter_branch_hits = |coverage: &BranchCoverage| -> usize {
+        let counter_statements = runner
+            .sierra_program
+            .funcs
+            .iter()
+            .filter(|func| func.id.to_string().starts_with("test::counter::"))
+            .flat_map(|func| function_statements(&runner.sierra_program, func.entry_point))
+            .collect::<UnorderedHashSet<_>>();
+        coverage
+            .statements
+            .iter()
+            .filter(|statement| counter_statements.contains(&statement.statement_idx))
+            .flat_map(|statement| statement.hits.iter())
+            .sum()
+    };
+    assert_eq!(counter_branch_hits(&coverage(&[])), 0);
+    let nonzero_coverage = coverage(&[2]);
+    assert!(counter_branch_hits(&nonzero_coverage) > 0);
+    // The zero amount takes the panicking branch of the counter, failing the proxy.
+    let zero_coverage = coverage(&[2, 0]);
+    assert!(zero_coverage.n_taken_branches() > nonzero_coverage.n_taken_branches());
+}
+
+#[test]
+fn test_inner_panic_policy() {
+    let (runner, class_hashes) = setup_contracts_runner(indoc! {"
+        #[starknet::contract]
+        mod counter {
+            #[storage]
+            struct Storage {}
+
+            #[external(v0)]
+            fn add(ref self: ContractState, amount: felt252) {
+                assert(amount != 0, 'zero amount');
+            }
+        }
+
+        #[starknet::contract]
+        mod proxy {
+            use starknet::ContractAddress;
+
+            #[storage]
+            struct Storage {}
+
+            #[external(v0)]
+            fn try_add(ref self: ContractState, counter: ContractAddress, amount: felt252) -> bool {
+                starknet::call_contract_syscall(counter, selector!(\"add\"), array![amount].span())
+                    .is_ok()
+            }
+
+            #[external(v0)]
+            fn relay(ref self: ContractState, counter: ContractAddress, amount: felt252) -> bool {
+                let address = starknet::get_contract_address();
+                let calldata = array![counter.into(), amount];
+                starknet::call_contract_syscall(address, selector!(\"try_add\"), calldata.span())
+                    .is_ok()
+            }
+        }
+    "});
+    let deploy = |class_hash, starknet_state| {
+        runner
+            .deploy_contract(
+                class_hash,
+                &Felt252::from(0),
+                vec![],
+                &Felt252::from(1000),
+                Some(u32::MAX as usize),
+                starknet_state,
+            )
+            .unwrap()
+    };
+    let (counter_address, result) = deploy(&class_hashes[0], Default::default());
+    let (proxy_address, result) = deploy(&class_hashes[1], result.starknet_state);
+    let selector = |name: &str| Felt252::from(starknet_keccak(name.as_bytes()));
+    let relay = |runner: &SierraCasmRunner| {
+        runner.call_contract(
+            &proxy_address,
+            &selector("relay"),
+            vec![counter_address.clone(), Felt252::from(0)],
+            &Felt252::from(1000),
+            Some(u32::MAX as usize),
+            result.starknet_state.clone(),
+        )
+    };
+
+    // By default, the panic is returned to the caller.
+    assert_eq!(relay(&runner).unwrap().value, RunResultValue::Success(vec![Felt252::from(1)]));
+
+    let runner = runner.with_inner_panic_policy(InnerPanicPolicy::Abort);
+    let Err(RunnerError::InnerCallPanicked(inner_call_panic)) = relay(&runner) else {
+        panic!("Expected the run to be aborted.");
+    };
+    assert_eq!(
+        inner_call_panic.call_chain,
+        [(proxy_address, selector("try_add")), (counter_address, selector("add"))]
+    );
+    assert_eq!(inner_call_panic.panic_data, [Felt252::from_bytes_be(b"zero amount")]);
+}
+
+#[test]
+fn test_library_call() {
+    let (runner, class_hashes) = setup_contracts_runner(indoc! {"
+        #[starknet::interface]
+        trait ICounter<T> {
+            fn add(ref self: T, amount: felt252) -> felt252;
+        }
+
+        #[starknet::contract]
+        mod counter {
+            #[storage]
+            struct Storage {
+                value: felt252,
+            }
+
+            #[external(v0)]
+            fn add(ref self: ContractState, amount: felt252) -> felt252 {
+                let value = self.value.read() + amount;
+                self.value.write(value);
+                value
+            }
+        }
+
+        #[starknet::contract]
+        mod delegator {
+            use starknet::ClassHash;
+            use super::{ICounterLibraryDispatcher, ICounterDispatcherTrait};
+
+            #[storage]
+            struct Storage {
+                value: felt252,
+            }
+
+            #[external(v0)]
+            fn add_with_class(
+                ref self: ContractState, class_hash: ClassHash, amount: felt252
+            ) -> felt252 {
+                ICounterLibraryDispatcher { class_hash }.add(amount)
+            }
+
+            #[external(v0)]
+            fn get(self: @ContractState) -> felt252 {
+                self.value.read()
+            }
+        }
+    "});
+    let counter_class_hash = &class_hashes[0];
+    let caller_address = Felt252::from(1000);
+    let (delegator_address, result) = runner
+        .deploy_contract(
+            &class_hashes[1],
+            &Felt252::from(0),
+            vec![],
+            &caller_address,
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+        .unwrap();
+    let mut starknet_state = result.starknet_state;
+    let selector = |name: &str| Felt252::from(starknet_keccak(name.as_bytes()));
+    for (amount, expected_value) in [(3, 3), (4, 7)] {
+        let result = runner
+            .call_contract(
+                &delegator_address,
+                &selector("add_with_class"),
+                vec![counter_class_hash.clone(), Felt252::from(amount)],
+                &caller_address,
+                Some(u32::MAX as usize),
+                starknet_state,
+            )
+            .unwrap();
+        assert_eq!(result.value, RunResultValue::Success(vec![Felt252::from(expected_value)]));
+        starknet_state = result.starknet_state;
+    }
+    // The library calls used the storage of the delegator.
+    let result = runner
+        .call_contract(
+            &delegator_address,
+            &selector("get"),
+            vec![],
+            &caller_address,
+            Some(u32::MAX as usize),
+            starknet_state,
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![Felt252::from(7)]));
+}
+
+#[test]
+fn test_replace_class() {
+    let (runner, class_hashes) = setup_contracts_runner(indoc! {"
+        #[starknet::interface]
+        trait IVersioned<T> {
+            fn version(self: @T) -> felt252;
+        }
+
+        #[starknet::contract]
+        mod v1 {
+            use starknet::{ClassHash, SyscallResultTrait, get_contract_address};
+            use super::{IVersionedDispatcher, IVersionedDispatcherTrait};
+
+            #[storage]
+            struct Storage {}
+
+            #[external(v0)]
+            fn version(self: @ContractState) -> felt252 {
+                1
+            }
+
+            #[external(v0)]
+            fn upgrade_and_get_version(ref self: ContractState, new_class: ClassHash) -> felt252 {
+                starknet::replace_class_syscall(new_class).unwrap_syscall();
+                IVersionedDispatcher { contract_address: get_contract_address() }.version()
+            }
+        }
+
+        #[starknet::contract]
+        mod v2 {
+            #[storage]
+            struct Storage {}
+
+            #[external(v0)]
+            fn version(self: @ContractState) -> felt252 {
+                2
+            }
+        }
+    "});
+    let caller_address = Felt252::from(1000);
+    let (contract_address, result) = runner
+        .deploy_contract(
+            &class_hashes[0],
+            &Felt252::from(0),
+            vec![],
+            &caller_address,
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+        .unwrap();
+    let selector = |name: &str| Felt252::from(starknet_keccak(name.as_bytes()));
+    // Calls made after the class replacement, in the same run, are routed to the new class.
+    let result = runner
+        .call_contract(
+            &contract_address,
+            &selector("upgrade_and_get_version"),
+            vec![class_hashes[1].clone()],
+            &caller_address,
+            Some(u32::MAX as usize),
+            result.starknet_state,
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![Felt252::from(2)]));
+    assert_eq!(
+        result.starknet_state.deployed_class_hash(&contract_address),
+        Some(&class_hashes[1])
+    );
+    // So are later calls.
+    let result = runner
+        .call_contract(
+            &contract_address,
+            &selector("version"),
+            vec![],
+            &caller_address,
+            Some(u32::MAX as usize),
+            result.starknet_state,
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![Felt252::from(2)]));
+}
+
+#[test]
+fn test_decode_events() {
+    let (runner, class_hashes, abis) = setup_contracts_runner_with_abis(indoc! {"
+        #[starknet::contract]
+        mod emitter {
+            #[storage]
+            struct Storage {}
+
+            #[event]
+            #[derive(Drop, starknet::Event)]
+            enum Event {
+                Transfer: Transfer,
+                #[flat]
+                Inner: InnerEvent,
+            }
+
+            #[derive(Drop, starknet::Event)]
+            struct Transfer {
+                #[key]
+                from: felt252,
+                amounts: Span<u256>,
+                ok: bool,
+            }
+
+            #[derive(Drop, starknet::Event)]
+            enum InnerEvent {
+                Ping: Ping,
+            }
+
+            #[derive(Drop, starknet::Event)]
+            struct Ping {
+                value: Option<felt252>,
+            }
+
+            #[external(v0)]
+            fn emit(ref self: ContractState) {
+                self.emit(Transfer { from: 5, amounts: array![7, 8].span(), ok: true });
+                self.emit(InnerEvent::Ping(Ping { value: Option::Some(9) }));
+            }
+        }
+    "});
+    let result = runner
+        .run_contract_entry_point_by_name(
+            &class_hashes[0],
+            "emit",
+            vec![],
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![]));
+    let decoder = EventDecoder::new(&abis[0]);
+    let events = result
+        .starknet_state
+        .contract_events(&Felt252::from(0))
+        .map(|(keys, data)| decoder.decode(keys, data).map(|event| event.to_string()))
+        .collect::<Option<Vec<_>>>()
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![
+            "test::emitter::Event::Transfer(test::emitter::Transfer { from: 5, amounts: \
+             [core::integer::u256 { low: 7, high: 0 }, core::integer::u256 { low: 8, high: 0 }], \
+             ok: core::bool::True })",
+            "test::emitter::Event::Inner(test::emitter::InnerEvent::Ping(test::emitter::Ping { \
+             value: core::option::Option::<core::felt252>::Some(9) }))",
+        ]
+    );
+    // Events not matching the ABI are not decoded.
+    assert_eq!(decoder.decode(&[Felt252::from(1)], &[]), None);
+    let transfer_selector = Felt252::from(starknet_keccak(b"Transfer"));
+    assert!(matches!(
+        decoder
+            .decode(&[transfer_selector, Felt252::from(5)], &[Felt252::from(0), Felt252::from(0)]),
+        Some(AbiValue::Enum { .. })
+    ));
+}
+
+#[test]
+fn test_run_result_events() {
+    let (runner, class_hashes) = setup_contracts_runner(indoc! {"
+        #[starknet::contract]
+        mod emitter {
+            #[storage]
+            struct Storage {}
+
+            #[event]
+            #[derive(Drop, starknet::Event)]
+            enum Event {
+                Ping: Ping,
+            }
+
+            #[derive(Drop, starknet::Event)]
+            struct Ping {
+                #[key]
+                value: felt252,
+            }
+
+            #[external(v0)]
+            fn ping(ref self: ContractState, value: felt252) {
+                self.emit(Ping { value });
+            }
+        }
+
+        #[starknet::contract]
+        mod proxy {
+            use starknet::{ContractAddress, SyscallResultTrait};
+
+            #[storage]
+            struct Storage {}
+
+            #[external(v0)]
+            fn ping(ref self: ContractState, emitter: ContractAddress) {
+                starknet::emit_event_syscall(array![1].span(), array![2].span()).unwrap_syscall();
+                starknet::call_contract_syscall(emitter, selector!(\"ping\"), array![3].span())
+                    .unwrap_syscall();
+            }
+        }
+    "});
+    let deploy = |class_hash, starknet_state| {
+        runner
+            .deploy_contract(
+                class_hash,
+                &Felt252::from(0),
+                vec![],
+                &Felt252::from(1000),
+                Some(u32::MAX as usize),
+                starknet_state,
+            )
+            .unwrap()
+    };
+    let (emitter_address, result) = deploy(&class_hashes[0], Default::default());
+    let (proxy_address, result) = deploy(&class_hashes[1], result.starknet_state);
+    let result = runner
+        .call_contract(
+            &proxy_address,
+            &Felt252::from(starknet_keccak(b"ping")),
+            vec![emitter_address.clone()],
+            &Felt252::from(1000),
+            Some(u32::MAX as usize),
+            result.starknet_state,
+        )
+        .unwrap();
+    let events = result
+        .events
+        .iter()
+        .map(|event| {
+            (event.contract_address.clone(), event.decoded.as_ref().map(ToString::to_string))
+        })
+        .collect_vec();
+    // The event of the proxy does not match its ABI, so it is not decoded.
+    assert_eq!(
+        events,
+        [
+            (proxy_address, None),
+            (
+                emitter_address,
+                Some("test::emitter::Event::Ping(test::emitter::Ping { value: 3 })".into())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_storage_address() {
+    let (runner, class_hashes) = setup_contracts_runner_with_entry_point_costs(indoc! {"
+        #[starknet::contract]
+        mod registry {
+            #[storage]
+            struct Storage {
+                count: felt252,
+                entries: LegacyMap<(felt252, felt252), felt252>,
+            }
+
+            #[external(v0)]
+            fn register(ref self: ContractState, a: felt252, b: felt252, value: felt252) {
+                self.count.write(self.count.read() + 1);
+                self.entries.write((a, b), value);
+            }
+        }
+    "});
+    let result = runner
+        .run_contract_entry_point_by_name(
+            &class_hashes[0],
+            "register",
+            vec![Felt252::from(2), Felt252::from(3), Felt252::from(7)],
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![]));
+    let storage = result.starknet_state.contract_storage(&Felt252::from(0)).unwrap();
+    let base_address = |name: &str| Felt252::from(starknet_keccak(name.as_bytes()));
+    assert_eq!(storage.get(&base_address("count")), Some(&Felt252::from(1)));
+    let entry_address =
+        calculate_storage_address(&base_address("entries"), &[Felt252::from(2), Felt252::from(3)]);
+    assert_eq!(storage.get(&entry_address), Some(&Felt252::from(7)));
+    assert_eq!(storage.len(), 2);
+}
+
+#[test]
+fn test_storage_variable_address() {
+    let (runner, class_hashes) = setup_contracts_runner_with_entry_point_costs(indoc! {"
+        #[starknet::contract]
+        mod balances {
+            use starknet::ContractAddress;
+
+            #[storage]
+            struct Storage {
+                total: u256,
+                balances: LegacyMap<(ContractAddress, u256), u128>,
+            }
+
+            #[external(v0)]
+            fn set(ref self: ContractState, owner: ContractAddress, id: u256, balance: u128) {
+                self.total.write(balance.into());
+                self.balances.write((owner, id), balance);
+            }
+        }
+    "});
+    let result = runner
+        .run_contract_entry_point_by_name(
+            &class_hashes[0],
+            "set",
+            vec![Felt252::from(0x123), Felt252::from(4), Felt252::from(5), Felt252::from(9)],
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![]));
+    let storage = result.starknet_state.contract_storage(&Felt252::from(0)).unwrap();
+    // The low and high words of a `u256` are stored at consecutive addresses.
+    let total_address = calculate_storage_variable_address("total", &[]);
+    assert_eq!(storage.get(&total_address), Some(&Felt252::from(9)));
+    assert_eq!(storage.get(&(total_address + Felt252::from(1))), Some(&Felt252::from(0)));
+    let balance_address = calculate_storage_variable_address(
+        "balances",
+        &[Felt252::from(0x123), Felt252::from(4), Felt252::from(5)],
+    );
+    assert_eq!(storage.get(&balance_address), Some(&Felt252::from(9)));
+    assert_eq!(storage.len(), 3);
+}
+
+#[test]
+fn test_used_resources() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn hash_twice() -> felt252 {
+            core::pedersen::pedersen(core::pedersen::pedersen(1, 2), 3)
+        }
+    "});
+    let func = runner.find_function("hash_twice").unwrap();
+    let result = runner
+        .run_function_with_starknet_context(func, &[], Some(u32::MAX as usize), Default::default())
+        .unwrap();
+    assert!(matches!(result.value, RunResultValue::Success(_)));
+    assert!(result.used_resources.n_steps > 0);
+    assert_eq!(result.used_resources.builtin_instance_counter.get("pedersen_builtin"), Some(&2));
+}
+
+#[test]
+fn test_diff_run_results() {
+    let (runner, class_hashes) = setup_contracts_runner(indoc! {"
+        #[starknet::contract]
+        mod adder {
+            #[storage]
+            struct Storage {
+                total: felt252,
+            }
+
+            #[event]
+            #[derive(Drop, starknet::Event)]
+            enum Event {
+                Added: Added,
+            }
+
+            #[derive(Drop, starknet::Event)]
+            struct Added {
+                value: felt252,
+            }
+
+            #[external(v0)]
+            fn add(ref self: ContractState, value: felt252) -> felt252 {
+                self.total.write(self.total.read() + value);
+                self.emit(Added { value });
+                value * 2
+            }
+        }
+    "});
+    let run = |value: u32| {
+        runner
+            .run_contract_entry_point_by_name(
+                &class_hashes[0],
+                "add",
+                vec![Felt252::from(value)],
+                Some(u32::MAX as usize),
+                Default::default(),
+            )
+            .unwrap()
+    };
+    let diff = diff_run_results(&run(3), &run(3));
+    assert!(diff.is_empty());
+
+    let diff = diff_run_results(&run(3), &run(4));
+    assert!(diff.behavior_changed());
+    assert_eq!(
+        diff.value,
+        Some((
+            ReturnedValue::Success(vec![Arg::Value(Felt252::from(6))]),
+            ReturnedValue::Success(vec![Arg::Value(Felt252::from(8))])
+        ))
+    );
+    let added_selector = Felt252::from(starknet_keccak(b"Added"));
+    assert_eq!(
+        diff.events,
+        vec![ContractEventsDiff {
+            contract_address: Felt252::from(0),
+            a: vec![(vec![added_selector.clone()], vec![Felt252::from(3)])],
+            b: vec![(vec![added_selector], vec![Felt252::from(4)])],
+        }]
+    );
+    assert_eq!(
+        diff.storage,
+        vec![StorageValueDiff {
+            contract_address: Felt252::from(0),
+            key: Felt252::from(starknet_keccak(b"total")),
+            a: Felt252::from(3),
+            b: Felt252::from(4),
+        }]
+    );
+}
+
+#[test]
+fn test_diff_run_results_array_contents() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn values() -> Array<felt252> {
+            array![1, 2]
+        }
+        fn values_after_allocation() -> Array<felt252> {
+            let _unused = array![3];
+            array![1, 2]
+        }
+        fn other_values() -> Array<felt252> {
+            array![1, 3]
+        }
+        fn nested_values() -> (Span<felt252>, Option<Box<Array<felt252>>>) {
+            (array![1].span(), Option::Some(BoxTrait::new(array![2, 3])))
+        }
+        fn nested_values_after_allocation() -> (Span<felt252>, Option<Box<Array<felt252>>>) {
+            let _unused = array![4, 5];
+            (array![1].span(), Option::Some(BoxTrait::new(array![2, 3])))
+        }
+    "});
+    let run = |name: &str| {
+        runner
+            .run_function_with_starknet_context(
+                runner.find_function(name).unwrap(),
+                &[],
+                Some(u32::MAX as usize),
+                Default::default(),
+            )
+            .unwrap()
+    };
+    let (values, values_after_allocation) = (run("::values"), run("::values_after_allocation"));
+    // The arrays are at different addresses, but have the same contents.
+    assert_ne!(values.value, values_after_allocation.value);
+    assert_eq!(diff_run_results(&values, &values_after_allocation).value, None);
+    let value = |value: u32| Arg::Value(Felt252::from(value));
+    assert_eq!(
+        diff_run_results(&values, &run("::other_values")).value,
+        Some((
+            ReturnedValue::Success(vec![Arg::Array(vec![value(1), value(2)])]),
+            ReturnedValue::Success(vec![Arg::Array(vec![value(1), value(3)])])
+        ))
+    );
+    let nested_values = run("::nested_values");
+    assert_eq!(
+        nested_values.value_contents,
+        Some(vec![
+            Arg::Array(vec![value(1)]),
+            // The `Option::Some` selector, followed by the contents of the box.
+            value(0),
+            Arg::Array(vec![value(2), value(3)]),
+        ])
+    );
+    assert_eq!(
+        diff_run_results(&nested_values, &run("::nested_values_after_allocation")).value,
+        None
+    );
+}
+
+#[test]
+fn test_hashing() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn hashes() -> (felt252, felt252, felt252) {
+            let (poseidon, _, _) = core::poseidon::hades_permutation(1, 2, 2);
+            (
+                core::pedersen::pedersen(1, 2),
+                poseidon,
+                core::poseidon::poseidon_hash_span(array![1, 2, 3].span()),
+            )
+        }
+    "});
+    let func = runner.find_function("hashes").unwrap();
+    let result = runner
+        .run_function_with_starknet_context(func, &[], Some(u32::MAX as usize), Default::default())
+        .unwrap();
+    let felt = |value: u32| Felt252::from(value);
+    assert_eq!(
+        result.value,
+        RunResultValue::Success(vec![
+            hashing::pedersen_hash(&felt(1), &felt(2)),
+            hashing::poseidon_hash(&felt(1), &felt(2)),
+            hashing::poseidon_hash_many(&[felt(1), felt(2), felt(3)]),
+        ])
+    );
+}
+
+#[test]
+fn test_unsupported_heavy_syscalls() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn hash() -> u256 {
+            keccak::keccak_u256s_le_inputs(array![1].span())
+        }
+    "});
+    let func = runner.find_function("hash").unwrap();
+    let result = runner
+        .run_function_with_starknet_context(func, &[], Some(u32::MAX as usize), Default::default())
+        .unwrap();
+    assert!(matches!(result.value, RunResultValue::Success(_)));
+
+    let func = func.clone();
+    let runner = runner.with_heavy_syscalls_config(HeavySyscallsConfig::unsupported());
+    let Err(error) = runner.run_function_with_starknet_context(
+        &func,
+        &[],
+        Some(u32::MAX as usize),
+        Default::default(),
+    ) else {
+        panic!("Run with an unsupported syscall should fail.");
+    };
+    assert!(
+        error.to_string().contains("Syscall `Keccak` is not supported in this run."),
+        "Unexpected error: {error}"
+    );
+}
+
+#[test]
+fn test_unsupported_heavy_syscalls_of_contract_calls() {
+    let (runner, class_hashes) = setup_contracts_runner(indoc! {"
+        #[starknet::contract]
+        mod hasher {
+            #[storage]
+            struct Storage {}
+
+            #[external(v0)]
+            fn hash(ref self: ContractState) -> u256 {
+                keccak::keccak_u256s_le_inputs(array![1].span())
+            }
+        }
+
+        #[starknet::contract]
+        mod proxy {
+            use starknet::{ContractAddress, SyscallResultTrait};
+
+            #[storage]
+            struct Storage {}
+
+            #[external(v0)]
+            fn hash(ref self: ContractState, hasher: ContractAddress) {
+                starknet::call_contract_syscall(hasher, selector!(\"hash\"), array![].span())
+                    .unwrap_syscall();
+            }
+        }
+    "});
+    let runner = runner.with_heavy_syscalls_config(HeavySyscallsConfig::unsupported());
+    let deploy = |class_hash, starknet_state| {
+        runner
+            .deploy_contract(
+                class_hash,
+                &Felt252::from(0),
+                vec![],
+                &Felt252::from(1000),
+                Some(u32::MAX as usize),
+                starknet_state,
+            )
+            .unwrap()
+    };
+    let (hasher_address, result) = deploy(&class_hashes[0], Default::default());
+    let (proxy_address, result) = deploy(&class_hashes[1], result.starknet_state);
+    let Err(error) = runner.call_contract(
+        &proxy_address,
+        &Felt252::from(starknet_keccak(b"hash")),
+        vec![hasher_address],
+        &Felt252::from(1000),
+        Some(u32::MAX as usize),
+        result.starknet_state,
+    ) else {
+        panic!("Run with an unsupported syscall in a contract call should fail.");
+    };
+    assert!(
+        error.to_string().contains("Syscall `Keccak` is not supported in this run."),
+        "Unexpected error: {error}"
+    );
+}
+
+#[test]
+fn test_run_metadata() {
+    let (mut runner, _) = setup_contracts_runner(indoc! {"
+        fn constant() -> felt252 {
+            5
+        }
+    "});
+    runner.run_profiler = true;
+    let func = runner.find_function("constant").unwrap();
+    let result = runner
+        .run_function_with_starknet_context(func, &[], Some(1000), Default::default())
+        .unwrap();
+    let metadata = result.metadata;
+    assert_eq!(metadata.compiler_version, current_compiler_version_id());
+    assert_eq!(metadata.sierra_version, current_sierra_version_id());
+    assert_eq!(metadata.available_gas, Some(1000));
+    assert!(metadata.run_profiler);
+    assert_eq!(metadata.builtin_costs, runner.builtin_costs);
+    // The peak memory is measured where `/proc` is available.
+    if cfg!(target_os = "linux") {
+        assert!(metadata.peak_memory.unwrap() > 0);
+        assert!(metadata.peak_memory_growth.unwrap() <= metadata.peak_memory.unwrap());
+    }
+}
+
+#[test]
+fn test_run_functions_in_sequence() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        use starknet::SyscallResultTrait;
+        use starknet::storage_access::storage_address_from_base;
+
+        fn write(value: felt252) {
+            let address = storage_address_from_base(starknet::storage_base_address_const::<1>());
+            starknet::storage_write_syscall(0, address, value).unwrap_syscall();
+        }
+        fn read() -> felt252 {
+            let address = storage_address_from_base(starknet::storage_base_address_const::<1>());
+            starknet::storage_read_syscall(0, address).unwrap_syscall()
+        }
+        fn double() {
+            write(read() * 2);
+        }
+        fn fail() {
+            panic_with_felt252('fail');
+        }
+        fn write_and_loop() {
+            write(7);
+            let mut i = 0;
+            while i != 100000 {
+                i += 1;
+            };
+        }
+    "});
+    let call = |function: &str, args: Vec<Arg>| SequentialCall {
+        function: function.into(),
+        args,
+        available_gas: Some(u32::MAX as usize),
+    };
+    let results = runner
+        .run_functions_in_sequence(
+            &[
+                call("write", vec![Arg::Value(5.into())]),
+                call("double", vec![]),
+                call("read", vec![]),
+            ],
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[2].value, RunResultValue::Success(vec![10.into()]));
+
+    // The calls following a panicking call are not run.
+    let results = runner
+        .run_functions_in_sequence(
+            &[
+                call("write", vec![Arg::Value(5.into())]),
+                call("fail", vec![]),
+                call("read", vec![]),
+            ],
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[1].value.panic_category(), Some(PanicCategory::Explicit));
+
+    // A call running out of gas is reverted, and the following calls are run.
+    let results = runner
+        .run_functions_in_sequence(
+            &[
+                call("write", vec![Arg::Value(5.into())]),
+                SequentialCall { available_gas: Some(100000), ..call("write_and_loop", vec![]) },
+                call("read", vec![]),
+            ],
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(results.len(), 3);
+    assert!(results[1].value.is_out_of_gas());
+    assert_eq!(results[2].value, RunResultValue::Success(vec![5.into()]));
+    assert!(results[0].gas_used().unwrap() > 0);
+}
+
+#[test]
+fn test_print_channels() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn log() {
+            println!(\"started\");
+            core::debug::print_to_channel('debug', array!['value', 5]);
+            core::debug::print_to_channel('trace', array!['step']);
+            println!(\"done\");
+        }
+    "});
+    let func = runner.find_function("log").unwrap().clone();
+    let runner = runner.with_print_capture(true);
+    let result = runner
+        .run_function_with_starknet_context(&func, &[], Some(u32::MAX as usize), Default::default())
+        .unwrap();
+    let printed_output = result.printed_output.unwrap();
+    assert_eq!(
+        printed_output.channels().collect_vec(),
+        [
+            ("stdout", "started\ndone\n"),
+            ("debug", "[DEBUG]\t0x76616c7565 ('value')\n[DEBUG]\t0x5\n"),
+            ("trace", "[DEBUG]\t0x73746570 ('step')\n"),
+        ]
+    );
+    assert_eq!(printed_output.channel("missing"), "");
+
+    // Each run starts with an empty output, so repeated runs by the same runner do not include the
+    // output of previous runs.
+    let result = runner
+        .run_function_with_starknet_context(&func, &[], Some(u32::MAX as usize), Default::default())
+        .unwrap();
+    assert_eq!(result.printed_output, Some(printed_output));
+}
+
+#[test]
+fn test_print_debug_formatting() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        #[derive(Drop, Debug)]
+        struct Point {
+            x: u8,
+            y: u16,
+        }
+
+        #[derive(Drop, Debug)]
+        enum Shape {
+            Empty,
+            Segment: (Point, Point),
+            Path: Array<Point>,
+        }
+
+        fn log() {
+            let segment = Shape::Segment((Point { x: 1, y: 2 }, Point { x: 3, y: 4 }));
+            println!(\"{:?}\", segment);
+            println!(\"{:?}\", Shape::Path(array![Point { x: 5, y: 6 }]));
+            println!(\"{:?} {:?}\", Shape::Empty, array![1_u256, 2].span());
+            println!(\"{:?} {:?}\", (true, 'ab'), Option::Some(Result::<u32, u32>::Err(7)));
+            let long_string: ByteArray = \"A string much longer than a single word of 31 bytes.\";
+            println!(\"{} {:?}\", long_string, long_string);
+        }
+    "});
+    let runner = runner.with_print_capture(true);
+    let func = runner.find_function("log").unwrap();
+    let result = runner
+        .run_function_with_starknet_context(func, &[], Some(u32::MAX as usize), Default::default())
+        .unwrap();
+    assert_eq!(
+        result.printed_output.unwrap().channel("stdout"),
+        indoc! {r#"
+            Shape::Segment((Point { x: 1, y: 2 }, Point { x: 3, y: 4 }))
+            Shape::Path([Point { x: 5, y: 6 }])
+            Shape::Empty(()) [1, 2]
+            (true, 24930) Option::Some(Result::Err(7))
+            A string much longer than a single word of 31 bytes. "A string much longer than a single word of 31 bytes."
+        "#}
+    );
+}
+
+#[test]
+fn test_statement_execution_counts() {
+    let (mut runner, _) = setup_contracts_runner(indoc! {"
+        fn count(n: felt252) -> felt252 {
+            if n == 0 { 0 } else { count(n - 1) + 1 }
+        }
+    "});
+    runner.run_profiler = true;
+    let func = runner.find_function("count").unwrap();
+    let result = runner
+        .run_function_with_starknet_context(
+            func,
+            &[Arg::Value(3.into())],
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+        .unwrap();
+    let counts = result.profiling_info.unwrap().sierra_statement_execution_counts;
+    assert_eq!(counts.len(), runner.sierra_program.statements.len());
+    // The function is entered once for each of 3, 2, 1 and 0.
+    assert_eq!(counts[func.entry_point.0], 4);
+}
+
+#[test]
+fn test_gas_usage_warning() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn constant() -> felt252 {
+            5
+        }
+    "});
+    assert_eq!(runner.gas_usage_warning(None), None);
+    assert_eq!(runner.gas_usage_warning(Some(1000)), Some(GasUsageWarning::UnusedAvailableGas));
+
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn sum(n: felt252) -> felt252 {
+            if n == 0 { 0 } else { n + sum(n - 1) }
+        }
+    "});
+    assert_eq!(runner.gas_usage_warning(Some(1000)), None);
+    assert_eq!(runner.gas_usage_warning(None), Some(GasUsageWarning::MissingAvailableGas));
+}
+
+#[test]
+fn test_gas_report() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn add(a: felt252, b: felt252) -> felt252 {
+            a + b
+        }
+        fn sum(mut n: felt252) -> felt252 {
+            let mut acc = 0;
+            while n != 0 {
+                acc = add(acc, n);
+                n -= 1;
+            };
+            acc
+        }
+    "});
+    let gas_report = runner.gas_report().unwrap();
+    let function_gas_info = |name: &str| {
+        gas_report
+            .functions
+            .iter()
+            .find(|func| func.id.to_string().ends_with(name))
+            .unwrap_or_else(|| panic!("Missing `{name}` in the gas report."))
+    };
+
+    let add = function_gas_info("::add");
+    assert!(!add.withdraws_gas);
+    assert!(add.withdrawals.is_empty());
+    assert!(add.required_gas > 0);
+
+    // The loop withdraws gas on each iteration, so its callers withdraw gas as well.
+    let sum = function_gas_info("::sum");
+    assert!(sum.withdraws_gas);
+    assert!(sum.withdrawals.is_empty());
+    let sum_loop = function_gas_info("::sum[expr15]");
+    assert!(sum_loop.withdraws_gas);
+    let [withdrawal] = sum_loop.withdrawals.as_slice() else {
+        panic!("Expected a single withdrawal in the loop.");
+    };
+    // The loop is a recursive function, so the withdrawal is in a cycle of calls.
+    assert!(withdrawal.in_cycle);
+    assert!(gas_report.to_string().contains(&format!(
+        "    Withdraws {} gas on each iteration of a cycle at statement {}.\n",
+        withdrawal.gas, withdrawal.statement_idx
+    )));
+
+    // The required gas and the withdrawals bound the gas used by runs.
+    let n = 5;
+    let bound = sum.required_gas + (n + 1) * withdrawal.gas;
+    let run_sum = |available_gas| {
+        runner.run_function_with_starknet_context(
+            runner.find_function("::sum").unwrap(),
+            &[Arg::Value(n.into())],
+            Some(available_gas),
+            Default::default(),
+        )
+    };
+    let result = run_sum(bound).unwrap();
+    assert!(matches!(result.value, RunResultValue::Success(_)));
+    assert!(result.gas_used().unwrap() <= bound);
+    assert!(matches!(run_sum(sum.required_gas - 1), Err(RunnerError::NotEnoughGasToCall)));
+}
+
+#[test]
+fn test_builtin_costs() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn hash_chain(n: felt252) -> felt252 {
+            if n == 0 { 0 } else { core::pedersen::pedersen(n, hash_chain(n - 1)) }
+        }
+    "});
+    let gas_used = |runner: &SierraCasmRunner| {
+        let func = runner.find_function("hash_chain").unwrap();
+        let result = runner
+            .run_function_with_starknet_context(
+                func,
+                &[Arg::Value(3.into())],
+                Some(u32::MAX as usize),
+                Default::default(),
+            )
+            .unwrap();
+        assert!(matches!(result.value, RunResultValue::Success(_)));
+        assert_eq!(result.metadata.builtin_costs, runner.builtin_costs);
+        result.gas_used().unwrap()
+    };
+    let with_builtin_costs = gas_used(&runner);
+    let runner = runner.with_builtin_costs(false);
+    let without_builtin_costs = gas_used(&runner);
+    // Only the costs of the pedersen builtin are excluded.
+    let pedersen_cost = token_gas_cost(CostTokenType::Pedersen);
+    assert!(with_builtin_costs > without_builtin_costs);
+    assert_eq!((with_builtin_costs - without_builtin_costs) % pedersen_cost, 0);
+}
+
+/// An observer recording the events of the functions of the test module, the syscalls and the
+/// step milestones.
+#[derive(Default)]
+struct RecordingObserver {
+    events: Vec<String>,
+    n_hints: usize,
+    milestones: Vec<usize>,
+}
+impl RunObserver for RecordingObserver {
+    fn on_function_entry(&mut self, _step: usize, function_id: &FunctionId) {
+        if function_id.to_string().starts_with("test::") {
+            self.events.push(format!("enter {function_id}"));
+        }
+    }
+
+    fn on_function_exit(&mut self, _step: usize, function_id: &FunctionId) {
+        if function_id.to_string().starts_with("test::") {
+            self.events.push(format!("exit {function_id}"));
+        }
+    }
+
+    fn on_hint(&mut self, _execution: &HintExecution) {
+        self.n_hints += 1;
+    }
+
+    fn on_syscall(&mut self, _step: usize, selector: &str) {
+        self.events.push(format!("syscall {selector}"));
+    }
+
+    fn on_step_milestone(&mut self, step: usize) {
+        self.milestones.push(step);
+    }
+
+    fn step_milestone_interval(&self) -> Option<usize> {
+        Some(10)
+    }
+}
+
+#[test]
+fn test_run_observer() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        #[inline(never)]
+        fn inner() -> starknet::ContractAddress {
+            starknet::get_contract_address()
+        }
+
+        fn outer() -> starknet::ContractAddress {
+            inner()
+        }
+    "});
+    let func = runner.find_function("outer").unwrap();
+    let mut observer = RecordingObserver::default();
+    let result = runner
+        .run_function_with_observer(
+            func,
+            &[],
+            Some(u32::MAX as usize),
+            Default::default(),
+            &mut observer,
+        )
+        .unwrap();
+    assert!(matches!(result.value, RunResultValue::Success(_)));
+    assert_eq!(
+        observer.events,
+        [
+            "enter test::outer",
+            "enter test::inner",
+            "syscall GetExecutionInfo",
+            "exit test::inner",
+            "exit test::outer",
+        ]
+    );
+    assert!(observer.n_hints > 0);
+    assert!(!observer.milestones.is_empty());
+    assert!(observer.milestones.iter().enumerate().all(|(i, step)| *step == i * 10));
+    // Observing a run does not log its hints.
+    assert_eq!(result.hint_executions, None);
+}
+
+#[test]
+fn test_run_observer_is_live() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn rec(n: felt252) -> felt252 {
+            if n == 0 {
+                0
+            } else {
+                rec(n - 1) + 1
+            }
+        }
+    "});
+    let runner = runner.with_max_call_depth(Some(3));
+    let func = runner.find_function("rec").unwrap();
+    let mut observer = RecordingObserver::default();
+    let result = runner.run_function_with_observer(
+        func,
+        &[Arg::Value(Felt252::from(10))],
+        Some(u32::MAX as usize),
+        Default::default(),
+        &mut observer,
+    );
+    assert!(matches!(result, Err(RunnerError::StackOverflow { .. })));
+    // The events up to the aborted step are observed although the run failed.
+    assert_eq!(observer.events, ["enter test::rec", "enter test::rec", "enter test::rec"]);
+}
+
+#[test]
+fn test_max_call_depth() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn rec(n: felt252) -> felt252 {
+            if n == 0 {
+                0
+            } else {
+                rec(n - 1) + 1
+            }
+        }
+    "});
+    let runner = runner.with_max_call_depth(Some(5));
+    let func = runner.find_function("rec").unwrap();
+    let run = |n: usize| {
+        runner.run_function_with_starknet_context(
+            func,
+            &[Arg::Value(n.into())],
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+    };
+    assert!(matches!(run(4).unwrap().value, RunResultValue::Success(_)));
+    let Err(err) = run(10) else { panic!("Expected a stack overflow.") };
+    assert_eq!(err.to_string(), "Maximal call depth of 5 exceeded. Call chain: test::rec (x6).");
+    assert_eq!(err.kind(), RunnerErrorKind::Vm);
+    assert!(matches!(
+        err,
+        RunnerError::StackOverflow { max_depth: 5, call_chain } if call_chain.len() == 6
+    ));
+}
+
+#[test]
+fn test_max_call_depth_of_contract_calls() {
+    let (runner, class_hashes) = setup_contracts_runner(indoc! {"
+        #[starknet::contract]
+        mod recursive {
+            use starknet::SyscallResultTrait;
+
+            #[storage]
+            struct Storage {}
+
+            #[external(v0)]
+            fn rec(ref self: ContractState, n: felt252) {
+                if n != 0 {
+                    let address = starknet::get_contract_address();
+                    starknet::call_contract_syscall(address, selector!(\"rec\"), array![n - 1].span())
+                        .unwrap_syscall();
+                }
+            }
+        }
+    "});
+    let runner = runner.with_max_call_depth(Some(5));
+    let (address, result) = runner
+        .deploy_contract(
+            &class_hashes[0],
+            &Felt252::from(0),
+            vec![],
+            &Felt252::from(1000),
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+        .unwrap();
+    let run = |n: usize| {
+        runner.call_contract(
+            &address,
+            &Felt252::from(starknet_keccak(b"rec")),
+            vec![Felt252::from(n)],
+            &Felt252::from(1000),
+            Some(u32::MAX as usize),
+            result.starknet_state.clone(),
+        )
+    };
+    assert!(matches!(run(0).unwrap().value, RunResultValue::Success(_)));
+    // The call chain continues through the runs of the called contracts.
+    let Err(err) = run(10) else { panic!("Expected a stack overflow.") };
+    assert_eq!(
+        err.to_string(),
+        "Maximal call depth of 5 exceeded. Call chain: test::recursive::__wrapper__rec -> \
+         test::recursive::rec -> test::recursive::__wrapper__rec -> test::recursive::rec -> \
+         core::starknet::info::get_contract_address -> core::starknet::info::get_execution_info."
+    );
+}
+
+#[test]
+fn test_max_steps() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn rec(n: felt252) -> felt252 {
+            if n == 0 {
+                0
+            } else {
+                rec(n - 1) + 1
+            }
+        }
+    "});
+    let func = runner.find_function("rec").unwrap().clone();
+    let run = |runner: &SierraCasmRunner, max_steps: Option<usize>| {
+        runner.run_function_with_max_steps(
+            &func,
+            &[Arg::Value(10.into())],
+            Some(u32::MAX as usize),
+            Default::default(),
+            max_steps,
+        )
+    };
+    let n_steps = run(&runner, None).unwrap().used_resources.n_steps;
+    assert!(matches!(run(&runner, Some(n_steps)).unwrap().value, RunResultValue::Success(_)));
+    let Err(err) = run(&runner, Some(n_steps - 1)) else { panic!("Expected a timeout.") };
+    assert_eq!(err.to_string(), format!("Maximal number of {} steps exceeded.", n_steps - 1));
+    assert_eq!(err.kind(), RunnerErrorKind::Timeout);
+    // The limit of the runner applies to runs without a limit of their own.
+    let runner = runner.with_max_steps(Some(10));
+    assert!(matches!(run(&runner, None), Err(RunnerError::MaxStepsExceeded { max_steps: 10 })));
+    assert!(matches!(run(&runner, Some(n_steps)).unwrap().value, RunResultValue::Success(_)));
+}
+
+#[test]
+fn test_max_steps_of_contract_calls() {
+    let (runner, class_hashes) = setup_contracts_runner(indoc! {"
+        #[starknet::contract]
+        mod counter {
+            #[storage]
+            struct Storage {}
+
+            #[external(v0)]
+            fn count(ref self: ContractState, n: felt252) -> felt252 {
+                if n == 0 {
+                    0
+                } else {
+                    count(ref self, n - 1) + 1
+                }
+            }
+        }
+
+        #[starknet::contract]
+        mod proxy {
+            use starknet::{ContractAddress, SyscallResultTrait};
+
+            #[storage]
+            struct Storage {}
+
+            #[external(v0)]
+            fn count_twice(ref self: ContractState, counter: ContractAddress) {
+                starknet::call_contract_syscall(counter, selector!(\"count\"), array![50].span())
+                    .unwrap_syscall();
+                starknet::call_contract_syscall(counter, selector!(\"count\"), array![50].span())
+                    .unwrap_syscall();
+            }
+        }
+    "});
+    let deploy = |class_hash, starknet_state| {
+        runner
+            .deploy_contract(
+                class_hash,
+                &Felt252::from(0),
+                vec![],
+                &Felt252::from(1000),
+                Some(u32::MAX as usize),
+                starknet_state,
+            )
+            .unwrap()
+    };
+    let (counter_address, result) = deploy(&class_hashes[0], Default::default());
+    let (proxy_address, result) = deploy(&class_hashes[1], result.starknet_state);
+    let selector = |name: &str| Felt252::from(starknet_keccak(name.as_bytes()));
+    let call = |runner: &SierraCasmRunner, address: &Felt252, name: &str, calldata| {
+        runner.call_contract(
+            address,
+            &selector(name),
+            calldata,
+            &Felt252::from(1000),
+            Some(u32::MAX as usize),
+            result.starknet_state.clone(),
+        )
+    };
+    let count_steps = call(&runner, &counter_address, "count", vec![Felt252::from(50)])
+        .unwrap()
+        .used_resources
+        .n_steps;
+    // Each run is within the limit on its own, but the steps of the calls count towards the limit
+    // of the calling run.
+    let runner = runner.with_max_steps(Some(count_steps * 3 / 2));
+    let Err(err) = call(&runner, &proxy_address, "count_twice", vec![counter_address.clone()])
+    else {
+        panic!("Expected a timeout.")
+    };
+    assert!(
+        matches!(err, RunnerError::MaxStepsExceeded { max_steps } if max_steps == count_steps * 3 / 2)
+    );
+}
+
+#[test]
+fn test_runner_error_kind() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn ask_oracle() -> felt252 {
+            *starknet::testing::cheatcode::<'oracle'>(array![].span()).at(0)
+        }
+    "});
+    let Err(err) = runner.find_function("missing") else { panic!("Expected a missing function.") };
+    assert_eq!(err.kind(), RunnerErrorKind::Setup);
+    let func = runner.find_function("ask_oracle").unwrap();
+    let Err(err) = runner.run_function_with_starknet_context(func, &[], None, Default::default())
+    else {
+        panic!("Expected an unknown cheatcode.")
+    };
+    assert_eq!(err.kind(), RunnerErrorKind::Hint);
+}
+
+#[test]
+fn test_unsupported_param_type() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        #[derive(Drop)]
+        struct Wrapper {
+            value: felt252,
+            boxed: Box<felt252>,
+        }
+
+        fn take_wrapper(a: felt252, wrapper: Wrapper) -> felt252 {
+            a + wrapper.value + wrapper.boxed.unbox()
+        }
+
+        fn take_span(a: Span<felt252>) -> u32 {
+            a.len()
+        }
+    "});
+    let func = runner.find_function("take_span").unwrap();
+    assert!(runner.check_function_signature(func).is_ok());
+    let func = runner.find_function("take_wrapper").unwrap();
+    let Err(err) = runner.run_function_with_starknet_context(
+        func,
+        &[Arg::Value(1.into()), Arg::Value(2.into()), Arg::Value(3.into())],
+        None,
+        Default::default(),
+    ) else {
+        panic!("Expected an unsupported param type.")
+    };
+    assert_eq!(
+        err.to_string(),
+        "Function param 1 of type `test::Wrapper` can't be passed as an argument, as it contains \
+         `Box<felt252>` - take the boxed value instead, and box it in the function."
+    );
+}
+
+#[test]
+fn test_replay() {
+    let code = indoc! {"
+        fn block_number_plus(x: u64) -> u64 {
+            starknet::get_block_info().unbox().block_number + x
+        }
+    "};
+    let (runner, _) = setup_contracts_runner(code);
+    let func = runner.find_function("block_number_plus").unwrap();
+    let starknet_state = StarknetState::default().with_block_context(BlockContext {
+        block_number: Felt252::from(10),
+        ..BlockContext::default()
+    });
+    let (result, replay) = runner
+        .record_run(func, &[Arg::Value(5.into())], Some(u32::MAX as usize), starknet_state)
+        .unwrap();
+    assert_eq!(replay.function, "test::block_number_plus");
+    assert_eq!(
+        replay.syscalls.iter().map(|(_, selector)| selector.as_str()).collect_vec(),
+        ["GetExecutionInfo"]
+    );
+    assert!(!replay.hints.is_empty());
+    assert_eq!(replay.outputs, ReplayOutputs::from(&result));
+
+    let replay = RunReplay::from_json(&replay.to_json()).unwrap();
+    // The replay is fed the recorded block number, although it runs from the default state.
+    let replayed = runner.replay_run(&replay).unwrap();
+    assert_eq!(replayed.value, result.value);
+    assert!(
+        matches!(&result.value, RunResultValue::Success(values) if values == &[Felt252::from(15)])
+    );
+
+    let mut tampered = replay.clone();
+    tampered.outputs.values[0].value += 1u32;
+    assert_eq!(
+        runner.replay_run(&tampered).err().unwrap().to_string(),
+        "Replay mismatch: the outputs of the run differ."
+    );
+    tampered = replay.clone();
+    tampered.args = vec![ReplayArg::Value(BigUintAsHex { value: 6u32.into() })];
+    assert!(matches!(
+        runner.replay_run(&tampered),
+        Err(RunnerError::ReplayMismatch(ReplayMismatch::OutputsDiffer))
+    ));
+
+    let (other_runner, _) = setup_contracts_runner(&format!("{code}\nfn other() {{}}"));
+    assert_eq!(
+        other_runner.replay_run(&replay).err().unwrap().to_string(),
+        "Replay mismatch: the run was recorded with a different program."
+    );
+}
+
+#[test]
+fn test_panic_category() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn succeed() {}
+        fn assertion() {
+            assert!(1 == 2);
+        }
+        fn overflow() -> u8 {
+            255_u8 + 1
+        }
+        fn unwrap() -> felt252 {
+            Option::<felt252>::None.unwrap()
+        }
+        fn explicit() {
+            panic!(\"custom\");
+        }
+        fn out_of_gas() {
+            out_of_gas()
+        }
+    "});
+    let category = |name: &str| {
+        runner
+            .run_function_with_starknet_context(
+                runner.find_function(name).unwrap(),
+                &[],
+                Some(100000),
+                Default::default(),
+            )
+            .unwrap()
+            .value
+            .panic_category()
+    };
+    assert_eq!(category("succeed"), None);
+    assert_eq!(category("assertion"), Some(PanicCategory::Assertion));
+    assert_eq!(category("overflow"), Some(PanicCategory::Overflow));
+    assert_eq!(category("unwrap"), Some(PanicCategory::Unwrap));
+    assert_eq!(category("explicit"), Some(PanicCategory::Explicit));
+    assert_eq!(category("out_of_gas"), Some(PanicCategory::OutOfGas));
+    assert_eq!(PanicCategory::OutOfGas.to_string(), "out-of-gas");
+}
+
+#[test]
+fn test_panic_backtrace() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(
+        &db,
+        indoc! {"
+            fn add(a: u8, b: u8) -> u8 {
+                a + b
+            }
+        "},
+    )
+    .unwrap();
+    let SierraProgramWithDebug { program, debug_info } =
+        arc_unwrap_or_clone(db.get_sierra_program(vec![test_module.crate_id]).unwrap());
+    let runner = SierraCasmRunner::new(
+        replace_sierra_ids_in_program(&db, &program),
+        Some(Default::default()),
+        Default::default(),
+        false,
+    )
+    .unwrap();
+    let func = runner.find_function("add").unwrap();
+    let run = |a: u8, b: u8| {
+        runner
+            .run_function_with_starknet_context(
+                func,
+                &[Arg::Value(a.into()), Arg::Value(b.into())],
+                Some(u32::MAX as usize),
+                Default::default(),
+            )
+            .unwrap()
+    };
+    assert_eq!(run(1, 2).panic_backtrace, None);
+    let backtrace = run(200, 100).panic_backtrace.unwrap();
+    let (_, location) =
+        debug_info.statements_locations.first_user_code_location(&db, &backtrace).unwrap();
+    let location = location.diagnostic_location(&db);
+    assert_eq!(location.span.take(&db.file_content(location.file_id).unwrap()), "a + b");
+}
+
+#[test]
+fn test_panic_snapshot() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(
+        &db,
+        indoc! {"
+            fn check(x: felt252) -> felt252 {
+                if x == 9 {
+                    let mut data = array![];
+                    data.append('nine');
+                    panic(data);
+                }
+                x
+            }
+        "},
+    )
+    .unwrap();
+    let program = db.get_sierra_program(vec![test_module.crate_id]).unwrap().program.clone();
+    let runner = SierraCasmRunner::new(
+        replace_sierra_ids_in_program(&db, &program),
+        None,
+        Default::default(),
+        false,
+    )
+    .unwrap();
+    let run = |runner: &SierraCasmRunner, x: u8| {
+        runner
+            .run_function_with_starknet_context(
+                runner.find_function("check").unwrap(),
+                &[Arg::Value(x.into())],
+                None,
+                Default::default(),
+            )
+            .unwrap()
+            .panic_snapshot
+    };
+    assert_eq!(run(&runner, 9), None);
+    let runner = runner.with_panic_snapshot(true);
+    assert_eq!(run(&runner, 8), None);
+    let snapshot = run(&runner, 9).unwrap();
+    assert_eq!(snapshot.function.to_string(), "test::check");
+    assert_eq!(snapshot.memory.len(), snapshot.ap);
+    assert_eq!(snapshot.frame[..], snapshot.memory[snapshot.fp..]);
+    assert!(snapshot.frame.contains(&Some(Felt252::from_bytes_be(b"nine"))));
+}
+
+#[test]
+fn test_block_context() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn block_context() -> (u64, u64, starknet::ContractAddress, felt252) {
+            let info = starknet::get_execution_info().unbox();
+            let block_info = info.block_info.unbox();
+            (
+                block_info.block_number,
+                block_info.block_timestamp,
+                block_info.sequencer_address,
+                info.tx_info.unbox().chain_id
+            )
+        }
+    "});
+    let block_context = BlockContext {
+        block_number: Felt252::from(1000),
+        block_timestamp: Felt252::from(1700000000),
+        sequencer_address: Felt252::from(0x5e9),
+        chain_id: Felt252::from_bytes_be(b"SN_SEPOLIA"),
+    };
+    let result = runner
+        .run_function_with_starknet_context(
+            runner.find_function("block_context").unwrap(),
+            &[],
+            Some(u32::MAX as usize),
+            StarknetState::default().with_block_context(block_context.clone()),
+        )
+        .unwrap();
+    let BlockContext { block_number, block_timestamp, sequencer_address, chain_id } = block_context;
+    assert_eq!(
+        result.value,
+        RunResultValue::Success(vec![block_number, block_timestamp, sequencer_address, chain_id])
+    );
+}
+
+#[test]
+fn test_tx_context() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn tx_context() -> Array<felt252> {
+            let tx_info = starknet::get_tx_info().unbox();
+            let mut context = array![];
+            (tx_info.version, tx_info.account_contract_address, tx_info.max_fee)
+                .serialize(ref context);
+            (tx_info.signature, tx_info.transaction_hash, tx_info.nonce).serialize(ref context);
+            context
+        }
+    "});
+    let tx_context = TxContext {
+        version: Felt252::from(1),
+        account_contract_address: Felt252::from(0xacc),
+        max_fee: Felt252::from(1000),
+        signature: vec![Felt252::from(0x51), Felt252::from(0x52)],
+        transaction_hash: Felt252::from(0x7a5),
+        nonce: Felt252::from(3),
+        ..Default::default()
+    };
+    let result = runner
+        .run_function_with_starknet_context(
+            runner.find_function("tx_context").unwrap(),
+            &[],
+            Some(u32::MAX as usize),
+            StarknetState::default().with_tx_context(tx_context),
+        )
+        .unwrap();
+    let RunResultValue::Success(values) = result.value else { panic!("Run failed.") };
+    assert_eq!(
+        read_array_result_as_vec(&result.memory, &values),
+        [1, 0xacc, 1000, 2, 0x51, 0x52, 0x7a5, 3].map(Felt252::from)
+    );
+}
+
+#[test]
+fn test_tx_context_v3() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn tx_context() -> Array<felt252> {
+            let tx_info = starknet::get_tx_info().unbox();
+            let mut context = array![];
+            (tx_info.resource_bounds, tx_info.tip, tx_info.paymaster_data).serialize(ref context);
+            (
+                tx_info.nonce_data_availability_mode,
+                tx_info.fee_data_availability_mode,
+                tx_info.account_deployment_data
+            )
+                .serialize(ref context);
+            context
+        }
+    "});
+    let l1_gas = Felt252::from_bytes_be(b"L1_GAS");
+    let tx_context = TxContext {
+        version: Felt252::from(3),
+        resource_bounds: vec![ResourceBounds {
+            resource: l1_gas.clone(),
+            max_amount: Felt252::from(1000),
+            max_price_per_unit: Felt252::from(10),
+        }],
+        tip: Felt252::from(7),
+        paymaster_data: vec![Felt252::from(0xa1), Felt252::from(0xa2)],
+        nonce_data_availability_mode: Felt252::from(1),
+        fee_data_availability_mode: Felt252::from(0),
+        account_deployment_data: vec![Felt252::from(0xd1)],
+        ..Default::default()
+    };
+    let result = runner
+        .run_function_with_starknet_context(
+            runner.find_function("tx_context").unwrap(),
+            &[],
+            Some(u32::MAX as usize),
+            StarknetState::default().with_tx_context(tx_context),
+        )
+        .unwrap();
+    let RunResultValue::Success(values) = result.value else { panic!("Run failed.") };
+    assert_eq!(
+        read_array_result_as_vec(&result.memory, &values),
+        chain!(
+            [Felt252::from(1), l1_gas],
+            [1000, 10, 7, 2, 0xa1, 0xa2, 1, 0, 1, 0xd1].map(Felt252::from)
+        )
+        .collect_vec()
+    );
+}
+
+#[test]
+fn test_signing_cheatcodes() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        use starknet::secp256_trait::{
+            Secp256Trait, Secp256PointTrait, is_valid_signature, recover_public_key
+        };
+        use starknet::secp256k1::Secp256k1Point;
+        use starknet::secp256r1::Secp256r1Point;
+        use starknet::testing;
+
+        fn stark() -> bool {
+            let (r, s) = testing::stark_sign(0x1234, 'message');
+            core::ecdsa::check_ecdsa_signature('message', testing::stark_public_key(0x1234), r, s)
+        }
+
+        fn secp256k1() -> bool {
+            let (x, y) = testing::secp256k1_public_key(0x1234);
+            let public_key: Secp256k1Point = Secp256Trait::secp256_ec_new_syscall(x, y)
+                .unwrap()
+                .unwrap();
+            let signature = testing::secp256k1_sign(0x1234, 0x5678);
+            let recovered: Secp256k1Point = recover_public_key(0x5678, signature).unwrap();
+            is_valid_signature(0x5678, signature.r, signature.s, public_key)
+                && recovered.get_coordinates().unwrap() == (x, y)
+        }
+
+        fn secp256r1() -> bool {
+            let (x, y) = testing::secp256r1_public_key(0x1234);
+            let public_key: Secp256r1Point = Secp256Trait::secp256_ec_new_syscall(x, y)
+                .unwrap()
+                .unwrap();
+            let signature = testing::secp256r1_sign(0x1234, 0x5678);
+            is_valid_signature(0x5678, signature.r, signature.s, public_key)
+        }
+    "});
+    let run = |runner: &SierraCasmRunner, name: &str| {
+        runner.run_function_with_starknet_context(
+            runner.find_function(name).unwrap(),
+            &[],
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+    };
+    // The cheatcodes are only available when enabled.
+    assert!(run(&runner, "stark").is_err());
+    let runner = runner.with_signing_cheatcodes(true);
+    for name in ["stark", "secp256k1", "secp256r1"] {
+        assert_eq!(
+            run(&runner, name).unwrap().value,
+            RunResultValue::Success(vec![Felt252::from(1)]),
+            "{name}"
+        );
+    }
+}
+
+/// A hint handler adding an `oracle` cheatcode, returning a single constant value.
+struct OracleHintHandler {
+    value: Felt252,
+}
+impl HintHandler for OracleHintHandler {
+    fn execute_hint(
+        &mut self,
+        vm: &mut VirtualMachine,
+        _exec_scopes: &mut ExecutionScopes,
+        hint: &Hint,
+    ) -> Result<bool, HintError> {
+        let Hint::Starknet(StarknetHint::Cheatcode { selector, output_start, output_end, .. }) =
+            hint
+        else {
+            return Ok(false);
+        };
+        if selector.value.to_bytes_be().1 != b"oracle" {
+            return Ok(false);
+        }
+        let mut res_segment = MemBuffer::new_segment(vm);
+        let res_segment_start = res_segment.ptr;
+        res_segment.write(self.value.clone())?;
+        let res_segment_end = res_segment.ptr;
+        vm.insert_value(cell_ref_to_relocatable(output_start, vm), res_segment_start)?;
+        vm.insert_value(cell_ref_to_relocatable(output_end, vm), res_segment_end)?;
+        Ok(true)
+    }
+}
+
+#[test]
+fn test_hint_handlers() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn ask_oracle() -> felt252 {
+            *starknet::testing::cheatcode::<'oracle'>(array![].span()).at(0)
+        }
+
+        fn set_block_number() -> u64 {
+            starknet::testing::set_block_number(5);
+            starknet::get_block_info().unbox().block_number
+        }
+    "});
+    let oracle = || -> Vec<Box<dyn HintHandler>> {
+        vec![Box::new(OracleHintHandler { value: Felt252::from(42) })]
+    };
+    let func = runner.find_function("ask_oracle").unwrap();
+    let result = runner
+        .run_function_with_hint_handlers(
+            func,
+            &[],
+            Some(u32::MAX as usize),
+            Default::default(),
+            oracle(),
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![Felt252::from(42)]));
+
+    // Hints not handled by the handlers are executed by the runner's hint processor.
+    let func = runner.find_function("set_block_number").unwrap();
+    let result = runner
+        .run_function_with_hint_handlers(
+            func,
+            &[],
+            Some(u32::MAX as usize),
+            Default::default(),
+            oracle(),
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![Felt252::from(5)]));
+}
+
+#[test]
+fn test_run_function_with_gas_retries() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn sum(n: felt252) -> felt252 {
+            if n == 0 { 0 } else { n + sum(n - 1) }
+        }
+    "});
+    let func = runner.find_function("sum").unwrap();
+    let args = [Arg::Value(10.into())];
+    let run = |available_gas| {
+        runner
+            .run_function_with_starknet_context(
+                func,
+                &args,
+                Some(available_gas),
+                Default::default(),
+            )
+            .unwrap()
+    };
+    let (minimal_gas, result) = runner
+        .run_function_with_gas_retries(func, &args, 100, 1000000, Default::default())
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![55.into()]));
+    assert!(!run(minimal_gas).value.is_out_of_gas());
+    assert!(run(minimal_gas - 1).value.is_out_of_gas());
+
+    // If even the maximal amount of gas is insufficient, the run with it is returned.
+    let (gas, result) = runner
+        .run_function_with_gas_retries(func, &args, 100, minimal_gas - 1, Default::default())
+        .unwrap();
+    assert_eq!(gas, minimal_gas - 1);
+    assert!(result.value.is_out_of_gas());
+}
+
+#[test]
+fn test_sierra_optimizations() {
+    let code = indoc! {"
+        fn collatz(n: u64, steps: u64) -> u64 {
+            if n == 1 {
+                steps
+            } else if n % 2 == 0 {
+                collatz(n / 2, steps + 1)
+            } else {
+                collatz(3 * n + 1, steps + 1)
+            }
+        }
+
+        fn unused() -> felt252 {
+            7
+        }
+    "};
+    let (runner, _) = setup_contracts_runner(code);
+    let (optimized_runner, _) = setup_contracts_runner(code);
+    let root = optimized_runner.find_function("collatz").unwrap().id.clone();
+    let optimized_runner = optimized_runner.with_sierra_optimizations(&[root]).unwrap();
+    let run = |runner: &SierraCasmRunner| {
+        let func = runner.find_function("collatz").unwrap();
+        runner
+            .run_function_with_starknet_context(
+                func,
+                &[Arg::Value(27.into()), Arg::Value(0.into())],
+                Some(u32::MAX as usize),
+                Default::default(),
+            )
+            .unwrap()
+    };
+    let result = run(&runner);
+    let optimized_result = run(&optimized_runner);
+    assert_eq!(result.value, RunResultValue::Success(vec![111.into()]));
+    assert_eq!(optimized_result.value, result.value);
+
+    assert!(runner.find_function("unused").is_ok());
+    assert!(optimized_runner.find_function("unused").is_err());
+    assert_eq!(runner.optimization_statistics(), None);
+    let statistics = optimized_runner.optimization_statistics().unwrap();
+    assert_eq!(statistics.first().unwrap().before.funcs, runner.sierra_program.funcs.len());
+    assert_eq!(statistics.last().unwrap().after.funcs, optimized_runner.sierra_program.funcs.len());
+    assert!(optimized_runner.sierra_program.funcs.len() < runner.sierra_program.funcs.len());
+}
+
+#[test]
+fn test_dead_code_report() {
+    let (runner, _) = setup_contracts_runner(indoc! {"
+        fn main() -> felt252 {
+            used()
+        }
+
+        #[inline(never)]
+        fn used() -> felt252 {
+            5
+        }
+
+        fn unused() -> felt252 {
+            used() + 7
+        }
+    "});
+    let main = runner.find_function("main").unwrap().id.clone();
+    let report = runner.dead_code_report(&[main]);
+    let dead_functions = report.functions.iter().map(|function| function.id.to_string());
+    assert_eq!(dead_functions.collect_vec(), vec!["test::unused"]);
+    assert!(report.dead_statements() > 0);
+}
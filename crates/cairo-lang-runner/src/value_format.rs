@@ -0,0 +1,236 @@
+use cairo_felt::Felt252;
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_lowering::db::LoweringGroup;
+use cairo_lang_lowering::ids::FunctionLongId;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::enm::SemanticEnumEx;
+use cairo_lang_semantic::items::structure::SemanticStructEx;
+use cairo_lang_semantic::{ConcreteTypeId, GenericArgumentId, TypeId, TypeLongId};
+use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use itertools::Itertools;
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+
+#[cfg(test)]
+#[path = "value_format_test.rs"]
+mod test;
+
+/// Returns the semantic return type of a Sierra function. The id must be the one generated by `db`,
+/// i.e. before any replacement of the Sierra ids.
+pub fn function_return_type(db: &dyn SierraGenGroup, function_id: &FunctionId) -> Option<TypeId> {
+    let lowering_db: &dyn LoweringGroup = db.upcast();
+    let function = db.lookup_intern_sierra_function(function_id.clone());
+    Some(function.signature(lowering_db).ok()?.return_type)
+}
+
+/// Returns the names of the parameters of a Sierra function, as in its semantic signature. The id
+/// must be the one generated by `db`, i.e. before any replacement of the Sierra ids.
+pub fn function_param_names(
+    db: &dyn SierraGenGroup,
+    function_id: &FunctionId,
+) -> Option<Vec<String>> {
+    let lowering_db: &dyn LoweringGroup = db.upcast();
+    let function = db.lookup_intern_sierra_function(function_id.clone());
+    let FunctionLongId::Semantic(function) = function.lookup(lowering_db) else {
+        return None;
+    };
+    let signature = lowering_db.concrete_function_signature(function).ok()?;
+    Some(signature.params.into_iter().map(|param| param.name.to_string()).collect())
+}
+
+/// Formats values returned from a run according to their semantic type, rendering structs, enums,
+/// tuples and arrays by their Cairo representation.
+pub struct ValueFormatter<'a> {
+    db: &'a dyn SierraGenGroup,
+    /// The memory of the run, used for following pointers (e.g. the content of arrays).
+    memory: &'a [Option<Felt252>],
+}
+impl<'a> ValueFormatter<'a> {
+    pub fn new(db: &'a dyn SierraGenGroup, memory: &'a [Option<Felt252>]) -> Self {
+        Self { db, memory }
+    }
+
+    fn semantic_db(&self) -> &'a dyn SemanticGroup {
+        self.db.upcast()
+    }
+
+    fn defs_db(&self) -> &'a dyn DefsGroup {
+        self.db.upcast()
+    }
+
+    /// Formats the given values as a value of type `ty`. Returns `None` if the values do not match
+    /// the type, or contain a type that cannot be formatted.
+    pub fn format(&self, ty: TypeId, values: &[Felt252]) -> Option<String> {
+        (self.type_size(ty)? == values.len()).then(|| self.format_value(ty, values)).flatten()
+    }
+
+    /// Formats a value of type `ty`, given exactly the values representing it.
+    fn format_value(&self, ty: TypeId, values: &[Felt252]) -> Option<String> {
+        match self.semantic_db().lookup_intern_type(ty) {
+            TypeLongId::Concrete(ConcreteTypeId::Struct(concrete_struct_id)) => {
+                let name = concrete_struct_id.struct_id(self.semantic_db()).name(self.defs_db());
+                let members = self.db.concrete_struct_members(concrete_struct_id).ok()?;
+                let member_values =
+                    self.split_values(members.values().map(|member| member.ty), values)?;
+                if name == "u256" {
+                    let [[low], [high]] = member_values[..] else { return None };
+                    let value: BigUint = low.to_biguint() + (high.to_biguint() << 128);
+                    return Some(value.to_string());
+                }
+                if members.is_empty() {
+                    return Some(name.to_string());
+                }
+                let members = members
+                    .iter()
+                    .zip(member_values)
+                    .map(|((member_name, member), values)| {
+                        Some(format!("{member_name}: {}", self.format_value(member.ty, values)?))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(format!("{name} {{ {} }}", members.join(", ")))
+            }
+            TypeLongId::Concrete(ConcreteTypeId::Enum(concrete_enum_id)) => {
+                let name = concrete_enum_id.enum_id(self.semantic_db()).name(self.defs_db());
+                let variants = self.db.concrete_enum_variants(concrete_enum_id).ok()?;
+                let (selector, payload) = values.split_first()?;
+                let selector = selector.to_usize()?;
+                // See `build_enum_init` in the Sierra to CASM compiler for the selector values.
+                let variant_idx = if variants.len() <= 2 {
+                    selector
+                } else {
+                    variants.len().checked_sub((selector + 1) / 2)?
+                };
+                let variant = variants.get(variant_idx)?;
+                let variant_name = variant.id.name(self.defs_db());
+                if name == "bool" {
+                    return Some(variant_name.to_lowercase());
+                }
+                // The payload is padded from the left to the size of the largest variant.
+                let variant_size = self.type_size(variant.ty)?;
+                let payload = &payload[payload.len().checked_sub(variant_size)?..];
+                if self.semantic_db().lookup_intern_type(variant.ty) == TypeLongId::Tuple(vec![]) {
+                    return Some(format!("{name}::{variant_name}"));
+                }
+                Some(format!("{name}::{variant_name}({})", self.format_value(variant.ty, payload)?))
+            }
+            TypeLongId::Concrete(ConcreteTypeId::Extern(concrete_extern_type_id)) => {
+                let name =
+                    concrete_extern_type_id.extern_type_id(self.semantic_db()).name(self.defs_db());
+                let generic_args = self
+                    .semantic_db()
+                    .lookup_intern_concrete_extern_type(concrete_extern_type_id)
+                    .generic_args;
+                let inner_ty = match generic_args.first() {
+                    Some(GenericArgumentId::Type(inner_ty)) => Some(*inner_ty),
+                    _ => None,
+                };
+                match (name.as_str(), values) {
+                    ("Array", [start, end]) => {
+                        let inner_ty = inner_ty?;
+                        let inner_size = self.type_size(inner_ty)?;
+                        let cells = self.memory.get(start.to_usize()?..end.to_usize()?)?;
+                        let cells = cells.iter().cloned().collect::<Option<Vec<_>>>()?;
+                        if inner_size == 0 {
+                            return Some("[]".to_string());
+                        }
+                        let elements = cells
+                            .chunks(inner_size)
+                            .map(|element| self.format_value(inner_ty, element))
+                            .collect::<Option<Vec<_>>>()?;
+                        Some(format!("[{}]", elements.join(", ")))
+                    }
+                    ("Box", [address]) => {
+                        let inner_ty = inner_ty?;
+                        let start = address.to_usize()?;
+                        let cells = self.memory.get(start..start + self.type_size(inner_ty)?)?;
+                        let cells = cells.iter().cloned().collect::<Option<Vec<_>>>()?;
+                        Some(format!("Box({})", self.format_value(inner_ty, &cells)?))
+                    }
+                    ("NonZero", _) => self.format_value(inner_ty?, values),
+                    ("i8" | "i16" | "i32" | "i64" | "i128", [value]) => {
+                        Some(value.to_signed_felt().to_string())
+                    }
+                    (_, [value]) => Some(value.to_string()),
+                    (_, values) => Some(format!("{name}({})", values.iter().join(", "))),
+                }
+            }
+            TypeLongId::Tuple(tys) => {
+                let elements = tys
+                    .iter()
+                    .zip(self.split_values(tys.iter().copied(), values)?)
+                    .map(|(ty, values)| self.format_value(*ty, values))
+                    .collect::<Option<Vec<_>>>()?;
+                match &elements[..] {
+                    [element] => Some(format!("({element},)")),
+                    _ => Some(format!("({})", elements.join(", "))),
+                }
+            }
+            TypeLongId::Snapshot(inner_ty) => self.format_value(inner_ty, values),
+            TypeLongId::GenericParameter(_) | TypeLongId::Var(_) | TypeLongId::Missing(_) => None,
+        }
+    }
+
+    /// Splits the given values into the values of each of the given types.
+    fn split_values<'b>(
+        &self,
+        tys: impl Iterator<Item = TypeId>,
+        mut values: &'b [Felt252],
+    ) -> Option<Vec<&'b [Felt252]>> {
+        let mut result = vec![];
+        for ty in tys {
+            let size = self.type_size(ty)?;
+            if size > values.len() {
+                return None;
+            }
+            let (current, rest) = values.split_at(size);
+            result.push(current);
+            values = rest;
+        }
+        values.is_empty().then_some(result)
+    }
+
+    /// Returns the number of memory cells a value of type `ty` occupies.
+    /// Matches the computation of the Sierra type sizes.
+    fn type_size(&self, ty: TypeId) -> Option<usize> {
+        match self.semantic_db().lookup_intern_type(ty) {
+            TypeLongId::Concrete(ConcreteTypeId::Struct(concrete_struct_id)) => self
+                .db
+                .concrete_struct_members(concrete_struct_id)
+                .ok()?
+                .values()
+                .map(|member| self.type_size(member.ty))
+                .sum(),
+            TypeLongId::Concrete(ConcreteTypeId::Enum(concrete_enum_id)) => {
+                let mut size = 1;
+                for variant in self.db.concrete_enum_variants(concrete_enum_id).ok()? {
+                    size = size.max(self.type_size(variant.ty)? + 1);
+                }
+                Some(size)
+            }
+            TypeLongId::Concrete(ConcreteTypeId::Extern(concrete_extern_type_id)) => {
+                let name =
+                    concrete_extern_type_id.extern_type_id(self.semantic_db()).name(self.defs_db());
+                match name.as_str() {
+                    "Array" | "EcPoint" | "SquashedFelt252Dict" => Some(2),
+                    "EcState" => Some(3),
+                    "U128MulGuarantee" => Some(4),
+                    "NonZero" | "Uninitialized" => {
+                        let generic_args = self
+                            .db
+                            .lookup_intern_concrete_extern_type(concrete_extern_type_id)
+                            .generic_args;
+                        match generic_args.first() {
+                            Some(GenericArgumentId::Type(inner_ty)) => self.type_size(*inner_ty),
+                            _ => None,
+                        }
+                    }
+                    _ => Some(1),
+                }
+            }
+            TypeLongId::Tuple(tys) => tys.into_iter().map(|ty| self.type_size(ty)).sum(),
+            TypeLongId::Snapshot(inner_ty) => self.type_size(inner_ty),
+            TypeLongId::GenericParameter(_) | TypeLongId::Var(_) | TypeLongId::Missing(_) => None,
+        }
+    }
+}
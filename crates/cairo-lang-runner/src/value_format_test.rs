@@ -0,0 +1,56 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::arc_unwrap_or_clone;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+
+use super::{function_return_type, ValueFormatter};
+use crate::{RunResultValue, SierraCasmRunner};
+
+cairo_lang_test_utils::test_file_test!(
+    value_format,
+    "src/value_format_test_data/",
+    {
+        value_format: "value_format",
+    },
+    test_value_format
+);
+
+pub fn test_value_format(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(&db, &inputs["cairo_code"]).unwrap();
+    DiagnosticsReporter::stderr().with_crates(&[test_module.crate_id]).ensure(&db).unwrap();
+
+    // Compile to Sierra.
+    let SierraProgramWithDebug { program: sierra_program, .. } =
+        arc_unwrap_or_clone(db.get_sierra_program(vec![test_module.crate_id]).unwrap());
+    let replaced_sierra_program = replace_sierra_ids_in_program(&db, &sierra_program);
+    let runner =
+        SierraCasmRunner::new(replaced_sierra_program.clone(), None, Default::default(), false)
+            .unwrap();
+    let func = runner.find_function(&inputs["function_name"]).unwrap();
+    let result =
+        runner.run_function_with_starknet_context(func, &[], None, Default::default()).unwrap();
+    let RunResultValue::Success(values) = result.value else {
+        panic!("Run unexpectedly panicked.");
+    };
+    // The functions of the program are in the same order before and after the ids replacement.
+    let func_idx = replaced_sierra_program.funcs.iter().position(|f| f.id == func.id).unwrap();
+    let return_type = function_return_type(&db, &sierra_program.funcs[func_idx].id).unwrap();
+    let formatted = ValueFormatter::new(&db, &result.memory).format(return_type, &values);
+
+    TestRunnerResult {
+        outputs: OrderedHashMap::from([(
+            "expected_value".into(),
+            formatted.unwrap_or_else(|| "Failed formatting.".into()),
+        )]),
+        error: None,
+    }
+}
@@ -12,6 +12,7 @@ use cairo_lang_syntax::node::ast::{self};
 use cairo_lang_syntax::node::db::SyntaxGroup;
 use cairo_lang_syntax::node::helpers::WrappedArgListHelper;
 use cairo_lang_syntax::node::{SyntaxNode, TypedSyntaxNode};
+pub use consteval_int::compute_constant_expr;
 use itertools::Itertools;
 
 use self::assert::AssertMacro;
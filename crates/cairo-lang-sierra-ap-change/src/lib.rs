@@ -59,6 +59,20 @@ pub enum ApChangeError {
     #[error("failed solving the ap changes")]
     SolvingApChangeEquationFailed,
 }
+impl ApChangeError {
+    /// Returns the index of the statement the error originated from, if there is one.
+    pub fn statement_idx(&self) -> Option<StatementIdx> {
+        match self {
+            ApChangeError::ProgramRegistryError(error) => error.statement_idx(),
+            ApChangeError::StatementOutOfBounds(statement_idx)
+            | ApChangeError::StatementOutOfOrder(statement_idx)
+            | ApChangeError::WrongNumApChangeBranches(statement_idx)
+            | ApChangeError::BadMergeAllocatedLocalsMismatch(statement_idx)
+            | ApChangeError::BadMergeBaseMismatch(statement_idx) => Some(*statement_idx),
+            ApChangeError::SolvingApChangeEquationFailed => None,
+        }
+    }
+}
 
 /// Helper to implement the `InvocationApChangeInfoProvider` for the equation generation.
 struct InvocationApChangeInfoProviderForEqGen<'a, TokenUsages: Fn(CostTokenType) -> usize> {
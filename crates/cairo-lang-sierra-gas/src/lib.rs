@@ -50,6 +50,19 @@ pub enum CostError {
     #[error("withdraw_gas does not support builtin yet, try using withdraw_gas_all instead")]
     WithdrawGasPreCostNotSupported,
 }
+impl CostError {
+    /// Returns the index of the statement the error originated from, if there is one.
+    pub fn statement_idx(&self) -> Option<StatementIdx> {
+        match self {
+            CostError::ProgramRegistryError(error) => error.statement_idx(),
+            CostError::StatementOutOfBounds(statement_idx)
+            | CostError::EnforceWalletValueFailed(statement_idx) => Some(*statement_idx),
+            CostError::SolvingGasEquationFailed
+            | CostError::UnexpectedCycle
+            | CostError::WithdrawGasPreCostNotSupported => None,
+        }
+    }
+}
 
 /// Helper to implement the `InvocationCostInfoProvider` for the equation generation.
 struct InvocationCostInfoProviderForEqGen<
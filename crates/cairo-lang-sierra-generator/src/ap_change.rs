@@ -2,13 +2,19 @@
 #[path = "ap_change_test.rs"]
 mod test;
 
+use cairo_lang_debug::DebugWithDb;
+use cairo_lang_defs::diagnostic_utils::StableLocation;
 use cairo_lang_diagnostics::Maybe;
 use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_sierra::extensions::function_call::FunctionCallLibfunc;
 use cairo_lang_sierra::extensions::lib_func::SierraApChange;
-use cairo_lang_sierra::program::GenStatement;
+use cairo_lang_sierra::extensions::NamedLibfunc;
+use cairo_lang_sierra::ids::{ConcreteLibfuncId, FunctionId};
+use cairo_lang_sierra::program::{GenStatement, GenericArg};
 
 use crate::db::SierraGenGroup;
 use crate::pre_sierra;
+use crate::replace_ids::{DebugReplacer, SierraIdReplacer};
 use crate::utils::get_libfunc_signature;
 
 /// Query implementation of [SierraGenGroup::get_ap_change].
@@ -38,3 +44,88 @@ pub fn get_ap_change(
     }
     Ok(SierraApChange::Known { new_vars_only: false })
 }
+
+/// A construct making the ap change of a function unknown.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UnknownApChangeCause {
+    /// A call to a function with an unknown ap change, e.g. a recursive function or a loop.
+    Call(FunctionId),
+    /// An invocation of a libfunc with an unknown ap change, e.g. `revoke_ap_tracking`.
+    Libfunc(ConcreteLibfuncId),
+}
+
+/// A statement making the ap change of a function unknown, as found by
+/// [find_unknown_ap_change_sites].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnknownApChangeSite {
+    pub cause: UnknownApChangeCause,
+    /// The location of the code generating the statement, if known.
+    pub location: Option<StableLocation>,
+}
+impl UnknownApChangeSite {
+    /// Formats the site as a diagnostic, with the location of the code causing it.
+    pub fn format(&self, db: &dyn SierraGenGroup) -> String {
+        let replacer = DebugReplacer::new(db);
+        let message = match &self.cause {
+            UnknownApChangeCause::Call(function_id) => format!(
+                "Call to `{}`, whose ap change is unknown.",
+                replacer.replace_function_id(function_id)
+            ),
+            UnknownApChangeCause::Libfunc(libfunc_id) => format!(
+                "Invocation of `{}`, whose ap change is unknown.",
+                replacer.replace_libfunc_id(libfunc_id)
+            ),
+        };
+        match self.location {
+            Some(location) => {
+                format!(
+                    "{message}\n --> {:?}",
+                    location.diagnostic_location(db.upcast()).debug(db.upcast())
+                )
+            }
+            None => message,
+        }
+    }
+}
+
+/// Returns the statements making the ap change of the given function unknown, by order of
+/// appearance, or an empty vector if its ap change is known.
+///
+/// A function with an unknown ap change can't be called where the ap change must be known, e.g.
+/// between the definition and the usage of a temporary variable, which gets revoked.
+pub fn find_unknown_ap_change_sites(
+    db: &dyn SierraGenGroup,
+    function_id: ConcreteFunctionWithBodyId,
+) -> Maybe<Vec<UnknownApChangeSite>> {
+    if matches!(db.get_ap_change(function_id)?, SierraApChange::Known { .. }) {
+        return Ok(vec![]);
+    }
+    let function = &*db.function_with_body_sierra(function_id)?;
+    let mut sites = vec![];
+    for statement in &function.body {
+        let pre_sierra::Statement::Sierra(GenStatement::Invocation(invocation)) =
+            &statement.statement
+        else {
+            continue;
+        };
+        let signature = get_libfunc_signature(db, invocation.libfunc_id.clone());
+        if !signature
+            .branch_signatures
+            .iter()
+            .any(|branch_signature| matches!(branch_signature.ap_change, SierraApChange::Unknown))
+        {
+            continue;
+        }
+        let long_id = db.lookup_intern_concrete_lib_func(invocation.libfunc_id.clone());
+        let cause = match long_id.generic_args.as_slice() {
+            [GenericArg::UserFunc(function_id)]
+                if long_id.generic_id.0 == FunctionCallLibfunc::STR_ID =>
+            {
+                UnknownApChangeCause::Call(function_id.clone())
+            }
+            _ => UnknownApChangeCause::Libfunc(invocation.libfunc_id.clone()),
+        };
+        sites.push(UnknownApChangeSite { cause, location: statement.location });
+    }
+    Ok(sites)
+}
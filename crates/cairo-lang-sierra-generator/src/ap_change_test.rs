@@ -6,6 +6,7 @@ use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use itertools::Itertools;
 
+use crate::ap_change::find_unknown_ap_change_sites;
 use crate::db::SierraGenGroup;
 use crate::test_utils::SierraGenDatabaseForTesting;
 
@@ -16,6 +17,15 @@ cairo_lang_test_utils::test_file_test!(
     contains_cycles_test
 );
 
+cairo_lang_test_utils::test_file_test!(
+    unknown_ap_change_sites,
+    "src/ap_change_test_data",
+    {
+        unknown_ap_change_sites: "unknown_ap_change_sites",
+    },
+    test_unknown_ap_change_sites
+);
+
 fn contains_cycles_test(
     inputs: &OrderedHashMap<String, String>,
     _args: &OrderedHashMap<String, String>,
@@ -46,3 +56,36 @@ fn contains_cycles_test(
 
     TestRunnerResult::success(OrderedHashMap::from([("result".into(), result)]))
 }
+
+fn test_unknown_ap_change_sites(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let db = &SierraGenDatabaseForTesting::default();
+    let test_module = setup_test_module(db, inputs["module_code"].as_str()).unwrap();
+
+    db.module_lowering_diagnostics(test_module.module_id)
+        .unwrap()
+        .expect_with_db(db, "Unexpected diagnostics.");
+
+    let result = db
+        .module_free_functions(test_module.module_id)
+        .unwrap()
+        .iter()
+        .map(|(free_function_id, _)| {
+            let function_id =
+                ConcreteFunctionWithBodyId::from_no_generics_free(db, *free_function_id).unwrap();
+            let sites = find_unknown_ap_change_sites(db, function_id).unwrap();
+            if sites.is_empty() {
+                return format!("{}: known ap change.\n", free_function_id.name(db));
+            }
+            format!(
+                "{}: unknown ap change.\n{}",
+                free_function_id.name(db),
+                sites.iter().map(|site| site.format(db)).join("")
+            )
+        })
+        .join("\n");
+
+    TestRunnerResult::success(OrderedHashMap::from([("result".into(), result)]))
+}
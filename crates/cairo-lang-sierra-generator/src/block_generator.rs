@@ -207,7 +207,7 @@ fn generate_push_values_statement_for_remapping(
         let var_on_stack_ty = context.get_variable_sierra_type(*output)?;
 
         if ty != var_on_stack_ty {
-            let debug_replacer = DebugReplacer { db: context.get_db() };
+            let debug_replacer = DebugReplacer::new(context.get_db());
             panic!(
                 "Internal compiler error: Inconsistent types in \
                  generate_push_values_statement_for_remapping(): ty: `{}`, var_on_stack_ty: `{}`",
@@ -337,7 +337,7 @@ fn generate_statement_call_code(
     let [branch_signature] = &libfunc_signature.branch_signatures[..] else {
         panic!(
             "Unexpected branches in '{}'.",
-            DebugReplacer { db: context.get_db() }.replace_libfunc_id(&libfunc_id)
+            DebugReplacer::new(context.get_db()).replace_libfunc_id(&libfunc_id)
         );
     };
     if matches!(branch_signature.ap_change, SierraApChange::Unknown) {
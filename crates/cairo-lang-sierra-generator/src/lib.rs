@@ -1,7 +1,7 @@
 //! Lowering from the semantic model down to Sierra. See [cairo_lang_semantic] and
 //! [cairo_lang_sierra].
 
-mod ap_change;
+pub mod ap_change;
 mod ap_tracking;
 mod block_generator;
 pub mod canonical_id_replacer;
@@ -24,5 +24,6 @@ pub mod statements_locations;
 mod store_variables;
 #[cfg(any(feature = "testing", test))]
 pub mod test_utils;
+pub mod type_layout;
 mod types;
 mod utils;
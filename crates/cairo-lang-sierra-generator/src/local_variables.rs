@@ -294,7 +294,7 @@ impl<'a> FindLocalsContext<'a> {
             libfunc_signature.branch_signatures.len(),
             1,
             "Unexpected branches in '{}'.",
-            DebugReplacer { db: self.db }.replace_libfunc_id(&concrete_function_id)
+            DebugReplacer::new(self.db).replace_libfunc_id(&concrete_function_id)
         );
         self.analyze_branch(
             &libfunc_signature.param_signatures,
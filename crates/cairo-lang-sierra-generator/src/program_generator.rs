@@ -61,7 +61,7 @@ fn collect_used_libfuncs(
 
 /// Generates the list of [cairo_lang_sierra::program::TypeDeclaration] for the given list of
 /// [ConcreteTypeId].
-fn generate_type_declarations(
+pub(crate) fn generate_type_declarations(
     db: &dyn SierraGenGroup,
     mut remaining_types: OrderedHashSet<ConcreteTypeId>,
 ) -> Vec<program::TypeDeclaration> {
@@ -147,7 +147,7 @@ fn collect_used_types(
             // If panic happens here, make sure the specified libfunc name is in one of the STR_IDs of
             // the libfuncs in the [`CoreLibfunc`] structured enum.
             .unwrap_or_else(|err| panic!("Failed to specialize: `{}`. Error: {err}",
-                DebugReplacer { db }.replace_libfunc_id(&libfunc.id)));
+                DebugReplacer::new(db).replace_libfunc_id(&libfunc.id)));
             chain!(
                 signature.param_signatures.into_iter().map(|param_signature| param_signature.ty),
                 signature
@@ -256,6 +256,14 @@ pub fn get_sierra_program(
     db: &dyn SierraGenGroup,
     requested_crate_ids: Vec<CrateId>,
 ) -> Maybe<Arc<SierraProgramWithDebug>> {
+    db.get_sierra_program_for_functions(find_requested_functions(db, requested_crate_ids)?)
+}
+
+/// Returns the functions to compile for the given crates - their free functions without generics.
+fn find_requested_functions(
+    db: &dyn SierraGenGroup,
+    requested_crate_ids: Vec<CrateId>,
+) -> Maybe<Vec<ConcreteFunctionWithBodyId>> {
     let mut requested_function_ids = vec![];
     for crate_id in requested_crate_ids {
         for module_id in db.crate_modules(crate_id).iter() {
@@ -269,5 +277,54 @@ pub fn get_sierra_program(
             }
         }
     }
-    db.get_sierra_program_for_functions(requested_function_ids)
+    Ok(requested_function_ids)
+}
+
+/// A Sierra program generated for the subset of the requested functions that compile
+/// successfully.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartialSierraProgram {
+    /// The program of the successfully compiled functions.
+    pub program: Arc<SierraProgramWithDebug>,
+    /// The requested functions that failed to compile, either directly or through a function they
+    /// call.
+    pub failed_functions: Vec<ConcreteFunctionWithBodyId>,
+}
+
+/// Same as [get_sierra_program], but instead of failing if any of the requested functions fails to
+/// compile, generates the program of the functions that compile successfully and reports the
+/// failing ones. Useful for keeping tooling working while some functions have errors.
+pub fn get_partial_sierra_program(
+    db: &dyn SierraGenGroup,
+    requested_crate_ids: Vec<CrateId>,
+) -> Maybe<PartialSierraProgram> {
+    let (healthy_functions, failed_functions) = find_requested_functions(db, requested_crate_ids)?
+        .into_iter()
+        .partition(|function_id| compiles_successfully(db, *function_id));
+    Ok(PartialSierraProgram {
+        program: db.get_sierra_program_for_functions(healthy_functions)?,
+        failed_functions,
+    })
+}
+
+/// Returns whether the Sierra code of the function, and of all the functions it calls, is
+/// successfully generated.
+fn compiles_successfully(db: &dyn SierraGenGroup, function_id: ConcreteFunctionWithBodyId) -> bool {
+    let mut processed_function_ids = UnorderedHashSet::<ConcreteFunctionWithBodyId>::default();
+    let mut function_id_queue = VecDeque::from([function_id]);
+    while let Some(function_id) = function_id_queue.pop_front() {
+        if !processed_function_ids.insert(function_id) {
+            continue;
+        }
+        let Ok(function) = db.function_with_body_sierra(function_id) else {
+            return false;
+        };
+        function_id_queue.extend(
+            function
+                .body
+                .iter()
+                .filter_map(|statement| try_get_function_with_body_id(db, statement)),
+        );
+    }
+    true
 }
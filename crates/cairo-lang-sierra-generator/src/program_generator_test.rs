@@ -2,6 +2,7 @@ use cairo_lang_defs::db::DefsGroup;
 use cairo_lang_defs::ids::ModuleItemId;
 use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
 use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::test_utils::setup_test_crate;
 use cairo_lang_utils::{arc_unwrap_or_clone, try_extract_matches};
 use indoc::indoc;
 use itertools::Itertools;
@@ -10,9 +11,11 @@ use test_case::test_case;
 use test_log::test;
 
 use crate::db::SierraGenGroup;
-use crate::program_generator::SierraProgramWithDebug;
+use crate::program_generator::{get_partial_sierra_program, SierraProgramWithDebug};
 use crate::replace_ids::replace_sierra_ids_in_program;
-use crate::test_utils::{checked_compile_to_sierra, setup_db_and_get_crate_id};
+use crate::test_utils::{
+    checked_compile_to_sierra, setup_db_and_get_crate_id, SierraGenDatabaseForTesting,
+};
 
 #[test]
 fn test_program_generator() {
@@ -144,3 +147,42 @@ fn test_only_include_dependencies(func_name: &str, sierra_used_funcs: &[&str]) {
         sierra_used_funcs
     );
 }
+
+#[test]
+fn test_partial_program() {
+    let db = SierraGenDatabaseForTesting::default();
+    let crate_id = setup_test_crate(
+        &db,
+        indoc! {"
+            fn healthy() -> felt252 {
+                helper()
+            }
+
+            fn helper() -> felt252 {
+                5
+            }
+
+            fn broken() -> felt252 {
+                missing()
+            }
+
+            fn calls_broken() -> felt252 {
+                broken() + helper()
+            }
+        "},
+    );
+    let partial_program = get_partial_sierra_program(&db, vec![crate_id]).unwrap();
+    assert_eq!(
+        partial_program
+            .failed_functions
+            .iter()
+            .map(|function_id| function_id.name(&db))
+            .collect_vec(),
+        vec!["broken", "calls_broken"]
+    );
+    let program = replace_sierra_ids_in_program(&db, &partial_program.program.program);
+    assert_eq!(
+        program.funcs.iter().map(|function| function.id.to_string()).collect_vec(),
+        vec!["test::healthy", "test::helper"]
+    );
+}
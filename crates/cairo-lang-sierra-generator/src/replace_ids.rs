@@ -1,43 +1,24 @@
 use cairo_lang_debug::DebugWithDb;
 use cairo_lang_sierra::program;
 use cairo_lang_utils::extract_matches;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use smol_str::SmolStr;
 
 use crate::db::{SierraGenGroup, SierraGeneratorTypeLongId};
 use crate::pre_sierra::{self, PushValue};
 
+#[cfg(test)]
+#[path = "replace_ids_test.rs"]
+mod test;
+
 pub trait SierraIdReplacer {
     /// Returns a new program where all the ids are replaced.
     fn apply(
         &self,
         program: &cairo_lang_sierra::program::Program,
     ) -> cairo_lang_sierra::program::Program {
-        let mut program = program.clone();
-        for statement in &mut program.statements {
-            if let cairo_lang_sierra::program::GenStatement::Invocation(p) = statement {
-                p.libfunc_id = self.replace_libfunc_id(&p.libfunc_id);
-            }
-        }
-        for type_declaration in &mut program.type_declarations {
-            type_declaration.id = self.replace_type_id(&type_declaration.id);
-            self.replace_generic_args(&mut type_declaration.long_id.generic_args);
-        }
-        for libfunc_declaration in &mut program.libfunc_declarations {
-            libfunc_declaration.id = self.replace_libfunc_id(&libfunc_declaration.id);
-            self.replace_generic_args(&mut libfunc_declaration.long_id.generic_args);
-        }
-        for function in &mut program.funcs {
-            function.id = self.replace_function_id(&function.id);
-            for param in &mut function.params {
-                param.ty = self.replace_type_id(&param.ty);
-            }
-            for ty in &mut function.signature.ret_types {
-                *ty = self.replace_type_id(ty);
-            }
-            for ty in &mut function.signature.param_types {
-                *ty = self.replace_type_id(ty);
-            }
-        }
-        program
+        replace_ids_in_program(self, program)
     }
 
     // Replaces libfunc_ids
@@ -76,6 +57,40 @@ pub trait SierraIdReplacer {
     }
 }
 
+/// Returns a new program where all the ids are replaced by `replacer`.
+fn replace_ids_in_program<Replacer: SierraIdReplacer + ?Sized>(
+    replacer: &Replacer,
+    program: &cairo_lang_sierra::program::Program,
+) -> cairo_lang_sierra::program::Program {
+    let mut program = program.clone();
+    for statement in &mut program.statements {
+        if let cairo_lang_sierra::program::GenStatement::Invocation(p) = statement {
+            p.libfunc_id = replacer.replace_libfunc_id(&p.libfunc_id);
+        }
+    }
+    for type_declaration in &mut program.type_declarations {
+        type_declaration.id = replacer.replace_type_id(&type_declaration.id);
+        replacer.replace_generic_args(&mut type_declaration.long_id.generic_args);
+    }
+    for libfunc_declaration in &mut program.libfunc_declarations {
+        libfunc_declaration.id = replacer.replace_libfunc_id(&libfunc_declaration.id);
+        replacer.replace_generic_args(&mut libfunc_declaration.long_id.generic_args);
+    }
+    for function in &mut program.funcs {
+        function.id = replacer.replace_function_id(&function.id);
+        for param in &mut function.params {
+            param.ty = replacer.replace_type_id(&param.ty);
+        }
+        for ty in &mut function.signature.ret_types {
+            *ty = replacer.replace_type_id(ty);
+        }
+        for ty in &mut function.signature.param_types {
+            *ty = replacer.replace_type_id(ty);
+        }
+    }
+    program
+}
+
 /// Replaces `cairo_lang_sierra::ids::{ConcreteLibfuncId, ConcreteTypeId, FunctionId}` with a dummy
 /// ids whose debug string is the string representing the expanded information about the id.
 /// For Libfuncs and Types - that would be recursively opening their generic arguments, for
@@ -84,10 +99,160 @@ pub trait SierraIdReplacer {
 ///  - For libfuncs: `felt252_const<2>` or `unbox<Box<Box<felt252>>>`.
 ///  - For types: `felt252` or `Box<Box<felt252>>`.
 ///  - For user functions: `test::foo`.
+///
+/// The verbosity of the names is controlled by [DebugNaming]. When applied to a program, short
+/// and mangled names shared by several ids of the program are suffixed by the numeric ids, e.g.
+/// `foo__3` and `foo__5` for `a::foo` and `b::foo`.
 pub struct DebugReplacer<'a> {
     pub db: &'a dyn SierraGenGroup,
+    /// The verbosity of the names given to the ids.
+    pub naming: DebugNaming,
+    /// The names shared by several ids, which are suffixed by the ids.
+    colliding_names: UnorderedHashSet<SmolStr>,
 }
+impl<'a> DebugReplacer<'a> {
+    /// Creates a replacer giving the ids their full names.
+    pub fn new(db: &'a dyn SierraGenGroup) -> Self {
+        Self { db, naming: DebugNaming::default(), colliding_names: Default::default() }
+    }
+
+    /// Sets the verbosity of the names given to the ids.
+    pub fn with_naming(mut self, naming: DebugNaming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// Returns the debug name of the id `id`, given its full name.
+    fn debug_name(&self, id: u64, full_name: String) -> SmolStr {
+        let name: SmolStr = match self.naming {
+            DebugNaming::Full => return full_name.into(),
+            DebugNaming::Short => strip_module_paths(&full_name).into(),
+            DebugNaming::Mangled => {
+                let short_name = strip_generic_args(&strip_module_paths(&full_name));
+                let base_name = short_name.rsplit("::").find(|segment| !segment.is_empty());
+                format!("{}__{id}", base_name.unwrap_or_default()).into()
+            }
+        };
+        if self.colliding_names.contains(&name) { format!("{name}__{id}").into() } else { name }
+    }
+
+    /// Adds the names shared by several ids of `program` to the colliding names, until all the
+    /// names are unique. Several rounds may be required, as suffixing the names of types may
+    /// make the names of the types and libfuncs using them unique.
+    fn collect_colliding_names(&mut self, program: &cairo_lang_sierra::program::Program) {
+        loop {
+            // The ids by their names, where types, libfuncs and user functions are distinguished
+            // by the kind of the id.
+            let mut ids_by_name = UnorderedHashMap::<SmolStr, (usize, u64)>::default();
+            let mut new_colliding_names = vec![];
+            let mut add = |name: Option<SmolStr>, id: (usize, u64)| {
+                let name = name.unwrap_or_default();
+                match ids_by_name.get(&name) {
+                    Some(other_id) if *other_id != id => new_colliding_names.push(name),
+                    Some(_) => {}
+                    None => {
+                        ids_by_name.insert(name, id);
+                    }
+                }
+            };
+            for declaration in &program.type_declarations {
+                add(self.replace_type_id(&declaration.id).debug_name, (0, declaration.id.id));
+            }
+            for declaration in &program.libfunc_declarations {
+                add(self.replace_libfunc_id(&declaration.id).debug_name, (1, declaration.id.id));
+            }
+            for function in &program.funcs {
+                add(self.replace_function_id(&function.id).debug_name, (2, function.id.id));
+            }
+            if new_colliding_names.is_empty() {
+                return;
+            }
+            self.colliding_names.extend(new_colliding_names);
+        }
+    }
+}
+
+/// The verbosity of the names given to ids by [DebugReplacer]. Names of generic instances can get
+/// very long with full paths, bloating the Sierra text and the profiler output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DebugNaming {
+    /// Names with full paths, e.g. `core::array::ArrayImpl::<core::felt252>::append`.
+    #[default]
+    Full,
+    /// Names without module paths, e.g. `ArrayImpl::<felt252>::append`, followed by the numeric id
+    /// when shared by several ids, e.g. `foo__3`.
+    Short,
+    /// Names of the items without their generic arguments, followed by the numeric id for keeping
+    /// them unique, e.g. `append__12`.
+    Mangled,
+}
+
+/// Removes the module paths from the paths in the given name, i.e. the segments starting with a
+/// lowercase letter followed by `::` and another segment, e.g. `core::option::Option::<test::A>`
+/// becomes `Option::<A>`.
+fn strip_module_paths(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut rest = name;
+    while let Some(c) = rest.chars().next() {
+        if !(c.is_ascii_alphabetic() || c == '_') {
+            result.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let end =
+            rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        let (segment, after) = rest.split_at(end);
+        match after.strip_prefix("::") {
+            Some(path_rest) if !c.is_ascii_uppercase() && !path_rest.starts_with('<') => {
+                rest = path_rest;
+            }
+            _ => {
+                result.push_str(segment);
+                rest = after;
+            }
+        }
+    }
+    result
+}
+
+/// Removes the generic arguments from the given name, along with the `::` preceding them, e.g.
+/// `Option::<felt252>::unwrap` becomes `Option::unwrap`.
+pub fn strip_generic_args(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut depth = 0_usize;
+    for c in name.chars() {
+        match c {
+            '<' => {
+                if depth == 0 && result.ends_with("::") {
+                    result.truncate(result.len() - 2);
+                }
+                depth += 1;
+            }
+            '>' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => result.push(c),
+            _ => {}
+        }
+    }
+    result
+}
+
 impl SierraIdReplacer for DebugReplacer<'_> {
+    fn apply(
+        &self,
+        program: &cairo_lang_sierra::program::Program,
+    ) -> cairo_lang_sierra::program::Program {
+        if self.naming == DebugNaming::Full {
+            return replace_ids_in_program(self, program);
+        }
+        let mut replacer = DebugReplacer {
+            db: self.db,
+            naming: self.naming,
+            colliding_names: self.colliding_names.clone(),
+        };
+        replacer.collect_colliding_names(program);
+        replace_ids_in_program(&replacer, program)
+    }
+
     fn replace_libfunc_id(
         &self,
         id: &cairo_lang_sierra::ids::ConcreteLibfuncId,
@@ -96,7 +261,7 @@ impl SierraIdReplacer for DebugReplacer<'_> {
         self.replace_generic_args(&mut long_id.generic_args);
         cairo_lang_sierra::ids::ConcreteLibfuncId {
             id: id.id,
-            debug_name: Some(long_id.to_string().into()),
+            debug_name: Some(self.debug_name(id.id, long_id.to_string())),
         }
     }
 
@@ -105,7 +270,9 @@ impl SierraIdReplacer for DebugReplacer<'_> {
         id: &cairo_lang_sierra::ids::ConcreteTypeId,
     ) -> cairo_lang_sierra::ids::ConcreteTypeId {
         match self.db.lookup_intern_concrete_type(id.clone()) {
-            SierraGeneratorTypeLongId::CycleBreaker(ty) => ty.format(self.db.upcast()).into(),
+            SierraGeneratorTypeLongId::CycleBreaker(ty) => {
+                self.debug_name(id.id, ty.format(self.db.upcast())).into()
+            }
             SierraGeneratorTypeLongId::Regular(long_id) => {
                 let mut long_id = long_id.as_ref().clone();
                 self.replace_generic_args(&mut long_id.generic_args);
@@ -125,7 +292,7 @@ impl SierraIdReplacer for DebugReplacer<'_> {
                 }
                 cairo_lang_sierra::ids::ConcreteTypeId {
                     id: id.id,
-                    debug_name: Some(long_id.to_string().into()),
+                    debug_name: Some(self.debug_name(id.id, long_id.to_string())),
                 }
             }
         }
@@ -139,10 +306,10 @@ impl SierraIdReplacer for DebugReplacer<'_> {
         let semantic_id = self.db.lookup_intern_sierra_function(sierra_id.clone());
         cairo_lang_sierra::ids::FunctionId {
             id: sierra_id.id,
-            debug_name: Some(
-                format!("{:?}", semantic_id.lookup(self.db.upcast()).debug(self.db.upcast()))
-                    .into(),
-            ),
+            debug_name: Some(self.debug_name(
+                sierra_id.id,
+                format!("{:?}", semantic_id.lookup(self.db.upcast()).debug(self.db.upcast())),
+            )),
         }
     }
 }
@@ -151,7 +318,7 @@ pub fn replace_sierra_ids(
     db: &dyn SierraGenGroup,
     statement: &pre_sierra::StatementWithLocation,
 ) -> pre_sierra::StatementWithLocation {
-    let replacer = DebugReplacer { db };
+    let replacer = DebugReplacer::new(db);
     match &statement.statement {
         pre_sierra::Statement::Sierra(cairo_lang_sierra::program::GenStatement::Invocation(p)) => {
             pre_sierra::StatementWithLocation {
@@ -196,5 +363,5 @@ pub fn replace_sierra_ids_in_program(
     db: &dyn SierraGenGroup,
     program: &cairo_lang_sierra::program::Program,
 ) -> cairo_lang_sierra::program::Program {
-    DebugReplacer { db }.apply(program)
+    DebugReplacer::new(db).apply(program)
 }
@@ -0,0 +1,57 @@
+use cairo_lang_utils::arc_unwrap_or_clone;
+use indoc::indoc;
+use test_case::test_case;
+
+use super::{
+    replace_sierra_ids_in_program, strip_generic_args, strip_module_paths, DebugNaming,
+    DebugReplacer, SierraIdReplacer,
+};
+use crate::db::SierraGenGroup;
+use crate::test_utils::setup_db_and_get_crate_id;
+
+#[test_case("core::felt252", "felt252"; "core type")]
+#[test_case("core::array::ArrayImpl::<core::felt252>::append", "ArrayImpl::<felt252>::append"; "impl function")]
+#[test_case("core::option::Option::<test::A>", "Option::<A>"; "generic type")]
+#[test_case("Tuple<core::integer::u8, core::integer::u16>", "Tuple<u8, u16>"; "tuple")]
+#[test_case("test::foo", "foo"; "free function")]
+fn test_strip_module_paths(name: &str, expected: &str) {
+    assert_eq!(strip_module_paths(name), expected);
+}
+
+#[test_case("Option::<felt252>::unwrap", "Option::unwrap"; "generic impl")]
+#[test_case("Box<Array<felt252>>", "Box"; "nested")]
+#[test_case("foo", "foo"; "no generic args")]
+fn test_strip_generic_args(name: &str, expected: &str) {
+    assert_eq!(strip_generic_args(name), expected);
+}
+
+#[test_case(DebugNaming::Short, &["foo__{a}", "foo__{b}", "main"]; "short")]
+#[test_case(DebugNaming::Mangled, &["foo__{a}", "foo__{b}", "main__{main}"]; "mangled")]
+fn test_unique_debug_names(naming: DebugNaming, expected: &[&str]) {
+    let (db, crate_id) = setup_db_and_get_crate_id(indoc! {"
+        mod a {
+            fn foo() -> felt252 { 1 }
+        }
+        mod b {
+            fn foo() -> felt252 { 2 }
+        }
+        fn main() -> felt252 {
+            a::foo() + b::foo()
+        }
+    "});
+    let program = arc_unwrap_or_clone(db.get_sierra_program(vec![crate_id]).unwrap()).program;
+    let full_names = replace_sierra_ids_in_program(&db, &program);
+    let id_of = |name: &str| {
+        full_names.funcs.iter().find(|f| f.id.to_string() == name).unwrap().id.id.to_string()
+    };
+    let (a, b, main) = (id_of("test::a::foo"), id_of("test::b::foo"), id_of("test::main"));
+    let names = DebugReplacer::new(&db).with_naming(naming).apply(&program);
+    let mut names = names.funcs.iter().map(|f| f.id.to_string()).collect::<Vec<_>>();
+    names.sort();
+    let mut expected = expected
+        .iter()
+        .map(|name| name.replace("{a}", &a).replace("{b}", &b).replace("{main}", &main))
+        .collect::<Vec<_>>();
+    expected.sort();
+    assert_eq!(names, expected);
+}
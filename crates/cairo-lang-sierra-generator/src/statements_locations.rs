@@ -1,8 +1,10 @@
 use cairo_lang_defs::diagnostic_utils::StableLocation;
+use cairo_lang_semantic::corelib::core_crate;
 use cairo_lang_sierra::program::StatementIdx;
 use cairo_lang_syntax::node::{Terminal, TypedSyntaxNode};
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 
 use crate::db::SierraGenGroup;
 #[cfg(test)]
@@ -70,6 +72,65 @@ pub fn containing_function_identifier(
     }
 }
 
+/// The version of the [SourceMap] format, increased on any change to its serialized form.
+pub const SOURCE_MAP_VERSION: u32 = 1;
+
+/// A mapping from the statements of a Sierra program to the Cairo source code that generated them,
+/// independent of the compiler database.
+///
+/// Serialized as a JSON object of the form:
+/// ```json
+/// {
+///   "version": 1,
+///   "statements": [
+///     {"file": "src/lib.cairo", "start_line": 3, "start_col": 4, "end_line": 3, "end_col": 9},
+///     null
+///   ]
+/// }
+/// ```
+/// where the `i`-th entry of `statements` is the span of the `i`-th Sierra statement, or `null`
+/// if the statement has no known source location. Lines and columns are 0 based, and the end of
+/// the span is exclusive. Spans in code generated by plugins point to the generated virtual file,
+/// named `<origin file>[<virtual file name>]`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SourceMap {
+    /// The version of the format, see [SOURCE_MAP_VERSION].
+    pub version: u32,
+    /// The source span of each statement, indexed by the statement index.
+    pub statements: Vec<Option<SourceSpan>>,
+}
+
+/// A span of Cairo source code, resolved into a file path and positions, so it can be used without
+/// the compiler database.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SourceSpan {
+    /// The full path of the file.
+    pub file: String,
+    /// The line of the start of the span, 0 based.
+    pub start_line: usize,
+    /// The column of the start of the span, 0 based.
+    pub start_col: usize,
+    /// The line of the end of the span, 0 based.
+    pub end_line: usize,
+    /// The column of the end of the span, 0 based.
+    pub end_col: usize,
+}
+impl SourceSpan {
+    /// Resolves the given location into a span, if its file is available.
+    pub fn from_location(db: &dyn SierraGenGroup, location: StableLocation) -> Option<Self> {
+        let location = location.diagnostic_location(db.upcast());
+        let start = location.span.start.position_in_file(db.upcast(), location.file_id)?;
+        let end = location.span.end.position_in_file(db.upcast(), location.file_id)?;
+        Some(Self {
+            file: location.file_id.full_path(db.upcast()),
+            start_line: start.line,
+            start_col: start.col,
+            end_line: end.line,
+            end_col: end.col,
+        })
+    }
+}
+
 /// The location of the Cairo source code which caused a statement to be generated.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct StatementsLocations {
@@ -98,4 +159,68 @@ impl StatementsLocations {
     ) -> UnorderedHashMap<StatementIdx, String> {
         self.locations.map(|s| containing_function_identifier(db, Some(*s)))
     }
+
+    /// Returns the location of the Cairo source code which caused the given statement to be
+    /// generated. If the statement has no known location, returns the location of the closest
+    /// preceding statement that has one.
+    pub fn nearest_statement_location(
+        &self,
+        statement_idx: StatementIdx,
+    ) -> Option<(StatementIdx, StableLocation)> {
+        (0..=statement_idx.0).rev().find_map(|idx| {
+            let idx = StatementIdx(idx);
+            self.locations.get(&idx).map(|location| (idx, *location))
+        })
+    }
+
+    /// Describes the Cairo function and the source location that generated the given statement,
+    /// e.g. `lib.cairo::sum, lib.cairo:6:3`. Statements without a known location are attributed to
+    /// the nearest preceding statement that has one.
+    pub fn describe_statement_location(
+        &self,
+        db: &dyn SierraGenGroup,
+        statement_idx: StatementIdx,
+    ) -> String {
+        let Some((_, location)) = self.nearest_statement_location(statement_idx) else {
+            return "unknown".into();
+        };
+        let function_identifier = containing_function_identifier(db, Some(location));
+        match SourceSpan::from_location(db, location) {
+            Some(SourceSpan { file, start_line, start_col, .. }) => {
+                format!("{function_identifier}, {file}:{}:{}", start_line + 1, start_col + 1)
+            }
+            None => function_identifier,
+        }
+    }
+
+    /// Returns the first of the given statements whose location is in user code - outside of the
+    /// core library - along with its location. Used to locate the source of a panic given its
+    /// backtrace, as the statements of the core library are rarely the culprits.
+    pub fn first_user_code_location(
+        &self,
+        db: &dyn SierraGenGroup,
+        statements: &[StatementIdx],
+    ) -> Option<(StatementIdx, StableLocation)> {
+        let core_crate = core_crate(db.upcast());
+        statements.iter().find_map(|idx| {
+            let location = self.locations.get(idx)?;
+            let in_core = db.file_modules(location.file_id(db.upcast())).is_ok_and(|modules| {
+                modules.first().is_some_and(|module| module.owning_crate(db.upcast()) == core_crate)
+            });
+            (!in_core).then_some((*idx, *location))
+        })
+    }
+
+    /// Returns the [SourceMap] of a program with `n_statements` statements.
+    pub fn source_map(&self, db: &dyn SierraGenGroup, n_statements: usize) -> SourceMap {
+        SourceMap {
+            version: SOURCE_MAP_VERSION,
+            statements: (0..n_statements)
+                .map(|idx| {
+                    let location = self.locations.get(&StatementIdx(idx))?;
+                    SourceSpan::from_location(db, *location)
+                })
+                .collect(),
+        }
+    }
 }
@@ -1,14 +1,15 @@
 use cairo_lang_diagnostics::get_location_marks;
 use cairo_lang_lowering::db::LoweringGroup;
 use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
-use cairo_lang_semantic::test_utils::setup_test_function;
+use cairo_lang_semantic::test_utils::{setup_test_crate, setup_test_function};
 use cairo_lang_test_utils::get_direct_or_file_content;
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use indoc::indoc;
 
 use crate::db::SierraGenGroup;
 use crate::replace_ids::replace_sierra_ids;
-use crate::statements_locations::containing_function_identifier;
+use crate::statements_locations::{containing_function_identifier, SourceSpan, SOURCE_MAP_VERSION};
 use crate::test_utils::SierraGenDatabaseForTesting;
 
 /// Compiles a single function to Sierra and checks the generated code, together with the
@@ -71,3 +72,32 @@ cairo_lang_test_utils::test_file_test!(
     },
     test_sierra_locations
 );
+
+#[test]
+fn test_source_map() {
+    let db = &SierraGenDatabaseForTesting::without_add_withdraw_gas();
+    let crate_id = setup_test_crate(
+        db,
+        indoc! {"
+            fn foo(a: felt252) -> felt252 {
+                bar(a)
+            }
+            #[inline(never)]
+            fn bar(a: felt252) -> felt252 {
+                a
+            }
+        "},
+    );
+    let program = db.get_sierra_program(vec![crate_id]).unwrap();
+    let source_map =
+        program.debug_info.statements_locations.source_map(db, program.program.statements.len());
+    assert_eq!(source_map.version, SOURCE_MAP_VERSION);
+    assert_eq!(source_map.statements.len(), program.program.statements.len());
+    assert!(source_map.statements.contains(&Some(SourceSpan {
+        file: "lib.cairo".into(),
+        start_line: 1,
+        start_col: 4,
+        end_line: 1,
+        end_col: 10,
+    })));
+}
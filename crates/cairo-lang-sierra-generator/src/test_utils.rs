@@ -66,6 +66,7 @@ impl SierraGenDatabaseForTesting {
         res.set_analyzer_plugins(suite.analyzer_plugins);
 
         res.set_optimization_config(Arc::new(OptimizationConfig::no_movable_functions()));
+        res.set_lowering_mutation(None);
 
         let corelib_path = detect_corelib().expect("Corelib not found in default location.");
         init_dev_corelib(&mut res, corelib_path);
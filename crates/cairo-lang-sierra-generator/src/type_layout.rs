@@ -0,0 +1,132 @@
+use std::fmt::{Display, Write};
+
+use cairo_lang_diagnostics::{Maybe, ToMaybe};
+use cairo_lang_semantic as semantic;
+use cairo_lang_semantic::items::enm::SemanticEnumEx;
+use cairo_lang_semantic::items::structure::SemanticStructEx;
+use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
+use cairo_lang_sierra::program::Program;
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+use cairo_lang_sierra_type_size::{get_type_size_map, TypeSizeMap};
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+
+use crate::db::SierraGenGroup;
+use crate::program_generator::generate_type_declarations;
+
+#[cfg(test)]
+#[path = "type_layout_test.rs"]
+mod test;
+
+/// The layout of the values of a type in memory, as passed as arguments to a Sierra program.
+///
+/// Note that this is not the `Serde` serialization used for calldata: an enum is laid out with a
+/// variant selector, which for enums of more than 2 variants is a jump offset rather than the
+/// variant index, and an `Array<T>` or `Span<T>` is laid out as its start and end pointers rather
+/// than as its length followed by its elements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeLayout {
+    /// The name of the type.
+    pub ty: String,
+    /// The size of the type, in felts.
+    pub size: usize,
+    /// The inner structure of the layout.
+    pub kind: TypeLayoutKind,
+}
+
+/// The inner structure of a [TypeLayout].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeLayoutKind {
+    /// A type with no inner structure, e.g. `felt252`, `u8` or `Array<T>` (whose 2 felts are the
+    /// pointers to the start and end of its elements).
+    Opaque,
+    /// A struct or a tuple, laid out as the concatenation of its members.
+    Struct { members: Vec<(String, TypeLayout)> },
+    /// An enum, laid out as the selector of its active variant, followed by a zero padding to the
+    /// size of the largest variant, followed by the value of the variant.
+    Enum { variants: Vec<(String, TypeLayout)> },
+}
+
+impl TypeLayout {
+    /// Writes the layout with its members, indented by `indent` levels.
+    fn fmt_indented(&self, f: &mut impl Write, indent: usize) -> std::fmt::Result {
+        let plural = if self.size == 1 { "" } else { "s" };
+        write!(f, "{} ({} felt{plural})", self.ty, self.size)?;
+        let members = match &self.kind {
+            TypeLayoutKind::Opaque => return Ok(()),
+            TypeLayoutKind::Struct { members } => members,
+            TypeLayoutKind::Enum { variants } => {
+                write!(f, "\n{}[variant selector] (1 felt)", "  ".repeat(indent + 1))?;
+                variants
+            }
+        };
+        for (name, layout) in members {
+            write!(f, "\n{}{name}: ", "  ".repeat(indent + 1))?;
+            layout.fmt_indented(f, indent + 1)?;
+        }
+        Ok(())
+    }
+}
+impl Display for TypeLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+/// Returns the memory layout of the given type, in felts.
+pub fn type_layout(db: &dyn SierraGenGroup, ty: semantic::TypeId) -> Maybe<TypeLayout> {
+    let concrete_ty = db.get_concrete_type_id(ty)?;
+    let program = Program {
+        type_declarations: generate_type_declarations(db, OrderedHashSet::from_iter([concrete_ty])),
+        libfunc_declarations: vec![],
+        statements: vec![],
+        funcs: vec![],
+    };
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(&program).ok().to_maybe()?;
+    let type_sizes = get_type_size_map(&program, &registry).to_maybe()?;
+    type_layout_ex(db, ty, &type_sizes)
+}
+
+/// Returns the memory layout of the given type, given the sizes of the Sierra types it consists
+/// of.
+fn type_layout_ex(
+    db: &dyn SierraGenGroup,
+    ty: semantic::TypeId,
+    type_sizes: &TypeSizeMap,
+) -> Maybe<TypeLayout> {
+    let size = *type_sizes.get(&db.get_concrete_type_id(ty)?).to_maybe()? as usize;
+    let layouts = |members: Vec<(String, semantic::TypeId)>| {
+        members
+            .into_iter()
+            .map(|(name, ty)| Ok((name, type_layout_ex(db, ty, type_sizes)?)))
+            .collect::<Maybe<Vec<_>>>()
+    };
+    let kind = match db.lookup_intern_type(ty) {
+        semantic::TypeLongId::Concrete(semantic::ConcreteTypeId::Struct(concrete_struct_id)) => {
+            let members = db.concrete_struct_members(concrete_struct_id)?;
+            TypeLayoutKind::Struct {
+                members: layouts(
+                    members.iter().map(|(name, member)| (name.to_string(), member.ty)).collect(),
+                )?,
+            }
+        }
+        semantic::TypeLongId::Concrete(semantic::ConcreteTypeId::Enum(concrete_enum_id)) => {
+            let variants = db.concrete_enum_variants(concrete_enum_id)?;
+            TypeLayoutKind::Enum {
+                variants: layouts(
+                    variants
+                        .iter()
+                        .map(|variant| (variant.id.name(db.upcast()).to_string(), variant.ty))
+                        .collect(),
+                )?,
+            }
+        }
+        semantic::TypeLongId::Tuple(tys) => TypeLayoutKind::Struct {
+            members: layouts(
+                tys.into_iter().enumerate().map(|(i, ty)| (i.to_string(), ty)).collect(),
+            )?,
+        },
+        semantic::TypeLongId::Snapshot(inner_ty) => type_layout_ex(db, inner_ty, type_sizes)?.kind,
+        _ => TypeLayoutKind::Opaque,
+    };
+    Ok(TypeLayout { ty: ty.format(db.upcast()), size, kind })
+}
@@ -0,0 +1,60 @@
+use cairo_lang_semantic::test_utils::setup_test_function;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::type_layout;
+use crate::test_utils::SierraGenDatabaseForTesting;
+
+#[test]
+fn test_type_layout() {
+    let db = &SierraGenDatabaseForTesting::default();
+    let test_function = setup_test_function(
+        db,
+        "fn foo(a: Shape, b: @(u8, u256), c: Array<Point>) {}",
+        "foo",
+        indoc! {"
+            #[derive(Drop)]
+            struct Point {
+                x: felt252,
+                y: felt252,
+            }
+            #[derive(Drop)]
+            enum Shape {
+                Dot: Point,
+                Circle: (Point, u32),
+                Empty,
+            }
+        "},
+    )
+    .unwrap();
+    let layouts = test_function
+        .signature
+        .params
+        .iter()
+        .map(|param| type_layout(db, param.ty).unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        layouts,
+        [
+            indoc! {"
+            test::Shape (4 felts)
+              [variant selector] (1 felt)
+              Dot: test::Point (2 felts)
+                x: core::felt252 (1 felt)
+                y: core::felt252 (1 felt)
+              Circle: (test::Point, core::integer::u32) (3 felts)
+                0: test::Point (2 felts)
+                  x: core::felt252 (1 felt)
+                  y: core::felt252 (1 felt)
+                1: core::integer::u32 (1 felt)
+              Empty: () (0 felts)"},
+            indoc! {"
+            @(core::integer::u8, core::integer::u256) (3 felts)
+              0: core::integer::u8 (1 felt)
+              1: core::integer::u256 (2 felts)
+                low: core::integer::u128 (1 felt)
+                high: core::integer::u128 (1 felt)"},
+            "core::array::Array::<test::Point> (2 felts)",
+        ]
+    );
+}
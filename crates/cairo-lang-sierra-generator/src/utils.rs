@@ -274,7 +274,7 @@ pub fn get_libfunc_signature(
         // the libfuncs in the [`CoreLibfunc`] structured enum.
         panic!(
             "Failed to specialize: `{}`. Error: {err}",
-            DebugReplacer { db }.replace_libfunc_id(&concrete_lib_func_id)
+            DebugReplacer::new(db).replace_libfunc_id(&concrete_lib_func_id)
         )
     })
 }
@@ -89,6 +89,34 @@ pub enum AnnotationError {
         actual: ApTracking,
     },
 }
+impl AnnotationError {
+    /// Returns the index of the statement the error originated from, if there is one.
+    pub fn statement_idx(&self) -> Option<StatementIdx> {
+        match self {
+            AnnotationError::InconsistentReferencesAnnotation(statement_idx)
+            | AnnotationError::AnnotationAlreadySet {
+                source_statement_idx: statement_idx, ..
+            }
+            | AnnotationError::InconsistentEnvironments { statement_idx, .. }
+            | AnnotationError::InconsistentFunctionId { statement_idx }
+            | AnnotationError::InvalidConvergence { statement_idx }
+            | AnnotationError::MissingAnnotationsForStatement(statement_idx)
+            | AnnotationError::MissingReferenceError { statement_idx, .. }
+            | AnnotationError::OverrideReferenceError {
+                source_statement_idx: statement_idx, ..
+            }
+            | AnnotationError::GasWalletError { source_statement_idx: statement_idx, .. }
+            | AnnotationError::ReferencesError { statement_idx, .. }
+            | AnnotationError::ApTrackingAlreadyEnabled { statement_idx }
+            | AnnotationError::ApChangeError { source_statement_idx: statement_idx, .. }
+            | AnnotationError::ApTrackingError { source_statement_idx: statement_idx, .. }
+            | AnnotationError::InvalidFunctionApChange { statement_idx, .. } => {
+                Some(*statement_idx)
+            }
+            AnnotationError::InvalidStatementIdx | AnnotationError::FrameStateError(_) => None,
+        }
+    }
+}
 
 /// Annotation that represent the state at each program statement.
 #[derive(Clone, Debug)]
@@ -5,7 +5,7 @@ use cairo_lang_casm::instructions::{Instruction, InstructionBody, RetInstruction
 use cairo_lang_sierra::extensions::core::{CoreConcreteLibfunc, CoreLibfunc, CoreType};
 use cairo_lang_sierra::extensions::lib_func::SierraApChange;
 use cairo_lang_sierra::extensions::ConcreteLibfunc;
-use cairo_lang_sierra::ids::{ConcreteTypeId, VarId};
+use cairo_lang_sierra::ids::{ConcreteTypeId, FunctionId, VarId};
 use cairo_lang_sierra::program::{
     BranchTarget, GenericArg, Invocation, Program, Statement, StatementIdx,
 };
@@ -57,6 +57,26 @@ pub enum CompilationError {
     #[error("Unsupported const type.")]
     UnsupportedConstType,
 }
+impl CompilationError {
+    /// Returns the index of the statement the error originated from, if there is one.
+    pub fn statement_idx(&self) -> Option<StatementIdx> {
+        match self {
+            CompilationError::ProgramRegistryError(error) => error.statement_idx(),
+            CompilationError::AnnotationError(error) => error.statement_idx(),
+            CompilationError::InvocationError { statement_idx, .. }
+            | CompilationError::ReturnArgumentsNotOnStack { statement_idx }
+            | CompilationError::ReferencesError { statement_idx, .. }
+            | CompilationError::LibfuncInvocationMismatch { statement_idx }
+            | CompilationError::DanglingReferences { statement_idx, .. }
+            | CompilationError::ExpectedBranchAlign {
+                source_statement_idx: statement_idx, ..
+            } => Some(*statement_idx),
+            CompilationError::FailedBuildingTypeInformation
+            | CompilationError::ConstDataMismatch
+            | CompilationError::UnsupportedConstType => None,
+        }
+    }
+}
 
 /// The casm program representation.
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -137,6 +157,88 @@ impl CairoProgram {
         }
         AssembledCairoProgram { bytecode, hints }
     }
+
+    /// Returns the location of each of the user functions of `program` within the bytecode, sorted
+    /// by their offsets. `program` must be the program this was compiled from.
+    ///
+    /// The code of a function is considered to span from its entry point to the entry point of the
+    /// following function, as the statements of each function are generated contiguously.
+    pub fn function_code_info(&self, program: &Program) -> Vec<FunctionCodeInfo> {
+        let statement_info = &self.debug_info.sierra_statement_info;
+        let code_size = statement_info.last().map_or(0, |info| info.code_offset);
+        let mut functions = program
+            .funcs
+            .iter()
+            .map(|function| {
+                (function.id.clone(), statement_info[function.entry_point.0].code_offset)
+            })
+            .collect::<Vec<_>>();
+        functions.sort_by_key(|(_, code_offset)| *code_offset);
+        let end_offsets = functions
+            .iter()
+            .skip(1)
+            .map(|(_, code_offset)| *code_offset)
+            .chain(std::iter::once(code_size))
+            .collect::<Vec<_>>();
+        zip_eq(functions, end_offsets)
+            .map(|((id, code_offset), end_offset)| FunctionCodeInfo {
+                id,
+                code_offset,
+                code_size: end_offset - code_offset,
+            })
+            .collect()
+    }
+
+    /// Returns static statistics of the compiled program. `program` must be the program this was
+    /// compiled from.
+    pub fn stats(&self, program: &Program) -> ProgramStats {
+        ProgramStats {
+            n_statements: program.statements.len(),
+            n_types: program.type_declarations.len(),
+            n_libfuncs: program.libfunc_declarations.len(),
+            n_functions: program.funcs.len(),
+            bytecode_length: self
+                .debug_info
+                .sierra_statement_info
+                .last()
+                .map_or(0, |info| info.code_offset),
+        }
+    }
+}
+
+/// Static statistics of a compiled program.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ProgramStats {
+    /// The number of Sierra statements.
+    pub n_statements: usize,
+    /// The number of Sierra type declarations.
+    pub n_types: usize,
+    /// The number of Sierra libfunc declarations.
+    pub n_libfuncs: usize,
+    /// The number of Sierra functions.
+    pub n_functions: usize,
+    /// The length of the compiled bytecode, in words.
+    pub bytecode_length: usize,
+}
+impl Display for ProgramStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Statements: {}", self.n_statements)?;
+        writeln!(f, "Types: {}", self.n_types)?;
+        writeln!(f, "Libfuncs: {}", self.n_libfuncs)?;
+        writeln!(f, "Functions: {}", self.n_functions)?;
+        writeln!(f, "Bytecode length: {}", self.bytecode_length)
+    }
+}
+
+/// The location of a user function within the bytecode of a compiled program.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct FunctionCodeInfo {
+    /// The Sierra id of the function.
+    pub id: FunctionId,
+    /// The offset of the function's entry point within the bytecode.
+    pub code_offset: usize,
+    /// The number of bytecode words the function's code occupies.
+    pub code_size: usize,
 }
 
 /// The debug information of a compilation from Sierra to casm.
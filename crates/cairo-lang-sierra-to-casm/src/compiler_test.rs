@@ -3,7 +3,7 @@ use indoc::indoc;
 use pretty_assertions;
 use test_case::test_case;
 
-use crate::compiler::compile;
+use crate::compiler::{compile, ProgramStats};
 use crate::metadata::{calc_metadata, calc_metadata_ap_change_only};
 use crate::test_utils::{read_sierra_example_file, strip_comments_and_linebreaks};
 
@@ -1247,3 +1247,47 @@ fn compiler_errors(sierra_code: &str, expected_result: &str) {
         expected_result
     );
 }
+
+#[test]
+fn function_code_info_and_stats() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            libfunc felt252_dup = dup<felt252>;
+            libfunc felt252_add = felt252_add;
+            libfunc store_temp_felt252 = store_temp<felt252>;
+            libfunc call_double = function_call<user@double>;
+
+            store_temp_felt252([0]) -> ([0]);
+            call_double([0]) -> ([1]);
+            return([1]);
+            felt252_dup([0]) -> ([0], [1]);
+            felt252_add([0], [1]) -> ([2]);
+            store_temp_felt252([2]) -> ([2]);
+            return([2]);
+
+            main@0([0]: felt252) -> (felt252);
+            double@3([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let cairo_program =
+        compile(&program, &calc_metadata_ap_change_only(&program).unwrap(), false).unwrap();
+    let info = cairo_program.function_code_info(&program);
+    pretty_assertions::assert_eq!(
+        info.iter()
+            .map(|function| (function.id.to_string(), function.code_offset, function.code_size))
+            .collect::<Vec<_>>(),
+        vec![("main".into(), 0, 4), ("double".into(), 4, 2)]
+    );
+    pretty_assertions::assert_eq!(
+        cairo_program.stats(&program),
+        ProgramStats {
+            n_statements: 7,
+            n_types: 1,
+            n_libfuncs: 4,
+            n_functions: 2,
+            bytecode_length: 6
+        }
+    );
+}
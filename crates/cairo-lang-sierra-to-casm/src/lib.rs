@@ -11,3 +11,4 @@ pub mod references;
 pub mod relocations;
 #[cfg(any(feature = "testing", test))]
 pub mod test_utils;
+pub mod validation;
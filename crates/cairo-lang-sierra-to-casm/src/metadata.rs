@@ -1,6 +1,6 @@
 use cairo_lang_sierra::extensions::gas::CostTokenType;
 use cairo_lang_sierra::ids::FunctionId;
-use cairo_lang_sierra::program::Program;
+use cairo_lang_sierra::program::{Program, StatementIdx};
 use cairo_lang_sierra_ap_change::ap_change_info::ApChangeInfo;
 use cairo_lang_sierra_ap_change::compute::calc_ap_changes as linear_calc_ap_changes;
 use cairo_lang_sierra_ap_change::{calc_ap_changes, ApChangeError};
@@ -29,6 +29,15 @@ pub enum MetadataError {
     #[error(transparent)]
     CostError(#[from] CostError),
 }
+impl MetadataError {
+    /// Returns the index of the statement the error originated from, if there is one.
+    pub fn statement_idx(&self) -> Option<StatementIdx> {
+        match self {
+            MetadataError::ApChangeError(error) => error.statement_idx(),
+            MetadataError::CostError(error) => error.statement_idx(),
+        }
+    }
+}
 
 /// Configuration for metadata computation.
 #[derive(Clone)]
@@ -0,0 +1,73 @@
+use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
+use cairo_lang_sierra::program::{Program, StatementIdx};
+use cairo_lang_sierra::program_registry::{ProgramRegistry, ProgramRegistryError};
+use cairo_lang_sierra::ProgramParser;
+use lalrpop_util::ParseError;
+use thiserror::Error;
+
+use crate::compiler::{compile, CompilationError};
+use crate::metadata::{calc_metadata, MetadataComputationConfig, MetadataError};
+
+#[cfg(test)]
+#[path = "validation_test.rs"]
+mod test;
+
+/// Error for validating a Sierra program.
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum ValidationError {
+    #[error("{line}:{column}: {message}")]
+    ParseError {
+        /// The line of the error in the code, starting from 1.
+        line: usize,
+        /// The column of the error in the line, starting from 1.
+        column: usize,
+        message: String,
+    },
+    #[error(transparent)]
+    ProgramRegistryError(Box<ProgramRegistryError>),
+    #[error(transparent)]
+    MetadataError(Box<MetadataError>),
+    #[error(transparent)]
+    CompilationError(Box<CompilationError>),
+}
+impl ValidationError {
+    /// Returns the index of the statement the error originated from, if there is one.
+    pub fn statement_idx(&self) -> Option<StatementIdx> {
+        match self {
+            ValidationError::ParseError { .. } => None,
+            ValidationError::ProgramRegistryError(error) => error.statement_idx(),
+            ValidationError::MetadataError(error) => error.statement_idx(),
+            ValidationError::CompilationError(error) => error.statement_idx(),
+        }
+    }
+}
+
+/// Parses and validates the code of a Sierra program - checking that its types and libfuncs
+/// specialize, that its ap changes and gas usage are solvable, and that it compiles to CASM.
+/// Returns the parsed program.
+pub fn validate_sierra(sierra_code: &str) -> Result<Program, ValidationError> {
+    let program = ProgramParser::new().parse(sierra_code).map_err(|error| {
+        let offset = match &error {
+            ParseError::InvalidToken { location }
+            | ParseError::UnrecognizedEof { location, .. } => *location,
+            ParseError::UnrecognizedToken { token: (start, _, _), .. }
+            | ParseError::ExtraToken { token: (start, _, _) } => *start,
+            ParseError::User { .. } => 0,
+        };
+        let (line, column) = line_and_column(sierra_code, offset);
+        ValidationError::ParseError { line, column, message: error.to_string() }
+    })?;
+    ProgramRegistry::<CoreType, CoreLibfunc>::new(&program)
+        .map_err(ValidationError::ProgramRegistryError)?;
+    let metadata = calc_metadata(&program, MetadataComputationConfig::default())
+        .map_err(|error| ValidationError::MetadataError(Box::new(error)))?;
+    compile(&program, &metadata, true).map_err(ValidationError::CompilationError)?;
+    Ok(program)
+}
+
+/// Returns the 1-based line and column of the given byte offset in the code.
+fn line_and_column(code: &str, offset: usize) -> (usize, usize) {
+    let prefix = &code[..offset.min(code.len())];
+    let line_start = prefix.rfind('\n').map_or(0, |idx| idx + 1);
+    (prefix.matches('\n').count() + 1, prefix[line_start..].chars().count() + 1)
+}
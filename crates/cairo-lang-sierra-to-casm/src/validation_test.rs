@@ -0,0 +1,67 @@
+use cairo_lang_sierra::program::StatementIdx;
+use indoc::indoc;
+use test_case::test_case;
+
+use super::validate_sierra;
+use crate::test_utils::read_sierra_example_file;
+
+#[test]
+fn validate_valid_program() {
+    validate_sierra(&read_sierra_example_file("fib_jumps")).unwrap();
+}
+
+#[test_case(indoc! {"
+                type felt252 = felt252;
+                libfunc felt252_dup = ;
+            "},
+            "2:23: Unrecognized token `;` found at 46:47\nExpected one of r#\"[a-zA-Z_][a-zA-Z_0-9]*\"#",
+            None;
+            "Parse error")]
+#[test_case(indoc! {"
+                type felt252 = felt252;
+
+                store_temp_felt252([1]) -> ([1]);
+
+                test_program@0([1]: felt252) -> ();
+            "},
+            "Could not find the requested libfunc",
+            None;
+            "Undeclared libfunc")]
+#[test_case(indoc! {"
+                type felt252 = felt252;
+                libfunc jump = jump;
+
+                jump() { 5() };
+                return();
+
+                test_program@0() -> ();
+            "},
+            "#0: Jump out of range",
+            Some(0);
+            "Jump out of range")]
+#[test_case(indoc! {"
+                type felt252 = felt252;
+                libfunc felt252_dup = dup<felt252>;
+
+                felt252_dup([1]) -> ([1], [2]);
+                felt252_dup([2]) -> ([1], [2]);
+                return();
+
+                test_program@0([1]: felt252) -> ();
+            "},
+            "#1->#2: [1] was overridden.",
+            Some(1);
+            "Reference override")]
+#[test_case(&read_sierra_example_file("fib_no_gas"),
+            "found an unexpected cycle during cost computation",
+            None;
+            "Unsolvable gas")]
+fn validate_invalid_program(
+    sierra_code: &str,
+    expected_error: &str,
+    expected_statement_idx: Option<usize>,
+) {
+    let error = validate_sierra(sierra_code).expect_err("Validation is expected to fail.");
+    pretty_assertions::assert_eq!(error.to_string(), expected_error);
+    assert_eq!(error.statement_idx(), expected_statement_idx.map(StatementIdx));
+}
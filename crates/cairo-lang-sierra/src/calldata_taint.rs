@@ -0,0 +1,384 @@
+//! Experimental static taint analysis of a Sierra program, finding which storage writes and
+//! emitted events of its entry points are influenced by their calldata - a lightweight
+//! information-flow analysis for auditing contracts.
+//!
+//! All the parameters of an entry point, other than the builtins, are considered calldata, as is
+//! the case for the `Span<felt252>` calldata of the wrappers of contract entry points. The taint
+//! of a value is the set of parameters of its function it may be derived from:
+//! * The outputs of a libfunc are derived from all of its inputs, other than the outputs of builtin
+//!   types, which hold no data.
+//! * The outputs of a function call are derived from the arguments the returned values of the
+//!   called function are derived from, recursively.
+//!
+//! Only explicit flows are tracked - values that depend on calldata only through the branches
+//! taken, e.g. a constant written to storage within an `if` on calldata, are not tainted.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
+
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use itertools::chain;
+
+use crate::extensions::bitwise::BitwiseType;
+use crate::extensions::core::{CoreConcreteLibfunc, CoreLibfunc, CoreType, CoreTypeConcrete};
+use crate::extensions::ec::EcOpType;
+use crate::extensions::gas::{BuiltinCostsType, GasBuiltinType};
+use crate::extensions::lib_func::OutputVarInfo;
+use crate::extensions::pedersen::PedersenType;
+use crate::extensions::poseidon::PoseidonType;
+use crate::extensions::range_check::RangeCheckType;
+use crate::extensions::segment_arena::SegmentArenaType;
+use crate::extensions::starknet::syscalls::SystemType;
+use crate::extensions::starknet::StarkNetConcreteLibfunc;
+use crate::extensions::{ConcreteLibfunc, ConcreteType, NoGenericArgsGenericType};
+use crate::ids::{ConcreteTypeId, FunctionId, GenericTypeId, VarId};
+use crate::program::{Function, Program, Statement, StatementIdx};
+use crate::program_registry::{ProgramRegistry, ProgramRegistryError};
+
+#[cfg(test)]
+#[path = "calldata_taint_test.rs"]
+mod test;
+
+/// The types of the builtins, whose values hold no data.
+const BUILTIN_TYPES: [GenericTypeId; 9] = [
+    BitwiseType::ID,
+    BuiltinCostsType::ID,
+    EcOpType::ID,
+    GasBuiltinType::ID,
+    PedersenType::ID,
+    PoseidonType::ID,
+    RangeCheckType::ID,
+    SegmentArenaType::ID,
+    SystemType::ID,
+];
+
+/// The kind of a statement calldata may flow to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SinkKind {
+    /// A `storage_write_syscall`, whose key is the written address, and whose value is the written
+    /// value.
+    StorageWrite,
+    /// An `emit_event_syscall`, whose key is the keys of the event, and whose value is its data.
+    EmitEvent,
+}
+
+/// A storage write or event emission influenced by the calldata of an entry point.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaintedSink {
+    /// The statement of the system call, possibly within a function called by the entry point.
+    pub statement_idx: StatementIdx,
+    /// The function call statements through which the sink is reached from the entry point,
+    /// outermost first - the shortest such path.
+    pub calls: Vec<StatementIdx>,
+    pub kind: SinkKind,
+    /// Whether the written address, or the keys of the event, are influenced by the calldata.
+    pub tainted_key: bool,
+    /// Whether the written value, or the data of the event, are influenced by the calldata.
+    pub tainted_value: bool,
+}
+
+/// The calldata influenced sinks of an entry point.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntryPointTaint {
+    pub entry_point: FunctionId,
+    /// The influenced sinks, by statement index.
+    pub sinks: Vec<TaintedSink>,
+}
+
+/// A report of which storage writes and emitted events of the entry points of a Sierra program are
+/// influenced by their calldata.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CalldataTaintReport {
+    /// The entry points, by the order they were given.
+    pub entry_points: Vec<EntryPointTaint>,
+}
+impl CalldataTaintReport {
+    /// Creates the report of the given entry points of the program.
+    pub fn new(
+        program: &Program,
+        entry_points: &[FunctionId],
+    ) -> Result<Self, Box<ProgramRegistryError>> {
+        let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(program)?;
+        let analysis = TaintAnalysis::new(program, &registry)?;
+        let entry_points = entry_points
+            .iter()
+            .map(|entry_point| {
+                let sinks = analysis
+                    .summaries
+                    .get(entry_point)
+                    .ok_or_else(|| {
+                        Box::new(ProgramRegistryError::MissingFunction(entry_point.clone()))
+                    })?
+                    .sinks
+                    .iter()
+                    .filter_map(|(statement_idx, sink)| {
+                        let tainted_key = !sink.key.is_empty();
+                        let tainted_value = !sink.value.is_empty();
+                        (tainted_key || tainted_value).then(|| TaintedSink {
+                            statement_idx: *statement_idx,
+                            calls: sink.calls.clone(),
+                            kind: sink.kind,
+                            tainted_key,
+                            tainted_value,
+                        })
+                    })
+                    .collect();
+                Ok(EntryPointTaint { entry_point: entry_point.clone(), sinks })
+            })
+            .collect::<Result<_, Box<ProgramRegistryError>>>()?;
+        Ok(Self { entry_points })
+    }
+
+    /// Formats the report, describing the location of each sink with `describe_sink`, e.g. by the
+    /// Cairo function and source location it was generated from.
+    pub fn format_with_locations(&self, describe_sink: impl Fn(&TaintedSink) -> String) -> String {
+        let mut report = String::new();
+        self.write_report(&mut report, &describe_sink).expect("Writing to a string failed.");
+        report
+    }
+
+    /// Writes the report, describing the location of each sink with `describe_sink`.
+    fn write_report(
+        &self,
+        f: &mut impl fmt::Write,
+        describe_sink: &dyn Fn(&TaintedSink) -> String,
+    ) -> fmt::Result {
+        for EntryPointTaint { entry_point, sinks } in &self.entry_points {
+            if sinks.is_empty() {
+                writeln!(f, "{entry_point}: no calldata influenced storage writes or events.")?;
+                continue;
+            }
+            writeln!(f, "{entry_point}:")?;
+            for tainted_sink in sinks {
+                let TaintedSink { kind, tainted_key, tainted_value, .. } = tainted_sink;
+                let (sink, key, value) = match kind {
+                    SinkKind::StorageWrite => ("storage write", "address", "value"),
+                    SinkKind::EmitEvent => ("event emission", "keys", "data"),
+                };
+                let tainted = [(*tainted_key, key), (*tainted_value, value)]
+                    .into_iter()
+                    .filter_map(|(tainted, name)| tainted.then_some(name))
+                    .collect::<Vec<_>>()
+                    .join(" and ");
+                writeln!(
+                    f,
+                    "    {sink} at {}: {tainted} influenced by calldata.",
+                    describe_sink(tainted_sink)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+impl fmt::Display for CalldataTaintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_report(f, &|sink| format!("statement {}", sink.statement_idx))
+    }
+}
+
+/// The parameters of a function a value may be derived from, by index.
+type Taint = BTreeSet<usize>;
+
+/// The taint of the operands of a sink.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct SinkTaint {
+    kind: SinkKind,
+    /// The function call statements through which the sink is reached, outermost first.
+    calls: Vec<StatementIdx>,
+    key: Taint,
+    value: Taint,
+}
+
+/// The taint flowing through a function, in terms of its parameters.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct FunctionSummary {
+    /// The taint of each of the returned values.
+    returns: Vec<Taint>,
+    /// The taint of the sinks of the function and of the functions it calls, by statement index.
+    sinks: BTreeMap<StatementIdx, SinkTaint>,
+}
+
+/// The summaries of all the functions of a program.
+struct TaintAnalysis<'a> {
+    program: &'a Program,
+    registry: &'a ProgramRegistry<CoreType, CoreLibfunc>,
+    summaries: UnorderedHashMap<FunctionId, FunctionSummary>,
+}
+impl<'a> TaintAnalysis<'a> {
+    /// Computes the summaries of the functions of the program, until a fixed point is reached, as
+    /// functions may be recursive.
+    fn new(
+        program: &'a Program,
+        registry: &'a ProgramRegistry<CoreType, CoreLibfunc>,
+    ) -> Result<Self, Box<ProgramRegistryError>> {
+        let mut analysis = Self { program, registry, summaries: Default::default() };
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for function in &program.funcs {
+                let summary = analysis.summarize(function)?;
+                if analysis.summaries.get(&function.id) != Some(&summary) {
+                    analysis.summaries.insert(function.id.clone(), summary);
+                    changed = true;
+                }
+            }
+        }
+        Ok(analysis)
+    }
+
+    /// Computes the summary of a function, given the current summaries of the functions it calls.
+    fn summarize(&self, function: &Function) -> Result<FunctionSummary, Box<ProgramRegistryError>> {
+        let mut params_state = OrderedHashMap::<VarId, Taint>::default();
+        for (idx, param) in function.params.iter().enumerate() {
+            let taint = if self.is_builtin(&param.ty)? { Taint::new() } else { Taint::from([idx]) };
+            params_state.insert(param.id.clone(), taint);
+        }
+        let mut summary = FunctionSummary {
+            returns: vec![Taint::new(); function.signature.ret_types.len()],
+            sinks: BTreeMap::new(),
+        };
+        // The taint of the variables at the start of each reached statement.
+        let mut states = UnorderedHashMap::<StatementIdx, OrderedHashMap<VarId, Taint>>::default();
+        states.insert(function.entry_point, params_state);
+        let mut pending = VecDeque::from([function.entry_point]);
+        while let Some(idx) = pending.pop_front() {
+            let mut state = states[&idx].clone();
+            let invocation = match &self.program.statements[idx.0] {
+                Statement::Return(vars) => {
+                    for (taint, var) in summary.returns.iter_mut().zip(vars) {
+                        taint.extend(state.get(var).into_iter().flatten());
+                    }
+                    continue;
+                }
+                Statement::Invocation(invocation) => invocation,
+            };
+            let args_taint: Vec<Taint> = invocation
+                .args
+                .iter()
+                .map(|var| state.swap_remove(var).unwrap_or_default())
+                .collect();
+            let libfunc = self.registry.get_libfunc(&invocation.libfunc_id)?;
+            let callee_summary = match libfunc {
+                CoreConcreteLibfunc::FunctionCall(libfunc) => {
+                    Some(self.summaries.get(&libfunc.function.id).cloned().unwrap_or_default())
+                }
+                _ => None,
+            };
+            let sink_kind = match libfunc {
+                CoreConcreteLibfunc::StarkNet(StarkNetConcreteLibfunc::StorageWrite(_)) => {
+                    Some(SinkKind::StorageWrite)
+                }
+                CoreConcreteLibfunc::StarkNet(StarkNetConcreteLibfunc::EmitEvent(_)) => {
+                    Some(SinkKind::EmitEvent)
+                }
+                _ => None,
+            };
+            if let Some(kind) = sink_kind {
+                // The key and the value are the last arguments of both system calls.
+                let [.., key, value] = &args_taint[..] else {
+                    panic!("Unexpected system call arguments.");
+                };
+                add_sink(
+                    &mut summary,
+                    idx,
+                    SinkTaint { kind, calls: vec![], key: key.clone(), value: value.clone() },
+                );
+            }
+            // Maps a taint in terms of the parameters of the callee to the taint of the arguments.
+            let map_taint = |callee_taint: &Taint| -> Taint {
+                callee_taint.iter().flat_map(|param_idx| &args_taint[*param_idx]).copied().collect()
+            };
+            if let Some(callee_summary) = &callee_summary {
+                for (statement_idx, sink) in &callee_summary.sinks {
+                    add_sink(
+                        &mut summary,
+                        *statement_idx,
+                        SinkTaint {
+                            kind: sink.kind,
+                            calls: chain!([idx], sink.calls.iter().copied()).collect(),
+                            key: map_taint(&sink.key),
+                            value: map_taint(&sink.value),
+                        },
+                    );
+                }
+            }
+            let all_args_taint: Taint = args_taint.iter().flatten().copied().collect();
+
+            for (branch, branch_signature) in
+                invocation.branches.iter().zip(libfunc.branch_signatures())
+            {
+                let mut branch_state = state.clone();
+                for (output_idx, (var, OutputVarInfo { ty, .. })) in
+                    branch.results.iter().zip(&branch_signature.vars).enumerate()
+                {
+                    let taint = if self.is_builtin(ty)? {
+                        Taint::new()
+                    } else if let Some(callee_summary) = &callee_summary {
+                        callee_summary.returns.get(output_idx).map(map_taint).unwrap_or_default()
+                    } else {
+                        all_args_taint.clone()
+                    };
+                    branch_state.insert(var.clone(), taint);
+                }
+                let target = idx.next(&branch.target);
+                let changed = match states.get_mut(&target) {
+                    Some(target_state) => merge_state(target_state, branch_state),
+                    None => {
+                        states.insert(target, branch_state);
+                        true
+                    }
+                };
+                if changed && !pending.contains(&target) {
+                    pending.push_back(target);
+                }
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Returns whether the given type is the type of a builtin.
+    fn is_builtin(&self, ty: &ConcreteTypeId) -> Result<bool, Box<ProgramRegistryError>> {
+        let concrete_type: &CoreTypeConcrete = self.registry.get_type(ty)?;
+        Ok(BUILTIN_TYPES.contains(&concrete_type.info().long_id.generic_id))
+    }
+}
+
+/// Adds the taint of a sink to the summary, merging it with its taint through other paths, and
+/// keeping the shortest path of calls to it.
+fn add_sink(summary: &mut FunctionSummary, statement_idx: StatementIdx, sink: SinkTaint) {
+    match summary.sinks.get_mut(&statement_idx) {
+        Some(existing) => {
+            if sink.calls.len() < existing.calls.len() {
+                existing.calls = sink.calls;
+            }
+            existing.key.extend(sink.key);
+            existing.value.extend(sink.value);
+        }
+        None => {
+            summary.sinks.insert(statement_idx, sink);
+        }
+    }
+}
+
+/// Merges a state into the state at the start of a statement. Returns whether the state changed.
+fn merge_state(
+    target: &mut OrderedHashMap<VarId, Taint>,
+    state: OrderedHashMap<VarId, Taint>,
+) -> bool {
+    let mut changed = false;
+    for (var, taint) in state {
+        match target.get_mut(&var) {
+            Some(existing) => {
+                let prev_len = existing.len();
+                existing.extend(taint);
+                changed |= existing.len() != prev_len;
+            }
+            None => {
+                target.insert(var, taint);
+                changed = true;
+            }
+        }
+    }
+    changed
+}
@@ -0,0 +1,191 @@
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::{CalldataTaintReport, EntryPointTaint, SinkKind, TaintedSink};
+use crate::program::StatementIdx;
+use crate::ProgramParser;
+
+#[test]
+fn calldata_taint_report() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type GasBuiltin = GasBuiltin;
+            type System = System;
+            type u32 = u32;
+            type StorageBaseAddress = StorageBaseAddress;
+            type StorageAddress = StorageAddress;
+            type Array = Array<felt252>;
+
+            libfunc base_address_const = storage_base_address_const<0>;
+            libfunc address_from_base = storage_address_from_base;
+            libfunc u32_const = u32_const<0>;
+            libfunc felt252_const = felt252_const<5>;
+            libfunc felt252_add = felt252_add;
+            libfunc storage_write = storage_write_syscall;
+            libfunc branch_align = branch_align;
+            libfunc drop_felt252 = drop<felt252>;
+            libfunc drop_array = drop<Array>;
+            libfunc call_write = function_call<user@test::write>;
+
+            base_address_const() -> ([3]);
+            address_from_base([3]) -> ([4]);
+            u32_const() -> ([5]);
+            storage_write([0], [1], [5], [4], [2]) { fallthrough([0], [1]) 6([0], [1], [6]) };
+            branch_align() -> ();
+            return([0], [1]);
+            branch_align() -> ();
+            drop_array([6]) -> ();
+            return([0], [1]);
+            felt252_const() -> ([3]);
+            felt252_add([2], [3]) -> ([4]);
+            call_write([0], [1], [4]) -> ([0], [1]);
+            return([0], [1]);
+            drop_felt252([2]) -> ();
+            felt252_const() -> ([2]);
+            call_write([0], [1], [2]) -> ([0], [1]);
+            return([0], [1]);
+
+            test::write@0([0]: GasBuiltin, [1]: System, [2]: felt252) -> (GasBuiltin, System);
+            test::tainted@9([0]: GasBuiltin, [1]: System, [2]: felt252) -> (GasBuiltin, System);
+            test::untainted@13([0]: GasBuiltin, [1]: System, [2]: felt252) -> (GasBuiltin, System);
+        "})
+        .unwrap();
+    let report =
+        CalldataTaintReport::new(&program, &["test::tainted".into(), "test::untainted".into()])
+            .unwrap();
+    assert_eq!(
+        report,
+        CalldataTaintReport {
+            entry_points: vec![
+                EntryPointTaint {
+                    entry_point: "test::tainted".into(),
+                    sinks: vec![TaintedSink {
+                        statement_idx: StatementIdx(3),
+                        calls: vec![StatementIdx(11)],
+                        kind: SinkKind::StorageWrite,
+                        tainted_key: false,
+                        tainted_value: true,
+                    }],
+                },
+                EntryPointTaint { entry_point: "test::untainted".into(), sinks: vec![] },
+            ]
+        }
+    );
+    assert_eq!(
+        report.to_string(),
+        indoc! {"
+            test::tainted:
+                storage write at statement 3: value influenced by calldata.
+            test::untainted: no calldata influenced storage writes or events.
+        "}
+    );
+}
+
+#[test]
+fn calldata_taint_report_tainted_key() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type RangeCheck = RangeCheck;
+            type GasBuiltin = GasBuiltin;
+            type System = System;
+            type u32 = u32;
+            type StorageBaseAddress = StorageBaseAddress;
+            type StorageAddress = StorageAddress;
+            type Array = Array<felt252>;
+
+            libfunc base_address_from_felt252 = storage_base_address_from_felt252;
+            libfunc address_from_base = storage_address_from_base;
+            libfunc u32_const = u32_const<0>;
+            libfunc felt252_const = felt252_const<5>;
+            libfunc storage_write = storage_write_syscall;
+            libfunc branch_align = branch_align;
+            libfunc drop_array = drop<Array>;
+
+            base_address_from_felt252([0], [3]) -> ([0], [4]);
+            address_from_base([4]) -> ([5]);
+            u32_const() -> ([6]);
+            felt252_const() -> ([7]);
+            storage_write([1], [2], [6], [5], [7]) { fallthrough([1], [2]) 7([1], [2], [8]) };
+            branch_align() -> ();
+            return([0], [1], [2]);
+            branch_align() -> ();
+            drop_array([8]) -> ();
+            return([0], [1], [2]);
+
+            test::keyed@0([0]: RangeCheck, [1]: GasBuiltin, [2]: System, [3]: felt252) \
+                -> (RangeCheck, GasBuiltin, System);
+        "})
+        .unwrap();
+    let report = CalldataTaintReport::new(&program, &["test::keyed".into()]).unwrap();
+    assert_eq!(
+        report.entry_points[0].sinks,
+        vec![TaintedSink {
+            statement_idx: StatementIdx(4),
+            calls: vec![],
+            kind: SinkKind::StorageWrite,
+            tainted_key: true,
+            tainted_value: false,
+        }]
+    );
+    assert_eq!(
+        report.to_string(),
+        indoc! {"
+            test::keyed:
+                storage write at statement 4: address influenced by calldata.
+        "}
+    );
+}
+
+#[test]
+fn calldata_taint_report_emit_event() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type GasBuiltin = GasBuiltin;
+            type System = System;
+            type Array = Array<felt252>;
+            type SnapshotArray = Snapshot<Array>;
+            type Span = Struct<ut@core::array::Span::<core::felt252>, SnapshotArray>;
+
+            libfunc array_new = array_new<felt252>;
+            libfunc snapshot_take = snapshot_take<Array>;
+            libfunc span_construct = struct_construct<Span>;
+            libfunc emit_event = emit_event_syscall;
+            libfunc branch_align = branch_align;
+            libfunc drop_array = drop<Array>;
+
+            array_new() -> ([3]);
+            snapshot_take([3]) -> ([4], [5]);
+            drop_array([4]) -> ();
+            span_construct([5]) -> ([6]);
+            emit_event([0], [1], [6], [2]) { fallthrough([0], [1]) 7([0], [1], [7]) };
+            branch_align() -> ();
+            return([0], [1]);
+            branch_align() -> ();
+            drop_array([7]) -> ();
+            return([0], [1]);
+
+            test::emit@0([0]: GasBuiltin, [1]: System, [2]: Span) -> (GasBuiltin, System);
+        "})
+        .unwrap();
+    let report = CalldataTaintReport::new(&program, &["test::emit".into()]).unwrap();
+    assert_eq!(
+        report.entry_points[0].sinks,
+        vec![TaintedSink {
+            statement_idx: StatementIdx(4),
+            calls: vec![],
+            kind: SinkKind::EmitEvent,
+            tainted_key: false,
+            tainted_value: true,
+        }]
+    );
+    assert_eq!(
+        report.format_with_locations(|sink| format!("`{}`", sink.statement_idx)),
+        indoc! {"
+            test::emit:
+                event emission at `4`: data influenced by calldata.
+        "}
+    );
+}
@@ -12,11 +12,14 @@
 use lalrpop_util::lalrpop_mod;
 
 pub mod algorithm;
+pub mod calldata_taint;
 pub mod debug_info;
 pub mod edit_state;
 pub mod extensions;
 pub mod fmt;
 pub mod ids;
+pub mod optimization;
+pub mod panic_reachability;
 mod pre_statement;
 pub mod program;
 pub mod program_registry;
@@ -0,0 +1,496 @@
+//! Sierra level optimizations - passes transforming a valid Sierra program into an equivalent, and
+//! smaller, valid Sierra program.
+//!
+//! Note that the passes change the indices of the statements, so any information keyed by
+//! statement index (e.g. statement locations, profiling data) is invalidated by them.
+
+use std::fmt;
+
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use itertools::chain;
+
+use crate::extensions::branch_align::BranchAlignLibfunc;
+use crate::extensions::unconditional_jump::UnconditionalJumpLibfunc;
+use crate::extensions::NoGenericArgsGenericLibfunc;
+use crate::ids::{ConcreteLibfuncId, ConcreteTypeId, FunctionId};
+use crate::program::{BranchTarget, GenericArg, Program, Statement, StatementIdx};
+
+#[cfg(test)]
+#[path = "optimization_test.rs"]
+mod test;
+
+/// A Sierra optimization pass.
+pub trait SierraPass {
+    /// The name of the pass, used for reporting.
+    fn name(&self) -> &str;
+
+    /// Applies the pass on the program.
+    fn apply(&self, program: &mut Program);
+}
+
+/// The size of a Sierra program, by its different components.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ProgramSize {
+    pub type_declarations: usize,
+    pub libfunc_declarations: usize,
+    pub statements: usize,
+    pub funcs: usize,
+}
+impl ProgramSize {
+    pub fn of(program: &Program) -> Self {
+        Self {
+            type_declarations: program.type_declarations.len(),
+            libfunc_declarations: program.libfunc_declarations.len(),
+            statements: program.statements.len(),
+            funcs: program.funcs.len(),
+        }
+    }
+}
+
+/// Statistics of a single application of a pass.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PassStatistics {
+    /// The name of the pass.
+    pub name: String,
+    /// The size of the program before the pass.
+    pub before: ProgramSize,
+    /// The size of the program after the pass.
+    pub after: ProgramSize,
+}
+impl fmt::Display for PassStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { name, before, after } = self;
+        write!(
+            f,
+            "{name}: types: {} -> {}, libfuncs: {} -> {}, statements: {} -> {}, functions: {} -> \
+             {}",
+            before.type_declarations,
+            after.type_declarations,
+            before.libfunc_declarations,
+            after.libfunc_declarations,
+            before.statements,
+            after.statements,
+            before.funcs,
+            after.funcs,
+        )
+    }
+}
+
+/// A pipeline of Sierra passes, applied by order.
+#[derive(Default)]
+pub struct OptimizationPipeline {
+    passes: Vec<Box<dyn SierraPass>>,
+}
+impl OptimizationPipeline {
+    /// Returns the pipeline of all the available passes, keeping the functions reachable from
+    /// `roots`.
+    pub fn all(roots: Vec<FunctionId>) -> Self {
+        Self::default()
+            .with_pass(DedupDeclarations)
+            .with_pass(BranchSimplification)
+            .with_pass(DeadFunctionElimination { roots })
+    }
+
+    /// Adds a pass at the end of the pipeline.
+    pub fn with_pass(mut self, pass: impl SierraPass + 'static) -> Self {
+        self.passes.push(Box::new(pass));
+        self
+    }
+
+    /// Applies the passes of the pipeline on the program, returning the statistics of each pass.
+    pub fn apply(&self, program: &mut Program) -> Vec<PassStatistics> {
+        self.passes
+            .iter()
+            .map(|pass| {
+                let before = ProgramSize::of(program);
+                pass.apply(program);
+                PassStatistics {
+                    name: pass.name().to_string(),
+                    before,
+                    after: ProgramSize::of(program),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Merges identical declarations - types (including consts) and libfuncs declared more than once
+/// under different ids - replacing all the usages of the removed ids.
+pub struct DedupDeclarations;
+impl SierraPass for DedupDeclarations {
+    fn name(&self) -> &str {
+        "dedup_declarations"
+    }
+
+    fn apply(&self, program: &mut Program) {
+        // Merging types may make other types identical (e.g. `Array<A>` and `Array<B>` when `A`
+        // and `B` are merged), so merging is repeated until there is no change.
+        loop {
+            let mut first_declarations = UnorderedHashMap::<_, ConcreteTypeId>::default();
+            let mut type_replacements =
+                UnorderedHashMap::<ConcreteTypeId, ConcreteTypeId>::default();
+            program.type_declarations.retain(|declaration| {
+                let key = (declaration.long_id.clone(), declaration.declared_type_info.clone());
+                if let Some(first) = first_declarations.get(&key) {
+                    type_replacements.insert(declaration.id.clone(), first.clone());
+                    false
+                } else {
+                    first_declarations.insert(key, declaration.id.clone());
+                    true
+                }
+            });
+            if type_replacements.is_empty() {
+                break;
+            }
+            replace_type_ids(program, &type_replacements);
+        }
+
+        let mut first_declarations = UnorderedHashMap::<_, ConcreteLibfuncId>::default();
+        let mut libfunc_replacements =
+            UnorderedHashMap::<ConcreteLibfuncId, ConcreteLibfuncId>::default();
+        program.libfunc_declarations.retain(|declaration| {
+            if let Some(first) = first_declarations.get(&declaration.long_id) {
+                libfunc_replacements.insert(declaration.id.clone(), first.clone());
+                false
+            } else {
+                first_declarations.insert(declaration.long_id.clone(), declaration.id.clone());
+                true
+            }
+        });
+        for statement in &mut program.statements {
+            if let Statement::Invocation(invocation) = statement {
+                if let Some(replacement) = libfunc_replacements.get(&invocation.libfunc_id) {
+                    invocation.libfunc_id = replacement.clone();
+                }
+            }
+        }
+        for declaration in &mut program.libfunc_declarations {
+            for arg in &mut declaration.long_id.generic_args {
+                if let GenericArg::Libfunc(id) = arg {
+                    if let Some(replacement) = libfunc_replacements.get(id) {
+                        *id = replacement.clone();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Replaces the usages of types according to the given replacements.
+fn replace_type_ids(
+    program: &mut Program,
+    replacements: &UnorderedHashMap<ConcreteTypeId, ConcreteTypeId>,
+) {
+    let replace = |id: &mut ConcreteTypeId| {
+        if let Some(replacement) = replacements.get(id) {
+            *id = replacement.clone();
+        }
+    };
+    let replace_args = |args: &mut Vec<GenericArg>| {
+        for arg in args {
+            if let GenericArg::Type(id) = arg {
+                replace(id);
+            }
+        }
+    };
+    for declaration in &mut program.type_declarations {
+        replace_args(&mut declaration.long_id.generic_args);
+    }
+    for declaration in &mut program.libfunc_declarations {
+        replace_args(&mut declaration.long_id.generic_args);
+    }
+    for function in &mut program.funcs {
+        function.signature.param_types.iter_mut().for_each(replace);
+        function.signature.ret_types.iter_mut().for_each(replace);
+        for param in &mut function.params {
+            replace(&mut param.ty);
+        }
+    }
+}
+
+/// Simplifies the control flow of the program - jumps to other jumps are redirected to the final
+/// target, and jumps to the next statement are removed.
+pub struct BranchSimplification;
+impl SierraPass for BranchSimplification {
+    fn name(&self) -> &str {
+        "branch_simplification"
+    }
+
+    fn apply(&self, program: &mut Program) {
+        let jump_libfuncs: UnorderedHashSet<_> = program
+            .libfunc_declarations
+            .iter()
+            .filter(|declaration| {
+                declaration.long_id.generic_id.0 == UnconditionalJumpLibfunc::STR_ID
+            })
+            .map(|declaration| declaration.id.clone())
+            .collect();
+        // Returns the target of the statement at `idx` if it is a jump.
+        let jump_target = |statements: &[Statement], idx: StatementIdx| {
+            let Some(Statement::Invocation(invocation)) = statements.get(idx.0) else {
+                return None;
+            };
+            if !jump_libfuncs.contains(&invocation.libfunc_id) {
+                return None;
+            }
+            match invocation.branches.as_slice() {
+                [branch] => Some(idx.next(&branch.target)),
+                _ => None,
+            }
+        };
+
+        for idx in 0..program.statements.len() {
+            let Some(mut target) = jump_target(&program.statements, StatementIdx(idx)) else {
+                continue;
+            };
+            // Bounding the number of followed jumps, to avoid looping over jump cycles.
+            for _ in 0..program.statements.len() {
+                match jump_target(&program.statements, target) {
+                    Some(next_target) if next_target != target => target = next_target,
+                    _ => break,
+                }
+            }
+            let Statement::Invocation(invocation) = &mut program.statements[idx] else {
+                unreachable!("Jump statements are invocations.");
+            };
+            invocation.branches[0].target = BranchTarget::Statement(target);
+        }
+
+        let removed: Vec<bool> = (0..program.statements.len())
+            .map(|idx| {
+                jump_target(&program.statements, StatementIdx(idx)) == Some(StatementIdx(idx + 1))
+                    // Removing a jump followed by a `branch_align` would make it the target of a
+                    // fallthrough, which is not allowed.
+                    && !is_branch_align(program, StatementIdx(idx + 1))
+            })
+            .collect();
+        remove_statements(program, &removed);
+    }
+}
+
+/// Returns whether the statement at `idx` is a `branch_align` invocation.
+fn is_branch_align(program: &Program, idx: StatementIdx) -> bool {
+    let Some(Statement::Invocation(invocation)) = program.statements.get(idx.0) else {
+        return false;
+    };
+    program.libfunc_declarations.iter().any(|declaration| {
+        declaration.id == invocation.libfunc_id
+            && declaration.long_id.generic_id.0 == BranchAlignLibfunc::STR_ID
+    })
+}
+
+/// Removes the functions, statements, libfuncs and types not reachable from the given root
+/// functions.
+pub struct DeadFunctionElimination {
+    /// The functions to keep, along with everything used by them.
+    pub roots: Vec<FunctionId>,
+}
+impl SierraPass for DeadFunctionElimination {
+    fn name(&self) -> &str {
+        "dead_function_elimination"
+    }
+
+    fn apply(&self, program: &mut Program) {
+        let ReachableItems { functions, libfuncs, types, statements } =
+            find_reachable_items(program, &self.roots);
+        program.funcs.retain(|function| functions.contains(&function.id));
+        program.libfunc_declarations.retain(|declaration| libfuncs.contains(&declaration.id));
+        program.type_declarations.retain(|declaration| types.contains(&declaration.id));
+        let removed: Vec<bool> = statements.into_iter().map(|reachable| !reachable).collect();
+        remove_statements(program, &removed);
+    }
+}
+
+/// A user function unreachable from the roots of a program.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeadFunction {
+    pub id: FunctionId,
+    /// The number of statements the function contributes to the program - its statements not
+    /// shared with any reachable function.
+    pub statements: usize,
+}
+
+/// A report of the items of a Sierra program unreachable from a set of root functions, e.g. the
+/// entry points of an executable or a contract.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DeadCodeReport {
+    /// The unreachable user functions, by order of declaration.
+    pub functions: Vec<DeadFunction>,
+    /// The unused libfunc declarations, by order of declaration.
+    pub libfuncs: Vec<ConcreteLibfuncId>,
+    /// The unused type declarations, by order of declaration.
+    pub types: Vec<ConcreteTypeId>,
+}
+impl DeadCodeReport {
+    /// Creates the report of the unreachable items of the program with the given roots.
+    pub fn new(program: &Program, roots: &[FunctionId]) -> Self {
+        let reachable = find_reachable_items(program, roots);
+        Self {
+            functions: program
+                .funcs
+                .iter()
+                .filter(|function| !reachable.functions.contains(&function.id))
+                .map(|function| DeadFunction {
+                    id: function.id.clone(),
+                    statements: function_statements(program, function.entry_point)
+                        .into_iter()
+                        .filter(|idx| !reachable.statements[idx.0])
+                        .count(),
+                })
+                .collect(),
+            libfuncs: program
+                .libfunc_declarations
+                .iter()
+                .filter(|declaration| !reachable.libfuncs.contains(&declaration.id))
+                .map(|declaration| declaration.id.clone())
+                .collect(),
+            types: program
+                .type_declarations
+                .iter()
+                .filter(|declaration| !reachable.types.contains(&declaration.id))
+                .map(|declaration| declaration.id.clone())
+                .collect(),
+        }
+    }
+
+    /// Returns the total number of unreachable statements.
+    pub fn dead_statements(&self) -> usize {
+        self.functions.iter().map(|function| function.statements).sum()
+    }
+}
+impl fmt::Display for DeadCodeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Unreachable functions: {} ({} statements).",
+            self.functions.len(),
+            self.dead_statements()
+        )?;
+        for DeadFunction { id, statements } in &self.functions {
+            writeln!(f, "  {id}: {statements} statements")?;
+        }
+        writeln!(f, "Unused libfuncs: {}.", self.libfuncs.len())?;
+        for id in &self.libfuncs {
+            writeln!(f, "  {id}")?;
+        }
+        writeln!(f, "Unused types: {}.", self.types.len())?;
+        for id in &self.types {
+            writeln!(f, "  {id}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The items of a program reachable from a set of root functions.
+pub(crate) struct ReachableItems {
+    pub functions: UnorderedHashSet<FunctionId>,
+    pub libfuncs: UnorderedHashSet<ConcreteLibfuncId>,
+    pub types: UnorderedHashSet<ConcreteTypeId>,
+    /// Whether each statement is reachable, by statement index.
+    pub statements: Vec<bool>,
+}
+
+/// Finds the items of the program reachable from the given root functions - the functions called
+/// from them, and the statements, libfuncs and types used by all of these functions.
+pub(crate) fn find_reachable_items(program: &Program, roots: &[FunctionId]) -> ReachableItems {
+    let functions: UnorderedHashMap<_, _> =
+        program.funcs.iter().map(|function| (function.id.clone(), function)).collect();
+    let libfuncs: UnorderedHashMap<_, _> = program
+        .libfunc_declarations
+        .iter()
+        .map(|declaration| (declaration.id.clone(), &declaration.long_id.generic_args))
+        .collect();
+    let types: UnorderedHashMap<_, _> = program
+        .type_declarations
+        .iter()
+        .map(|declaration| (declaration.id.clone(), &declaration.long_id.generic_args))
+        .collect();
+
+    let mut reachable = ReachableItems {
+        functions: Default::default(),
+        libfuncs: Default::default(),
+        types: Default::default(),
+        statements: vec![false; program.statements.len()],
+    };
+    let mut pending_args: Vec<GenericArg> =
+        roots.iter().cloned().map(GenericArg::UserFunc).collect();
+    while let Some(arg) = pending_args.pop() {
+        match arg {
+            GenericArg::UserFunc(function_id) => {
+                if !reachable.functions.insert(function_id.clone()) {
+                    continue;
+                }
+                let Some(function) = functions.get(&function_id) else { continue };
+                pending_args.extend(
+                    chain!(&function.signature.param_types, &function.signature.ret_types)
+                        .cloned()
+                        .map(GenericArg::Type),
+                );
+                for idx in function_statements(program, function.entry_point) {
+                    reachable.statements[idx.0] = true;
+                    if let Statement::Invocation(invocation) = &program.statements[idx.0] {
+                        pending_args.push(GenericArg::Libfunc(invocation.libfunc_id.clone()));
+                    }
+                }
+            }
+            GenericArg::Libfunc(id) => {
+                if let Some(args) = libfuncs.get(&id) {
+                    if reachable.libfuncs.insert(id) {
+                        pending_args.extend(args.iter().cloned());
+                    }
+                }
+            }
+            GenericArg::Type(id) => {
+                if let Some(args) = types.get(&id) {
+                    if reachable.types.insert(id) {
+                        pending_args.extend(args.iter().cloned());
+                    }
+                }
+            }
+            GenericArg::UserType(_) | GenericArg::Value(_) => {}
+        }
+    }
+    reachable
+}
+
+/// Returns the statements of the function starting at `entry_point` - the statements reachable
+/// from it by branching, sorted by index.
+pub fn function_statements(program: &Program, entry_point: StatementIdx) -> Vec<StatementIdx> {
+    let mut visited = vec![false; program.statements.len()];
+    let mut pending = vec![entry_point];
+    while let Some(idx) = pending.pop() {
+        let Some(is_visited) = visited.get_mut(idx.0) else { continue };
+        if std::mem::replace(is_visited, true) {
+            continue;
+        }
+        if let Statement::Invocation(invocation) = &program.statements[idx.0] {
+            pending.extend(invocation.branches.iter().map(|branch| idx.next(&branch.target)));
+        }
+    }
+    (0..visited.len()).filter(|idx| visited[*idx]).map(StatementIdx).collect()
+}
+
+/// Removes the statements marked as removed, updating the branch targets and function entry
+/// points. Targets of removed statements are redirected to the following kept statement.
+fn remove_statements(program: &mut Program, removed: &[bool]) {
+    let mut new_indices = vec![0; removed.len()];
+    let mut next_index = removed.iter().filter(|is_removed| !**is_removed).count();
+    for (idx, is_removed) in removed.iter().enumerate().rev() {
+        if !is_removed {
+            next_index -= 1;
+        }
+        new_indices[idx] = next_index;
+    }
+    let remap = |idx: StatementIdx| StatementIdx(new_indices.get(idx.0).copied().unwrap_or(idx.0));
+    let statements = std::mem::take(&mut program.statements);
+    program.statements = statements
+        .into_iter()
+        .zip(removed)
+        .filter(|(_, is_removed)| !**is_removed)
+        .map(|(statement, _)| statement.map(remap))
+        .collect();
+    for function in &mut program.funcs {
+        function.entry_point = remap(function.entry_point);
+    }
+}
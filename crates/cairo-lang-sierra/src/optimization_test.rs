@@ -0,0 +1,240 @@
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::{
+    BranchSimplification, DeadCodeReport, DeadFunctionElimination, DedupDeclarations,
+    OptimizationPipeline, SierraPass,
+};
+use crate::extensions::core::{CoreLibfunc, CoreType};
+use crate::program::Program;
+use crate::program_registry::ProgramRegistry;
+use crate::ProgramParser;
+
+/// Applies the pass on the given program, validates the result and returns it as text.
+fn apply_pass(pass: impl SierraPass, sierra_code: &str) -> String {
+    let mut program = ProgramParser::new().parse(sierra_code).unwrap();
+    pass.apply(&mut program);
+    validate(&program);
+    program.to_string()
+}
+
+fn validate(program: &Program) {
+    ProgramRegistry::<CoreType, CoreLibfunc>::new(program).expect("Invalid program.");
+}
+
+#[test]
+fn dedup_declarations() {
+    assert_eq!(
+        apply_pass(
+            DedupDeclarations,
+            indoc! {"
+                type felt252 = felt252;
+                type felt252_copy = felt252;
+                type Const5 = const<felt252, 5>;
+                type Const5Copy = const<felt252_copy, 5>;
+                type Box5 = Box<felt252>;
+                type Box5Copy = Box<felt252_copy>;
+
+                libfunc store5 = const_as_box<Const5>;
+                libfunc store5copy = const_as_box<Const5Copy>;
+                libfunc drop_box = drop<Box5>;
+                libfunc drop_box_copy = drop<Box5Copy>;
+
+                store5() -> ([0]);
+                store5copy() -> ([1]);
+                drop_box([0]) -> ();
+                drop_box_copy([1]) -> ();
+                return();
+
+                foo@0() -> ();
+            "}
+        ),
+        indoc! {"
+            type felt252 = felt252;
+            type Const5 = const<felt252, 5>;
+            type Box5 = Box<felt252>;
+
+            libfunc store5 = const_as_box<Const5>;
+            libfunc drop_box = drop<Box5>;
+
+            store5() -> ([0]); // 0
+            store5() -> ([1]); // 1
+            drop_box([0]) -> (); // 2
+            drop_box([1]) -> (); // 3
+            return(); // 4
+
+            foo@0() -> ();
+        "}
+    );
+}
+
+#[test]
+fn branch_simplification() {
+    assert_eq!(
+        apply_pass(
+            BranchSimplification,
+            indoc! {"
+                type felt252 = felt252;
+                type NonZeroFelt252 = NonZero<felt252>;
+
+                libfunc branch_align = branch_align;
+                libfunc jump = jump;
+                libfunc felt252_is_zero = felt252_is_zero;
+                libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+
+                felt252_is_zero([0]) { fallthrough() 4([0]) };
+                branch_align() -> ();
+                jump() { 3() };
+                jump() { 7() };
+                branch_align() -> ();
+                drop_nz_felt252([0]) -> ();
+                jump() { 7() };
+                return();
+
+                foo@0([0]: felt252) -> ();
+            "}
+        ),
+        indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc branch_align = branch_align;
+            libfunc jump = jump;
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+
+            felt252_is_zero([0]) { fallthrough() 4([0]) }; // 0
+            branch_align() -> (); // 1
+            jump() { 6() }; // 2
+            jump() { 6() }; // 3
+            branch_align() -> (); // 4
+            drop_nz_felt252([0]) -> (); // 5
+            return(); // 6
+
+            foo@0([0]: felt252) -> ();
+        "}
+    );
+}
+
+#[test]
+fn dead_function_elimination() {
+    assert_eq!(
+        apply_pass(
+            DeadFunctionElimination { roots: vec!["foo".into()] },
+            indoc! {"
+                type felt252 = felt252;
+                type u128 = u128;
+
+                libfunc call_bar = function_call<user@bar>;
+                libfunc call_baz = function_call<user@baz>;
+                libfunc felt252_drop = drop<felt252>;
+                libfunc u128_drop = drop<u128>;
+
+                call_bar([0]) -> ();
+                return();
+                felt252_drop([0]) -> ();
+                return();
+                u128_drop([0]) -> ();
+                call_baz() -> ();
+                return();
+                return();
+
+                foo@0([0]: felt252) -> ();
+                bar@2([0]: felt252) -> ();
+                unused@4([0]: u128) -> ();
+                baz@7() -> ();
+            "}
+        ),
+        indoc! {"
+            type felt252 = felt252;
+
+            libfunc call_bar = function_call<user@bar>;
+            libfunc felt252_drop = drop<felt252>;
+
+            call_bar([0]) -> (); // 0
+            return(); // 1
+            felt252_drop([0]) -> (); // 2
+            return(); // 3
+
+            foo@0([0]: felt252) -> ();
+            bar@2([0]: felt252) -> ();
+        "}
+    );
+}
+
+#[test]
+fn pipeline_statistics() {
+    let mut program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            libfunc jump = jump;
+            libfunc felt252_drop = drop<felt252>;
+
+            jump() { 1() };
+            return();
+            felt252_drop([0]) -> ();
+            return();
+
+            foo@0() -> ();
+            unused@2([0]: felt252) -> ();
+        "})
+        .unwrap();
+    let statistics = OptimizationPipeline::all(vec!["foo".into()]).apply(&mut program);
+    validate(&program);
+    assert_eq!(
+        statistics.iter().map(|statistics| statistics.to_string()).collect::<Vec<_>>(),
+        vec![
+            "dedup_declarations: types: 1 -> 1, libfuncs: 2 -> 2, statements: 4 -> 4, functions: \
+             2 -> 2",
+            "branch_simplification: types: 1 -> 1, libfuncs: 2 -> 2, statements: 4 -> 3, \
+             functions: 2 -> 2",
+            "dead_function_elimination: types: 1 -> 0, libfuncs: 2 -> 0, statements: 3 -> 1, \
+             functions: 2 -> 1",
+        ]
+    );
+}
+
+#[test]
+fn dead_code_report() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type u128 = u128;
+
+            libfunc call_bar = function_call<user@bar>;
+            libfunc call_baz = function_call<user@baz>;
+            libfunc felt252_drop = drop<felt252>;
+            libfunc u128_drop = drop<u128>;
+
+            call_bar([0]) -> ();
+            return();
+            felt252_drop([0]) -> ();
+            return();
+            u128_drop([0]) -> ();
+            call_baz() -> ();
+            return();
+            return();
+
+            foo@0([0]: felt252) -> ();
+            bar@2([0]: felt252) -> ();
+            unused@4([0]: u128) -> ();
+            baz@7() -> ();
+        "})
+        .unwrap();
+    let report = DeadCodeReport::new(&program, &["foo".into()]);
+    assert_eq!(report.dead_statements(), 4);
+    assert_eq!(
+        report.to_string(),
+        indoc! {"
+            Unreachable functions: 2 (4 statements).
+              unused: 3 statements
+              baz: 1 statements
+            Unused libfuncs: 2.
+              call_baz
+              u128_drop
+            Unused types: 1.
+              u128
+        "}
+    );
+}
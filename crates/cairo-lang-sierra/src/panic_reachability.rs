@@ -0,0 +1,177 @@
+//! Static analysis of the user functions of a Sierra program that can panic.
+//!
+//! Sierra has no panics of its own - a panic is lowered into returning an error variant holding a
+//! `core::panics::Panic` struct, which is then propagated by all the callers. So a panic starts at
+//! the construction of a `core::panics::Panic` struct, e.g. within `Option::unwrap`, out of bounds
+//! array accesses or integer overflow checks, and the functions that can panic are all the
+//! functions that can call a function constructing one.
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+
+use crate::extensions::function_call::FunctionCallLibfunc;
+use crate::extensions::structure::{StructConstructLibfunc, StructType};
+use crate::extensions::{NamedLibfunc, NamedType};
+use crate::ids::{ConcreteLibfuncId, FunctionId, UserTypeId};
+use crate::optimization::function_statements;
+use crate::program::{GenericArg, Program, Statement, StatementIdx};
+
+#[cfg(test)]
+#[path = "panic_reachability_test.rs"]
+mod test;
+
+/// The name of the struct whose construction starts a panic.
+const PANIC_STRUCT_NAME: &str = "core::panics::Panic";
+
+/// An example of a call chain through which an entry point can panic.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PanicPath {
+    /// The called functions, from the entry point to the function starting the panic.
+    pub functions: Vec<FunctionId>,
+    /// The statement starting the panic, in the last function of `functions`.
+    pub statement_idx: StatementIdx,
+}
+
+/// Whether an entry point of a program can panic.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntryPointPanics {
+    pub entry_point: FunctionId,
+    /// The shortest call chain through which the entry point can panic, or `None` if it can't.
+    pub path: Option<PanicPath>,
+}
+
+/// A report of which entry points of a Sierra program can reach a panic, e.g. the entry points of
+/// a contract aiming to be panic free.
+///
+/// The analysis is conservative - a panic is considered reachable if a statement starting it is
+/// reachable by branching and calls, regardless of the values for which the branches are taken.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PanicReachabilityReport {
+    /// The entry points, by the order they were given.
+    pub entry_points: Vec<EntryPointPanics>,
+}
+impl PanicReachabilityReport {
+    /// Creates the report of the given entry points of the program.
+    pub fn new(program: &Program, entry_points: &[FunctionId]) -> Self {
+        let analysis = PanicAnalysis::new(program);
+        Self {
+            entry_points: entry_points
+                .iter()
+                .map(|entry_point| EntryPointPanics {
+                    entry_point: entry_point.clone(),
+                    path: analysis.find_panic_path(entry_point),
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the entry points that can panic.
+    pub fn panicking_entry_points(&self) -> impl Iterator<Item = &EntryPointPanics> {
+        self.entry_points.iter().filter(|entry_point| entry_point.path.is_some())
+    }
+}
+impl fmt::Display for PanicReachabilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for EntryPointPanics { entry_point, path } in &self.entry_points {
+            match path {
+                Some(PanicPath { functions, statement_idx }) => writeln!(
+                    f,
+                    "{entry_point}: may panic, e.g. through {} (statement {statement_idx}).",
+                    functions
+                        .iter()
+                        .map(|function| function.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" -> ")
+                )?,
+                None => writeln!(f, "{entry_point}: panic free.")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The panics and calls of the functions of a program.
+struct PanicAnalysis {
+    /// The first statement starting a panic in each function, for functions starting panics.
+    panic_statements: UnorderedHashMap<FunctionId, StatementIdx>,
+    /// The functions called by each function, by order of first call.
+    callees: UnorderedHashMap<FunctionId, Vec<FunctionId>>,
+}
+impl PanicAnalysis {
+    fn new(program: &Program) -> Self {
+        let panic_user_type = UserTypeId::from_string(PANIC_STRUCT_NAME);
+        let panic_types: Vec<_> = program
+            .type_declarations
+            .iter()
+            .filter(|declaration| {
+                declaration.long_id.generic_id == StructType::ID
+                    && matches!(
+                        declaration.long_id.generic_args.first(),
+                        Some(GenericArg::UserType(user_type)) if *user_type == panic_user_type
+                    )
+            })
+            .map(|declaration| GenericArg::Type(declaration.id.clone()))
+            .collect();
+        let mut panic_libfuncs = Vec::<ConcreteLibfuncId>::new();
+        let mut called_functions = UnorderedHashMap::<ConcreteLibfuncId, FunctionId>::default();
+        for declaration in &program.libfunc_declarations {
+            let long_id = &declaration.long_id;
+            if long_id.generic_id.0 == StructConstructLibfunc::STR_ID
+                && long_id.generic_args.iter().any(|arg| panic_types.contains(arg))
+            {
+                panic_libfuncs.push(declaration.id.clone());
+            } else if long_id.generic_id.0 == FunctionCallLibfunc::STR_ID {
+                if let [GenericArg::UserFunc(function_id)] = long_id.generic_args.as_slice() {
+                    called_functions.insert(declaration.id.clone(), function_id.clone());
+                }
+            }
+        }
+
+        let mut analysis =
+            Self { panic_statements: Default::default(), callees: Default::default() };
+        for function in &program.funcs {
+            let mut callees = Vec::new();
+            for idx in function_statements(program, function.entry_point) {
+                let Statement::Invocation(invocation) = &program.statements[idx.0] else {
+                    continue;
+                };
+                if panic_libfuncs.contains(&invocation.libfunc_id) {
+                    analysis.panic_statements.entry(function.id.clone()).or_insert(idx);
+                } else if let Some(callee) = called_functions.get(&invocation.libfunc_id) {
+                    if !callees.contains(callee) {
+                        callees.push(callee.clone());
+                    }
+                }
+            }
+            analysis.callees.insert(function.id.clone(), callees);
+        }
+        analysis
+    }
+
+    /// Finds the shortest call chain from `entry_point` to a function starting a panic.
+    fn find_panic_path(&self, entry_point: &FunctionId) -> Option<PanicPath> {
+        // The function through which each visited function was first reached.
+        let mut callers = UnorderedHashMap::<FunctionId, Option<FunctionId>>::default();
+        callers.insert(entry_point.clone(), None);
+        let mut pending = VecDeque::from([entry_point.clone()]);
+        while let Some(function_id) = pending.pop_front() {
+            if let Some(statement_idx) = self.panic_statements.get(&function_id) {
+                let mut functions = vec![function_id.clone()];
+                while let Some(Some(caller)) = callers.get(functions.last().unwrap()) {
+                    functions.push(caller.clone());
+                }
+                functions.reverse();
+                return Some(PanicPath { functions, statement_idx: *statement_idx });
+            }
+            for callee in self.callees.get(&function_id).into_iter().flatten() {
+                if !callers.contains_key(callee) {
+                    callers.insert(callee.clone(), Some(function_id.clone()));
+                    pending.push_back(callee.clone());
+                }
+            }
+        }
+        None
+    }
+}
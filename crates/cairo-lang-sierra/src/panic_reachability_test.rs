@@ -0,0 +1,67 @@
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::{EntryPointPanics, PanicPath, PanicReachabilityReport};
+use crate::program::StatementIdx;
+use crate::ProgramParser;
+
+#[test]
+fn panic_reachability_report() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type Panic = Struct<ut@core::panics::Panic>;
+            type Unit = Struct<ut@Tuple>;
+
+            libfunc panic_construct = struct_construct<Panic>;
+            libfunc unit_construct = struct_construct<Unit>;
+            libfunc drop_panic = drop<Panic>;
+            libfunc call_middle = function_call<user@test::middle>;
+            libfunc call_inner = function_call<user@test::inner>;
+            libfunc call_safe = function_call<user@test::safe>;
+
+            call_safe() -> ();
+            call_middle() -> ();
+            return();
+            call_inner() -> ();
+            return();
+            panic_construct() -> ([0]);
+            drop_panic([0]) -> ();
+            return();
+            unit_construct() -> ([0]);
+            return([0]);
+
+            test::outer@0() -> ();
+            test::middle@3() -> ();
+            test::inner@5() -> ();
+            test::safe@8() -> (Unit);
+        "})
+        .unwrap();
+    let report =
+        PanicReachabilityReport::new(&program, &["test::outer".into(), "test::safe".into()]);
+    assert_eq!(
+        report,
+        PanicReachabilityReport {
+            entry_points: vec![
+                EntryPointPanics {
+                    entry_point: "test::outer".into(),
+                    path: Some(PanicPath {
+                        functions: vec![
+                            "test::outer".into(),
+                            "test::middle".into(),
+                            "test::inner".into()
+                        ],
+                        statement_idx: StatementIdx(5),
+                    }),
+                },
+                EntryPointPanics { entry_point: "test::safe".into(), path: None },
+            ]
+        }
+    );
+    assert_eq!(
+        report.to_string(),
+        indoc! {"
+            test::outer: may panic, e.g. through test::outer -> test::middle -> test::inner (statement 5).
+            test::safe: panic free.
+        "}
+    );
+}
@@ -66,6 +66,32 @@ pub enum ProgramRegistryError {
     #[error("#{0}: Jump out of range")]
     JumpOutOfRange(StatementIdx),
 }
+impl ProgramRegistryError {
+    /// Returns the index of the statement the error originated from, if there is one.
+    pub fn statement_idx(&self) -> Option<StatementIdx> {
+        match self {
+            ProgramRegistryError::LibfuncInvocationInputCountMismatch(statement_idx)
+            | ProgramRegistryError::LibfuncInvocationBranchCountMismatch(statement_idx)
+            | ProgramRegistryError::LibfuncInvocationBranchResultCountMismatch(statement_idx, _)
+            | ProgramRegistryError::LibfuncInvocationBranchTargetMismatch(statement_idx, _)
+            | ProgramRegistryError::BranchBackwards { src: statement_idx, .. }
+            | ProgramRegistryError::BranchNotToBranchAlign { src: statement_idx, .. }
+            | ProgramRegistryError::MultipleJumpsToSameStatement { src1: statement_idx, .. }
+            | ProgramRegistryError::JumpOutOfRange(statement_idx) => Some(*statement_idx),
+            ProgramRegistryError::FunctionIdAlreadyExists(_)
+            | ProgramRegistryError::MissingFunction(_)
+            | ProgramRegistryError::TypeSpecialization { .. }
+            | ProgramRegistryError::TypeConcreteIdAlreadyExists(_)
+            | ProgramRegistryError::TypeAlreadyDeclared(_)
+            | ProgramRegistryError::MissingType(_)
+            | ProgramRegistryError::LibfuncSpecialization { .. }
+            | ProgramRegistryError::LibfuncConcreteIdAlreadyExists(_)
+            | ProgramRegistryError::MissingLibfunc(_)
+            | ProgramRegistryError::TypeInfoDeclarationMismatch(_)
+            | ProgramRegistryError::FunctionWithUnstorableType { .. } => None,
+        }
+    }
+}
 
 type TypeMap<TType> = HashMap<ConcreteTypeId, TType>;
 type LibfuncMap<TLibfunc> = HashMap<ConcreteLibfuncId, TLibfunc>;
@@ -13,6 +13,11 @@ impl Contract {
         Self { items }
     }
 
+    /// Returns the items of the ABI.
+    pub fn items(&self) -> impl Iterator<Item = &Item> {
+        self.items.iter()
+    }
+
     pub fn json(&self) -> String {
         serde_json::to_string_pretty(&self).unwrap()
     }
@@ -0,0 +1,469 @@
+//! Encoding of JSON values into Starknet calldata and decoding of calldata into JSON values, given
+//! the ABI of a contract.
+//!
+//! The JSON representation of the values of the different types is:
+//! * `felt252`, addresses, class hashes and `bytes31` - a number, or a decimal or `0x`-prefixed
+//!   hexadecimal string. Decoded as a hexadecimal string.
+//! * Integers (including `u256`) - a number, or a decimal or `0x`-prefixed hexadecimal string.
+//!   Decoded as a decimal string.
+//! * `bool` - a boolean.
+//! * `ByteArray` - a string.
+//! * Structs - an object, mapping the names of the members to their values.
+//! * Enums - an object with a single key, mapping the name of the active variant to its value.
+//!   Variants of type `()` may also be given as a string holding the name of the variant.
+//! * Tuples, `Array`s and `Span`s - an array.
+//! * `()` - `null`.
+use cairo_felt::Felt252;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{Num, One, Zero};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::abi::{Contract, Enum, Input, Item, Output, Struct};
+
+#[cfg(test)]
+#[path = "abi_codec_test.rs"]
+mod test;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum AbiCodecError {
+    #[error("Function `{0}` was not found in the ABI.")]
+    MissingFunction(String),
+    #[error("Type `{0}` was not found in the ABI.")]
+    UnknownType(String),
+    #[error("Expected {expected} arguments, got {actual}.")]
+    WrongArgumentCount { expected: usize, actual: usize },
+    #[error("Missing argument `{0}`.")]
+    MissingArgument(String),
+    #[error("Invalid value `{value}` for type `{ty}`.")]
+    InvalidValue { ty: String, value: String },
+    #[error("Missing member `{member}` of type `{ty}`.")]
+    MissingMember { ty: String, member: String },
+    #[error("Unknown variant `{variant}` of type `{ty}`.")]
+    UnknownVariant { ty: String, variant: String },
+    #[error("Calldata ended while decoding a value of type `{0}`.")]
+    CalldataTooShort(String),
+    #[error("Invalid calldata `{value}` for type `{ty}`.")]
+    InvalidCalldata { ty: String, value: String },
+    #[error("{0} felts were left unused after decoding the calldata.")]
+    CalldataTooLong(usize),
+}
+
+/// Encodes the arguments of the function `function` of the contract into calldata. The arguments
+/// are given either as an object mapping the names of the inputs to their values, or as an array
+/// of the values of the inputs in order.
+pub fn encode_calldata(
+    abi: &Contract,
+    function: &str,
+    args: &Value,
+) -> Result<Vec<Felt252>, AbiCodecError> {
+    let (inputs, _) = find_function(abi, function)?;
+    let values: Vec<&Value> = match args {
+        Value::Array(values) => {
+            if values.len() != inputs.len() {
+                return Err(AbiCodecError::WrongArgumentCount {
+                    expected: inputs.len(),
+                    actual: values.len(),
+                });
+            }
+            values.iter().collect()
+        }
+        Value::Object(values) => inputs
+            .iter()
+            .map(|input| {
+                values
+                    .get(&input.name)
+                    .ok_or_else(|| AbiCodecError::MissingArgument(input.name.clone()))
+            })
+            .collect::<Result<_, _>>()?,
+        _ => {
+            return Err(AbiCodecError::InvalidValue {
+                ty: format!("arguments of `{function}`"),
+                value: args.to_string(),
+            });
+        }
+    };
+    let mut calldata = vec![];
+    for (input, value) in inputs.iter().zip(values) {
+        encode_value(abi, &input.ty, value, &mut calldata)?;
+    }
+    Ok(calldata)
+}
+
+/// Decodes the calldata of the function `function` of the contract into an object mapping the
+/// names of the inputs to their values.
+pub fn decode_calldata(
+    abi: &Contract,
+    function: &str,
+    calldata: &[Felt252],
+) -> Result<Value, AbiCodecError> {
+    let (inputs, _) = find_function(abi, function)?;
+    let mut data = calldata.iter();
+    let mut values = Map::new();
+    for input in inputs {
+        values.insert(input.name.clone(), decode_value(abi, &input.ty, &mut data)?);
+    }
+    ensure_consumed(data)?;
+    Ok(Value::Object(values))
+}
+
+/// Decodes the values returned by the function `function` of the contract into an array of the
+/// values of its outputs.
+pub fn decode_outputs(
+    abi: &Contract,
+    function: &str,
+    data: &[Felt252],
+) -> Result<Value, AbiCodecError> {
+    let (_, outputs) = find_function(abi, function)?;
+    let mut data = data.iter();
+    let values = outputs
+        .iter()
+        .map(|output| decode_value(abi, &output.ty, &mut data))
+        .collect::<Result<_, _>>()?;
+    ensure_consumed(data)?;
+    Ok(Value::Array(values))
+}
+
+/// Encodes a value of the type `ty`, appending its serialization to `calldata`.
+pub fn encode_value(
+    abi: &Contract,
+    ty: &str,
+    value: &Value,
+    calldata: &mut Vec<Felt252>,
+) -> Result<(), AbiCodecError> {
+    let invalid_value =
+        || AbiCodecError::InvalidValue { ty: ty.to_string(), value: value.to_string() };
+    let ty = ty.strip_prefix('@').unwrap_or(ty);
+    if let Some(bound) = felt_like_bound(ty) {
+        let value = parse_integer(value).ok_or_else(invalid_value)?;
+        if value.sign() == Sign::Minus || value >= BigInt::from(bound) {
+            return Err(invalid_value());
+        }
+        calldata.push(Felt252::from(value));
+    } else if let Some((signed, bits)) = integer_bits(ty) {
+        let value = parse_integer(value).ok_or_else(invalid_value)?;
+        let (min, max) = integer_range(signed, bits);
+        if value < min || value > max {
+            return Err(invalid_value());
+        }
+        calldata.push(Felt252::from(value));
+    } else if ty == U256 && !value.is_object() {
+        let value = parse_integer(value).ok_or_else(invalid_value)?;
+        if value.sign() == Sign::Minus || value.bits() > 256 {
+            return Err(invalid_value());
+        }
+        let low_mask = (BigInt::one() << 128) - 1;
+        calldata.push(Felt252::from(&value & low_mask));
+        calldata.push(Felt252::from(value >> 128));
+    } else if ty == BOOL {
+        calldata.push(Felt252::from(value.as_bool().ok_or_else(invalid_value)?));
+    } else if ty == BYTE_ARRAY {
+        encode_byte_array(value.as_str().ok_or_else(invalid_value)?.as_bytes(), calldata);
+    } else if let Some(element_ty) = array_element_type(ty) {
+        let elements = value.as_array().ok_or_else(invalid_value)?;
+        calldata.push(Felt252::from(elements.len()));
+        for element in elements {
+            encode_value(abi, element_ty, element, calldata)?;
+        }
+    } else if let Some(member_tys) = tuple_member_types(ty) {
+        if member_tys.is_empty() {
+            return if value.is_null() { Ok(()) } else { Err(invalid_value()) };
+        }
+        let members = value.as_array().ok_or_else(invalid_value)?;
+        if members.len() != member_tys.len() {
+            return Err(invalid_value());
+        }
+        for (member_ty, member) in member_tys.into_iter().zip(members) {
+            encode_value(abi, member_ty, member, calldata)?;
+        }
+    } else if let Some(strct) = find_struct(abi, ty) {
+        let members = value.as_object().ok_or_else(invalid_value)?;
+        for member in &strct.members {
+            let value = members.get(&member.name).ok_or_else(|| AbiCodecError::MissingMember {
+                ty: ty.to_string(),
+                member: member.name.clone(),
+            })?;
+            encode_value(abi, &member.ty, value, calldata)?;
+        }
+    } else if let Some(enm) = find_enum(abi, ty) {
+        let (variant_name, variant_value) = match value {
+            Value::String(name) => (name, &Value::Null),
+            Value::Object(variants) if variants.len() == 1 => variants.iter().next().unwrap(),
+            _ => return Err(invalid_value()),
+        };
+        let (index, variant) = enm
+            .variants
+            .iter()
+            .enumerate()
+            .find(|(_, variant)| &variant.name == variant_name)
+            .ok_or_else(|| AbiCodecError::UnknownVariant {
+                ty: ty.to_string(),
+                variant: variant_name.clone(),
+            })?;
+        calldata.push(Felt252::from(index));
+        encode_value(abi, &variant.ty, variant_value, calldata)?;
+    } else {
+        return Err(AbiCodecError::UnknownType(ty.to_string()));
+    }
+    Ok(())
+}
+
+/// Decodes a value of the type `ty` from the beginning of `data`, advancing it past the value.
+pub fn decode_value<'a>(
+    abi: &Contract,
+    ty: &str,
+    data: &mut impl Iterator<Item = &'a Felt252>,
+) -> Result<Value, AbiCodecError> {
+    let ty = ty.strip_prefix('@').unwrap_or(ty);
+    let mut next = || data.next().ok_or_else(|| AbiCodecError::CalldataTooShort(ty.to_string()));
+    let invalid_calldata = |value: &Felt252| AbiCodecError::InvalidCalldata {
+        ty: ty.to_string(),
+        value: format!("{:#x}", value.to_biguint()),
+    };
+    Ok(if let Some(bound) = felt_like_bound(ty) {
+        let value = next()?;
+        if value.to_biguint() >= bound {
+            return Err(invalid_calldata(value));
+        }
+        Value::String(format!("{:#x}", value.to_biguint()))
+    } else if let Some((signed, bits)) = integer_bits(ty) {
+        let value = next()?;
+        let integer = if signed { value.to_signed_felt() } else { value.to_bigint() };
+        let (min, max) = integer_range(signed, bits);
+        if integer < min || integer > max {
+            return Err(invalid_calldata(value));
+        }
+        Value::String(integer.to_string())
+    } else if ty == U256 {
+        let (low, high) = (next()?, next()?);
+        if low.bits() > 128 {
+            return Err(invalid_calldata(low));
+        }
+        if high.bits() > 128 {
+            return Err(invalid_calldata(high));
+        }
+        Value::String(((high.to_biguint() << 128_u32) + low.to_biguint()).to_string())
+    } else if ty == BOOL {
+        let value = next()?;
+        match value.to_biguint() {
+            value if value.is_zero() => Value::Bool(false),
+            value if value.is_one() => Value::Bool(true),
+            _ => return Err(invalid_calldata(value)),
+        }
+    } else if ty == BYTE_ARRAY {
+        return decode_byte_array(data);
+    } else if let Some(element_ty) = array_element_type(ty) {
+        let len = next()?;
+        let len = usize::try_from(len.to_biguint()).map_err(|_| invalid_calldata(len))?;
+        Value::Array(
+            (0..len).map(|_| decode_value(abi, element_ty, data)).collect::<Result<_, _>>()?,
+        )
+    } else if let Some(member_tys) = tuple_member_types(ty) {
+        if member_tys.is_empty() {
+            return Ok(Value::Null);
+        }
+        Value::Array(
+            member_tys
+                .into_iter()
+                .map(|member_ty| decode_value(abi, member_ty, data))
+                .collect::<Result<_, _>>()?,
+        )
+    } else if let Some(strct) = find_struct(abi, ty) {
+        let mut members = Map::new();
+        for member in &strct.members {
+            members.insert(member.name.clone(), decode_value(abi, &member.ty, data)?);
+        }
+        Value::Object(members)
+    } else if let Some(enm) = find_enum(abi, ty) {
+        let index = next()?;
+        let variant = usize::try_from(index.to_biguint())
+            .ok()
+            .and_then(|index| enm.variants.get(index))
+            .ok_or_else(|| invalid_calldata(index))?;
+        let mut variants = Map::new();
+        variants.insert(variant.name.clone(), decode_value(abi, &variant.ty, data)?);
+        Value::Object(variants)
+    } else {
+        return Err(AbiCodecError::UnknownType(ty.to_string()));
+    })
+}
+
+const BOOL: &str = "core::bool";
+const U256: &str = "core::integer::u256";
+const BYTE_ARRAY: &str = "core::byte_array::ByteArray";
+/// The number of bytes in a full word of a `ByteArray`.
+const BYTES_IN_WORD: usize = 31;
+
+/// Returns the inputs and outputs of the function `function` of the contract.
+fn find_function<'a>(
+    abi: &'a Contract,
+    function: &str,
+) -> Result<(&'a [Input], &'a [Output]), AbiCodecError> {
+    let find_in = |item: &'a Item| match item {
+        Item::Function(f) if f.name == function => Some((&f.inputs[..], &f.outputs[..])),
+        Item::Constructor(c) if c.name == function => Some((&c.inputs[..], &[][..])),
+        Item::L1Handler(h) if h.name == function => Some((&h.inputs[..], &h.outputs[..])),
+        _ => None,
+    };
+    abi.items()
+        .find_map(|item| match item {
+            Item::Interface(interface) => interface.items.iter().find_map(find_in),
+            _ => find_in(item),
+        })
+        .ok_or_else(|| AbiCodecError::MissingFunction(function.to_string()))
+}
+
+fn find_struct<'a>(abi: &'a Contract, ty: &str) -> Option<&'a Struct> {
+    abi.items().find_map(|item| match item {
+        Item::Struct(strct) if strct.name == ty => Some(strct),
+        _ => None,
+    })
+}
+
+fn find_enum<'a>(abi: &'a Contract, ty: &str) -> Option<&'a Enum> {
+    abi.items().find_map(|item| match item {
+        Item::Enum(enm) if enm.name == ty => Some(enm),
+        _ => None,
+    })
+}
+
+/// Returns the exclusive upper bound of the values of a type represented by a single felt, if the
+/// type is such a non-integer type.
+fn felt_like_bound(ty: &str) -> Option<BigUint> {
+    match ty {
+        "core::felt252"
+        | "core::starknet::contract_address::ContractAddress"
+        | "core::starknet::class_hash::ClassHash"
+        | "core::starknet::storage_access::StorageAddress" => Some(Felt252::prime()),
+        "core::bytes_31::bytes31" => Some(BigUint::one() << (8 * BYTES_IN_WORD)),
+        _ => None,
+    }
+}
+
+/// Returns whether the type is a signed integer, along with its number of bits, if it is a
+/// single-felt integer type.
+fn integer_bits(ty: &str) -> Option<(bool, u32)> {
+    let name = ty.strip_prefix("core::integer::")?;
+    let signed = match &name[..1] {
+        "u" => false,
+        "i" => true,
+        _ => return None,
+    };
+    match name[1..].parse() {
+        Ok(bits @ (8 | 16 | 32 | 64 | 128)) => Some((signed, bits)),
+        _ => None,
+    }
+}
+
+/// Returns the inclusive range of the values of an integer type.
+fn integer_range(signed: bool, bits: u32) -> (BigInt, BigInt) {
+    if signed {
+        (-(BigInt::one() << (bits - 1)), (BigInt::one() << (bits - 1)) - 1)
+    } else {
+        (BigInt::zero(), (BigInt::one() << bits) - 1)
+    }
+}
+
+/// Returns the element type of an `Array` or a `Span` type.
+fn array_element_type(ty: &str) -> Option<&str> {
+    ty.strip_prefix("core::array::Array::<")
+        .or_else(|| ty.strip_prefix("core::array::Span::<"))?
+        .strip_suffix('>')
+}
+
+/// Returns the member types of a tuple type.
+fn tuple_member_types(ty: &str) -> Option<Vec<&str>> {
+    let inner = ty.strip_prefix('(')?.strip_suffix(')')?;
+    let mut members = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' | '<' => depth += 1,
+            ')' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                members.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    members.push(inner[start..].trim());
+    members.retain(|member| !member.is_empty());
+    Some(members)
+}
+
+/// Parses an integer given as a JSON number, or as a decimal or `0x`-prefixed hexadecimal string.
+fn parse_integer(value: &Value) -> Option<BigInt> {
+    match value {
+        Value::Number(number) => number.as_i64().map(BigInt::from),
+        Value::String(s) => {
+            let (negative, s) = match s.strip_prefix('-') {
+                Some(s) => (true, s),
+                None => (false, s.as_str()),
+            };
+            let value = match s.strip_prefix("0x") {
+                Some(hex) => BigInt::from_str_radix(hex, 16),
+                None => BigInt::from_str_radix(s, 10),
+            }
+            .ok()?;
+            Some(if negative { -value } else { value })
+        }
+        _ => None,
+    }
+}
+
+/// Encodes a `ByteArray` - the full words, followed by the pending word and its length.
+fn encode_byte_array(bytes: &[u8], calldata: &mut Vec<Felt252>) {
+    let chunks = bytes.chunks(BYTES_IN_WORD);
+    let (full_words, pending_word): (Vec<_>, Vec<_>) =
+        chunks.partition(|chunk| chunk.len() == BYTES_IN_WORD);
+    let pending_word = pending_word.first().copied().unwrap_or_default();
+    calldata.push(Felt252::from(full_words.len()));
+    calldata.extend(full_words.into_iter().map(Felt252::from_bytes_be));
+    calldata.push(Felt252::from_bytes_be(pending_word));
+    calldata.push(Felt252::from(pending_word.len()));
+}
+
+/// Decodes a `ByteArray` into a string.
+fn decode_byte_array<'a>(
+    data: &mut impl Iterator<Item = &'a Felt252>,
+) -> Result<Value, AbiCodecError> {
+    let mut next = || data.next().ok_or_else(|| AbiCodecError::CalldataTooShort(BYTE_ARRAY.into()));
+    let invalid_calldata = |value: &Felt252| AbiCodecError::InvalidCalldata {
+        ty: BYTE_ARRAY.into(),
+        value: format!("{:#x}", value.to_biguint()),
+    };
+    let word_bytes = |word: &Felt252, len: usize| {
+        let bytes = if word.is_zero() { vec![] } else { word.to_bytes_be() };
+        if bytes.len() > len {
+            return Err(invalid_calldata(word));
+        }
+        Ok([vec![0; len - bytes.len()], bytes].concat())
+    };
+    let len = next()?;
+    let len = usize::try_from(len.to_biguint()).map_err(|_| invalid_calldata(len))?;
+    let mut bytes = vec![];
+    for _ in 0..len {
+        bytes.extend(word_bytes(next()?, BYTES_IN_WORD)?);
+    }
+    let (pending_word, pending_len) = (next()?, next()?);
+    match usize::try_from(pending_len.to_biguint()) {
+        Ok(pending_len) if pending_len < BYTES_IN_WORD => {
+            bytes.extend(word_bytes(pending_word, pending_len)?);
+        }
+        _ => return Err(invalid_calldata(pending_len)),
+    }
+    String::from_utf8(bytes).map(Value::String).map_err(|err| AbiCodecError::InvalidCalldata {
+        ty: BYTE_ARRAY.into(),
+        value: format!("{:?}", err.into_bytes()),
+    })
+}
+
+/// Returns an error if there is data left after all the values were decoded.
+fn ensure_consumed<'a>(data: impl Iterator<Item = &'a Felt252>) -> Result<(), AbiCodecError> {
+    match data.count() {
+        0 => Ok(()),
+        left => Err(AbiCodecError::CalldataTooLong(left)),
+    }
+}
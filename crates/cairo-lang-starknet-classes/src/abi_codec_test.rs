@@ -0,0 +1,135 @@
+use cairo_felt::Felt252;
+use indoc::indoc;
+use serde_json::{json, Value};
+use test_case::test_case;
+
+use super::{decode_calldata, decode_outputs, encode_calldata, AbiCodecError};
+use crate::abi::Contract;
+
+fn test_abi() -> Contract {
+    serde_json::from_str(indoc! {r#"
+        [
+          {
+            "type": "struct",
+            "name": "core::integer::u256",
+            "members": [
+              {"name": "low", "type": "core::integer::u128"},
+              {"name": "high", "type": "core::integer::u128"}
+            ]
+          },
+          {
+            "type": "struct",
+            "name": "core::array::Span::<core::felt252>",
+            "members": [{"name": "snapshot", "type": "@core::array::Array::<core::felt252>"}]
+          },
+          {
+            "type": "struct",
+            "name": "core::byte_array::ByteArray",
+            "members": [
+              {"name": "data", "type": "core::array::Array::<core::bytes_31::bytes31>"},
+              {"name": "pending_word", "type": "core::felt252"},
+              {"name": "pending_word_len", "type": "core::integer::u32"}
+            ]
+          },
+          {
+            "type": "enum",
+            "name": "core::bool",
+            "variants": [{"name": "False", "type": "()"}, {"name": "True", "type": "()"}]
+          },
+          {
+            "type": "enum",
+            "name": "test::Action",
+            "variants": [
+              {"name": "Stop", "type": "()"},
+              {"name": "Move", "type": "(core::integer::i8, core::integer::i8)"}
+            ]
+          },
+          {
+            "type": "interface",
+            "name": "test::IContract",
+            "items": [
+              {
+                "type": "function",
+                "name": "transfer",
+                "inputs": [
+                  {"name": "to", "type": "core::starknet::contract_address::ContractAddress"},
+                  {"name": "amount", "type": "core::integer::u256"},
+                  {"name": "memo", "type": "core::felt252"}
+                ],
+                "outputs": [{"type": "core::bool"}],
+                "state_mutability": "external"
+              },
+              {
+                "type": "function",
+                "name": "act",
+                "inputs": [
+                  {"name": "actions", "type": "core::array::Span::<test::Action>"},
+                  {"name": "name", "type": "core::byte_array::ByteArray"}
+                ],
+                "outputs": [],
+                "state_mutability": "external"
+              }
+            ]
+          },
+          {
+            "type": "constructor",
+            "name": "constructor",
+            "inputs": [{"name": "owner", "type": "core::starknet::contract_address::ContractAddress"}]
+          }
+        ]
+    "#})
+    .unwrap()
+}
+
+fn felts(values: &[u128]) -> Vec<Felt252> {
+    values.iter().map(|value| Felt252::from(*value)).collect()
+}
+
+#[test]
+fn test_encode_decode_transfer() {
+    let abi = test_abi();
+    let calldata = felts(&[0x123, 5, 1, 7]);
+    let by_name =
+        json!({"to": "0x123", "amount": "340282366920938463463374607431768211461", "memo": 7});
+    assert_eq!(encode_calldata(&abi, "transfer", &by_name), Ok(calldata.clone()));
+    let by_position = json!([291, {"low": 5, "high": "0x1"}, "7"]);
+    assert_eq!(encode_calldata(&abi, "transfer", &by_position), Ok(calldata.clone()));
+    assert_eq!(
+        decode_calldata(&abi, "transfer", &calldata),
+        Ok(
+            json!({"to": "0x123", "amount": "340282366920938463463374607431768211461", "memo": "0x7"})
+        )
+    );
+    assert_eq!(decode_outputs(&abi, "transfer", &felts(&[1])), Ok(json!([true])));
+}
+
+#[test]
+fn test_encode_decode_act() {
+    let abi = test_abi();
+    let args = json!({
+        "actions": [{"Move": ["-1", "2"]}, {"Stop": null}],
+        "name": "A name that is longer than a word.",
+    });
+    let calldata = encode_calldata(&abi, "act", &args).unwrap();
+    let minus_one = Felt252::from(-1);
+    assert_eq!(calldata[..6], [felts(&[2, 1]), vec![minus_one], felts(&[2, 0, 1])].concat());
+    assert_eq!(decode_calldata(&abi, "act", &calldata), Ok(args));
+    assert_eq!(encode_calldata(&abi, "act", &json!([["Stop"], ""])), Ok(felts(&[1, 0, 0, 0, 0])));
+}
+
+#[test_case("transfer", json!([1, 2]), AbiCodecError::WrongArgumentCount { expected: 3, actual: 2 }; "wrong args count")]
+#[test_case("transfer", json!({"to": 1, "amount": 2}), AbiCodecError::MissingArgument("memo".into()); "missing arg")]
+#[test_case("constructor", json!(["-1"]), AbiCodecError::InvalidValue { ty: "core::starknet::contract_address::ContractAddress".into(), value: "\"-1\"".into() }; "negative address")]
+#[test_case("act", json!([[{"Jump": null}], ""]), AbiCodecError::UnknownVariant { ty: "test::Action".into(), variant: "Jump".into() }; "unknown variant")]
+#[test_case("act", json!([[{"Move": [128, 0]}], ""]), AbiCodecError::InvalidValue { ty: "core::integer::i8".into(), value: "128".into() }; "out of range")]
+#[test_case("mint", json!([]), AbiCodecError::MissingFunction("mint".into()); "missing function")]
+fn test_encode_errors(function: &str, args: Value, expected: AbiCodecError) {
+    assert_eq!(encode_calldata(&test_abi(), function, &args), Err(expected));
+}
+
+#[test_case(felts(&[1, 2]), AbiCodecError::CalldataTooShort("core::integer::u256".into()); "too short")]
+#[test_case(felts(&[1, 2, 0, 3, 4]), AbiCodecError::CalldataTooLong(1); "too long")]
+#[test_case([felts(&[1]), vec![Felt252::from(u128::MAX) + 1u32], felts(&[0, 3])].concat(), AbiCodecError::InvalidCalldata { ty: "core::integer::u256".into(), value: "0x100000000000000000000000000000000".into() }; "invalid u256")]
+fn test_decode_errors(calldata: Vec<Felt252>, expected: AbiCodecError) {
+    assert_eq!(decode_calldata(&test_abi(), "transfer", &calldata), Err(expected));
+}
@@ -2,9 +2,13 @@ use std::io::BufReader;
 
 use cairo_felt::Felt252;
 use cairo_lang_test_utils::compare_contents_or_fix_with_path;
+use starknet_crypto::{poseidon_hash_many, FieldElement};
 use test_case::test_case;
 
-use crate::casm_contract_class::{BigUintAsHex, CasmContractClass, StarknetSierraCompilationError};
+use crate::casm_contract_class::{
+    BigUintAsHex, CasmContractClass, CasmContractEntryPoint, CasmContractEntryPoints,
+    StarknetSierraCompilationError,
+};
 use crate::contract_class::ContractClass;
 use crate::test_utils::get_example_file_path;
 
@@ -51,3 +55,38 @@ fn test_casm_contract_from_contract_class_from_contracts_crate(name: &str) {
         serde_json::to_string_pretty(&casm_contract).unwrap() + "\n",
     );
 }
+
+/// Tests the compiled class hash against the hash defined by the Starknet specification, built
+/// directly from its components: the Poseidon hash of the version, the entry points by type and
+/// the bytecode.
+#[test]
+fn test_compiled_class_hash() {
+    let contract = CasmContractClass {
+        bytecode: vec![BigUintAsHex { value: 1u32.into() }, BigUintAsHex { value: 2u32.into() }],
+        entry_points_by_type: CasmContractEntryPoints {
+            external: vec![],
+            l1_handler: vec![],
+            constructor: vec![CasmContractEntryPoint {
+                selector: 5u32.into(),
+                offset: 1,
+                builtins: vec!["range_check".into()],
+            }],
+        },
+        ..CasmContractClass::default()
+    };
+    let felt = |value: &str| FieldElement::from_hex_be(value).unwrap();
+    let expected = poseidon_hash_many(&[
+        // "COMPILED_CLASS_V1" as a short string.
+        felt("0x434f4d50494c45445f434c4153535f5631"),
+        poseidon_hash_many(&[]),
+        poseidon_hash_many(&[]),
+        poseidon_hash_many(&[
+            felt("0x5"),
+            felt("0x1"),
+            // "range_check" as a short string.
+            poseidon_hash_many(&[felt("0x72616e67655f636865636b")]),
+        ]),
+        poseidon_hash_many(&[felt("0x1"), felt("0x2")]),
+    ]);
+    assert_eq!(contract.compiled_class_hash(), Felt252::from_bytes_be(&expected.to_bytes_be()));
+}
@@ -1,13 +1,18 @@
+use cairo_felt::Felt252;
 use cairo_lang_sierra as sierra;
 use cairo_lang_utils::bigint::{deserialize_big_uint, serialize_big_uint, BigUintAsHex};
+use itertools::Itertools;
 use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
+use starknet_crypto::{poseidon_hash_many, FieldElement};
 use thiserror::Error;
 
 use crate::abi::Contract;
 use crate::allowed_libfuncs::{lookup_allowed_libfuncs_list, AllowedLibfuncsError, ListSelector};
-use crate::compiler_version::{current_compiler_version_id, current_sierra_version_id};
-use crate::felt252_serde::{sierra_from_felt252s, sierra_to_felt252s, Felt252SerdeError};
+use crate::compiler_version::{current_compiler_version_id, current_sierra_version_id, VersionId};
+pub use crate::felt252_serde::Felt252SerdeError;
+use crate::felt252_serde::{sierra_from_felt252s, sierra_to_felt252s, versions_from_felt252s};
+use crate::keccak::starknet_keccak;
 
 #[cfg(test)]
 #[path = "contract_class_test.rs"]
@@ -60,6 +65,12 @@ impl ContractClass {
         Ok(sierra_program)
     }
 
+    /// Returns the versions of Sierra and of the compiler the contract class was compiled with,
+    /// without extracting the Sierra program.
+    pub fn versions(&self) -> Result<(VersionId, VersionId), Felt252SerdeError> {
+        versions_from_felt252s(&self.sierra_program)
+    }
+
     /// Sanity checks the contract class.
     /// Currently only checks that if ABI exists, its counts match the entry points counts.
     pub fn sanity_check(&self) {
@@ -92,6 +103,61 @@ impl ContractClass {
         }
         Ok(())
     }
+
+    /// Returns the hash value for the contract class, as computed by Starknet on declaration.
+    /// The ABI is hashed by its compact JSON representation, which is how it is declared by the
+    /// common tooling.
+    pub fn class_hash(&self) -> Felt252 {
+        // Compute hashes on each component separately.
+        let external_funcs_hash = entry_points_hash(&self.entry_points_by_type.external);
+        let l1_handlers_hash = entry_points_hash(&self.entry_points_by_type.l1_handler);
+        let constructors_hash = entry_points_hash(&self.entry_points_by_type.constructor);
+        let abi = match &self.abi {
+            Some(abi) => serde_json::to_string(abi).unwrap(),
+            None => String::new(),
+        };
+        let abi_hash = biguint_to_field_element(&starknet_keccak(abi.as_bytes()));
+        let sierra_program_hash = poseidon_hash_many(
+            &self
+                .sierra_program
+                .iter()
+                .map(|big_uint| biguint_to_field_element(&big_uint.value))
+                .collect_vec(),
+        );
+
+        // Compute total hash by hashing each component on top of the previous one.
+        let version = format!("CONTRACT_CLASS_V{}", self.contract_class_version);
+        Felt252::from_bytes_be(
+            &poseidon_hash_many(&[
+                FieldElement::from_byte_slice_be(version.as_bytes()).unwrap(),
+                external_funcs_hash,
+                l1_handlers_hash,
+                constructors_hash,
+                abi_hash,
+                sierra_program_hash,
+            ])
+            .to_bytes_be(),
+        )
+    }
+}
+
+/// Returns the hash for a set of entry points.
+fn entry_points_hash(entry_points: &[ContractEntryPoint]) -> FieldElement {
+    poseidon_hash_many(
+        &entry_points
+            .iter()
+            .flat_map(|entry_point| {
+                [
+                    biguint_to_field_element(&entry_point.selector),
+                    FieldElement::from(entry_point.function_idx),
+                ]
+            })
+            .collect_vec(),
+    )
+}
+
+fn biguint_to_field_element(value: &BigUint) -> FieldElement {
+    FieldElement::from_byte_slice_be(&value.to_bytes_be()).unwrap()
 }
 
 const DEFAULT_CONTRACT_CLASS_VERSION: &str = "0.1.0";
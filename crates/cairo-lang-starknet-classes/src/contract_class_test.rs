@@ -1,8 +1,11 @@
 use std::io::BufReader;
 
+use cairo_felt::Felt252;
+use cairo_lang_utils::bigint::BigUintAsHex;
 use indoc::indoc;
 use num_bigint::BigUint;
 use pretty_assertions::assert_eq;
+use starknet_crypto::{poseidon_hash_many, FieldElement};
 use test_case::test_case;
 
 use crate::contract_class::{
@@ -72,3 +75,49 @@ fn test_full_contract_deserialization_from_contracts_crate(name: &str) {
     let serialized = serde_json::to_value(&contract).unwrap();
     assert_eq!(serialized, deserialized);
 }
+
+/// Tests the class hash against the hash defined by the Starknet specification, built directly from
+/// its components: the Poseidon hash of the class version, the entry points by type, the Starknet
+/// Keccak of the ABI string and the Sierra program.
+#[test]
+fn test_class_hash() {
+    let contract = ContractClass {
+        sierra_program: vec![
+            BigUintAsHex { value: 1u32.into() },
+            BigUintAsHex { value: 2u32.into() },
+        ],
+        sierra_program_debug_info: None,
+        contract_class_version: DEFAULT_CONTRACT_CLASS_VERSION.to_string(),
+        entry_points_by_type: ContractEntryPoints {
+            external: vec![ContractEntryPoint { selector: 5u32.into(), function_idx: 3 }],
+            l1_handler: vec![],
+            constructor: vec![],
+        },
+        abi: None,
+    };
+    let felt = |value: &str| FieldElement::from_hex_be(value).unwrap();
+    let expected = poseidon_hash_many(&[
+        // "CONTRACT_CLASS_V0.1.0" as a short string.
+        felt("0x434f4e54524143545f434c4153535f56302e312e30"),
+        poseidon_hash_many(&[felt("0x5"), felt("0x3")]),
+        poseidon_hash_many(&[]),
+        poseidon_hash_many(&[]),
+        // The Starknet Keccak of the empty string.
+        felt("0x1d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+        poseidon_hash_many(&[felt("0x1"), felt("0x2")]),
+    ]);
+    assert_eq!(contract.class_hash(), Felt252::from_bytes_be(&expected.to_bytes_be()));
+}
+
+/// Tests the class hash covers the ABI.
+#[test]
+fn test_class_hash_covers_abi() {
+    let contract_path =
+        get_example_file_path("minimal_contract__minimal_contract.contract_class.json");
+    let mut contract: ContractClass =
+        serde_json::from_reader(BufReader::new(std::fs::File::open(contract_path).unwrap()))
+            .unwrap();
+    let class_hash = contract.class_hash();
+    contract.abi = None;
+    assert_ne!(contract.class_hash(), class_hash);
+}
@@ -91,6 +91,18 @@ pub fn sierra_from_felt252s(
     Ok((sierra_version_id, compiler_version_id, Program::deserialize(&program_felts)?.0))
 }
 
+/// Deserializes the Sierra and compiler versions of a Sierra program serialized into felt252s,
+/// without deserializing the program itself.
+///
+/// Returns (sierra_version_id, compiler_version_id).
+pub fn versions_from_felt252s(
+    felts: &[BigUintAsHex],
+) -> Result<(VersionId, VersionId), Felt252SerdeError> {
+    let (sierra_version_id, remaining) = VersionId::deserialize(felts)?;
+    let (compiler_version_id, _) = VersionId::deserialize(remaining)?;
+    Ok((sierra_version_id, compiler_version_id))
+}
+
 /// Trait for serializing and deserializing into a felt252 vector.
 trait Felt252Serde: Sized {
     fn serialize(&self, output: &mut Vec<BigUintAsHex>) -> Result<(), Felt252SerdeError>;
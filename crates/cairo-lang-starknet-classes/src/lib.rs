@@ -6,6 +6,7 @@
 //! Learn more at [starkware.io](http://starknet.io/).
 
 pub mod abi;
+pub mod abi_codec;
 pub mod allowed_libfuncs;
 pub mod casm_contract_class;
 pub mod compiler_version;
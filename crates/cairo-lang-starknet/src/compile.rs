@@ -3,22 +3,32 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
 use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_compiler::project::setup_project;
-use cairo_lang_compiler::CompilerConfig;
+use cairo_lang_compiler::{optimize_sierra_program, CompilerConfig};
 use cairo_lang_defs::ids::TopLevelLanguageElementId;
 use cairo_lang_diagnostics::ToOption;
 use cairo_lang_filesystem::ids::CrateId;
 use cairo_lang_lowering::db::LoweringGroup;
 use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_sierra::calldata_taint::CalldataTaintReport;
+use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_sierra::program::Program;
 use cairo_lang_sierra_generator::canonical_id_replacer::CanonicalReplacer;
 use cairo_lang_sierra_generator::db::SierraGenGroup;
-use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
-use cairo_lang_sierra_generator::replace_ids::{replace_sierra_ids_in_program, SierraIdReplacer};
+use cairo_lang_sierra_generator::program_generator::{
+    SierraProgramDebugInfo, SierraProgramWithDebug,
+};
+use cairo_lang_sierra_generator::replace_ids::{
+    replace_sierra_ids_in_program, DebugReplacer, SierraIdReplacer,
+};
+use cairo_lang_sierra_generator::statements_locations::SourceMap;
 use cairo_lang_starknet_classes::allowed_libfuncs::ListSelector;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use cairo_lang_starknet_classes::contract_class::{
     ContractClass, ContractEntryPoint, ContractEntryPoints,
 };
 use cairo_lang_utils::arc_unwrap_or_clone;
 use itertools::{chain, Itertools};
+use serde::{Deserialize, Serialize};
 
 use crate::abi::AbiBuilder;
 use crate::aliased::Aliased;
@@ -43,6 +53,8 @@ pub fn compile_path(
     let mut db = RootDatabase::builder()
         .detect_corelib()
         .with_plugin_suite(starknet_plugin_suite())
+        .with_cfg(compiler_config.cfg_set.clone())
+        .with_inlining_strategy(compiler_config.inlining_strategy)
         .build()?;
 
     let main_crate_ids = setup_project(&mut db, Path::new(&path))?;
@@ -50,6 +62,25 @@ pub fn compile_path(
     compile_contract_in_prepared_db(&db, contract_path, main_crate_ids, compiler_config)
 }
 
+/// Compiles the specified contract in the project at `path` into all of its artifacts at once. See
+/// [compile_path].
+pub fn compile_path_artifacts(
+    path: &Path,
+    contract_path: Option<&str>,
+    compiler_config: CompilerConfig<'_>,
+) -> Result<ContractArtifacts> {
+    let mut db = RootDatabase::builder()
+        .detect_corelib()
+        .with_plugin_suite(starknet_plugin_suite())
+        .with_cfg(compiler_config.cfg_set.clone())
+        .with_inlining_strategy(compiler_config.inlining_strategy)
+        .build()?;
+
+    let main_crate_ids = setup_project(&mut db, Path::new(&path))?;
+
+    compile_contract_artifacts_in_prepared_db(&db, contract_path, main_crate_ids, compiler_config)
+}
+
 /// Runs StarkNet contract compiler on the specified contract.
 /// If no contract was specified, verify that there is only one.
 /// Otherwise, return an error.
@@ -59,19 +90,33 @@ pub fn compile_contract_in_prepared_db(
     main_crate_ids: Vec<CrateId>,
     mut compiler_config: CompilerConfig<'_>,
 ) -> Result<ContractClass> {
+    let contract = find_single_contract(db, contract_path, main_crate_ids, &mut compiler_config)?;
+    let mut classes = compile_prepared_db(db, &[&contract], compiler_config)?;
+    assert_eq!(classes.len(), 1);
+    Ok(classes.remove(0))
+}
+
+/// Finds the contract given by `contract_path` in the main crates, or the only contract in them if
+/// no path is given.
+fn find_single_contract(
+    db: &RootDatabase,
+    contract_path: Option<&str>,
+    main_crate_ids: Vec<CrateId>,
+    compiler_config: &mut CompilerConfig<'_>,
+) -> Result<ContractDeclaration> {
     let mut contracts = find_contracts(db, &main_crate_ids);
 
     // TODO(ilya): Add contract names.
     if let Some(contract_path) = contract_path {
         contracts.retain(|contract| contract.submodule_id.full_path(db) == contract_path);
     };
-    let contract = match contracts.len() {
+    match contracts.len() {
         0 => {
             // Report diagnostics as they might reveal the reason why no contract was found.
             compiler_config.diagnostics_reporter.ensure(db)?;
             anyhow::bail!("Contract not found.");
         }
-        1 => &contracts[0],
+        1 => Ok(contracts.remove(0)),
         _ => {
             let contract_names =
                 contracts.iter().map(|contract| contract.submodule_id.full_path(db)).join("\n  ");
@@ -81,12 +126,47 @@ pub fn compile_contract_in_prepared_db(
                 contract_names
             );
         }
-    };
+    }
+}
 
-    let contracts = vec![contract];
-    let mut classes = compile_prepared_db(db, &contracts, compiler_config)?;
-    assert_eq!(classes.len(), 1);
-    Ok(classes.remove(0))
+/// All the artifacts of the compilation of a contract, produced by a single compilation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractArtifacts {
+    /// The contract class, including the ABI and the debug names of the Sierra program.
+    pub contract_class: ContractClass,
+    /// The text of the Sierra program.
+    pub sierra: String,
+    /// The compiled CASM contract class.
+    pub casm: CasmContractClass,
+    /// The mapping from the Sierra statements to the source code.
+    pub source_map: SourceMap,
+    /// The report of the storage writes and emitted events of the entry points influenced by their
+    /// calldata, if requested by [CompilerConfig::calldata_taint_report].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calldata_taint_report: Option<String>,
+}
+
+/// Compiles the contract given by `contract_path` (or the only contract in the main crates) into
+/// all of its artifacts at once. See [compile_contract_in_prepared_db].
+pub fn compile_contract_artifacts_in_prepared_db(
+    db: &RootDatabase,
+    contract_path: Option<&str>,
+    main_crate_ids: Vec<CrateId>,
+    mut compiler_config: CompilerConfig<'_>,
+) -> Result<ContractArtifacts> {
+    let contract = find_single_contract(db, contract_path, main_crate_ids, &mut compiler_config)?;
+    compiler_config.diagnostics_reporter.ensure(db)?;
+    let CompiledContract { contract_class, sierra_program, debug_info, calldata_taint_report } =
+        compile_contract_with_debug_info(db, &contract, &compiler_config)?;
+    let casm = CasmContractClass::from_contract_class(contract_class.clone(), false)
+        .with_context(|| "Compilation to CASM failed.")?;
+    Ok(ContractArtifacts {
+        contract_class,
+        sierra: sierra_program.to_string(),
+        casm,
+        source_map: debug_info.statements_locations.source_map(db, sierra_program.statements.len()),
+        calldata_taint_report,
+    })
 }
 
 /// Runs Starknet contracts compiler.
@@ -124,15 +204,48 @@ fn compile_contract_with_prepared_and_checked_db(
     contract: &ContractDeclaration,
     compiler_config: &CompilerConfig<'_>,
 ) -> Result<ContractClass> {
+    Ok(compile_contract_with_debug_info(db, contract, compiler_config)?.contract_class)
+}
+
+/// A compiled contract class, along with the Sierra program it was compiled from.
+struct CompiledContract {
+    contract_class: ContractClass,
+    sierra_program: Program,
+    debug_info: SierraProgramDebugInfo,
+    /// The calldata taint report of the entry points, if requested.
+    calldata_taint_report: Option<String>,
+}
+
+/// Same as [compile_contract_with_prepared_and_checked_db], additionally returning the Sierra
+/// program of the contract class with its debug info.
+fn compile_contract_with_debug_info(
+    db: &RootDatabase,
+    contract: &ContractDeclaration,
+    compiler_config: &CompilerConfig<'_>,
+) -> Result<CompiledContract> {
     let SemanticEntryPoints { external, l1_handler, constructor } =
         extract_semantic_entrypoints(db, contract)?;
-    let SierraProgramWithDebug { program: mut sierra_program, .. } = arc_unwrap_or_clone(
+    let mut sierra_program_with_debug = arc_unwrap_or_clone(
         db.get_sierra_program_for_functions(
             chain!(&external, &l1_handler, &constructor).map(|f| f.value).collect(),
         )
         .to_option()
         .with_context(|| "Compilation failed without any diagnostics.")?,
     );
+    let roots: Vec<_> = chain!(&external, &l1_handler, &constructor)
+        .map(|f| db.intern_sierra_function(f.value.function_id(db).expect("Function error.")))
+        .collect();
+    // The report is computed before the optimization, which drops the statements locations.
+    let calldata_taint_report = if compiler_config.calldata_taint_report {
+        Some(calldata_taint_report(db, &sierra_program_with_debug, &roots)?)
+    } else {
+        None
+    };
+    if compiler_config.optimize_sierra {
+        optimize_sierra_program(&mut sierra_program_with_debug, roots);
+    }
+    let SierraProgramWithDebug { program: mut sierra_program, debug_info } =
+        sierra_program_with_debug;
 
     if compiler_config.replace_ids {
         sierra_program = replace_sierra_ids_in_program(db, &sierra_program);
@@ -158,7 +271,34 @@ fn compile_contract_with_prepared_and_checked_db(
         ),
     )?;
     contract_class.sanity_check();
-    Ok(contract_class)
+    Ok(CompiledContract { contract_class, sierra_program, debug_info, calldata_taint_report })
+}
+
+/// Returns the calldata taint report of the given entry points of the program, naming the entry
+/// points by their debug names and locating the sinks by the Cairo functions and source locations
+/// they were generated from.
+fn calldata_taint_report(
+    db: &RootDatabase,
+    sierra_program_with_debug: &SierraProgramWithDebug,
+    entry_points: &[FunctionId],
+) -> Result<String> {
+    let SierraProgramWithDebug { program, debug_info } = sierra_program_with_debug;
+    let mut report = CalldataTaintReport::new(program, entry_points)
+        .with_context(|| "Calldata taint analysis failed.")?;
+    let replacer = DebugReplacer::new(db);
+    for entry_point in &mut report.entry_points {
+        entry_point.entry_point = replacer.replace_function_id(&entry_point.entry_point);
+    }
+    let statements_locations = &debug_info.statements_locations;
+    Ok(report.format_with_locations(|sink| {
+        // Locates the sink by the innermost user code on its path, rather than by the system call
+        // within the core library.
+        let path = chain!([sink.statement_idx], sink.calls.iter().rev().copied()).collect_vec();
+        let statement_idx = statements_locations
+            .first_user_code_location(db, &path)
+            .map_or(sink.statement_idx, |(statement_idx, _)| statement_idx);
+        statements_locations.describe_statement_location(db, statement_idx)
+    }))
 }
 
 pub struct SemanticEntryPoints {
@@ -1,8 +1,14 @@
 use cairo_lang_starknet_classes::allowed_libfuncs::ListSelector;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet_classes::contract_class::ContractEntryPoint;
 use cairo_lang_test_utils::compare_contents_or_fix_with_path;
+use indoc::indoc;
 use test_case::test_case;
 
-use crate::test_utils::{get_example_file_path, get_test_contract};
+use crate::test_utils::{
+    get_example_file_path, get_optimized_test_contract, get_test_contract,
+    get_test_contract_artifacts, get_test_contract_artifacts_with_calldata_taint,
+};
 
 /// Tests that the sierra compiled from a contract in the contracts crate is the same as in
 /// <test_case>.sierra, and that the resulted json is the same as in
@@ -41,3 +47,68 @@ fn test_compile_path_from_contracts_crate(example_contract_path: &str) {
         sierra_program.to_string(),
     );
 }
+
+#[test]
+fn test_compile_contract_artifacts() {
+    let contract_path = "cairo_level_tests::contracts::minimal_contract::minimal_contract";
+    let artifacts = get_test_contract_artifacts(contract_path);
+    assert_eq!(artifacts.contract_class, get_test_contract(contract_path));
+    let sierra_program = artifacts.contract_class.extract_sierra_program().unwrap();
+    assert!(artifacts.sierra.contains("minimal_contract::__wrapper__empty@0("));
+    assert_eq!(artifacts.source_map.statements.len(), sierra_program.statements.len());
+    assert!(
+        artifacts
+            .source_map
+            .statements
+            .iter()
+            .flatten()
+            .any(|span| span.file.ends_with("minimal_contract.cairo"))
+    );
+    assert_eq!(
+        artifacts.casm,
+        CasmContractClass::from_contract_class(artifacts.contract_class.clone(), false).unwrap()
+    );
+    assert_eq!(artifacts.calldata_taint_report, None);
+}
+
+#[test]
+fn test_compile_contract_calldata_taint_report() {
+    let artifacts = get_test_contract_artifacts_with_calldata_taint(
+        "cairo_level_tests::contracts::hello_starknet::hello_starknet",
+    );
+    assert_eq!(
+        artifacts.calldata_taint_report.unwrap(),
+        indoc! {"
+            cairo_level_tests::contracts::hello_starknet::hello_starknet::__wrapper__HelloStarknetImpl__increase_balance:
+                storage write at hello_starknet.cairo::hello_starknet::HelloStarknetImpl::increase_balance, cairo_level_tests/contracts/hello_starknet.cairo:19:13: value influenced by calldata.
+            cairo_level_tests::contracts::hello_starknet::hello_starknet::__wrapper__HelloStarknetImpl__get_balance: no calldata influenced storage writes or events.
+        "}
+    );
+}
+
+#[test]
+fn test_compile_optimized_contract() {
+    let contract_path = "cairo_level_tests::contracts::erc20::erc_20";
+    let contract = get_test_contract(contract_path);
+    let optimized_contract = get_optimized_test_contract(contract_path);
+    let selectors = |entry_points: &[ContractEntryPoint]| {
+        entry_points.iter().map(|entry_point| entry_point.selector.clone()).collect::<Vec<_>>()
+    };
+    assert_eq!(
+        selectors(&optimized_contract.entry_points_by_type.external),
+        selectors(&contract.entry_points_by_type.external)
+    );
+    assert_eq!(
+        selectors(&optimized_contract.entry_points_by_type.constructor),
+        selectors(&contract.entry_points_by_type.constructor)
+    );
+    let program = contract.extract_sierra_program().unwrap();
+    let optimized_program = optimized_contract.extract_sierra_program().unwrap();
+    assert!(optimized_program.statements.len() <= program.statements.len());
+    assert!(optimized_program.libfunc_declarations.len() <= program.libfunc_declarations.len());
+    // The optimized class is still a valid class, compiling to CASM.
+    optimized_contract
+        .validate_version_compatible(ListSelector::ListName("all".to_string()))
+        .unwrap();
+    CasmContractClass::from_contract_class(optimized_contract, false).unwrap();
+}
@@ -1,8 +1,8 @@
 use anyhow::{bail, Context};
 use cairo_felt::Felt252;
 use cairo_lang_defs::ids::{
-    FileIndex, FreeFunctionId, LanguageElementId, LookupItemId, ModuleFileId, ModuleId,
-    ModuleItemId, SubmoduleId,
+    FileIndex, FreeFunctionId, GenericTypeId, LanguageElementId, LookupItemId, ModuleFileId,
+    ModuleId, ModuleItemId, SubmoduleId,
 };
 use cairo_lang_diagnostics::ToOption;
 use cairo_lang_filesystem::ids::CrateId;
@@ -20,9 +20,10 @@ use cairo_lang_semantic::Expr;
 use cairo_lang_sierra::ids::FunctionId;
 use cairo_lang_sierra_generator::db::SierraGenGroup;
 use cairo_lang_sierra_generator::replace_ids::SierraIdReplacer;
+use cairo_lang_starknet_classes::abi::Contract;
 use cairo_lang_starknet_classes::keccak::starknet_keccak;
 use cairo_lang_syntax::node::helpers::{GetIdentifier, PathSegmentEx, QueryAttrs};
-use cairo_lang_syntax::node::TypedSyntaxNode;
+use cairo_lang_syntax::node::{ast, Terminal, TypedSyntaxNode};
 use cairo_lang_utils::extract_matches;
 use cairo_lang_utils::ordered_hash_map::{
     deserialize_ordered_hashmap_vec, serialize_ordered_hashmap_vec, OrderedHashMap,
@@ -31,10 +32,15 @@ use itertools::chain;
 use serde::{Deserialize, Serialize};
 use {cairo_lang_lowering as lowering, cairo_lang_semantic as semantic};
 
+use crate::abi::AbiBuilder;
 use crate::aliased::Aliased;
 use crate::compile::{extract_semantic_entrypoints, SemanticEntryPoints};
 use crate::plugin::aux_data::StarkNetContractAuxData;
-use crate::plugin::consts::{ABI_ATTR, ABI_ATTR_EMBED_V0_ARG};
+use crate::plugin::consts::{
+    ABI_ATTR, ABI_ATTR_EMBED_V0_ARG, CONTRACT_STATE_NAME, STORAGE_ATTR, STORAGE_STRUCT_NAME,
+    SUBSTORAGE_ATTR,
+};
+use crate::plugin::storage::{try_extract_mapping_types, MappingType};
 
 #[cfg(test)]
 #[path = "contract_test.rs"]
@@ -288,6 +294,9 @@ pub struct ContractInfo {
         deserialize_with = "deserialize_ordered_hashmap_vec"
     )]
     pub l1_handlers: OrderedHashMap<Felt252, FunctionId>,
+    /// The ABI of the contract, if it could be generated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abi: Option<Contract>,
 }
 
 /// Returns the list of functions in a given module.
@@ -334,10 +343,14 @@ fn analyze_contract<T: SierraIdReplacer>(
         .map(|f| get_selector_and_sierra_function(db, &f, replacer))
         .collect();
 
+    let abi = AbiBuilder::from_submodule(db.upcast(), contract.submodule_id, Default::default())
+        .ok()
+        .and_then(|builder| builder.finalize().ok());
     let contract_info = ContractInfo {
         externals,
         l1_handlers,
         constructor: constructors.into_iter().next().map(|x| x.1),
+        abi,
     };
     Ok((class_hash, contract_info))
 }
@@ -354,3 +367,137 @@ pub fn get_selector_and_sierra_function<T: SierraIdReplacer>(
     let selector = starknet_keccak(function_with_body.alias.as_bytes()).into();
     (selector, sierra_id)
 }
+
+/// The kind of a storage variable of a contract.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub enum StorageVariableKind {
+    /// A variable stored starting at its base address.
+    Simple,
+    /// A `LegacyMap`, whose entry for `key` is stored starting at the address
+    /// `storage_base_address_from_felt252(LegacyHash::hash(base_address, key))`.
+    LegacyMap { key_type: String, value_type: String },
+}
+
+/// A storage variable of a contract.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct StorageVariable {
+    /// The name of the variable.
+    pub name: String,
+    /// The type of the variable, as written in the storage struct.
+    pub ty: String,
+    /// The kind of the variable, describing how the addresses of its values are derived.
+    pub kind: StorageVariableKind,
+    /// The base address of the variable - the `starknet_keccak` of its name.
+    pub base_address: Felt252,
+    /// The name of the substorage member of the contract the variable belongs to, for variables of
+    /// embedded components.
+    pub substorage: Option<String>,
+}
+
+/// Returns the base address of the storage variable `name` - the `starknet_keccak` of its name, as
+/// used by the code generated for the variable.
+pub fn storage_variable_base_address(name: &str) -> Felt252 {
+    starknet_keccak(name.as_bytes()).into()
+}
+
+/// Returns the storage variables of a contract, including the variables of its substorages.
+pub fn get_storage_layout(
+    db: &dyn SemanticGroup,
+    contract: &ContractDeclaration,
+) -> anyhow::Result<Vec<StorageVariable>> {
+    let mut variables = vec![];
+    for member in get_storage_struct_members(db, contract.submodule_id)? {
+        let name = member.name(db.upcast()).text(db.upcast()).to_string();
+        if member.has_attr(db.upcast(), SUBSTORAGE_ATTR) {
+            let component_module_id = get_substorage_module(db, contract, &name)?;
+            for member in get_storage_struct_members(db, component_module_id)? {
+                variables.push(get_storage_variable(db, &member, Some(name.clone())));
+            }
+        } else {
+            variables.push(get_storage_variable(db, &member, None));
+        }
+    }
+    Ok(variables)
+}
+
+/// Returns the members of the storage struct of a contract or component module.
+fn get_storage_struct_members(
+    db: &dyn SemanticGroup,
+    submodule_id: SubmoduleId,
+) -> anyhow::Result<Vec<ast::Member>> {
+    let syntax_db = db.upcast();
+    let module_ast = submodule_id.stable_ptr(db.upcast()).lookup(syntax_db);
+    let ast::MaybeModuleBody::Some(body) = module_ast.body(syntax_db) else {
+        bail!("Module `{}` has no body.", submodule_id.name(db.upcast()));
+    };
+    body.items(syntax_db)
+        .elements(syntax_db)
+        .into_iter()
+        .find_map(|item| match item {
+            ast::ModuleItem::Struct(item)
+                if item.name(syntax_db).text(syntax_db) == STORAGE_STRUCT_NAME
+                    && item.has_attr(syntax_db, STORAGE_ATTR) =>
+            {
+                Some(item.members(syntax_db).elements(syntax_db))
+            }
+            _ => None,
+        })
+        .with_context(|| {
+            format!("Storage struct not found in `{}`.", submodule_id.name(db.upcast()))
+        })
+}
+
+/// Returns the module of the component embedded in a contract as the substorage member `name`.
+fn get_substorage_module(
+    db: &dyn SemanticGroup,
+    contract: &ContractDeclaration,
+    name: &str,
+) -> anyhow::Result<SubmoduleId> {
+    let Some(ModuleItemId::Struct(state_struct_id)) = db
+        .module_item_by_name(contract.module_id(), CONTRACT_STATE_NAME.into())
+        .to_option()
+        .with_context(|| "Failed to initiate a lookup in the contract module.")?
+    else {
+        bail!("Failed to get `{CONTRACT_STATE_NAME}` of the contract.");
+    };
+    let members =
+        db.struct_members(state_struct_id).to_option().with_context(|| "Failed to get members.")?;
+    let member = members
+        .get(name)
+        .with_context(|| format!("Substorage member `{name}` not found in contract state."))?;
+    if let semantic::TypeLongId::Concrete(concrete) = db.lookup_intern_type(member.ty) {
+        if let GenericTypeId::Struct(component_state_id) = concrete.generic_type(db) {
+            if let ModuleId::Submodule(submodule_id) = component_state_id.parent_module(db.upcast())
+            {
+                return Ok(submodule_id);
+            }
+        }
+    }
+    bail!("Failed to get the component of substorage member `{name}`.")
+}
+
+/// Returns the storage variable defined by a member of a storage struct.
+fn get_storage_variable(
+    db: &dyn SemanticGroup,
+    member: &ast::Member,
+    substorage: Option<String>,
+) -> StorageVariable {
+    let syntax_db = db.upcast();
+    let name = member.name(syntax_db).text(syntax_db).to_string();
+    let type_ast = member.type_clause(syntax_db).ty(syntax_db);
+    let kind = match try_extract_mapping_types(syntax_db, &type_ast) {
+        Some((key_type, value_type, MappingType::Legacy)) => StorageVariableKind::LegacyMap {
+            key_type: key_type.as_syntax_node().get_text_without_trivia(syntax_db),
+            value_type: value_type.as_syntax_node().get_text_without_trivia(syntax_db),
+        },
+        // Non legacy mappings are rejected by the storage plugin.
+        Some((_, _, MappingType::NonLegacy)) | None => StorageVariableKind::Simple,
+    };
+    StorageVariable {
+        ty: type_ast.as_syntax_node().get_text_without_trivia(syntax_db),
+        kind,
+        base_address: storage_variable_base_address(&name),
+        name,
+        substorage,
+    }
+}
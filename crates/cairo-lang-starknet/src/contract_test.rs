@@ -1,10 +1,14 @@
 use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_semantic::test_utils::{get_crate_semantic_diagnostics, setup_test_crate};
+use cairo_lang_starknet_classes::keccak::starknet_keccak;
 use indoc::indoc;
 use itertools::Itertools;
 use pretty_assertions::assert_eq;
 
-use crate::contract::{find_contracts, get_contract_internal_module_abi_functions};
+use crate::contract::{
+    find_contracts, get_contract_internal_module_abi_functions, get_storage_layout,
+    StorageVariable, StorageVariableKind,
+};
 use crate::plugin::consts::EXTERNAL_MODULE;
 use crate::starknet_plugin_suite;
 
@@ -53,3 +57,73 @@ fn test_contract_resolving() {
     get_crate_semantic_diagnostics(db, crate_id)
         .expect_with_db(db, "Unexpected semantic diagnostics");
 }
+
+#[test]
+fn test_storage_layout() {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_plugin_suite(starknet_plugin_suite())
+        .build()
+        .unwrap();
+    let crate_id = setup_test_crate(
+        db,
+        indoc! {"
+            #[starknet::component]
+            mod ownable {
+                #[storage]
+                struct Storage {
+                    owner: starknet::ContractAddress,
+                }
+            }
+
+            #[starknet::contract]
+            mod erc_20 {
+                component!(path: super::ownable, storage: ownable_storage, event: OwnableEvent);
+
+                #[storage]
+                struct Storage {
+                    total_supply: u256,
+                    balances: LegacyMap<starknet::ContractAddress, u256>,
+                    #[substorage(v0)]
+                    ownable_storage: super::ownable::Storage,
+                }
+
+                #[event]
+                #[derive(Drop, starknet::Event)]
+                enum Event {
+                    OwnableEvent: super::ownable::Event,
+                }
+            }
+        "},
+    );
+
+    let contracts = find_contracts(db, &[crate_id]);
+    let variable = |name: &str, ty: &str, kind, substorage: Option<&str>| StorageVariable {
+        name: name.into(),
+        ty: ty.into(),
+        kind,
+        base_address: starknet_keccak(name.as_bytes()).into(),
+        substorage: substorage.map(Into::into),
+    };
+    assert_eq!(
+        get_storage_layout(db, &contracts[0]).unwrap(),
+        vec![
+            variable("total_supply", "u256", StorageVariableKind::Simple, None),
+            variable(
+                "balances",
+                "LegacyMap<starknet::ContractAddress, u256>",
+                StorageVariableKind::LegacyMap {
+                    key_type: "starknet::ContractAddress".into(),
+                    value_type: "u256".into(),
+                },
+                None
+            ),
+            variable(
+                "owner",
+                "starknet::ContractAddress",
+                StorageVariableKind::Simple,
+                Some("ownable_storage")
+            ),
+        ]
+    );
+}
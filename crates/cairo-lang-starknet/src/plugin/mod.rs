@@ -16,7 +16,7 @@ mod embeddable;
 mod entry_point;
 pub mod events;
 mod starknet_module;
-mod storage;
+pub(crate) mod storage;
 mod utils;
 
 use dispatcher::handle_trait;
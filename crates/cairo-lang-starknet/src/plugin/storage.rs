@@ -324,7 +324,7 @@ fn get_substorage_member_code(
 }
 
 /// The type of the mapping storage member.
-enum MappingType {
+pub(crate) enum MappingType {
     /// Pedersen based.
     Legacy,
     /// Poseidon based.
@@ -333,7 +333,7 @@ enum MappingType {
 
 /// Given a type, if it is of form `{Legacy,}Map::<K, V>`, returns `K` and `V` and the mapping type.
 /// Otherwise, returns None.
-fn try_extract_mapping_types(
+pub(crate) fn try_extract_mapping_types(
     db: &dyn SyntaxGroup,
     type_ast: &ast::Expr,
 ) -> Option<(ast::GenericArg, ast::GenericArg, MappingType)> {
@@ -6,14 +6,16 @@ use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
 use cairo_lang_compiler::project::ProjectConfig;
 use cairo_lang_compiler::CompilerConfig;
 use cairo_lang_filesystem::db::FilesGroup;
-use cairo_lang_filesystem::ids::Directory;
+use cairo_lang_filesystem::ids::{CrateId, Directory};
 use cairo_lang_starknet_classes::allowed_libfuncs::BUILTIN_ALL_LIBFUNCS_LIST;
 use cairo_lang_starknet_classes::contract_class::ContractClass;
 use cairo_lang_test_utils::test_lock;
 use itertools::Itertools;
 use once_cell::sync::Lazy;
 
-use crate::compile::compile_contract_in_prepared_db;
+use crate::compile::{
+    compile_contract_artifacts_in_prepared_db, compile_contract_in_prepared_db, ContractArtifacts,
+};
 use crate::starknet_plugin_suite;
 
 /// Returns a path to example contract that matches `name`.
@@ -55,6 +57,52 @@ pub static SHARED_DB_WITH_CONTRACTS: Lazy<Mutex<RootDatabase>> = Lazy::new(|| {
 
 /// Returns the compiled test contract from the contracts crate, with replaced ids.
 pub fn get_test_contract(example_file_name: &str) -> ContractClass {
+    compile_test_contract(example_file_name, compile_contract_in_prepared_db)
+}
+
+/// Returns the compiled test contract from the contracts crate, with replaced ids and the Sierra
+/// optimization pipeline applied.
+pub fn get_optimized_test_contract(example_file_name: &str) -> ContractClass {
+    compile_test_contract(example_file_name, |db, contract_path, main_crate_ids, config| {
+        compile_contract_in_prepared_db(
+            db,
+            contract_path,
+            main_crate_ids,
+            CompilerConfig { optimize_sierra: true, ..config },
+        )
+    })
+}
+
+/// Returns all the artifacts of the test contract from the contracts crate, with replaced ids.
+pub fn get_test_contract_artifacts(example_file_name: &str) -> ContractArtifacts {
+    compile_test_contract(example_file_name, compile_contract_artifacts_in_prepared_db)
+}
+
+/// Returns all the artifacts of the test contract from the contracts crate, with replaced ids and
+/// the calldata taint report.
+pub fn get_test_contract_artifacts_with_calldata_taint(
+    example_file_name: &str,
+) -> ContractArtifacts {
+    compile_test_contract(example_file_name, |db, contract_path, main_crate_ids, config| {
+        compile_contract_artifacts_in_prepared_db(
+            db,
+            contract_path,
+            main_crate_ids,
+            CompilerConfig { calldata_taint_report: true, ..config },
+        )
+    })
+}
+
+/// Compiles the test contract from the contracts crate with `compile`, with replaced ids.
+fn compile_test_contract<T>(
+    example_file_name: &str,
+    compile: impl FnOnce(
+        &RootDatabase,
+        Option<&str>,
+        Vec<CrateId>,
+        CompilerConfig<'_>,
+    ) -> anyhow::Result<T>,
+) -> T {
     let locked_db = test_lock(&SHARED_DB_WITH_CONTRACTS);
     let db = locked_db.snapshot();
     drop(locked_db);
@@ -74,7 +122,7 @@ pub fn get_test_contract(example_file_name: &str) -> ContractClass {
     };
     let main_crate_ids = vec![**contracts_crate_id];
     let diagnostics_reporter = DiagnosticsReporter::default().with_crates(&main_crate_ids);
-    compile_contract_in_prepared_db(
+    compile(
         &db,
         Some(example_file_name),
         main_crate_ids,
@@ -82,6 +130,7 @@ pub fn get_test_contract(example_file_name: &str) -> ContractClass {
             replace_ids: true,
             allowed_libfuncs_list_name: Some(BUILTIN_ALL_LIBFUNCS_LIST.to_string()),
             diagnostics_reporter,
+            ..CompilerConfig::default()
         },
     )
     .expect("compile_path failed")
@@ -38,7 +38,12 @@ pub use plugin::TestPlugin;
 const TEST_ATTR: &str = "test";
 const SHOULD_PANIC_ATTR: &str = "should_panic";
 const IGNORE_ATTR: &str = "ignore";
-const AVAILABLE_GAS_ATTR: &str = "available_gas";
+/// An attribute setting the gas available for running a function, e.g. `#[available_gas(1000)]`.
+pub const AVAILABLE_GAS_ATTR: &str = "available_gas";
+/// An attribute limiting the number of VM steps of running a function, e.g.
+/// `#[max_steps(100000)]`.
+pub const MAX_STEPS_ATTR: &str = "max_steps";
+const SETUP_ATTR: &str = "setup";
 const STATIC_GAS_ARG: &str = "static";
 
 /// Runs Cairo compiler.
@@ -84,45 +89,35 @@ pub fn compile_test_prepared_db(
             })
             .collect();
     let all_tests = find_all_tests(db, test_crate_ids.clone());
+    let setup_functions = find_setup_functions(db, test_crate_ids.clone());
     let SierraProgramWithDebug { program: sierra_program, debug_info } = arc_unwrap_or_clone(
         db.get_sierra_program_for_functions(
             chain!(
                 all_entry_points.into_iter(),
-                all_tests.iter().flat_map(|(func_id, _cfg)| {
-                    ConcreteFunctionWithBodyId::from_no_generics_free(db, *func_id)
-                })
+                chain!(all_tests.iter().map(|(func_id, _cfg)| func_id), &setup_functions).flat_map(
+                    |func_id| { ConcreteFunctionWithBodyId::from_no_generics_free(db, *func_id) }
+                )
             )
             .collect(),
         )
         .to_option()
         .with_context(|| "Compilation failed without any diagnostics.")?,
     );
-    let replacer = DebugReplacer { db };
+    let replacer = DebugReplacer::new(db);
     let sierra_program = replacer.apply(&sierra_program);
     let statements_functions = debug_info.statements_locations.get_statements_functions_map(db);
 
     let named_tests = all_tests
         .into_iter()
-        .map(|(func_id, test)| {
-            (
-                format!(
-                    "{:?}",
-                    FunctionLongId {
-                        function: ConcreteFunction {
-                            generic_function: GenericFunctionId::Free(func_id),
-                            generic_args: vec![]
-                        }
-                    }
-                    .debug(db)
-                ),
-                test,
-            )
-        })
+        .map(|(func_id, test)| (function_name(db, func_id), test))
         .collect_vec();
+    let setup_functions =
+        setup_functions.into_iter().map(|func_id| function_name(db, func_id)).collect_vec();
     let contracts_info = get_contracts_info(db, main_crate_ids.clone(), &replacer)?;
 
     Ok(TestCompilation {
         named_tests,
+        setup_functions,
         sierra_program,
         function_set_costs,
         contracts_info,
@@ -144,6 +139,10 @@ pub struct TestCompilation {
     )]
     pub function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
     pub named_tests: Vec<(String, TestConfig)>,
+    /// The names of the functions marked as `#[setup]`, in order. They are run before the tests,
+    /// and each test starts from the starknet state they leave.
+    #[serde(default)]
+    pub setup_functions: Vec<String>,
     pub sierra_program: Program,
     /// A map between sierra statement index and the string representation of the Cairo function
     /// that generated it. The function representation is composed of the function name and the
@@ -180,6 +179,38 @@ fn find_all_tests(
     tests
 }
 
+/// Finds the functions marked as `#[setup]` in the requested crates.
+fn find_setup_functions(db: &dyn SemanticGroup, main_crates: Vec<CrateId>) -> Vec<FreeFunctionId> {
+    let mut setup_functions = vec![];
+    for crate_id in main_crates {
+        let modules = db.crate_modules(crate_id);
+        for module_id in modules.iter() {
+            let Ok(free_functions) = db.module_free_functions_ids(*module_id) else {
+                continue;
+            };
+            setup_functions.extend(free_functions.iter().copied().filter(|func_id| {
+                db.function_with_body_attributes(FunctionWithBodyId::Free(*func_id))
+                    .is_ok_and(|attrs| attrs.iter().any(|attr| attr.id.as_str() == SETUP_ATTR))
+            }));
+        }
+    }
+    setup_functions
+}
+
+/// Returns the full name of a free function, as used for naming tests.
+fn function_name(db: &(dyn SemanticGroup + 'static), func_id: FreeFunctionId) -> String {
+    format!(
+        "{:?}",
+        FunctionLongId {
+            function: ConcreteFunction {
+                generic_function: GenericFunctionId::Free(func_id),
+                generic_args: vec![]
+            }
+        }
+        .debug(db)
+    )
+}
+
 /// The suite of plugins for compilation for testing.
 pub fn test_plugin_suite() -> PluginSuite {
     let mut suite = PluginSuite::default();
@@ -1,9 +1,11 @@
-use cairo_lang_defs::plugin::{MacroPlugin, MacroPluginMetadata, PluginResult};
+use cairo_lang_defs::plugin::{MacroPlugin, MacroPluginMetadata, PluginDiagnostic, PluginResult};
 use cairo_lang_syntax::attribute::structured::AttributeListStructurize;
 use cairo_lang_syntax::node::ast;
 use cairo_lang_syntax::node::db::SyntaxGroup;
 
-use super::{AVAILABLE_GAS_ATTR, IGNORE_ATTR, SHOULD_PANIC_ATTR, TEST_ATTR};
+use super::{
+    AVAILABLE_GAS_ATTR, IGNORE_ATTR, MAX_STEPS_ATTR, SETUP_ATTR, SHOULD_PANIC_ATTR, TEST_ATTR,
+};
 use crate::test_config::try_extract_test_config;
 
 /// Plugin to create diagnostics for tests attributes.
@@ -18,24 +20,32 @@ impl MacroPlugin for TestPlugin {
         item_ast: ast::ModuleItem,
         _metadata: &MacroPluginMetadata<'_>,
     ) -> PluginResult {
-        PluginResult {
-            code: None,
-            diagnostics: if let ast::ModuleItem::FreeFunction(free_func_ast) = item_ast {
-                try_extract_test_config(db, free_func_ast.attributes(db).structurize(db)).err()
-            } else {
-                None
+        let ast::ModuleItem::FreeFunction(free_func_ast) = item_ast else {
+            return PluginResult::default();
+        };
+        let attrs = free_func_ast.attributes(db).structurize(db);
+        let mut diagnostics = vec![];
+        if let Some(setup_attr) = attrs.iter().find(|attr| attr.id.as_str() == SETUP_ATTR) {
+            let signature = free_func_ast.declaration(db).signature(db);
+            if !signature.parameters(db).elements(db).is_empty() {
+                diagnostics.push(PluginDiagnostic::error(
+                    setup_attr.id_stable_ptr.untyped(),
+                    "Setup functions should not have parameters.".into(),
+                ));
             }
-            .unwrap_or_default(),
-            remove_original_item: false,
         }
+        diagnostics.extend(try_extract_test_config(db, attrs).err().unwrap_or_default());
+        PluginResult { code: None, diagnostics, remove_original_item: false }
     }
 
     fn declared_attributes(&self) -> Vec<String> {
         vec![
             TEST_ATTR.to_string(),
             AVAILABLE_GAS_ATTR.to_string(),
+            MAX_STEPS_ATTR.to_string(),
             SHOULD_PANIC_ATTR.to_string(),
             IGNORE_ATTR.to_string(),
+            SETUP_ATTR.to_string(),
         ]
     }
 }
@@ -10,7 +10,10 @@ use num_bigint::{BigInt, Sign};
 use num_traits::ToPrimitive;
 use serde::{Deserialize, Serialize};
 
-use super::{AVAILABLE_GAS_ATTR, IGNORE_ATTR, SHOULD_PANIC_ATTR, STATIC_GAS_ARG, TEST_ATTR};
+use super::{
+    AVAILABLE_GAS_ATTR, IGNORE_ATTR, MAX_STEPS_ATTR, SETUP_ATTR, SHOULD_PANIC_ATTR, STATIC_GAS_ARG,
+    TEST_ATTR,
+};
 
 /// Expectation for a panic case.
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
@@ -35,6 +38,8 @@ pub enum TestExpectation {
 pub struct TestConfig {
     /// The amount of gas the test requested.
     pub available_gas: Option<usize>,
+    /// The maximum number of VM steps the test may run, if the test has a step budget.
+    pub max_steps: Option<usize>,
     /// The expected result of the run.
     pub expectation: TestExpectation,
     /// Should the test be ignored.
@@ -50,8 +55,24 @@ pub fn try_extract_test_config(
     let test_attr = attrs.iter().find(|attr| attr.id.as_str() == TEST_ATTR);
     let ignore_attr = attrs.iter().find(|attr| attr.id.as_str() == IGNORE_ATTR);
     let available_gas_attr = attrs.iter().find(|attr| attr.id.as_str() == AVAILABLE_GAS_ATTR);
+    let max_steps_attr = attrs.iter().find(|attr| attr.id.as_str() == MAX_STEPS_ATTR);
     let should_panic_attr = attrs.iter().find(|attr| attr.id.as_str() == SHOULD_PANIC_ATTR);
+    let setup_attr = attrs.iter().find(|attr| attr.id.as_str() == SETUP_ATTR);
     let mut diagnostics = vec![];
+    if let Some(attr) = setup_attr {
+        if !attr.args.is_empty() {
+            diagnostics.push(PluginDiagnostic::error(
+                attr.id_stable_ptr.untyped(),
+                "Attribute should not have arguments.".into(),
+            ));
+        }
+        if test_attr.is_some() {
+            diagnostics.push(PluginDiagnostic::error(
+                attr.id_stable_ptr.untyped(),
+                "A test can't be a setup function.".into(),
+            ));
+        }
+    }
     if let Some(attr) = test_attr {
         if !attr.args.is_empty() {
             diagnostics.push(PluginDiagnostic::error(
@@ -79,6 +100,7 @@ pub fn try_extract_test_config(
         false
     };
     let available_gas = extract_available_gas(available_gas_attr, db, &mut diagnostics);
+    let max_steps = max_steps_attr.and_then(|attr| extract_max_steps(attr, db, &mut diagnostics));
     let (should_panic, expected_panic_felts) = if let Some(attr) = should_panic_attr {
         if attr.args.is_empty() {
             (true, None)
@@ -106,6 +128,7 @@ pub fn try_extract_test_config(
     } else {
         Some(TestConfig {
             available_gas,
+            max_steps,
             expectation: if should_panic {
                 TestExpectation::Panics(if let Some(felts) = expected_panic_felts {
                     PanicExpectation::Exact(felts)
@@ -133,29 +156,98 @@ fn extract_available_gas(
         // loops will run out of gas.
         return Some(u32::MAX as usize);
     };
-    match &attr.args[..] {
-        [AttributeArg { variant: AttributeArgVariant::Unnamed { value, .. }, .. }] => match value {
-            ast::Expr::Path(path)
-                if path.as_syntax_node().get_text_without_trivia(db) == STATIC_GAS_ARG =>
-            {
-                return None;
-            }
-            ast::Expr::Literal(literal) => {
-                literal.numeric_value(db).and_then(|v| v.to_i64()).and_then(|v| v.to_usize())
-            }
-            _ => None,
-        },
+    match parse_available_gas(db, attr) {
+        Some(AvailableGas::Amount(gas)) => Some(gas),
+        Some(AvailableGas::Static) => None,
+        None => {
+            diagnostics.push(malformed_available_gas_diagnostic(attr));
+            None
+        }
+    }
+}
+
+/// The gas set by an `available_gas` attribute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AvailableGas {
+    /// The gas required by the function according to its static costs, set by
+    /// `#[available_gas(static)]`.
+    Static,
+    /// A fixed amount of gas.
+    Amount(usize),
+}
+
+/// Parses the gas of an `available_gas` attribute. Returns `None` if the attribute is malformed.
+pub fn parse_available_gas(db: &dyn SyntaxGroup, attr: &Attribute) -> Option<AvailableGas> {
+    let [AttributeArg { variant: AttributeArgVariant::Unnamed { value, .. }, .. }] = &attr.args[..]
+    else {
+        return None;
+    };
+    match value {
+        ast::Expr::Path(path)
+            if path.as_syntax_node().get_text_without_trivia(db) == STATIC_GAS_ARG =>
+        {
+            Some(AvailableGas::Static)
+        }
+        ast::Expr::Literal(literal) => {
+            parse_non_negative_literal(db, literal).map(AvailableGas::Amount)
+        }
         _ => None,
     }
-    .on_none(|| {
-        diagnostics.push(PluginDiagnostic::error(
-            attr.args_stable_ptr.untyped(),
-            format!(
-                "Attribute should have a single non-negative literal in `i64` range or \
-                 `{STATIC_GAS_ARG}`."
-            ),
-        ))
-    })
+}
+
+/// Returns the diagnostic of a malformed `available_gas` attribute.
+pub fn malformed_available_gas_diagnostic(attr: &Attribute) -> PluginDiagnostic {
+    non_negative_literal_diagnostic(attr, &format!(" or `{STATIC_GAS_ARG}`"))
+}
+
+/// Parses the number of steps of a `max_steps` attribute. Returns `None` if the attribute is
+/// malformed.
+pub fn parse_max_steps(db: &dyn SyntaxGroup, attr: &Attribute) -> Option<usize> {
+    let [
+        AttributeArg {
+            variant: AttributeArgVariant::Unnamed { value: ast::Expr::Literal(literal), .. },
+            ..
+        },
+    ] = &attr.args[..]
+    else {
+        return None;
+    };
+    parse_non_negative_literal(db, literal)
+}
+
+/// Returns the diagnostic of a malformed `max_steps` attribute.
+pub fn malformed_max_steps_diagnostic(attr: &Attribute) -> PluginDiagnostic {
+    non_negative_literal_diagnostic(attr, "")
+}
+
+/// Parses a non-negative literal in `i64` range.
+fn parse_non_negative_literal(
+    db: &dyn SyntaxGroup,
+    literal: &ast::TerminalLiteralNumber,
+) -> Option<usize> {
+    literal.numeric_value(db)?.to_i64()?.to_usize()
+}
+
+/// Returns the diagnostic of an attribute whose argument should be a single non-negative literal
+/// in `i64` range. `alternatives` is appended to the description of the allowed arguments, for
+/// attributes allowing other arguments as well.
+fn non_negative_literal_diagnostic(attr: &Attribute, alternatives: &str) -> PluginDiagnostic {
+    PluginDiagnostic::error(
+        attr.args_stable_ptr.untyped(),
+        format!(
+            "Attribute should have a single non-negative literal in `i64` range{alternatives}."
+        ),
+    )
+}
+
+/// Extracts the maximum number of steps from the `max_steps` attribute.
+/// Adds a diagnostic if the attribute is malformed.
+fn extract_max_steps(
+    attr: &Attribute,
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+) -> Option<usize> {
+    parse_max_steps(db, attr).on_none(|| diagnostics.push(malformed_max_steps_diagnostic(attr)))
 }
 
 /// Tries to extract the expected panic bytes out of the given `should_panic` attribute.
@@ -1,5 +1,7 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use std::vec::IntoIter;
 
 use anyhow::{bail, Context, Result};
@@ -9,11 +11,13 @@ use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
 use cairo_lang_compiler::project::setup_project;
 use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
 use cairo_lang_filesystem::ids::CrateId;
-use cairo_lang_runner::casm_run::format_next_item;
+use cairo_lang_runner::casm_run::{format_next_item, STDOUT_CHANNEL};
 use cairo_lang_runner::profiling::{
     ProfilingInfo, ProfilingInfoProcessor, ProfilingInfoProcessorParams,
 };
-use cairo_lang_runner::{RunResultValue, SierraCasmRunner};
+use cairo_lang_runner::{
+    PrintedOutput, RunResultValue, RunnerError, SierraCasmRunner, StarknetState,
+};
 use cairo_lang_sierra::extensions::gas::CostTokenType;
 use cairo_lang_sierra::ids::FunctionId;
 use cairo_lang_sierra::program::{Program, StatementIdx};
@@ -30,9 +34,14 @@ use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
 use colored::Colorize;
 use itertools::Itertools;
+use mutation::{run_mutation_testing, MutationTestingSummary};
 use num_traits::ToPrimitive;
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use report::{write_report, TestReportConfig};
+use serde::Serialize;
 
+pub mod mutation;
+pub mod report;
 #[cfg(test)]
 mod test;
 
@@ -40,6 +49,8 @@ mod test;
 pub struct TestRunner {
     compiler: TestCompiler,
     config: TestRunConfig,
+    /// The path the tests are compiled from, for compiling them again in each parallel shard.
+    path: PathBuf,
 }
 
 impl TestRunner {
@@ -59,13 +70,81 @@ impl TestRunner {
         config: TestRunConfig,
     ) -> Result<Self> {
         let compiler = TestCompiler::try_new(path, starknet, allow_warnings)?;
-        Ok(Self { compiler, config })
+        Ok(Self { compiler, config, path: path.to_path_buf() })
     }
 
     /// Runs the tests and process the results for a summary.
     pub fn run(&self) -> Result<Option<TestsSummary>> {
-        let runner = CompiledTestRunner::new(self.compiler.build()?, self.config.clone());
-        runner.run(Some(&self.compiler.db))
+        let report = self.config.report.clone();
+        let (summary, filtered_out) = match (self.config.shard, self.config.parallel_shards) {
+            (None, Some(n_shards)) if n_shards > 1 => self.run_parallel_shards(n_shards)?,
+            _ => CompiledTestRunner::new(self.compiler.build()?, self.config.clone())
+                .run_summary(Some(&self.compiler.db))?,
+        };
+        report_summary(summary, filtered_out, report.as_ref())
+    }
+
+    /// Splits the tests into `n_shards` shards and runs the shards in parallel threads. Each shard
+    /// compiles the tests in a database of its own, as a database can't be shared between threads,
+    /// and runs its tests as a regular run would. Returns the merged summary of the shards, with
+    /// the tests in their order, and the number of filtered out tests.
+    fn run_parallel_shards(&self, n_shards: usize) -> Result<(TestsSummary, usize)> {
+        let (path, starknet) = (&self.path, self.compiler.starknet);
+        let shard_results = std::thread::scope(|scope| {
+            let handles = (1..=n_shards)
+                .map(|index| {
+                    let shard = TestShard { index, count: n_shards };
+                    let config =
+                        TestRunConfig { shard: Some(shard), report: None, ..self.config.clone() };
+                    scope.spawn(move || -> Result<_> {
+                        // The diagnostics were already reported by the compilation of the runner.
+                        let compiler = TestCompiler::try_new_with_reporter(
+                            path,
+                            starknet,
+                            DiagnosticsReporter::ignoring().allow_warnings(),
+                        )?;
+                        let runner = CompiledTestRunner::new(compiler.build()?, config);
+                        let (summary, filtered_out) = runner.run_summary(Some(&compiler.db))?;
+                        Ok((shard, summary, filtered_out))
+                    })
+                })
+                .collect_vec();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("A test shard thread panicked."))
+                .collect::<Result<Vec<_>>>()
+        })?;
+        let mut merged = TestsSummary {
+            passed: vec![],
+            failed: vec![],
+            ignored: vec![],
+            failures: vec![],
+            test_results: vec![],
+        };
+        let mut positions = UnorderedHashMap::default();
+        let mut n_tests = 0;
+        let mut n_shard_tests = 0;
+        for (shard, summary, filtered_out) in shard_results {
+            // The k-th test of a shard is the k-th test assigned to it in the round-robin.
+            for (k, result) in summary.test_results.iter().enumerate() {
+                positions.insert(result.name.clone(), k * shard.count + shard.index - 1);
+            }
+            n_tests = filtered_out + summary.test_results.len();
+            n_shard_tests += summary.test_results.len();
+            merged.passed.extend(summary.passed);
+            merged.failed.extend(summary.failed);
+            merged.ignored.extend(summary.ignored);
+            merged.failures.extend(summary.failures);
+            merged.test_results.extend(summary.test_results);
+        }
+        merged.sort_by_positions(&positions);
+        Ok((merged, n_tests - n_shard_tests))
+    }
+
+    /// Runs experimental mutation testing of the tested code, using the tests included by the
+    /// config. See [mutation].
+    pub fn run_mutation_testing(&mut self) -> Result<MutationTestingSummary> {
+        run_mutation_testing(&mut self.compiler, &self.config)
     }
 }
 
@@ -87,53 +166,84 @@ impl CompiledTestRunner {
 
     /// Execute preconfigured test execution.
     pub fn run(self, db: Option<&RootDatabase>) -> Result<Option<TestsSummary>> {
+        let report = self.config.report.clone();
+        let (summary, filtered_out) = self.run_summary(db)?;
+        report_summary(summary, filtered_out, report.as_ref())
+    }
+
+    /// Runs the filtered tests, and returns the summary of the run and the number of filtered out
+    /// tests.
+    fn run_summary(self, db: Option<&RootDatabase>) -> Result<(TestsSummary, usize)> {
         let (compiled, filtered_out) = filter_test_cases(
             self.compiled,
             self.config.include_ignored,
             self.config.ignored,
             self.config.filter,
+            self.config.shard,
         );
 
-        let TestsSummary { passed, failed, ignored, failed_run_results } = run_tests(
+        let summary = run_tests(
             if self.config.run_profiler == RunProfilerConfig::Cairo { db } else { None },
             compiled.named_tests,
+            compiled.setup_functions,
             compiled.sierra_program,
             compiled.function_set_costs,
             compiled.contracts_info,
             self.config.run_profiler != RunProfilerConfig::None,
             compiled.statements_functions,
         )?;
+        Ok((summary, filtered_out))
+    }
+}
 
-        if failed.is_empty() {
-            println!(
-                "test result: {}. {} passed; {} failed; {} ignored; {filtered_out} filtered out;",
-                "ok".bright_green(),
-                passed.len(),
-                failed.len(),
-                ignored.len()
-            );
-            Ok(None)
-        } else {
-            println!("failures:");
-            for (failure, run_result) in failed.iter().zip_eq(failed_run_results) {
-                print!("   {failure} - ");
-                match run_result {
-                    RunResultValue::Success(_) => {
-                        println!("expected panic but finished successfully.");
-                    }
-                    RunResultValue::Panic(values) => {
-                        println!("{}", format_for_panic(values.into_iter()));
-                    }
-                }
-            }
-            println!();
-            bail!(
-                "test result: {}. {} passed; {} failed; {} ignored",
-                "FAILED".bright_red(),
-                passed.len(),
-                failed.len(),
-                ignored.len()
-            );
+/// Writes the report of the summary of a run, if requested, and prints the result of the run.
+/// Fails if any of the tests failed.
+fn report_summary(
+    summary: TestsSummary,
+    filtered_out: usize,
+    report: Option<&TestReportConfig>,
+) -> Result<Option<TestsSummary>> {
+    if let Some(report) = report {
+        write_report(&summary, filtered_out, report)?;
+    }
+
+    let TestsSummary { passed, failed, ignored, failures, .. } = summary;
+    if failed.is_empty() {
+        println!(
+            "test result: {}. {} passed; {} failed; {} ignored; {filtered_out} filtered out;",
+            "ok".bright_green(),
+            passed.len(),
+            failed.len(),
+            ignored.len()
+        );
+        Ok(None)
+    } else {
+        println!("failures:");
+        for (name, failure) in failed.iter().zip_eq(failures) {
+            println!("   {name} - {}", format_failure(&failure));
+        }
+        println!();
+        bail!(
+            "test result: {}. {} passed; {} failed; {} ignored",
+            "FAILED".bright_red(),
+            passed.len(),
+            failed.len(),
+            ignored.len()
+        );
+    }
+}
+
+/// Formats the reason of the failure of a test with the given result.
+fn format_failure(failure: &TestFailure) -> String {
+    match failure {
+        TestFailure::UnexpectedResult(RunResultValue::Success(_)) => {
+            "expected panic but finished successfully.".into()
+        }
+        TestFailure::UnexpectedResult(RunResultValue::Panic(values)) => {
+            format_for_panic(values.clone().into_iter())
+        }
+        TestFailure::MaxStepsExceeded { max_steps } => {
+            format!("exceeded the step budget of {max_steps} steps.")
         }
     }
 }
@@ -149,6 +259,70 @@ fn format_for_panic(mut felts: IntoIter<Felt252>) -> String {
     format!("Panicked with {panic_values_string}.")
 }
 
+/// The operands of a failed comparison assertion, e.g. `assert_eq!(a, b)`, for rendering them side
+/// by side.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AssertionFailure {
+    /// The comparison operator of the assertion, e.g. `==`.
+    pub operator: String,
+    /// The left operand of the assertion.
+    pub lhs: AssertionOperand,
+    /// The right operand of the assertion.
+    pub rhs: AssertionOperand,
+    /// The custom message of the assertion, if it has one.
+    pub message: Option<String>,
+}
+
+/// An operand of a failed comparison assertion.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AssertionOperand {
+    /// The expression of the operand, as written in the assertion.
+    pub expr: String,
+    /// The value of the operand, formatted using `Debug`.
+    pub value: String,
+}
+
+impl AssertionFailure {
+    /// The comparison operators of the comparison assertions.
+    const OPERATORS: [&'static str; 6] = ["==", "!=", "<=", ">=", "<", ">"];
+
+    /// Parses the panic data of a test into a failed comparison assertion, if it is the panic of
+    /// one of the comparison assertion macros, e.g. `assert_eq!` or `assert_lt!`.
+    pub fn from_panic_data(panic_data: &[Felt252]) -> Option<Self> {
+        let mut felts = panic_data.iter().cloned();
+        let message = format_next_item(&mut felts).filter(|item| item.is_string())?.get();
+        if felts.next().is_some() {
+            return None;
+        }
+        let (assertion, rest) = message.strip_prefix("assertion `")?.split_once("` failed")?;
+        // The operands may contain the operator themselves, so all the splits are tried.
+        Self::OPERATORS.into_iter().find_map(|operator| {
+            let separator = format!(" {operator} ");
+            assertion.match_indices(&separator).find_map(|(index, _)| {
+                let (lhs, rhs) = (&assertion[..index], &assertion[index + separator.len()..]);
+                let rhs_start = rest.rfind(&format!("\n{rhs}: "))?;
+                let lhs_start = rest[..rhs_start].rfind(&format!("\n{lhs}: "))?;
+                let user_message = match &rest[..lhs_start] {
+                    "." => None,
+                    user_message => Some(user_message.strip_prefix(": ")?.to_string()),
+                };
+                Some(Self {
+                    operator: operator.to_string(),
+                    lhs: AssertionOperand {
+                        expr: lhs.to_string(),
+                        value: rest[lhs_start + lhs.len() + 3..rhs_start].to_string(),
+                    },
+                    rhs: AssertionOperand {
+                        expr: rhs.to_string(),
+                        value: rest[rhs_start + rhs.len() + 3..].to_string(),
+                    },
+                    message: user_message,
+                })
+            })
+        })
+    }
+}
+
 /// Whether to run the profiler, and what results to produce.
 /// With `None`, don't run the profiler.
 /// With `Sierra`, run the profiler and produce sierra profiling information.
@@ -169,6 +343,46 @@ pub struct TestRunConfig {
     pub ignored: bool,
     /// Whether to run the profiler and how.
     pub run_profiler: RunProfilerConfig,
+    /// The shard of the tests to run, if the tests are split into shards.
+    pub shard: Option<TestShard>,
+    /// The number of shards to split the tests into and run in parallel threads, each compiling
+    /// the tests in a database of its own. Unlike the tests of a single run, which can't run in
+    /// parallel with the Cairo profiler as it requires the database, the shards always run in
+    /// parallel. Ignored when running a single `shard`.
+    pub parallel_shards: Option<usize>,
+    /// The report of the results to write, if requested.
+    pub report: Option<TestReportConfig>,
+}
+
+/// A shard of the tests, for splitting the tests between several runs, e.g. between CI jobs. The
+/// filtered tests are assigned to the shards in a round-robin manner, by their order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TestShard {
+    /// The 1-based index of the shard.
+    pub index: usize,
+    /// The number of shards.
+    pub count: usize,
+}
+impl TestShard {
+    /// Returns whether the test at the given position among the filtered tests is in the shard.
+    pub fn contains(&self, position: usize) -> bool {
+        position % self.count == self.index - 1
+    }
+}
+impl FromStr for TestShard {
+    type Err = String;
+
+    /// Parses a shard from `index/count`, e.g. `2/4` for the second of 4 shards.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("Invalid shard `{s}`, expected `index/count`, e.g. `2/4`.");
+        let (index, count) = s.split_once('/').ok_or_else(invalid)?;
+        let index = index.trim().parse::<usize>().map_err(|_| invalid())?;
+        let count = count.trim().parse::<usize>().map_err(|_| invalid())?;
+        if index == 0 || index > count {
+            return Err(format!("Invalid shard `{s}`, the index must be between 1 and {count}."));
+        }
+        Ok(Self { index, count })
+    }
 }
 
 /// The test cases compiler.
@@ -187,7 +401,20 @@ impl TestCompiler {
     /// * `path` - The path to compile and run its tests
     /// * `starknet` - Add the starknet plugin to run the tests
     pub fn try_new(path: &Path, starknet: bool, allow_warnings: bool) -> Result<Self> {
-        let db = &mut {
+        let mut reporter = DiagnosticsReporter::stderr();
+        if allow_warnings {
+            reporter = reporter.allow_warnings();
+        }
+        Self::try_new_with_reporter(path, starknet, reporter)
+    }
+
+    /// Configures a new test compiler, reporting the diagnostics of the compilation to `reporter`.
+    fn try_new_with_reporter(
+        path: &Path,
+        starknet: bool,
+        reporter: DiagnosticsReporter<'_>,
+    ) -> Result<Self> {
+        let mut db = {
             let mut b = RootDatabase::builder();
             b.detect_corelib();
             b.with_cfg(CfgSet::from_iter([Cfg::name("test")]));
@@ -199,21 +426,12 @@ impl TestCompiler {
             b.build()?
         };
 
-        let main_crate_ids = setup_project(db, Path::new(&path))?;
-        let mut reporter = DiagnosticsReporter::stderr().with_crates(&main_crate_ids);
-        if allow_warnings {
-            reporter = reporter.allow_warnings();
-        }
-        if reporter.check(db) {
+        let main_crate_ids = setup_project(&mut db, Path::new(&path))?;
+        if reporter.with_crates(&main_crate_ids).check(&db) {
             bail!("failed to compile: {}", path.display());
         }
 
-        Ok(Self {
-            db: db.snapshot(),
-            test_crate_ids: main_crate_ids.clone(),
-            main_crate_ids,
-            starknet,
-        })
+        Ok(Self { db, test_crate_ids: main_crate_ids.clone(), main_crate_ids, starknet })
     }
 
     /// Build the tests and collect metadata.
@@ -234,6 +452,7 @@ impl TestCompiler {
 /// * `include_ignored` - Include ignored tests as well.
 /// * `ignored` - Run ignored tests only.l
 /// * `filter` - Include only tests containing the filter string.
+/// * `shard` - Include only the tests of the shard, out of the otherwise included tests.
 /// # Returns
 /// * (`TestCompilation`, `usize`) - The filtered test cases and the number of filtered out cases.
 pub fn filter_test_cases(
@@ -241,6 +460,7 @@ pub fn filter_test_cases(
     include_ignored: bool,
     ignored: bool,
     filter: String,
+    shard: Option<TestShard>,
 ) -> (TestCompilation, usize) {
     let total_tests_count = compiled.named_tests.len();
     let named_tests = compiled.named_tests
@@ -255,6 +475,9 @@ pub fn filter_test_cases(
         .filter(|(name, _)| name.contains(&filter))
         // Filtering unignored tests in `ignored` mode
         .filter(|(_, test)| !ignored || test.ignored)
+        .enumerate()
+        .filter(|(position, _)| shard.map_or(true, |shard| shard.contains(*position)))
+        .map(|(_, named_test)| named_test)
         .collect_vec();
     let filtered_out = total_tests_count - named_tests.len();
     let tests = TestCompilation { named_tests, ..compiled };
@@ -264,7 +487,17 @@ pub fn filter_test_cases(
 /// The status of a ran test.
 enum TestStatus {
     Success,
-    Fail(RunResultValue),
+    Fail(TestFailure),
+}
+
+/// The reason a test failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum TestFailure {
+    /// The run did not end as expected - it panicked, or didn't panic when it should have.
+    UnexpectedResult(RunResultValue),
+    /// The run was aborted for running more steps than allowed by the `#[max_steps(N)]` attribute
+    /// of the test.
+    MaxStepsExceeded { max_steps: usize },
 }
 
 /// The result of a ran test.
@@ -275,6 +508,10 @@ struct TestResult {
     gas_usage: Option<i64>,
     /// The profiling info of the run, if requested.
     profiling_info: Option<ProfilingInfo>,
+    /// The output printed by the run.
+    printed_output: Option<PrintedOutput>,
+    /// The time the run took.
+    run_time: Duration,
 }
 
 /// Summary data of the ran tests.
@@ -282,43 +519,97 @@ pub struct TestsSummary {
     passed: Vec<String>,
     failed: Vec<String>,
     ignored: Vec<String>,
-    failed_run_results: Vec<RunResultValue>,
+    /// The reasons of the failures of the failed tests, in the order of `failed`.
+    failures: Vec<TestFailure>,
+    /// The results of the tests.
+    test_results: Vec<TestCaseResult>,
+}
+
+/// The outcome of a test.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+/// The result of a single test, as reported in test reports.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestCaseResult {
+    /// The full path of the test.
+    pub name: String,
+    pub outcome: TestOutcome,
+    /// The gas usage of the test, if relevant.
+    pub gas_usage: Option<i64>,
+    /// The time the run of the test took, or `None` for ignored tests.
+    pub run_time: Option<Duration>,
+    /// The reason of the failure, for failed tests.
+    pub failure: Option<String>,
+    /// The operands of the failed assertion, for tests failed by a comparison assertion.
+    pub assertion: Option<AssertionFailure>,
+    /// The output printed by the test, if any.
+    pub printed_output: Option<String>,
+}
+
+impl TestsSummary {
+    /// Sorts the tests of the summary by their positions in the given mapping from test name, as
+    /// the tests finish running in an arbitrary order when running in parallel.
+    fn sort_by_positions(&mut self, positions: &UnorderedHashMap<String, usize>) {
+        self.passed.sort_by_key(|name| positions[name]);
+        self.ignored.sort_by_key(|name| positions[name]);
+        (self.failed, self.failures) = std::mem::take(&mut self.failed)
+            .into_iter()
+            .zip_eq(std::mem::take(&mut self.failures))
+            .sorted_by_key(|(name, _)| positions[name])
+            .unzip();
+        self.test_results.sort_by_key(|result| positions[&result.name]);
+    }
+
+    /// Returns the results of the ran and ignored tests, in the order of the tests.
+    pub fn test_results(&self) -> &[TestCaseResult] {
+        &self.test_results
+    }
 }
 
-/// Runs the tests and process the results for a summary.
+/// Runs the tests and process the results for a summary, in which the tests are in the order of
+/// `named_tests`.
+#[allow(clippy::too_many_arguments)]
 pub fn run_tests(
     db: Option<&RootDatabase>,
     named_tests: Vec<(String, TestConfig)>,
+    setup_functions: Vec<String>,
     sierra_program: Program,
     function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
     contracts_info: OrderedHashMap<Felt252, ContractInfo>,
     run_profiler: bool,
     statements_functions: UnorderedHashMap<StatementIdx, String>,
 ) -> Result<TestsSummary> {
-    let runner = SierraCasmRunner::new(
+    let (runner, starknet_state) = setup_test_runner(
         sierra_program.clone(),
-        Some(MetadataComputationConfig {
-            function_set_costs,
-            linear_gas_solver: true,
-            linear_ap_change_solver: true,
-        }),
+        function_set_costs,
         contracts_info,
         run_profiler,
-    )
-    .with_context(|| "Failed setting up runner.")?;
+        &setup_functions,
+    )?;
     println!("running {} tests", named_tests.len());
+    let test_positions: UnorderedHashMap<String, usize> = named_tests
+        .iter()
+        .enumerate()
+        .map(|(position, (name, _))| (name.clone(), position))
+        .collect();
     let wrapped_summary = Mutex::new(Ok(TestsSummary {
         passed: vec![],
         failed: vec![],
         ignored: vec![],
-        failed_run_results: vec![],
+        failures: vec![],
+        test_results: vec![],
     }));
 
     // Run in parallel if possible. If running with db, parallelism is impossible.
     if db.is_none() {
         named_tests
             .into_par_iter()
-            .map(|(name, test)| run_single_test(test, name, &runner))
+            .map(|(name, test)| run_single_test(test, name, &runner, &starknet_state))
             .for_each(|res| {
                 update_summary(
                     &wrapped_summary,
@@ -337,7 +628,7 @@ pub fn run_tests(
         eprintln!("Note: Tests don't run in parallel when running with a database.");
         named_tests
             .into_iter()
-            .map(move |(name, test)| run_single_test(test, name, &runner))
+            .map(move |(name, test)| run_single_test(test, name, &runner, &starknet_state))
             .for_each(|test_result| {
                 update_summary(
                     &wrapped_summary,
@@ -350,35 +641,117 @@ pub fn run_tests(
             });
     }
 
-    wrapped_summary.into_inner().unwrap()
+    let mut summary = wrapped_summary.into_inner().unwrap()?;
+    summary.sort_by_positions(&test_positions);
+    Ok(summary)
 }
 
-/// Runs a single test and returns a tuple of its name and result.
+/// Creates the runner of the tests of the given program, and runs the setup functions with it.
+/// Returns the runner along with the starknet state the tests start from.
+fn setup_test_runner(
+    sierra_program: Program,
+    function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
+    contracts_info: OrderedHashMap<Felt252, ContractInfo>,
+    run_profiler: bool,
+    setup_functions: &[String],
+) -> Result<(SierraCasmRunner, StarknetState)> {
+    let runner = SierraCasmRunner::new(
+        sierra_program,
+        Some(MetadataComputationConfig {
+            function_set_costs,
+            linear_gas_solver: true,
+            linear_ap_change_solver: true,
+        }),
+        contracts_info,
+        run_profiler,
+    )
+    .with_context(|| "Failed setting up runner.")?
+    // The output of each test is captured, so that the outputs of tests running in parallel are
+    // not interleaved.
+    .with_print_capture(true);
+    let starknet_state = run_setup_functions(&runner, setup_functions)?;
+    Ok((runner, starknet_state))
+}
+
+/// Runs the setup functions in order, and returns the starknet state they leave, which the tests
+/// start from.
+fn run_setup_functions(
+    runner: &SierraCasmRunner,
+    setup_functions: &[String],
+) -> Result<StarknetState> {
+    let mut starknet_state = StarknetState::default();
+    for name in setup_functions {
+        let func = runner.find_function(name)?;
+        let result = runner
+            .run_function_with_starknet_context(func, &[], Some(u32::MAX as usize), starknet_state)
+            .with_context(|| format!("Failed to run the setup function `{name}`."))?;
+        if let Some(printed_output) = &result.printed_output {
+            print!("{}", format_printed_output(printed_output));
+        }
+        if let RunResultValue::Panic(values) = result.value {
+            bail!("Setup function `{name}` failed: {}", format_for_panic(values.into_iter()));
+        }
+        starknet_state = result.starknet_state;
+    }
+    Ok(starknet_state)
+}
+
+/// Runs a single test, starting from the given starknet state, and returns a tuple of its name
+/// and result.
 fn run_single_test(
     test: TestConfig,
     name: String,
     runner: &SierraCasmRunner,
+    starknet_state: &StarknetState,
 ) -> anyhow::Result<(String, Option<TestResult>)> {
     if test.ignored {
         return Ok((name, None));
     }
     let func = runner.find_function(name.as_str())?;
-    let result = runner
-        .run_function_with_starknet_context(func, &[], test.available_gas, Default::default())
-        .with_context(|| format!("Failed to run the function `{}`.", name.as_str()))?;
+    let run_start = Instant::now();
+    let result = match runner.run_function_with_max_steps(
+        func,
+        &[],
+        test.available_gas,
+        starknet_state.clone(),
+        test.max_steps,
+    ) {
+        Ok(result) => result,
+        // Exceeding the step budget fails the test, aborting its run.
+        Err(RunnerError::MaxStepsExceeded { max_steps }) => {
+            return Ok((
+                name,
+                Some(TestResult {
+                    status: TestStatus::Fail(TestFailure::MaxStepsExceeded { max_steps }),
+                    gas_usage: None,
+                    profiling_info: None,
+                    printed_output: None,
+                    run_time: run_start.elapsed(),
+                }),
+            ));
+        }
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("Failed to run the function `{}`.", name.as_str()));
+        }
+    };
     Ok((
         name,
         Some(TestResult {
             status: match &result.value {
                 RunResultValue::Success(_) => match test.expectation {
                     TestExpectation::Success => TestStatus::Success,
-                    TestExpectation::Panics(_) => TestStatus::Fail(result.value),
+                    TestExpectation::Panics(_) => {
+                        TestStatus::Fail(TestFailure::UnexpectedResult(result.value))
+                    }
                 },
                 RunResultValue::Panic(value) => match test.expectation {
-                    TestExpectation::Success => TestStatus::Fail(result.value),
+                    TestExpectation::Success => {
+                        TestStatus::Fail(TestFailure::UnexpectedResult(result.value))
+                    }
                     TestExpectation::Panics(panic_expectation) => match panic_expectation {
                         PanicExpectation::Exact(expected) if value != &expected => {
-                            TestStatus::Fail(result.value)
+                            TestStatus::Fail(TestFailure::UnexpectedResult(result.value))
                         }
                         _ => TestStatus::Success,
                     },
@@ -394,6 +767,8 @@ fn run_single_test(
                     runner.initial_required_gas(func).map(|gas| gas.into_or_panic::<i64>())
                 }),
             profiling_info: result.profiling_info,
+            printed_output: result.printed_output,
+            run_time: result.metadata.run_time,
         }),
     ))
 }
@@ -419,16 +794,45 @@ fn update_summary(
         }
     };
     let summary = wrapped_summary.as_mut().unwrap();
-    let (res_type, status_str, gas_usage, profiling_info) = match status {
-        Some(TestResult { status: TestStatus::Success, gas_usage, profiling_info }) => {
-            (&mut summary.passed, "ok".bright_green(), gas_usage, profiling_info)
-        }
-        Some(TestResult { status: TestStatus::Fail(run_result), gas_usage, profiling_info }) => {
-            summary.failed_run_results.push(run_result);
-            (&mut summary.failed, "fail".bright_red(), gas_usage, profiling_info)
+    let (outcome, status_str, gas_usage, profiling_info, printed_output, run_time) = match status {
+        Some(TestResult {
+            status: TestStatus::Success,
+            gas_usage,
+            profiling_info,
+            printed_output,
+            run_time,
+        }) => (
+            TestOutcome::Passed,
+            "ok".bright_green(),
+            gas_usage,
+            profiling_info,
+            printed_output,
+            Some(run_time),
+        ),
+        Some(TestResult {
+            status: TestStatus::Fail(failure),
+            gas_usage,
+            profiling_info,
+            printed_output,
+            run_time,
+        }) => {
+            summary.failures.push(failure);
+            (
+                TestOutcome::Failed,
+                "fail".bright_red(),
+                gas_usage,
+                profiling_info,
+                printed_output,
+                Some(run_time),
+            )
         }
-        None => (&mut summary.ignored, "ignored".bright_yellow(), None, None),
+        None => (TestOutcome::Ignored, "ignored".bright_yellow(), None, None, None, None),
     };
+    let printed_output =
+        printed_output.map(|printed_output| format_printed_output(&printed_output));
+    if let Some(printed_output) = &printed_output {
+        print!("{printed_output}");
+    }
     if let Some(gas_usage) = gas_usage {
         println!("test {name} ... {status_str} (gas usage est.: {gas_usage})");
     } else {
@@ -441,5 +845,42 @@ fn update_summary(
             profiling_processor.process_ex(&profiling_info, profiling_params);
         println!("Profiling info:\n{processed_profiling_info}");
     }
-    res_type.push(name);
+    summary.test_results.push(TestCaseResult {
+        name: name.clone(),
+        outcome,
+        gas_usage,
+        run_time,
+        failure: match outcome {
+            TestOutcome::Failed => summary.failures.last().map(format_failure),
+            TestOutcome::Passed | TestOutcome::Ignored => None,
+        },
+        assertion: match (outcome, summary.failures.last()) {
+            (
+                TestOutcome::Failed,
+                Some(TestFailure::UnexpectedResult(RunResultValue::Panic(panic_data))),
+            ) => AssertionFailure::from_panic_data(panic_data),
+            _ => None,
+        },
+        printed_output: printed_output.filter(|printed_output| !printed_output.is_empty()),
+    });
+    match outcome {
+        TestOutcome::Passed => summary.passed.push(name),
+        TestOutcome::Failed => summary.failed.push(name),
+        TestOutcome::Ignored => summary.ignored.push(name),
+    }
+}
+
+/// Formats the output printed by a test, with the output of each channel other than the standard
+/// output following the name of the channel.
+fn format_printed_output(printed_output: &PrintedOutput) -> String {
+    printed_output
+        .channels()
+        .map(|(channel, output)| {
+            if channel == STDOUT_CHANNEL {
+                output.to_string()
+            } else {
+                format!("[{channel}]\n{output}")
+            }
+        })
+        .join("")
 }
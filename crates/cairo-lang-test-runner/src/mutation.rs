@@ -0,0 +1,188 @@
+//! Experimental mutation testing - applies small mutations to the lowering of the functions of the
+//! tested crates, reruns the tests against each mutant, and reports the mutants no test detected.
+//!
+//! The mutants are compiled using the db of the [TestCompiler], so only the queries depending on
+//! the mutated function are recomputed for each mutant.
+
+use std::fmt;
+
+use anyhow::{bail, Result};
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::{FunctionWithBodyId, ModuleItemId};
+use cairo_lang_lowering::db::LoweringGroup;
+use cairo_lang_lowering::mutation::{function_mutations, Mutation};
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::attribute::SemanticQueryAttrs;
+use cairo_lang_semantic::ConcreteFunctionWithBodyId;
+use cairo_lang_sierra_generator::statements_locations::SourceSpan;
+use cairo_lang_test_plugin::TestCompilation;
+
+use crate::{
+    filter_test_cases, run_single_test, setup_test_runner, TestCompiler, TestResult, TestRunConfig,
+    TestStatus,
+};
+
+/// The outcome of running the tests against a mutant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MutantOutcome {
+    /// A test failed, detecting the mutation.
+    Killed { test: String },
+    /// All the tests passed, so the mutation went undetected.
+    Survived,
+    /// The mutant could not be compiled or run, e.g. as a mutated literal is out of the range of
+    /// its type.
+    Invalid { error: String },
+}
+
+/// The result of running the tests against a single mutant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MutantResult {
+    /// The name of the mutated function.
+    pub function: String,
+    /// A description of the mutation.
+    pub description: String,
+    /// The location of the mutated code, as `file:line:column`.
+    pub location: String,
+    pub outcome: MutantOutcome,
+}
+
+/// The results of mutation testing, by the order of the mutated code.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MutationTestingSummary {
+    pub mutants: Vec<MutantResult>,
+}
+impl MutationTestingSummary {
+    /// Returns the mutants no test detected.
+    pub fn surviving_mutants(&self) -> impl Iterator<Item = &MutantResult> {
+        self.mutants.iter().filter(|mutant| mutant.outcome == MutantOutcome::Survived)
+    }
+
+    /// Returns the number of mutants with outcomes matching the given predicate.
+    fn count(&self, predicate: impl Fn(&MutantOutcome) -> bool) -> usize {
+        self.mutants.iter().filter(|mutant| predicate(&mutant.outcome)).count()
+    }
+}
+impl fmt::Display for MutationTestingSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "mutation testing: {} killed; {} survived; {} invalid;",
+            self.count(|outcome| matches!(outcome, MutantOutcome::Killed { .. })),
+            self.count(|outcome| matches!(outcome, MutantOutcome::Survived)),
+            self.count(|outcome| matches!(outcome, MutantOutcome::Invalid { .. })),
+        )?;
+        for MutantResult { function, description, location, .. } in self.surviving_mutants() {
+            writeln!(f, "   survived: {description} in {function} at {location}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs mutation testing of the non-generic functions of the main crates of the compiler, other
+/// than the tests themselves, using the tests included by the config.
+///
+/// Fails if any of the included tests fails without mutations.
+pub fn run_mutation_testing(
+    compiler: &mut TestCompiler,
+    config: &TestRunConfig,
+) -> Result<MutationTestingSummary> {
+    if let Some(failed) = first_failing_test(compiler.build()?, config)? {
+        bail!("Mutation testing requires passing tests, but `{failed}` failed.");
+    }
+
+    let mut summary = MutationTestingSummary::default();
+    for mutation in collect_mutations(compiler) {
+        let db = &compiler.db;
+        let location = mutation.location(db).ok().and_then(|location| {
+            let span = SourceSpan::from_location(db, location)?;
+            Some(format!("{}:{}:{}", span.file, span.start_line + 1, span.start_col + 1))
+        });
+        let mut result = MutantResult {
+            function: mutation.function.name(db).to_string(),
+            description: mutation.describe(db).unwrap_or_else(|_| "unknown mutation".into()),
+            location: location.unwrap_or_else(|| "unknown location".into()),
+            outcome: MutantOutcome::Survived,
+        };
+
+        compiler.db.set_lowering_mutation(Some(mutation));
+        let outcome = compiler
+            .build()
+            .and_then(|compiled| first_failing_test(compiled, config))
+            .map(|failed| match failed {
+                Some(test) => MutantOutcome::Killed { test },
+                None => MutantOutcome::Survived,
+            });
+        compiler.db.set_lowering_mutation(None);
+        result.outcome =
+            outcome.unwrap_or_else(|error| MutantOutcome::Invalid { error: format!("{error:#}") });
+        summary.mutants.push(result);
+    }
+    Ok(summary)
+}
+
+/// Returns the possible mutations of the functions of the main crates of the compiler.
+fn collect_mutations(compiler: &TestCompiler) -> Vec<Mutation> {
+    let db = &compiler.db;
+    let mut mutations = vec![];
+    for crate_id in &compiler.main_crate_ids {
+        for module_id in db.crate_modules(*crate_id).iter() {
+            let Ok(module_items) = db.module_items(*module_id) else { continue };
+            let mut function_ids = vec![];
+            for item in module_items.iter() {
+                match item {
+                    ModuleItemId::FreeFunction(free_function) => {
+                        function_ids.push(FunctionWithBodyId::Free(*free_function));
+                    }
+                    ModuleItemId::Impl(impl_def_id) => {
+                        if !db.impl_def_generic_params(*impl_def_id).is_ok_and(|p| p.is_empty()) {
+                            continue;
+                        }
+                        let Ok(impl_functions) = db.impl_functions(*impl_def_id) else { continue };
+                        function_ids.extend(
+                            impl_functions.values().map(|id| FunctionWithBodyId::Impl(*id)),
+                        );
+                    }
+                    _ => {}
+                }
+            }
+            for function_id in function_ids {
+                if function_id.has_attr(db, "test").unwrap_or(true) {
+                    continue;
+                }
+                let Ok(function) = ConcreteFunctionWithBodyId::from_generic(db, function_id) else {
+                    continue;
+                };
+                if !db.lookup_intern_concrete_function_with_body(function).generic_args.is_empty() {
+                    continue;
+                }
+                mutations.extend(function_mutations(db, function).unwrap_or_default());
+            }
+        }
+    }
+    mutations
+}
+
+/// Runs the tests included by the config, and returns the name of the first failing test, if any.
+fn first_failing_test(compiled: TestCompilation, config: &TestRunConfig) -> Result<Option<String>> {
+    let (compiled, _) = filter_test_cases(
+        compiled,
+        config.include_ignored,
+        config.ignored,
+        config.filter.clone(),
+        config.shard,
+    );
+    let (runner, starknet_state) = setup_test_runner(
+        compiled.sierra_program,
+        compiled.function_set_costs,
+        compiled.contracts_info,
+        false,
+        &compiled.setup_functions,
+    )?;
+    for (name, test) in compiled.named_tests {
+        let (name, result) = run_single_test(test, name, &runner, &starknet_state)?;
+        if let Some(TestResult { status: TestStatus::Fail(_), .. }) = result {
+            return Ok(Some(name));
+        }
+    }
+    Ok(None)
+}
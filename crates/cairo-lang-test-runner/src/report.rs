@@ -0,0 +1,188 @@
+//! Reports of test runs, for consumption by other tools: JUnit XML for CI systems, and JSON for
+//! other tools, e.g. web UIs.
+
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::{AssertionFailure, TestCaseResult, TestOutcome, TestsSummary};
+
+#[cfg(test)]
+#[path = "report_test.rs"]
+mod test;
+
+/// The format of a test report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// A JUnit XML report, as consumed by most CI systems.
+    JUnit,
+    /// A JSON report, see [JsonReport].
+    Json,
+}
+
+/// Configuration of the report of a test run.
+#[derive(Clone, Debug)]
+pub struct TestReportConfig {
+    pub format: ReportFormat,
+    /// The path of the file to write the report to.
+    pub path: PathBuf,
+}
+
+/// Writes the report of the given test run, in which `filtered_out` tests were filtered out.
+pub fn write_report(
+    summary: &TestsSummary,
+    filtered_out: usize,
+    config: &TestReportConfig,
+) -> Result<()> {
+    let report = format_report(summary, filtered_out, config.format);
+    std::fs::write(&config.path, report)
+        .with_context(|| format!("Failed to write the test report to `{}`.", config.path.display()))
+}
+
+/// Formats the report of the given test run in the given format.
+pub fn format_report(summary: &TestsSummary, filtered_out: usize, format: ReportFormat) -> String {
+    match format {
+        ReportFormat::JUnit => format_junit_report(summary.test_results()),
+        ReportFormat::Json => {
+            let report = JsonReport::new(summary.test_results(), filtered_out);
+            serde_json::to_string_pretty(&report).expect("Failed to serialize the report.")
+        }
+    }
+}
+
+/// The name of the test suite in JUnit reports.
+const JUNIT_SUITE_NAME: &str = "cairo-test";
+
+/// Formats a JUnit XML report of the given results.
+///
+/// The test cases are grouped by the module of the test in their `classname`, and the printed
+/// output of the tests is reported in their `system-out`.
+fn format_junit_report(results: &[TestCaseResult]) -> String {
+    let count = |outcome| results.iter().filter(|result| result.outcome == outcome).count();
+    let counts = format!(
+        r#"tests="{}" failures="{}" skipped="{}" time="{}""#,
+        results.len(),
+        count(TestOutcome::Failed),
+        count(TestOutcome::Ignored),
+        format_seconds(results.iter().filter_map(|result| result.run_time).sum()),
+    );
+    let mut report = String::new();
+    writeln!(report, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+    writeln!(report, "<testsuites {counts}>").unwrap();
+    writeln!(report, r#"  <testsuite name="{JUNIT_SUITE_NAME}" {counts}>"#).unwrap();
+    for result in results {
+        let classname = result.name.rsplit_once("::").map_or("", |(module, _)| module);
+        write!(
+            report,
+            r#"    <testcase name="{}" classname="{}" time="{}""#,
+            escape_xml(&result.name),
+            escape_xml(classname),
+            format_seconds(result.run_time.unwrap_or_default()),
+        )
+        .unwrap();
+        if result.outcome == TestOutcome::Passed && result.printed_output.is_none() {
+            writeln!(report, "/>").unwrap();
+            continue;
+        }
+        writeln!(report, ">").unwrap();
+        match result.outcome {
+            TestOutcome::Passed => {}
+            TestOutcome::Failed => {
+                let failure = escape_xml(result.failure.as_deref().unwrap_or_default());
+                writeln!(report, r#"      <failure message="{failure}"/>"#).unwrap();
+            }
+            TestOutcome::Ignored => writeln!(report, "      <skipped/>").unwrap(),
+        }
+        if let Some(printed_output) = &result.printed_output {
+            writeln!(report, "      <system-out>{}</system-out>", escape_xml(printed_output))
+                .unwrap();
+        }
+        writeln!(report, "    </testcase>").unwrap();
+    }
+    writeln!(report, "  </testsuite>").unwrap();
+    writeln!(report, "</testsuites>").unwrap();
+    report
+}
+
+/// Formats a duration as seconds, as expected in the `time` attributes of JUnit reports.
+fn format_seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+}
+
+/// Escapes text for use in XML attributes and character data.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than whitespace are not allowed in XML.
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A JSON report of a test run.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct JsonReport<'a> {
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+    pub filtered_out: usize,
+    pub tests: Vec<JsonTestReport<'a>>,
+}
+
+impl<'a> JsonReport<'a> {
+    fn new(results: &'a [TestCaseResult], filtered_out: usize) -> Self {
+        let count = |outcome| results.iter().filter(|result| result.outcome == outcome).count();
+        Self {
+            passed: count(TestOutcome::Passed),
+            failed: count(TestOutcome::Failed),
+            ignored: count(TestOutcome::Ignored),
+            filtered_out,
+            tests: results
+                .iter()
+                .map(|result| JsonTestReport {
+                    name: &result.name,
+                    outcome: match result.outcome {
+                        TestOutcome::Passed => "passed",
+                        TestOutcome::Failed => "failed",
+                        TestOutcome::Ignored => "ignored",
+                    },
+                    gas_usage: result.gas_usage,
+                    run_time_ms: result.run_time.map(|run_time| run_time.as_millis()),
+                    failure: result.failure.as_deref(),
+                    assertion: result.assertion.as_ref(),
+                    printed_output: result.printed_output.as_deref(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The report of a single test in a [JsonReport].
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct JsonTestReport<'a> {
+    /// The full path of the test.
+    pub name: &'a str,
+    /// One of `passed`, `failed` or `ignored`.
+    pub outcome: &'static str,
+    pub gas_usage: Option<i64>,
+    /// The time the run of the test took in milliseconds, or `null` for ignored tests.
+    pub run_time_ms: Option<u128>,
+    /// The reason of the failure, for failed tests.
+    pub failure: Option<&'a str>,
+    /// The operator, the operands and the message of the failed assertion, for tests failed by a
+    /// comparison assertion, e.g. `assert_eq!`.
+    pub assertion: Option<&'a AssertionFailure>,
+    pub printed_output: Option<&'a str>,
+}
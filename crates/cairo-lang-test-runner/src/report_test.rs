@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use indoc::indoc;
+
+use super::{format_report, ReportFormat};
+use crate::{AssertionFailure, AssertionOperand, TestCaseResult, TestOutcome, TestsSummary};
+
+/// Returns the summary of a run with a passed, a failed and an ignored test.
+fn summary() -> TestsSummary {
+    let result =
+        |name: &str, outcome, failure: Option<&str>, printed_output: Option<&str>| TestCaseResult {
+            name: name.into(),
+            outcome,
+            gas_usage: (outcome != TestOutcome::Ignored).then_some(2000),
+            run_time: (outcome != TestOutcome::Ignored).then_some(Duration::from_millis(1500)),
+            failure: failure.map(String::from),
+            assertion: None,
+            printed_output: printed_output.map(String::from),
+        };
+    TestsSummary {
+        passed: vec!["test::test_pass".into()],
+        failed: vec!["test::inner::test_fail".into()],
+        ignored: vec!["test::test_ignored".into()],
+        failures: vec![],
+        test_results: vec![
+            result("test::test_pass", TestOutcome::Passed, None, None),
+            result(
+                "test::inner::test_fail",
+                TestOutcome::Failed,
+                Some("Panicked with 'a < b'."),
+                Some("x = 1\n"),
+            ),
+            result("test::test_ignored", TestOutcome::Ignored, None, None),
+        ],
+    }
+}
+
+#[test]
+fn test_junit_report() {
+    assert_eq!(
+        format_report(&summary(), 1, ReportFormat::JUnit),
+        indoc! {r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <testsuites tests="3" failures="1" skipped="1" time="3.000">
+              <testsuite name="cairo-test" tests="3" failures="1" skipped="1" time="3.000">
+                <testcase name="test::test_pass" classname="test" time="1.500"/>
+                <testcase name="test::inner::test_fail" classname="test::inner" time="1.500">
+                  <failure message="Panicked with &apos;a &lt; b&apos;."/>
+                  <system-out>x = 1
+            </system-out>
+                </testcase>
+                <testcase name="test::test_ignored" classname="test" time="0.000">
+                  <skipped/>
+                </testcase>
+              </testsuite>
+            </testsuites>
+        "#}
+    );
+}
+
+#[test]
+fn test_json_report() {
+    let report: serde_json::Value =
+        serde_json::from_str(&format_report(&summary(), 1, ReportFormat::Json)).unwrap();
+    assert_eq!(
+        report,
+        serde_json::json!({
+            "passed": 1,
+            "failed": 1,
+            "ignored": 1,
+            "filtered_out": 1,
+            "tests": [
+                {
+                    "name": "test::test_pass",
+                    "outcome": "passed",
+                    "gas_usage": 2000,
+                    "run_time_ms": 1500,
+                    "failure": null,
+                    "assertion": null,
+                    "printed_output": null,
+                },
+                {
+                    "name": "test::inner::test_fail",
+                    "outcome": "failed",
+                    "gas_usage": 2000,
+                    "run_time_ms": 1500,
+                    "failure": "Panicked with 'a < b'.",
+                    "assertion": null,
+                    "printed_output": "x = 1\n",
+                },
+                {
+                    "name": "test::test_ignored",
+                    "outcome": "ignored",
+                    "gas_usage": null,
+                    "run_time_ms": null,
+                    "failure": null,
+                    "assertion": null,
+                    "printed_output": null,
+                },
+            ],
+        })
+    );
+}
+
+#[test]
+fn test_json_report_assertion() {
+    let mut summary = summary();
+    summary.test_results[1].assertion = Some(AssertionFailure {
+        operator: "<".into(),
+        lhs: AssertionOperand { expr: "a".into(), value: "3".into() },
+        rhs: AssertionOperand { expr: "b".into(), value: "2".into() },
+        message: Some("a is too large".into()),
+    });
+    let report: serde_json::Value =
+        serde_json::from_str(&format_report(&summary, 1, ReportFormat::Json)).unwrap();
+    assert_eq!(
+        report["tests"][1]["assertion"],
+        serde_json::json!({
+            "operator": "<",
+            "lhs": { "expr": "a", "value": "3" },
+            "rhs": { "expr": "b", "value": "2" },
+            "message": "a is too large",
+        })
+    );
+}
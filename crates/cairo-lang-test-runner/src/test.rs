@@ -1,8 +1,19 @@
 use cairo_felt::{felt_str, Felt252};
-use cairo_lang_utils::byte_array::BYTE_ARRAY_MAGIC;
+use cairo_lang_runner::RunResultValue;
+use cairo_lang_sierra::program::Program;
+use cairo_lang_test_plugin::test_config::TestExpectation;
+use cairo_lang_test_plugin::TestConfig;
+use cairo_lang_utils::byte_array::{BYTES_IN_WORD, BYTE_ARRAY_MAGIC};
 use itertools::Itertools;
+use test_case::test_case;
 
-use crate::{format_for_panic, TestCompilation, TestCompiler};
+use crate::mutation::MutantOutcome;
+use crate::report::{format_report, ReportFormat};
+use crate::{
+    filter_test_cases, format_for_panic, run_tests, AssertionFailure, AssertionOperand,
+    RunProfilerConfig, TestCaseResult, TestCompilation, TestCompiler, TestFailure, TestOutcome,
+    TestRunConfig, TestRunner, TestShard, TestsSummary,
+};
 
 #[test]
 fn test_compiled_serialization() {
@@ -201,3 +212,298 @@ fn test_format_for_panic() {
         "Panicked with (0x9999, \"hello\", 0x776f726c64 ('world'), 0x8888)."
     );
 }
+
+/// Returns the panic data of a panic with the given ByteArray message.
+fn byte_array_panic_data(message: &str) -> Vec<Felt252> {
+    let chunks = message.as_bytes().chunks(BYTES_IN_WORD).collect_vec();
+    let (full_words, pending_word) = match chunks.split_last() {
+        Some((last, full_words)) if last.len() < BYTES_IN_WORD => (full_words, *last),
+        _ => (&chunks[..], &[][..]),
+    };
+    let mut felts = vec![felt_str!(BYTE_ARRAY_MAGIC, 16), Felt252::from(full_words.len())];
+    felts.extend(full_words.iter().map(|word| Felt252::from_bytes_be(word)));
+    felts.push(Felt252::from_bytes_be(pending_word));
+    felts.push(Felt252::from(pending_word.len()));
+    felts
+}
+
+/// Returns a failed assertion with the given operator, operands and message.
+fn assertion(
+    operator: &str,
+    (lhs_expr, lhs_value): (&str, &str),
+    (rhs_expr, rhs_value): (&str, &str),
+    message: Option<&str>,
+) -> Option<AssertionFailure> {
+    Some(AssertionFailure {
+        operator: operator.into(),
+        lhs: AssertionOperand { expr: lhs_expr.into(), value: lhs_value.into() },
+        rhs: AssertionOperand { expr: rhs_expr.into(), value: rhs_value.into() },
+        message: message.map(String::from),
+    })
+}
+
+#[test_case(
+    "assertion `a == b` failed.\na: 1\nb: 2",
+    assertion("==", ("a", "1"), ("b", "2"), None);
+    "eq"
+)]
+#[test_case(
+    "assertion `x.len() <= 3` failed: too long: 4\nx.len(): 4\n3: 3",
+    assertion("<=", ("x.len()", "4"), ("3", "3"), Some("too long: 4"));
+    "le with message"
+)]
+#[test_case(
+    "assertion `a == b != c` failed.\na == b: false\nc: false",
+    assertion("!=", ("a == b", "false"), ("c", "false"), None);
+    "operator in operand"
+)]
+#[test_case(
+    "assertion `p != q` failed.\np: Point { x: 1, y: 2 }\nq: Point { x: 1, y: 2 }",
+    assertion("!=", ("p", "Point { x: 1, y: 2 }"), ("q", "Point { x: 1, y: 2 }"), None);
+    "long values"
+)]
+#[test_case("assertion failed: `a`.", None; "assert")]
+#[test_case("assertion `a == b` failed", None; "no operands")]
+fn test_assertion_failure(message: &str, expected: Option<AssertionFailure>) {
+    assert_eq!(AssertionFailure::from_panic_data(&byte_array_panic_data(message)), expected);
+}
+
+#[test_case("2/4", Ok(TestShard { index: 2, count: 4 }); "valid")]
+#[test_case("0/4", Err("Invalid shard `0/4`, the index must be between 1 and 4.".into()); "zero")]
+#[test_case("5/4", Err("Invalid shard `5/4`, the index must be between 1 and 4.".into()); "too large")]
+#[test_case("2", Err("Invalid shard `2`, expected `index/count`, e.g. `2/4`.".into()); "no count")]
+fn test_parse_shard(shard: &str, expected: Result<TestShard, String>) {
+    assert_eq!(shard.parse::<TestShard>(), expected);
+}
+
+#[test]
+fn test_filter_test_shards() {
+    let test_config = |ignored| TestConfig {
+        available_gas: None,
+        max_steps: None,
+        expectation: TestExpectation::Success,
+        ignored,
+    };
+    let compiled = TestCompilation {
+        contracts_info: Default::default(),
+        function_set_costs: Default::default(),
+        setup_functions: vec![],
+        named_tests: (0..7)
+            .map(|i| (format!("test::test_{i}"), test_config(i == 3)))
+            .chain([("test::other".into(), test_config(false))])
+            .collect(),
+        sierra_program: Program {
+            type_declarations: vec![],
+            libfunc_declarations: vec![],
+            statements: vec![],
+            funcs: vec![],
+        },
+        statements_functions: Default::default(),
+    };
+    let shard_tests = |index| {
+        let shard = Some(TestShard { index, count: 3 });
+        let (compiled, filtered_out) =
+            filter_test_cases(compiled.clone(), false, false, "test_".into(), shard);
+        (compiled.named_tests.into_iter().map(|(name, _)| name).collect_vec(), filtered_out)
+    };
+    // The tests are split after filtering, including the ignored tests.
+    assert_eq!(
+        shard_tests(1),
+        (vec!["test::test_0".into(), "test::test_3".into(), "test::test_6".into()], 5)
+    );
+    assert_eq!(shard_tests(2), (vec!["test::test_1".into(), "test::test_4".into()], 6));
+    assert_eq!(shard_tests(3), (vec!["test::test_2".into(), "test::test_5".into()], 6));
+}
+
+#[test]
+fn test_parallel_shards() {
+    use std::path::PathBuf;
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data").join("sharding.cairo");
+
+    let config = TestRunConfig {
+        filter: String::new(),
+        include_ignored: false,
+        ignored: false,
+        run_profiler: RunProfilerConfig::None,
+        shard: None,
+        parallel_shards: Some(2),
+        report: None,
+    };
+    let runner = TestRunner::new(&path, false, false, config).unwrap();
+    let (summary, filtered_out) = runner.run_parallel_shards(2).unwrap();
+    let test_name = |i| format!("sharding::sharding::test_{i}");
+    // The merged summary keeps the order of the tests, which were split between the shards.
+    assert_eq!(summary.passed, vec![test_name(0), test_name(3)]);
+    assert_eq!(summary.failed, vec![test_name(1), test_name(4)]);
+    assert_eq!(summary.ignored, vec![test_name(2)]);
+    assert_eq!(
+        summary.test_results.iter().map(|result| result.name.clone()).collect_vec(),
+        (0..5).map(test_name).collect_vec()
+    );
+    assert_eq!(filtered_out, 0);
+}
+
+#[test]
+fn test_setup_functions() {
+    use std::path::PathBuf;
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data");
+
+    let compiled = TestCompiler::try_new(&path, true, false).unwrap().build().unwrap();
+    assert_eq!(compiled.setup_functions, vec!["contracts::tests::setup_block".to_string()]);
+    let summary = run_tests(
+        None,
+        compiled.named_tests,
+        compiled.setup_functions,
+        compiled.sierra_program,
+        compiled.function_set_costs,
+        compiled.contracts_info,
+        false,
+        compiled.statements_functions,
+    )
+    .unwrap();
+    assert_eq!(summary.failed, Vec::<String>::new());
+    assert!(summary.passed.contains(&"contracts::tests::test_setup_state".to_string()));
+}
+
+#[test]
+fn test_summary_sorted_by_positions() {
+    let result = |name: &str, outcome| TestCaseResult {
+        name: name.into(),
+        outcome,
+        gas_usage: None,
+        run_time: None,
+        failure: None,
+        assertion: None,
+        printed_output: None,
+    };
+    let mut summary = TestsSummary {
+        passed: vec!["c".into(), "a".into()],
+        failed: vec!["e".into(), "b".into()],
+        ignored: vec!["d".into()],
+        failures: vec![
+            TestFailure::UnexpectedResult(RunResultValue::Panic(vec![Felt252::from(5)])),
+            TestFailure::UnexpectedResult(RunResultValue::Panic(vec![Felt252::from(2)])),
+        ],
+        test_results: vec![
+            result("c", TestOutcome::Passed),
+            result("e", TestOutcome::Failed),
+            result("a", TestOutcome::Passed),
+            result("d", TestOutcome::Ignored),
+            result("b", TestOutcome::Failed),
+        ],
+    };
+    let positions = ["a", "b", "c", "d", "e"]
+        .into_iter()
+        .enumerate()
+        .map(|(position, name)| (name.to_string(), position))
+        .collect();
+    summary.sort_by_positions(&positions);
+    assert_eq!(summary.passed, vec!["a".to_string(), "c".to_string()]);
+    assert_eq!(summary.failed, vec!["b".to_string(), "e".to_string()]);
+    assert_eq!(
+        summary.failures,
+        vec![
+            TestFailure::UnexpectedResult(RunResultValue::Panic(vec![Felt252::from(2)])),
+            TestFailure::UnexpectedResult(RunResultValue::Panic(vec![Felt252::from(5)])),
+        ]
+    );
+    assert_eq!(
+        summary.test_results.iter().map(|result| result.name.as_str()).collect_vec(),
+        vec!["a", "b", "c", "d", "e"]
+    );
+}
+
+#[test]
+fn test_mutation_testing() {
+    use std::path::PathBuf;
+    let path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data").join("mutation_testing.cairo");
+
+    let compiler = TestCompiler::try_new(&path, false, false).unwrap();
+    let config = TestRunConfig {
+        filter: String::new(),
+        include_ignored: false,
+        ignored: false,
+        run_profiler: RunProfilerConfig::None,
+        shard: None,
+        parallel_shards: None,
+        report: None,
+    };
+    let mut runner = TestRunner { compiler, config, path };
+    let summary = runner.run_mutation_testing().unwrap();
+    let outcomes = summary
+        .mutants
+        .iter()
+        .map(|mutant| (mutant.description.as_str(), mutant.outcome.clone()))
+        .collect_vec();
+    let killed = || MutantOutcome::Killed {
+        test: "mutation_testing::mutation_testing::test_clamp_large".into(),
+    };
+    // The tests don't check the boundary of the condition.
+    assert_eq!(
+        outcomes,
+        vec![
+            ("replaced literal 10 with 11", MutantOutcome::Survived),
+            ("replaced literal 10 with 9", MutantOutcome::Survived),
+            ("flipped condition", killed()),
+            ("replaced literal 10 with 11", killed()),
+            ("replaced literal 10 with 9", killed()),
+        ]
+    );
+    let mut summary_lines = summary.to_string().lines().map(str::to_string).collect_vec();
+    assert_eq!(summary_lines.remove(0), "mutation testing: 3 killed; 2 survived; 0 invalid;");
+    assert_eq!(summary_lines.len(), 2);
+    assert!(summary_lines[0].ends_with("mutation_testing.cairo:2:12"), "{}", summary_lines[0]);
+}
+
+#[test]
+fn test_max_steps() {
+    use std::path::PathBuf;
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data").join("max_steps.cairo");
+
+    let compiled = TestCompiler::try_new(&path, false, false).unwrap().build().unwrap();
+    let summary = run_tests(
+        None,
+        compiled.named_tests,
+        compiled.setup_functions,
+        compiled.sierra_program,
+        compiled.function_set_costs,
+        compiled.contracts_info,
+        false,
+        compiled.statements_functions,
+    )
+    .unwrap();
+    assert_eq!(summary.passed, vec!["max_steps::max_steps::test_within_budget".to_string()]);
+    assert_eq!(summary.failed, vec!["max_steps::max_steps::test_over_budget".to_string()]);
+    assert_eq!(summary.failures, [TestFailure::MaxStepsExceeded { max_steps: 10 }]);
+}
+
+#[test]
+fn test_report_assertion() {
+    use std::path::PathBuf;
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data").join("assertions.cairo");
+
+    let compiled = TestCompiler::try_new(&path, false, false).unwrap().build().unwrap();
+    let summary = run_tests(
+        None,
+        compiled.named_tests,
+        compiled.setup_functions,
+        compiled.sierra_program,
+        compiled.function_set_costs,
+        compiled.contracts_info,
+        false,
+        compiled.statements_functions,
+    )
+    .unwrap();
+    let report: serde_json::Value =
+        serde_json::from_str(&format_report(&summary, 0, ReportFormat::Json)).unwrap();
+    assert_eq!(
+        report["tests"][0]["assertion"],
+        serde_json::json!({
+            "operator": "<",
+            "lhs": { "expr": "a", "value": "3" },
+            "rhs": { "expr": "b", "value": "2" },
+            "message": "a is too large",
+        })
+    );
+}
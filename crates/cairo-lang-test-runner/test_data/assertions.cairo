@@ -0,0 +1,6 @@
+#[test]
+fn test_assert_lt() {
+    let a: u32 = 3;
+    let b: u32 = 2;
+    assert_lt!(a, b, "a is too large");
+}
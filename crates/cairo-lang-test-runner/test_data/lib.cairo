@@ -35,6 +35,16 @@ mod tests {
 
     use super::{Balance, IBalance, IBalanceDispatcher, IBalanceDispatcherTrait};
 
+    #[setup]
+    fn setup_block() {
+        starknet::testing::set_block_number(7);
+    }
+
+    #[test]
+    fn test_setup_state() {
+        assert_eq!(starknet::get_block_info().unbox().block_number, 7);
+    }
+
     #[test]
     fn test_flow() {
         let calldata = array![100];
@@ -0,0 +1,25 @@
+fn sum(n: u32) -> u32 {
+    if n == 0 {
+        0
+    } else {
+        n + sum(n - 1)
+    }
+}
+
+// The attribute is allowed on functions other than tests, for running them.
+#[max_steps(100000)]
+fn sum_to_ten() -> u32 {
+    sum(10)
+}
+
+#[test]
+#[max_steps(100000)]
+fn test_within_budget() {
+    assert(sum_to_ten() == 55, 'sum');
+}
+
+#[test]
+#[max_steps(10)]
+fn test_over_budget() {
+    assert(sum(10) == 55, 'sum');
+}
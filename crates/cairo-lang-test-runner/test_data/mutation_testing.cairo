@@ -0,0 +1,17 @@
+fn clamp(x: u32) -> u32 {
+    if x > 10 {
+        10
+    } else {
+        x
+    }
+}
+
+#[test]
+fn test_clamp_large() {
+    assert(clamp(20) == 10, 'large');
+}
+
+#[test]
+fn test_clamp_small() {
+    assert(clamp(3) == 3, 'small');
+}
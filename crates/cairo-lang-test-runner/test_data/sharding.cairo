@@ -0,0 +1,21 @@
+#[test]
+fn test_0() {}
+
+#[test]
+fn test_1() {
+    panic!("failed");
+}
+
+#[test]
+#[ignore]
+fn test_2() {}
+
+#[test]
+fn test_3() {
+    assert(1 + 1 == 2, 'sum');
+}
+
+#[test]
+fn test_4() {
+    assert(1 + 1 == 3, 'sum');
+}
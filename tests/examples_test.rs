@@ -11,7 +11,7 @@ use cairo_lang_filesystem::db::FilesGroupEx;
 use cairo_lang_filesystem::flag::Flag;
 use cairo_lang_filesystem::ids::{CrateId, FlagId};
 use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
-use cairo_lang_runner::{token_gas_cost, Arg, RunResultValue, SierraCasmRunner};
+use cairo_lang_runner::{token_gas_cost, Arg, RunResultValue, RunnerError, SierraCasmRunner};
 use cairo_lang_sierra::extensions::gas::CostTokenType;
 use cairo_lang_sierra_generator::db::SierraGenGroup;
 use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
@@ -323,6 +323,46 @@ fn run_function_auto_gas_test(
     );
 }
 
+#[rstest]
+fn find_function_suggestions_test(example_dir_data: &ExampleDirData) {
+    let runner = SierraCasmRunner::new(
+        checked_compile_to_sierra("fib_caller", example_dir_data, false),
+        None,
+        Default::default(),
+        false,
+    )
+    .expect("Failed setting up runner.");
+    assert_matches!(
+        runner.find_function("::fbi"),
+        Err(RunnerError::MissingFunction { suggestions, .. }) if suggestions == ["examples::fib::fib"]
+    );
+    assert_matches!(
+        runner.find_function("::unrelated"),
+        Err(RunnerError::MissingFunction { suggestions, .. }) if suggestions.is_empty()
+    );
+}
+
+#[rstest]
+fn run_function_with_gas_retries_test(example_dir_data: &ExampleDirData) {
+    let runner = SierraCasmRunner::new(
+        checked_compile_to_sierra("fib", example_dir_data, true),
+        Some(Default::default()),
+        Default::default(),
+        false,
+    )
+    .expect("Failed setting up runner.");
+    let func = runner.find_function("").expect("Failed finding the function.");
+    let args = [1, 1, 10].map(|value| Arg::Value(Felt252::from(value)));
+    let (minimal_gas, result) = runner
+        .run_function_with_gas_retries(func, &args, 1000, 200000, Default::default())
+        .expect("Failed running the function.");
+    assert_eq!(result.value, RunResultValue::Success(vec![Felt252::from(89)]));
+    let result = runner
+        .run_function_with_starknet_context(func, &args, Some(minimal_gas - 1), Default::default())
+        .expect("Failed running the function.");
+    assert!(result.value.is_out_of_gas());
+}
+
 #[rstest]
 #[case::size_2(2, 1)]
 #[case::size_3(3, 2)]